@@ -0,0 +1,327 @@
+use crate::utils::out;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AntiCheat {
+    /// Easy Anti-Cheat
+    #[serde(rename = "EasyAntiCheat")]
+    Eac,
+    BattlEye,
+    /// Riot Vanguard, a kernel driver that can't run under Wine
+    Vanguard,
+    /// Call of Duty's kernel-level anti-cheat
+    Ricochet,
+}
+
+impl AntiCheat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Eac => "Easy Anti-Cheat",
+            Self::BattlEye => "BattlEye",
+            Self::Vanguard => "Vanguard",
+            Self::Ricochet => "Ricochet",
+        }
+    }
+
+    /// Proton runtime the anti-cheat needs
+    fn proton_runtime(&self) -> Option<ProtonRuntime> {
+        match self {
+            Self::Eac => Some(ProtonRuntime {
+                name: "Proton EasyAntiCheat Runtime",
+                appid: 1826330,
+                variable: "PROTON_EAC_RUNTIME",
+            }),
+            Self::BattlEye => Some(ProtonRuntime {
+                name: "Proton BattlEye Runtime",
+                appid: 1161040,
+                variable: "PROTON_BATTLEYE_RUNTIME",
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Steam tool Proton loads an anti-cheat's Linux support from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtonRuntime {
+    /// Steam tool name, also its directory under steamapps/common
+    pub name: &'static str,
+    pub appid: u32,
+    /// Variable Proton finds the runtime through
+    pub variable: &'static str,
+}
+
+/// Linux support status, as tracked by AreWeAntiCheatYet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AntiCheatStatus {
+    /// The developer enabled Linux/Proton support
+    Supported,
+    /// Works without official support
+    Running,
+    Broken,
+    /// The developer blocks Linux players
+    Denied,
+    /// Anti-cheat files were found but the game's status isn't known
+    Unknown,
+}
+
+impl AntiCheatStatus {
+    pub fn is_incompatible(&self) -> bool {
+        matches!(self, Self::Broken | Self::Denied)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AntiCheatInfo {
+    pub anti_cheats: Vec<AntiCheat>,
+    pub status: AntiCheatStatus,
+    /// AreWeAntiCheatYet entry for the game
+    pub url: String,
+}
+
+/// A title whose anti-cheat status is known
+struct KnownTitle {
+    /// Words of the title's name
+    fragment: &'static str,
+    steam_appid: Option<u32>,
+    anti_cheat: AntiCheat,
+    status: AntiCheatStatus,
+}
+
+const KNOWN_TITLES: &[KnownTitle] = &[
+    KnownTitle {
+        fragment: "valorant",
+        steam_appid: None,
+        anti_cheat: AntiCheat::Vanguard,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "league of legends",
+        steam_appid: None,
+        anti_cheat: AntiCheat::Vanguard,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "fortnite",
+        steam_appid: None,
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "apex legends",
+        steam_appid: Some(1172470),
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "call of duty",
+        steam_appid: Some(1938090),
+        anti_cheat: AntiCheat::Ricochet,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "pubg",
+        steam_appid: Some(578080),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "rainbow six siege",
+        steam_appid: Some(359550),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "destiny 2",
+        steam_appid: Some(1085660),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "rust",
+        steam_appid: Some(252490),
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Denied,
+    },
+    KnownTitle {
+        fragment: "halo infinite",
+        steam_appid: Some(1240440),
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Supported,
+    },
+    KnownTitle {
+        fragment: "dead by daylight",
+        steam_appid: Some(381210),
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Supported,
+    },
+    KnownTitle {
+        fragment: "elden ring",
+        steam_appid: Some(1245620),
+        anti_cheat: AntiCheat::Eac,
+        status: AntiCheatStatus::Running,
+    },
+    KnownTitle {
+        fragment: "arma 3",
+        steam_appid: Some(107410),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Supported,
+    },
+    KnownTitle {
+        fragment: "dayz",
+        steam_appid: Some(221100),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Supported,
+    },
+    KnownTitle {
+        fragment: "ark: survival evolved",
+        steam_appid: Some(346110),
+        anti_cheat: AntiCheat::BattlEye,
+        status: AntiCheatStatus::Supported,
+    },
+];
+
+/// Anti-cheat status of a title from the built-in list, by Steam app ID or
+/// the words of its name
+pub fn known_title(name: &str, steam_appid: Option<u32>) -> Option<AntiCheatInfo> {
+    let name_words = format!(" {} ", words(name).join(" "));
+    KNOWN_TITLES
+        .iter()
+        .find(|title| {
+            (steam_appid.is_some() && title.steam_appid == steam_appid)
+                || name_words.contains(&format!(" {} ", words(title.fragment).join(" ")))
+        })
+        .map(|title| AntiCheatInfo {
+            anti_cheats: vec![title.anti_cheat],
+            status: title.status,
+            url: areweanticheatyet_url(if name.is_empty() {
+                title.fragment
+            } else {
+                name
+            }),
+        })
+}
+
+/// Detect a game's anti-cheat from the built-in list, falling back to
+/// anti-cheat files in its install directory
+pub fn detect(game: &crate::game::Game) -> Option<AntiCheatInfo> {
+    if let Some(info) = known_title(&game.name, game.steam_appid()) {
+        return Some(info);
+    }
+
+    let anti_cheats = scan_install(&game.install_path);
+    (!anti_cheats.is_empty()).then(|| AntiCheatInfo {
+        anti_cheats,
+        status: AntiCheatStatus::Unknown,
+        url: areweanticheatyet_url(&game.name),
+    })
+}
+
+fn scan_install(install_path: &Path) -> Vec<AntiCheat> {
+    let mut found = Vec::new();
+    for entry in walkdir::WalkDir::new(install_path)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        let anti_cheat = if name == "easyanticheat"
+            || name.starts_with("easyanticheat_")
+            || name == "start_protected_game.exe"
+        {
+            AntiCheat::Eac
+        } else if name == "battleye" || name.starts_with("beservice") {
+            AntiCheat::BattlEye
+        } else {
+            continue;
+        };
+        if !found.contains(&anti_cheat) {
+            found.push(anti_cheat);
+        }
+    }
+    found
+}
+
+/// Lowercase alphanumeric words of a title
+fn words(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn areweanticheatyet_url(name: &str) -> String {
+    format!(
+        "https://areweanticheatyet.com/game/{}",
+        words(name).join("-")
+    )
+}
+
+impl AntiCheatInfo {
+    pub fn names(&self) -> String {
+        self.anti_cheats
+            .iter()
+            .map(|a| a.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Warn before launching a game whose anti-cheat blocks or may block Linux
+    pub fn warn(&self, game_name: &str) {
+        match self.status {
+            AntiCheatStatus::Broken | AntiCheatStatus::Denied => {
+                out!(
+                    "⚠️ {} uses {}, which is known not to work on Linux. Online play will likely fail.",
+                    game_name,
+                    self.names()
+                );
+                println!("   See {}", self.url);
+            }
+            AntiCheatStatus::Unknown => {
+                out!(
+                    "⚠️ {} uses {}, which may block Linux players. See {}",
+                    game_name,
+                    self.names(),
+                    self.url
+                );
+            }
+            AntiCheatStatus::Supported | AntiCheatStatus::Running => {}
+        }
+    }
+
+    /// Variables pointing Proton at the installed anti-cheat runtimes, and
+    /// the runtimes that are missing
+    pub fn proton_runtimes(&self) -> (Vec<(String, String)>, Vec<ProtonRuntime>) {
+        let mut env = Vec::new();
+        let mut missing = Vec::new();
+        for runtime in self
+            .anti_cheats
+            .iter()
+            .filter_map(AntiCheat::proton_runtime)
+        {
+            match steam_common_dirs()
+                .into_iter()
+                .map(|dir| dir.join(runtime.name))
+                .find(|dir| dir.exists())
+            {
+                Some(dir) => env.push((
+                    runtime.variable.to_string(),
+                    dir.to_string_lossy().to_string(),
+                )),
+                None => missing.push(runtime),
+            }
+        }
+        (env, missing)
+    }
+}
+
+fn steam_common_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    vec![
+        home.join(".local/share/Steam/steamapps/common"),
+        home.join(".steam/steam/steamapps/common"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam/steamapps/common"),
+    ]
+}
@@ -9,11 +9,16 @@
 #[cfg(feature = "container-bolt")]
 use bolt::{BoltFileBuilder, BoltRuntime};
 use chrono::{DateTime, Utc};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::game_launcher::SyncMode;
 use crate::protondb::{ProtonDBClient, ProtonDBTier};
+#[cfg(feature = "container-bolt")]
+use crate::utils::eout;
+use crate::utils::out;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameContainer {
@@ -32,6 +37,7 @@ pub struct GameContainer {
     pub protondb_tier: Option<ProtonDBTier>,
     pub category: GameCategory,
     pub nvidia_config: Option<NvidiaConfig>,
+    pub amd_config: Option<AmdConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -44,6 +50,36 @@ pub enum GameCategory {
     Unknown,
 }
 
+impl GameCategory {
+    /// Lowercase name used on the command line and in registry queries
+    pub fn slug(&self) -> &'static str {
+        match self {
+            GameCategory::Competitive => "competitive",
+            GameCategory::AAA => "aaa",
+            GameCategory::Indie => "indie",
+            GameCategory::VR => "vr",
+            GameCategory::Streaming => "streaming",
+            GameCategory::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "competitive" => Some(GameCategory::Competitive),
+            "aaa" => Some(GameCategory::AAA),
+            "indie" => Some(GameCategory::Indie),
+            "vr" => Some(GameCategory::VR),
+            "streaming" => Some(GameCategory::Streaming),
+            _ => None,
+        }
+    }
+
+    /// Category of a well-known title, from the game hints
+    pub fn for_game(name: &str, steam_appid: Option<u32>) -> Option<Self> {
+        crate::game_hints::GameHints::load().category(name, steam_appid)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NvidiaConfig {
     pub dlss_enabled: bool,
@@ -54,6 +90,76 @@ pub struct NvidiaConfig {
     pub core_clock_offset: Option<i32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AmdVulkanDriver {
+    Radv,
+    Amdvlk,
+}
+
+impl AmdVulkanDriver {
+    /// Value for `AMD_VULKAN_ICD`
+    pub fn icd_name(&self) -> &'static str {
+        match self {
+            AmdVulkanDriver::Radv => "RADV",
+            AmdVulkanDriver::Amdvlk => "AMDVLK",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanCurvePoint {
+    /// Temperature in °C
+    pub temperature: u32,
+    /// Fan speed in percent
+    pub speed: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmdConfig {
+    /// amdgpu power profile, e.g. `3D_FULL_SCREEN`, `VR` or `COMPUTE`
+    pub power_profile: Option<String>,
+    pub vulkan_driver: AmdVulkanDriver,
+    /// RADV_PERFTEST features, e.g. `gpl`, `sam`, `rt`
+    pub radv_perftest: Vec<String>,
+    /// Custom fan curve, only supported on RDNA3 and newer
+    pub fan_curve: Vec<FanCurvePoint>,
+}
+
+impl AmdConfig {
+    pub fn for_category(category: &GameCategory) -> Self {
+        let (power_profile, radv_perftest): (Option<&str>, &[&str]) = match category {
+            GameCategory::Competitive => (Some("3D_FULL_SCREEN"), &["gpl"]),
+            GameCategory::AAA => (Some("3D_FULL_SCREEN"), &["gpl", "sam"]),
+            GameCategory::VR => (Some("VR"), &["gpl"]),
+            _ => (None, &[]),
+        };
+
+        Self {
+            power_profile: power_profile.map(|p| p.to_string()),
+            vulkan_driver: AmdVulkanDriver::Radv,
+            radv_perftest: radv_perftest.iter().map(|f| f.to_string()).collect(),
+            fan_curve: vec![],
+        }
+    }
+
+    /// Whether this changes GPU settings, rather than only the environment
+    pub fn tunes_gpu(&self) -> bool {
+        self.power_profile.is_some() || !self.fan_curve.is_empty()
+    }
+
+    /// Environment variables selecting the Vulkan driver and its features
+    pub fn environment(&self) -> Vec<(String, String)> {
+        let mut env = vec![(
+            "AMD_VULKAN_ICD".to_string(),
+            self.vulkan_driver.icd_name().to_string(),
+        )];
+        if self.vulkan_driver == AmdVulkanDriver::Radv && !self.radv_perftest.is_empty() {
+            env.push(("RADV_PERFTEST".to_string(), self.radv_perftest.join(",")));
+        }
+        env
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationProfile {
     pub name: String,
@@ -63,8 +169,22 @@ pub struct OptimizationProfile {
     pub wine_tricks: Vec<String>,
     pub launch_options: Vec<String>,
     pub nvidia_config: Option<NvidiaConfig>,
+    #[serde(default)]
+    pub amd_config: Option<AmdConfig>,
     pub cpu_governor: Option<String>,
     pub nice_level: Option<i32>,
+    /// Precompile and cache shaders at launch, see `LaunchOptions::shader_precache`
+    #[serde(default)]
+    pub shader_precache: bool,
+    /// Wine synchronization, see `SyncMode`
+    #[serde(default)]
+    pub sync_mode: SyncMode,
+    /// FSR/NIS upscaling for games without their own setting
+    #[serde(default)]
+    pub upscaling: Option<crate::graphics::UpscalingSettings>,
+    /// Frame rate cap for games without their own
+    #[serde(default)]
+    pub fps_limit: Option<u32>,
     pub created: DateTime<Utc>,
     pub rating: f32,
     pub downloads: u32,
@@ -72,6 +192,175 @@ pub struct OptimizationProfile {
     pub compatible_games: Vec<String>,
 }
 
+/// CPU frequency governors of the Linux kernel
+pub const CPU_GOVERNORS: &[&str] = &[
+    "performance",
+    "powersave",
+    "ondemand",
+    "conservative",
+    "schedutil",
+    "userspace",
+];
+
+/// Characters a shell would interpret, never needed in launch options or
+/// verbs that end up on a command line
+const SHELL_METACHARACTERS: &[char] = &[
+    ';', '&', '|', '`', '$', '<', '>', '(', ')', '{', '}', '\\', '"', '\'', '\n', '\r',
+];
+
+/// `value` without its `$VAR`, `${VAR}` and `$$` references, which the
+/// launcher expands itself rather than through a shell
+fn without_variable_references(value: &str) -> String {
+    let is_name = |name: &str| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let mut literal = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        literal.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let consumed = if after.starts_with('$') {
+            1
+        } else if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) if is_name(&braced[..end]) => end + 2,
+                _ => 0,
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if is_name(&after[..end]) { end } else { 0 }
+        };
+        if consumed == 0 {
+            // Left for the metacharacter check to reject
+            literal.push('$');
+        }
+        rest = &after[consumed..];
+    }
+    literal.push_str(rest);
+    literal
+}
+
+impl OptimizationProfile {
+    /// Problems that make the profile unsafe or unusable, for profiles from
+    /// files and the community registry. Empty when it is fine to save.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.name.trim().is_empty()
+            || self.name.starts_with('.')
+            || self.name.contains(['/', '\\'])
+        {
+            problems.push(format!("name: '{}' is not a valid file name", self.name));
+        }
+        if let Some(nice) = self.nice_level
+            && !(-20..=19).contains(&nice)
+        {
+            problems.push(format!("nice_level: {} is outside -20 to 19", nice));
+        }
+        let fps_limits = crate::game::FPS_LIMITS;
+        if let Some(limit) = self.fps_limit.filter(|limit| !fps_limits.contains(limit)) {
+            problems.push(format!(
+                "fps_limit: {} is outside {} to {}",
+                limit,
+                fps_limits.start(),
+                fps_limits.end()
+            ));
+        }
+        if let Some(governor) = &self.cpu_governor
+            && !CPU_GOVERNORS.contains(&governor.as_str())
+        {
+            problems.push(format!(
+                "cpu_governor: '{}' is not one of {}",
+                governor,
+                CPU_GOVERNORS.join(", ")
+            ));
+        }
+
+        if let Some(nvidia) = &self.nvidia_config {
+            if let Some(watts) = nvidia.power_limit
+                && !(30..=1000).contains(&watts)
+            {
+                problems.push(format!(
+                    "nvidia_config.power_limit: {} W is outside 30 to 1000 W",
+                    watts
+                ));
+            }
+            for (key, offset, range) in [
+                ("core_clock_offset", nvidia.core_clock_offset, -1000..=1000),
+                ("memory_clock_offset", nvidia.memory_clock_offset, -2000..=4000),
+            ] {
+                if let Some(offset) = offset.filter(|offset| !range.contains(offset)) {
+                    problems.push(format!(
+                        "nvidia_config.{}: {:+} MHz is outside {:+} to {:+} MHz",
+                        key,
+                        offset,
+                        range.start(),
+                        range.end()
+                    ));
+                }
+            }
+        }
+
+        if let Some(amd) = &self.amd_config {
+            if let Some(profile) = &amd.power_profile
+                && !profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                problems.push(format!(
+                    "amd_config.power_profile: '{}' is not a profile name",
+                    profile
+                ));
+            }
+            for point in &amd.fan_curve {
+                if point.temperature > 110 || point.speed > 100 {
+                    problems.push(format!(
+                        "amd_config.fan_curve: {}°C→{}% is outside 0-110°C and 0-100%",
+                        point.temperature, point.speed
+                    ));
+                }
+            }
+        }
+
+        for option in &self.launch_options {
+            if without_variable_references(option).contains(SHELL_METACHARACTERS) {
+                problems.push(format!(
+                    "launch_options: '{}' contains shell characters",
+                    option
+                ));
+            } else if let Some((key, _)) = option
+                .split_once('=')
+                .filter(|_| !option.starts_with('-'))
+            {
+                let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid_key {
+                    problems.push(format!(
+                        "launch_options: '{}' is not a valid variable name",
+                        key
+                    ));
+                }
+            }
+        }
+        for (key, values) in [
+            ("wine_tricks", self.wine_tricks.as_slice()),
+            ("proton_version", self.proton_version.as_slice()),
+        ] {
+            for value in values {
+                if value.contains(SHELL_METACHARACTERS) || value.contains(char::is_whitespace) {
+                    problems.push(format!(
+                        "{}: '{}' contains shell characters or spaces",
+                        key, value
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ContainerStatus {
     Running,
@@ -86,17 +375,37 @@ pub enum ContainerStatus {
 pub struct BoltSystemMetrics {
     pub running_containers: usize,
     pub total_containers: usize,
+    /// Host-wide usage in percent
     pub cpu_usage: f64,
     pub memory_usage: f64,
     pub gpu_usage: f64,
+    /// Combined receive and transmit rate of the running containers' own
+    /// network interfaces in MB/s
     pub network_activity: f64,
 }
 
+/// What launching a game with a profile would set up, worked out without
+/// launching anything or changing system settings
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilePreview {
+    pub profile: String,
+    pub game: String,
+    pub command: String,
+    /// Profile launch options that are arguments rather than variables
+    pub arguments: Vec<String>,
+    pub environment: Vec<String>,
+    pub wine_tricks: Vec<String>,
+    pub nice_level: Option<i32>,
+    /// Governor and GPU changes, against the current values
+    pub system_changes: Vec<String>,
+}
+
 pub struct BoltGameManager {
     #[cfg(feature = "container-bolt")]
     runtime: Option<BoltRuntime>,
     containers: Arc<RwLock<HashMap<String, GameContainer>>>,
     metrics: Arc<RwLock<Option<BoltSystemMetrics>>>,
+    sampler: Arc<Mutex<MetricsSampler>>,
     optimization_manager: OptimizationManager,
     drift_client: DriftClient,
     protondb_client: ProtonDBClient,
@@ -104,17 +413,30 @@ pub struct BoltGameManager {
     _phantom: std::marker::PhantomData<()>,
 }
 
+/// Turns the cumulative counters in `/proc` into usage since the previous sample
+#[derive(Default)]
+struct MetricsSampler {
+    /// Busy and total jiffies across all CPUs
+    cpu: (u64, u64),
+    /// Network bytes of each running container and when they were read
+    network: HashMap<String, (u64, Instant)>,
+    /// Init process of each container, whose /proc entry shows the
+    /// container's network namespace
+    pids: HashMap<String, u32>,
+}
+
 /// Manages optimization profiles for games
 pub struct OptimizationManager {
     profiles: Arc<RwLock<HashMap<String, OptimizationProfile>>>,
     profile_dir: std::path::PathBuf,
 }
 
-/// Client for community profile sharing (Drift registry simulation)
+/// Client for the Drift community profile registry
 pub struct DriftClient {
     base_url: String,
     client: reqwest::Client,
     auth_token: Option<String>,
+    offline: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,6 +457,22 @@ pub struct ProfileMetadata {
     pub gpu_vendor: Option<String>,
 }
 
+/// A single rating left on a community profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileReview {
+    pub author: String,
+    pub rating: f32,
+    pub comment: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+/// ProtonDB client for the user's config, the defaults if it can't be read
+fn configured_protondb_client() -> ProtonDBClient {
+    crate::config::Config::load()
+        .map(|config| ProtonDBClient::from_config(&config))
+        .unwrap_or_else(|_| ProtonDBClient::new())
+}
+
 impl BoltGameManager {
     pub fn new() -> anyhow::Result<Self> {
         let config_dir = dirs::config_dir()
@@ -152,40 +490,55 @@ impl BoltGameManager {
             ),
             containers: Arc::new(RwLock::new(HashMap::new())),
             metrics: Arc::new(RwLock::new(None)),
+            sampler: Arc::new(Mutex::new(MetricsSampler::default())),
             optimization_manager: OptimizationManager::new(profile_dir.clone())?,
             drift_client: DriftClient::new(),
-            protondb_client: ProtonDBClient::new(),
+            protondb_client: configured_protondb_client(),
             #[cfg(not(feature = "container-bolt"))]
             _phantom: std::marker::PhantomData,
         })
     }
 
+    /// Whether games can run in Bolt containers, false when its runtime
+    /// failed to start
+    #[cfg(feature = "container-bolt")]
+    pub fn is_available(&self) -> bool {
+        self.runtime.is_some()
+    }
+
     /// Launch a game with ProtonDB integration and automatic optimization
+    #[cfg(feature = "container-bolt")]
     pub async fn launch_game_with_protondb(
         &self,
         game_id: &str,
         config: &crate::game::Game,
         steam_appid: Option<u32>,
     ) -> anyhow::Result<String> {
-        println!("🚀 Launching {} with ProtonDB optimization...", config.name);
+        out!("🚀 Launching {} with ProtonDB optimization...", config.name);
 
         // Get ProtonDB compatibility data
         let mut protondb_tier = None;
         let mut recommended_proton = None;
 
         if let Some(appid) = steam_appid {
-            if let Ok(compatibility) = self.protondb_client.generate_compatibility_report(appid, &config.name).await {
+            // Launch without ProtonDB data rather than wait on a slow server
+            let lookup = tokio::time::timeout(
+                crate::protondb::LAUNCH_LOOKUP_TIMEOUT,
+                self.protondb_client.generate_compatibility_report(appid, &config.name),
+            )
+            .await;
+            if let Ok(Ok(compatibility)) = lookup {
                 protondb_tier = Some(compatibility.tier.clone());
                 recommended_proton = Some(compatibility.recommended_proton.clone());
 
-                println!("📊 ProtonDB: {} - {}",
+                out!("📊 ProtonDB: {} - {}",
                     compatibility.tier_display,
                     compatibility.tier_description
                 );
 
                 // Apply ProtonDB recommendations
                 for tip in &compatibility.compatibility_tips {
-                    println!("💡 {}", tip);
+                    out!("💡 {}", tip);
                 }
             }
         }
@@ -193,20 +546,30 @@ impl BoltGameManager {
         // Detect game category for optimization
         let category = self.detect_game_category(config, steam_appid).await;
 
-        // Get or create optimization profile
-        let profile_name = format!("{}-optimized", game_id);
-        let profile = self.optimization_manager.get_or_create_profile(
-            &profile_name,
-            &category,
-            recommended_proton.as_deref(),
-            protondb_tier.as_ref(),
-        ).await?;
+        // Use the game's linked profile, otherwise get or create one
+        let linked = config
+            .profile
+            .as_deref()
+            .and_then(|name| self.optimization_manager.get_profile(name));
+        let profile = match linked {
+            Some(profile) => profile,
+            None => {
+                let profile_name = format!("{}-optimized", game_id);
+                self.optimization_manager.get_or_create_profile(
+                    &profile_name,
+                    &category,
+                    recommended_proton.as_deref(),
+                    protondb_tier.as_ref(),
+                ).await?
+            }
+        };
 
         // Launch with optimizations
         self.launch_game_optimized(game_id, config, &profile, steam_appid, protondb_tier).await
     }
 
     /// Launch game with full optimization pipeline
+    #[cfg(feature = "container-bolt")]
     async fn launch_game_optimized(
         &self,
         game_id: &str,
@@ -215,15 +578,7 @@ impl BoltGameManager {
         steam_appid: Option<u32>,
         protondb_tier: Option<ProtonDBTier>,
     ) -> anyhow::Result<String> {
-        #[cfg(feature = "container-bolt")]
-        {
-            self.launch_game_bolt(game_id, config, profile, steam_appid, protondb_tier).await
-        }
-
-        #[cfg(not(feature = "container-bolt"))]
-        {
-            Err(anyhow::anyhow!("Bolt support not compiled in"))
-        }
+        self.launch_game_bolt(game_id, config, profile, steam_appid, protondb_tier).await
     }
 
     #[cfg(feature = "container-bolt")]
@@ -235,8 +590,9 @@ impl BoltGameManager {
         steam_appid: Option<u32>,
         protondb_tier: Option<ProtonDBTier>,
     ) -> anyhow::Result<String> {
-        println!("🎮 Launching {} with profile: {}", config.name, profile.name);
+        out!("🎮 Launching {} with profile: {}", config.name, profile.name);
 
+        let forge_config = crate::config::Config::load()?;
         let runtime = self.runtime.as_ref().ok_or_else(|| anyhow::anyhow!("Bolt runtime not initialized"))?;
 
         // Use profile's Proton version or default
@@ -250,27 +606,46 @@ impl BoltGameManager {
         let nvidia_config = profile.nvidia_config.clone().unwrap_or_else(|| {
             self.create_nvidia_config_for_category(&profile.game_category)
         });
+        let amd_config = profile
+            .amd_config
+            .clone()
+            .unwrap_or_else(|| AmdConfig::for_category(&profile.game_category));
 
         // Apply system optimizations
-        self.apply_system_optimizations(profile).await?;
+        let container_name = format!("ghostforge-{}", game_id);
+        self.apply_system_optimizations(&container_name, profile, &nvidia_config, &amd_config)
+            .await;
 
         // Create container with optimizations
-        let container_name = format!("ghostforge-{}", game_id);
-        let env_vars = self.build_optimized_environment(config, profile, &nvidia_config);
-
-        runtime.run_container(
-            "bolt://gaming-optimized:latest",
-            Some(&container_name),
-            &[], // ports
-            &[format!("{}:/game", config.install_path.to_string_lossy())], // volumes
-            &env_vars,
-            true, // detached
-        ).await.map_err(|e| anyhow::anyhow!("Failed to launch container: {}", e))?;
-
-        // Launch the game
-        let launch_cmd = self.build_launch_command(config, profile);
-        runtime.launch_game(&launch_cmd, &profile.launch_options)
-            .await.map_err(|e| anyhow::anyhow!("Failed to launch game: {}", e))?;
+        let env_vars = self.build_optimized_environment(
+            config,
+            profile,
+            &nvidia_config,
+            &amd_config,
+            &forge_config,
+        );
+
+        let launched = async {
+            runtime.run_container(
+                "bolt://gaming-optimized:latest",
+                Some(&container_name),
+                &[], // ports
+                &[format!("{}:/game", config.install_path.to_string_lossy())], // volumes
+                &env_vars,
+                true, // detached
+            ).await.map_err(|e| anyhow::anyhow!("Failed to launch container: {}", e))?;
+
+            // Launch the game
+            let launch_cmd = self.build_launch_command(config, profile);
+            runtime.launch_game(&launch_cmd, &profile.launch_options)
+                .await.map_err(|e| anyhow::anyhow!("Failed to launch game: {}", e))
+        }.await;
+
+        // Don't leave the GPU tuned for a game that never started
+        if let Err(e) = launched {
+            self.restore_system_optimizations(&container_name);
+            return Err(e);
+        }
 
         // Create enhanced container tracking
         let game_container = GameContainer {
@@ -289,15 +664,17 @@ impl BoltGameManager {
             protondb_tier,
             category: profile.game_category.clone(),
             nvidia_config: Some(nvidia_config),
+            amd_config: Some(amd_config),
         };
 
         self.containers.write().insert(container_name.clone(), game_container);
 
-        println!("✅ {} launched successfully with {} profile", config.name, profile.name);
+        out!("✅ {} launched successfully with {} profile", config.name, profile.name);
         Ok(container_name)
     }
 
     /// Legacy launch method for backward compatibility
+    #[cfg(feature = "container-bolt")]
     pub async fn launch_game(
         &self,
         game_id: &str,
@@ -305,8 +682,16 @@ impl BoltGameManager {
     ) -> anyhow::Result<String> {
         // For backward compatibility, try to get Steam AppID and use enhanced launch
         let steam_appid = if let Some(launcher) = &config.launcher {
-            if launcher.contains("steam") {
-                self.protondb_client.get_steam_appid(&config.name).await.unwrap_or(None)
+            if launcher.to_lowercase().contains("steam") {
+                match config.launcher_id.as_ref().and_then(|id| id.parse().ok()) {
+                    Some(appid) => Some(appid),
+                    None => self
+                        .protondb_client
+                        .find_steam_app(&config.name)
+                        .await
+                        .unwrap_or(None)
+                        .map(|(appid, _)| appid),
+                }
             } else {
                 None
             }
@@ -318,30 +703,10 @@ impl BoltGameManager {
     }
 
     /// Detect game category for optimization
-    async fn detect_game_category(&self, config: &crate::game::Game, steam_appid: Option<u32>) -> GameCategory {
-        // Check game name for category hints
-        let name_lower = config.name.to_lowercase();
-
-        // Competitive games
-        if name_lower.contains("counter-strike") || name_lower.contains("cs2") ||
-           name_lower.contains("valorant") || name_lower.contains("overwatch") ||
-           name_lower.contains("rocket league") || name_lower.contains("dota") ||
-           name_lower.contains("league of legends") {
-            return GameCategory::Competitive;
-        }
-
-        // VR games
-        if name_lower.contains("vr") || name_lower.contains("virtual reality") ||
-           name_lower.contains("half-life: alyx") || name_lower.contains("beat saber") {
-            return GameCategory::VR;
-        }
-
-        // AAA games (common publishers/franchises)
-        if name_lower.contains("call of duty") || name_lower.contains("battlefield") ||
-           name_lower.contains("cyberpunk") || name_lower.contains("witcher") ||
-           name_lower.contains("assassin's creed") || name_lower.contains("red dead") ||
-           name_lower.contains("grand theft auto") || name_lower.contains("gta") {
-            return GameCategory::AAA;
+    pub async fn detect_game_category(&self, config: &crate::game::Game, steam_appid: Option<u32>) -> GameCategory {
+        // Check the game hints for well-known titles
+        if let Some(category) = GameCategory::for_game(&config.name, steam_appid) {
+            return category;
         }
 
         // Use ProtonDB data if available
@@ -397,15 +762,16 @@ impl BoltGameManager {
         }
     }
 
-    /// Apply system-level optimizations
-    async fn apply_system_optimizations(&self, profile: &OptimizationProfile) -> anyhow::Result<()> {
-        // Set CPU governor
-        if let Some(governor) = &profile.cpu_governor {
-            let _ = std::process::Command::new("cpupower")
-                .args(&["frequency-set", "-g", governor])
-                .status();
-        }
-
+    #[cfg(feature = "container-bolt")]
+    /// Apply system-level optimizations for `session`, recording the previous
+    /// settings so they can be restored when it stops
+    async fn apply_system_optimizations(
+        &self,
+        session: &str,
+        profile: &OptimizationProfile,
+        nvidia_config: &NvidiaConfig,
+        amd_config: &AmdConfig,
+    ) {
         // Set process priority
         if let Some(nice) = profile.nice_level {
             let _ = std::process::Command::new("renice")
@@ -413,11 +779,85 @@ impl BoltGameManager {
                 .status();
         }
 
-        Ok(())
+        // CPU governor plus the GPU tuning for whichever vendor is present;
+        // each setting may fail on its own
+        let (snapshot, report) = crate::tuning::apply(
+            profile.cpu_governor.as_deref(),
+            crate::tuning::has_nvidia_gpu().then_some(nvidia_config),
+            crate::tuning::amd_gpu_device().map(|_| amd_config),
+        );
+        if !report.failed.is_empty() {
+            out!("⚠️  Some system tuning could not be applied:");
+        }
+        report.print();
+
+        if let Err(e) = crate::tuning::TuningState::begin_session(session, &snapshot) {
+            eout!("⚠️  Failed to save tuning state: {}", e);
+        }
+    }
+
+    #[cfg(feature = "container-bolt")]
+    /// Put back the settings recorded by `apply_system_optimizations` once no
+    /// other tuned session needs them
+    fn restore_system_optimizations(&self, session: &str) {
+        match crate::tuning::TuningState::end_session(session) {
+            Ok(report) => report.print(),
+            Err(e) => eout!("⚠️  Failed to restore system settings: {}", e),
+        }
+    }
+
+    /// Resolve what `launch_game_optimized` would do for `config` with
+    /// `profile`, with the same NVIDIA/AMD defaults for the category
+    pub fn preview_profile(
+        &self,
+        config: &crate::game::Game,
+        profile: &OptimizationProfile,
+        forge_config: &crate::config::Config,
+    ) -> ProfilePreview {
+        let nvidia_config = profile.nvidia_config.clone().unwrap_or_else(|| {
+            self.create_nvidia_config_for_category(&profile.game_category)
+        });
+        let amd_config = profile
+            .amd_config
+            .clone()
+            .unwrap_or_else(|| AmdConfig::for_category(&profile.game_category));
+
+        ProfilePreview {
+            profile: profile.name.clone(),
+            game: config.name.clone(),
+            command: self.build_launch_command(config, profile),
+            arguments: profile
+                .launch_options
+                .iter()
+                .filter(|option| !option.contains('='))
+                .cloned()
+                .collect(),
+            environment: self.build_optimized_environment(
+                config,
+                profile,
+                &nvidia_config,
+                &amd_config,
+                forge_config,
+            ),
+            wine_tricks: profile.wine_tricks.clone(),
+            nice_level: profile.nice_level,
+            system_changes: crate::tuning::plan(
+                profile.cpu_governor.as_deref(),
+                crate::tuning::has_nvidia_gpu().then_some(&nvidia_config),
+                crate::tuning::amd_gpu_device().map(|_| &amd_config),
+            ),
+        }
     }
 
     /// Build optimized environment variables
-    fn build_optimized_environment(&self, config: &crate::game::Game, profile: &OptimizationProfile, nvidia_config: &NvidiaConfig) -> Vec<String> {
+    fn build_optimized_environment(
+        &self,
+        config: &crate::game::Game,
+        profile: &OptimizationProfile,
+        nvidia_config: &NvidiaConfig,
+        amd_config: &AmdConfig,
+        forge_config: &crate::config::Config,
+    ) -> Vec<String> {
         let mut env_vars = vec![
             "DISPLAY=:0".to_string(),
             "NVIDIA_VISIBLE_DEVICES=all".to_string(),
@@ -435,17 +875,36 @@ impl BoltGameManager {
             env_vars.push("NVIDIA_RTX_ENABLED=1".to_string());
         }
 
+        // AMD Vulkan driver selection, ignored on other vendors
+        for (key, value) in amd_config.environment() {
+            env_vars.push(format!("{}={}", key, value));
+        }
+
         // Wine/Proton environment
         if let Some(proton_version) = &profile.proton_version {
             env_vars.push(format!("PROTON_VERSION={}", proton_version));
         }
         env_vars.push("WINEARCH=win64".to_string());
-        env_vars.push(format!("WINEPREFIX=/wine-prefix/{}", config.name.replace(" ", "-")));
+        let wine_prefix = format!("/wine-prefix/{}", config.name.replace(" ", "-"));
+        env_vars.push(format!("WINEPREFIX={}", wine_prefix));
+        for (key, value) in profile.sync_mode.configured(&forge_config.general).environment() {
+            env_vars.push(format!("{}={}", key, value));
+        }
+        let upscaling = config.upscaling.as_ref().or(profile.upscaling.as_ref());
+        for (key, value) in upscaling.iter().flat_map(|u| u.environment()) {
+            env_vars.push(format!("{}={}", key, value));
+        }
 
-        // Apply profile launch options as environment variables
+        // Apply profile launch options as environment variables, with
+        // placeholders like ${GAME_DIR} expanded for this game
+        let variables = crate::game_launcher::launch_variables(
+            config,
+            std::path::Path::new(&wine_prefix),
+            &forge_config.paths,
+        );
         for option in &profile.launch_options {
             if option.contains("=") {
-                env_vars.push(option.clone());
+                env_vars.push(crate::game_launcher::expand_variables(option, &variables));
             }
         }
 
@@ -468,7 +927,7 @@ impl BoltGameManager {
 
     /// Scan and optimize entire Steam library
     pub async fn scan_and_optimize_steam_library(&self) -> anyhow::Result<Vec<OptimizationProfile>> {
-        println!("🔍 Scanning Steam library for optimization...");
+        out!("🔍 Scanning Steam library for optimization...");
 
         // This would scan Steam's library and create optimized profiles
         // For now, create some example profiles
@@ -486,8 +945,13 @@ impl BoltGameManager {
                 "__GL_YIELD=USLEEP".to_string(),
             ],
             nvidia_config: Some(self.create_nvidia_config_for_category(&GameCategory::Competitive)),
+            amd_config: Some(AmdConfig::for_category(&GameCategory::Competitive)),
             cpu_governor: Some("performance".to_string()),
             nice_level: Some(-15),
+            shader_precache: false,
+            sync_mode: SyncMode::Auto,
+            upscaling: None,
+            fps_limit: None,
             created: Utc::now(),
             rating: 4.8,
             downloads: 0,
@@ -507,8 +971,13 @@ impl BoltGameManager {
             wine_tricks: vec!["vcrun2019".to_string(), "corefonts".to_string()],
             launch_options: vec![],
             nvidia_config: Some(self.create_nvidia_config_for_category(&GameCategory::AAA)),
+            amd_config: Some(AmdConfig::for_category(&GameCategory::AAA)),
             cpu_governor: Some("performance".to_string()),
             nice_level: Some(-10),
+            shader_precache: true,
+            sync_mode: SyncMode::Auto,
+            upscaling: None,
+            fps_limit: None,
             created: Utc::now(),
             rating: 4.6,
             downloads: 0,
@@ -519,15 +988,10 @@ impl BoltGameManager {
         self.optimization_manager.save_profile(&aaa_profile).await?;
         created_profiles.push(aaa_profile);
 
-        println!("✅ Created {} optimization profiles", created_profiles.len());
+        out!("✅ Created {} optimization profiles", created_profiles.len());
         Ok(created_profiles)
     }
 
-    /// Get ProtonDB client for external use
-    pub fn protondb(&self) -> &ProtonDBClient {
-        &self.protondb_client
-    }
-
     /// Get optimization manager for external use
     pub fn optimization_manager(&self) -> &OptimizationManager {
         &self.optimization_manager
@@ -538,6 +1002,10 @@ impl BoltGameManager {
         &self.drift_client
     }
 
+    pub fn drift_client_mut(&mut self) -> &mut DriftClient {
+        &mut self.drift_client
+    }
+
     // Existing methods for compatibility...
 
     #[cfg(feature = "container-bolt")]
@@ -557,48 +1025,236 @@ impl BoltGameManager {
             container.status = ContainerStatus::Stopped;
         }
 
+        self.restore_system_optimizations(container_id);
+
         Ok(())
     }
 
+    /// Fetch the last `tail` lines of a container's output
+    #[cfg(feature = "container-bolt")]
+    pub async fn container_logs(&self, container_id: &str, tail: usize) -> anyhow::Result<String> {
+        let output = tokio::process::Command::new("bolt")
+            .args(["logs", "--tail", &tail.to_string(), container_id])
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run bolt logs: {}", e))?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch logs: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(crate::container::format_logs(&output.stdout, &output.stderr))
+    }
+
+    /// Without Bolt, read the logs from the podman or docker runtime
     #[cfg(not(feature = "container-bolt"))]
-    pub async fn stop_game(&self, _container_id: &str) -> anyhow::Result<()> {
-        Err(anyhow::anyhow!("Bolt support not compiled in"))
+    pub async fn container_logs(&self, container_id: &str, tail: usize) -> anyhow::Result<String> {
+        let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+        let manager = crate::container::ContainerManager::new(config_dir)?;
+        manager.container_logs(container_id, tail).await
     }
 
+    #[cfg(feature = "container-bolt")]
     pub fn get_containers(&self) -> Vec<GameContainer> {
         self.containers.read().values().cloned().collect()
     }
 
-    #[cfg(feature = "container-bolt")]
-    pub async fn get_system_metrics(&self) -> anyhow::Result<BoltSystemMetrics> {
-        // Simplified metrics for now
-        Ok(BoltSystemMetrics {
-            running_containers: self.containers.read().values().filter(|c| matches!(c.status, ContainerStatus::Running)).count(),
-            total_containers: self.containers.read().len(),
-            cpu_usage: 0.0,
-            memory_usage: 0.0,
-            gpu_usage: 0.0,
-            network_activity: 0.0,
-        })
+    pub fn get_cached_metrics(&self) -> Option<BoltSystemMetrics> {
+        self.metrics.read().clone()
     }
 
-    #[cfg(not(feature = "container-bolt"))]
-    pub async fn get_system_metrics(&self) -> anyhow::Result<BoltSystemMetrics> {
-        Ok(BoltSystemMetrics {
-            running_containers: 0,
-            total_containers: 0,
-            cpu_usage: 0.0,
-            memory_usage: 0.0,
-            gpu_usage: 0.0,
-            network_activity: 0.0,
-        })
+    /// Refresh the cached metrics every `interval` on a background thread,
+    /// which exits once the manager is dropped
+    pub fn start_metrics_sampler(&self, interval: Duration) -> anyhow::Result<()> {
+        let containers = Arc::downgrade(&self.containers);
+        let metrics = Arc::downgrade(&self.metrics);
+        let sampler = Arc::clone(&self.sampler);
+
+        std::thread::Builder::new()
+            .name("bolt-metrics".to_string())
+            .spawn(move || {
+                while let (Some(containers), Some(metrics)) =
+                    (containers.upgrade(), metrics.upgrade())
+                {
+                    let sample = sampler.lock().sample(&containers.read());
+                    *metrics.write() = Some(sample);
+
+                    // Don't keep the manager alive while sleeping
+                    drop((containers, metrics));
+                    std::thread::sleep(interval);
+                }
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to start metrics sampler: {}", e))?;
+
+        Ok(())
     }
+}
 
-    pub fn get_cached_metrics(&self) -> Option<BoltSystemMetrics> {
-        self.metrics.read().clone()
+impl MetricsSampler {
+    fn sample(&mut self, containers: &HashMap<String, GameContainer>) -> BoltSystemMetrics {
+        BoltSystemMetrics {
+            running_containers: containers
+                .values()
+                .filter(|c| matches!(c.status, ContainerStatus::Running))
+                .count(),
+            total_containers: containers.len(),
+            cpu_usage: self.cpu_usage().unwrap_or(0.0),
+            memory_usage: memory_usage().unwrap_or(0.0),
+            gpu_usage: gpu_usage().unwrap_or(0.0),
+            network_activity: self.network_activity(containers),
+        }
+    }
+
+    /// CPU usage from the aggregate `cpu` line of `/proc/stat`. The first
+    /// sample reports the average since boot.
+    fn cpu_usage(&mut self) -> Option<f64> {
+        let stat = std::fs::read_to_string("/proc/stat").ok()?;
+        let fields: Vec<u64> = stat
+            .lines()
+            .next()?
+            .strip_prefix("cpu ")?
+            .split_whitespace()
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if fields.len() < 5 {
+            return None;
+        }
+
+        // idle and iowait
+        let idle = fields[3] + fields[4];
+        let total: u64 = fields.iter().take(8).sum();
+        let busy = total - idle;
+
+        let (prev_busy, prev_total) = std::mem::replace(&mut self.cpu, (busy, total));
+        let elapsed = total.saturating_sub(prev_total);
+        if elapsed == 0 {
+            return None;
+        }
+        Some(busy.saturating_sub(prev_busy) as f64 / elapsed as f64 * 100.0)
+    }
+
+    /// Receive and transmit rate of the running containers, in MB/s.
+    /// Containers are counted from their second sample on.
+    fn network_activity(&mut self, containers: &HashMap<String, GameContainer>) -> f64 {
+        let now = Instant::now();
+        let mut previous = std::mem::take(&mut self.network);
+        let mut rate = 0.0;
+
+        for container in containers
+            .values()
+            .filter(|c| matches!(c.status, ContainerStatus::Running))
+        {
+            let Some(bytes) = self.container_pid(&container.id).and_then(network_bytes) else {
+                continue;
+            };
+            if let Some((prev_bytes, prev_time)) = previous.remove(&container.id) {
+                let seconds = now.duration_since(prev_time).as_secs_f64();
+                if seconds > 0.0 {
+                    rate += bytes.saturating_sub(prev_bytes) as f64 / seconds / 1_000_000.0;
+                }
+            }
+            self.network.insert(container.id.clone(), (bytes, now));
+        }
+
+        self.pids.retain(|id, _| self.network.contains_key(id));
+        rate
+    }
+
+    /// PID of a container's init process, asked from Bolt once and reused
+    /// while the process lives
+    fn container_pid(&mut self, id: &str) -> Option<u32> {
+        if let Some(&pid) = self.pids.get(id)
+            && std::path::Path::new(&format!("/proc/{}", pid)).exists()
+        {
+            return Some(pid);
+        }
+
+        let output = std::process::Command::new("bolt")
+            .args(["inspect", "--format", "{{.State.Pid}}", id])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let pid: u32 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+        if pid == 0 {
+            return None;
+        }
+        self.pids.insert(id.to_string(), pid);
+        Some(pid)
     }
 }
 
+/// Bytes received and sent on every interface but loopback in the network
+/// namespace of `pid`
+fn network_bytes(pid: u32) -> Option<u64> {
+    let net_dev = std::fs::read_to_string(format!("/proc/{}/net/dev", pid)).ok()?;
+    let bytes = net_dev
+        .lines()
+        .skip(2)
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(iface, _)| iface.trim() != "lo")
+        .filter_map(|(_, counters)| {
+            let counters: Vec<u64> = counters
+                .split_whitespace()
+                .filter_map(|v| v.parse().ok())
+                .collect();
+            // rx_bytes is the first column, tx_bytes the ninth
+            Some(counters.first()? + counters.get(8)?)
+        })
+        .sum();
+    Some(bytes)
+}
+
+/// Used memory from `/proc/meminfo`, counting reclaimable cache as free
+fn memory_usage() -> Option<f64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let field = |name: &str| -> Option<f64> {
+        meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    };
+
+    let total = field("MemTotal")?;
+    let available = field("MemAvailable")?;
+    (total > 0.0).then(|| (total - available) / total * 100.0)
+}
+
+/// GPU utilization from `nvidia-smi`, or from amdgpu's `gpu_busy_percent`
+fn gpu_usage() -> Option<f64> {
+    if crate::tuning::has_nvidia_gpu() {
+        let output = std::process::Command::new("nvidia-smi")
+            .args([
+                "--query-gpu=utilization.gpu",
+                "--format=csv,noheader,nounits",
+            ])
+            .output()
+            .ok()?;
+        if output.status.success() {
+            return String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()?
+                .trim()
+                .parse()
+                .ok();
+        }
+    }
+
+    let device = crate::tuning::amd_gpu_device()?;
+    std::fs::read_to_string(device.join("gpu_busy_percent"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
 // Implementation for OptimizationManager
 impl OptimizationManager {
     pub fn new(profile_dir: std::path::PathBuf) -> anyhow::Result<Self> {
@@ -668,8 +1324,17 @@ impl OptimizationManager {
             wine_tricks,
             launch_options,
             nvidia_config: None, // Will be set by caller
+            amd_config: None,
             cpu_governor: Some("performance".to_string()),
             nice_level: Some(-10),
+            shader_precache: matches!(category, GameCategory::AAA),
+            // Games rated below Gold are often unstable with esync
+            sync_mode: match protondb_tier {
+                Some(ProtonDBTier::Platinum | ProtonDBTier::Gold) | None => SyncMode::Auto,
+                Some(_) => SyncMode::Fsync,
+            },
+            upscaling: None,
+            fps_limit: None,
             created: Utc::now(),
             rating: 0.0,
             downloads: 0,
@@ -698,6 +1363,38 @@ impl OptimizationManager {
         self.profiles.read().get(name).cloned()
     }
 
+    /// Profile a launch of `game` uses: the one assigned to it, one named
+    /// after it, or the recommendation for its category
+    pub fn profile_for_game(&self, game: &crate::game::Game) -> Option<OptimizationProfile> {
+        game.profile
+            .as_deref()
+            .and_then(|name| self.get_profile(name))
+            .or_else(|| self.get_profile(&game.name))
+            .or_else(|| {
+                GameCategory::for_game(&game.name, game.steam_appid())
+                    .and_then(|category| self.recommended_profile(&category))
+            })
+    }
+
+    /// Best profile for games of `category`: the built-in one, otherwise
+    /// the highest rated. None for uncategorized games.
+    pub fn recommended_profile(&self, category: &GameCategory) -> Option<OptimizationProfile> {
+        if *category == GameCategory::Unknown {
+            return None;
+        }
+        self.profiles
+            .read()
+            .values()
+            .filter(|profile| profile.game_category == *category)
+            .max_by(|a, b| {
+                (a.author == "system")
+                    .cmp(&(b.author == "system"))
+                    .then(a.rating.total_cmp(&b.rating))
+                    .then(b.name.cmp(&a.name))
+            })
+            .cloned()
+    }
+
     pub async fn delete_profile(&self, name: &str) -> anyhow::Result<()> {
         let profile_file = self.profile_dir.join(format!("{}.json", name));
         if profile_file.exists() {
@@ -713,24 +1410,245 @@ impl DriftClient {
     pub fn new() -> Self {
         Self {
             base_url: "https://registry.ghostforge.dev".to_string(),
-            client: reqwest::Client::new(),
-            auth_token: None,
+            client: crate::download::http_client(),
+            auth_token: Self::load_token(),
+            offline: false,
         }
     }
 
-    pub async fn search_profiles(&self, query: &str, category: Option<&GameCategory>) -> anyhow::Result<Vec<CommunityProfile>> {
-        // Simulate community profile search
-        // In real implementation, this would query the Drift registry
+    /// File holding the token saved by `forge profile login`
+    pub fn token_path() -> std::path::PathBuf {
+        dirs::config_dir()
+            .unwrap_or_default()
+            .join("ghostforge")
+            .join("drift-token")
+    }
+
+    fn load_token() -> Option<String> {
+        let token = std::fs::read_to_string(Self::token_path()).ok()?;
+        let token = token.trim();
+        (!token.is_empty()).then(|| token.to_string())
+    }
+
+    /// Persist the registry token, readable only by the current user
+    pub fn save_token(&mut self, token: &str) -> anyhow::Result<()> {
+        use std::io::Write;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let path = Self::token_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&path)?;
+        // `mode` only applies on creation; tighten an existing file too
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        file.write_all(token.trim().as_bytes())?;
+
+        self.auth_token = Some(token.trim().to_string());
+        Ok(())
+    }
+
+    /// Forget the saved token; returns false if there was none
+    pub fn clear_token(&mut self) -> anyhow::Result<bool> {
+        self.auth_token = None;
+        let path = Self::token_path();
+        if path.exists() {
+            std::fs::remove_file(path)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn require_login(&self) -> anyhow::Result<()> {
+        if self.auth_token.is_none() {
+            return Err(anyhow::anyhow!(
+                "Not logged in to the Drift registry, please run 'forge profile login'"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serve built-in sample profiles instead of talking to the registry
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("User-Agent", "GhostForge");
+
+        match &self.auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Send a request and turn non-2xx responses into errors carrying the body
+    async fn send(&self, request: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let response = crate::download::send_with_retry(request).await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(anyhow::anyhow!(
+            "Drift registry returned {}: {}",
+            status,
+            body.trim()
+        ))
+    }
+
+    pub async fn search_profiles(
+        &self,
+        query: &str,
+        category: Option<&GameCategory>,
+    ) -> anyhow::Result<Vec<CommunityProfile>> {
+        if self.offline {
+            return Ok(Self::sample_profiles(query, category));
+        }
+
+        let mut params = vec![("q", query.to_string())];
+        if let Some(category) = category {
+            params.push(("category", category.slug().to_string()));
+        }
+
+        let request = self
+            .request(reqwest::Method::GET, "/profiles")
+            .query(&params);
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    pub async fn install_profile(
+        &self,
+        profile_id: &str,
+        profile_dir: &std::path::Path,
+    ) -> anyhow::Result<OptimizationProfile> {
+        let community_profile = if self.offline {
+            Self::sample_profiles(profile_id, None)
+                .into_iter()
+                .find(|p| p.id == profile_id)
+                .ok_or_else(|| anyhow::anyhow!("Profile not found: {}", profile_id))?
+        } else {
+            let request = self.request(
+                reqwest::Method::GET,
+                &format!("/profiles/{}", path_segment(profile_id)),
+            );
+            self.send(request).await?.json::<CommunityProfile>().await?
+        };
+
+        let problems = community_profile.profile.validate();
+        if !problems.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Refusing to install profile {}: {}",
+                profile_id,
+                problems.join("; ")
+            ));
+        }
+
+        std::fs::create_dir_all(profile_dir)?;
+        let profile_file = profile_dir.join(format!("{}.json", community_profile.profile.name));
+        let json = serde_json::to_string_pretty(&community_profile.profile)?;
+        std::fs::write(profile_file, json)?;
+
+        out!(
+            "✅ Installed community profile: {} ({}⭐ {} downloads)",
+            community_profile.profile.name,
+            community_profile.metadata.rating,
+            community_profile.metadata.downloads
+        );
+
+        Ok(community_profile.profile)
+    }
+
+    pub async fn share_profile(&self, profile: &OptimizationProfile) -> anyhow::Result<String> {
+        out!("🌍 Sharing profile '{}' to community...", profile.name);
+
+        if self.offline {
+            return Ok(format!(
+                "{}-{}",
+                profile.author,
+                profile.name.replace(" ", "-")
+            ));
+        }
+        self.require_login()?;
+
+        #[derive(Deserialize)]
+        struct ShareResponse {
+            id: String,
+        }
+
+        let request = self
+            .request(reqwest::Method::POST, "/profiles")
+            .json(profile);
+        let shared: ShareResponse = self.send(request).await?.json().await?;
+        Ok(shared.id)
+    }
+
+    pub async fn rate_profile(
+        &self,
+        profile_id: &str,
+        rating: f32,
+        comment: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if self.offline {
+            return Ok(());
+        }
+        self.require_login()?;
+
+        let mut body = serde_json::json!({ "rating": rating });
+        if let Some(comment) = comment {
+            body["comment"] = serde_json::Value::from(comment);
+        }
+
+        let request = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/profiles/{}/ratings", path_segment(profile_id)),
+            )
+            .json(&body);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    /// Fetch the ratings and comments left on a profile, newest first
+    pub async fn get_reviews(&self, profile_id: &str) -> anyhow::Result<Vec<ProfileReview>> {
+        if self.offline {
+            return Ok(Vec::new());
+        }
+
+        let request = self.request(
+            reqwest::Method::GET,
+            &format!("/profiles/{}/ratings", path_segment(profile_id)),
+        );
+        let mut reviews: Vec<ProfileReview> = self.send(request).await?.json().await?;
+        reviews.sort_by_key(|r| std::cmp::Reverse(r.created));
+        Ok(reviews)
+    }
+
+    /// Built-in profiles served in offline mode
+    fn sample_profiles(query: &str, category: Option<&GameCategory>) -> Vec<CommunityProfile> {
         let mut profiles = Vec::new();
 
-        // Mock some community profiles
-        if query.to_lowercase().contains("competitive") ||
-           (category.is_some() && matches!(category.unwrap(), GameCategory::Competitive)) {
+        if query.is_empty()
+            || query.to_lowercase().contains("competitive")
+            || query == "esports-cs2"
+            || matches!(category, Some(GameCategory::Competitive))
+        {
             profiles.push(CommunityProfile {
                 id: "esports-cs2".to_string(),
                 profile: OptimizationProfile {
                     name: "esports-cs2".to_string(),
-                    description: "Professional eSports configuration for Counter-Strike 2".to_string(),
+                    description: "Professional eSports configuration for Counter-Strike 2"
+                        .to_string(),
                     game_category: GameCategory::Competitive,
                     proton_version: Some("GE-Proton8-26".to_string()),
                     wine_tricks: vec![],
@@ -748,8 +1666,13 @@ impl DriftClient {
                         memory_clock_offset: Some(1200),
                         core_clock_offset: Some(250),
                     }),
+                    amd_config: None,
                     cpu_governor: Some("performance".to_string()),
                     nice_level: Some(-20),
+                    shader_precache: false,
+                    sync_mode: SyncMode::Auto,
+                    upscaling: None,
+                    fps_limit: None,
                     created: Utc::now(),
                     rating: 4.9,
                     downloads: 15420,
@@ -768,55 +1691,22 @@ impl DriftClient {
             });
         }
 
-        Ok(profiles)
-    }
-
-    pub async fn install_profile(&self, profile_id: &str, profile_dir: &std::path::Path) -> anyhow::Result<OptimizationProfile> {
-        // Simulate downloading and installing a community profile
-        let profiles = self.search_profiles(profile_id, None).await?;
-
-        if let Some(community_profile) = profiles.into_iter().find(|p| p.id == profile_id) {
-            let profile_file = profile_dir.join(format!("{}.json", community_profile.profile.name));
-            let json = serde_json::to_string_pretty(&community_profile.profile)?;
-            std::fs::write(profile_file, json)?;
-
-            println!("✅ Installed community profile: {} ({}⭐ {} downloads)",
-                community_profile.profile.name,
-                community_profile.metadata.rating,
-                community_profile.metadata.downloads
-            );
-
-            Ok(community_profile.profile)
-        } else {
-            Err(anyhow::anyhow!("Profile not found: {}", profile_id))
-        }
-    }
-
-    pub async fn share_profile(&self, profile: &OptimizationProfile) -> anyhow::Result<String> {
-        // Simulate uploading profile to community registry
-        println!("🌍 Sharing profile '{}' to community...", profile.name);
-
-        // In real implementation, this would upload to Drift registry
-        // For now, just simulate success
-        let profile_id = format!("{}-{}", profile.author, profile.name.replace(" ", "-"));
-
-        println!("✅ Profile shared successfully! ID: {}", profile_id);
-        Ok(profile_id)
-    }
-
-    pub async fn rate_profile(&self, profile_id: &str, rating: f32) -> anyhow::Result<()> {
-        println!("⭐ Rated profile '{}' with {:.1}/5.0 stars", profile_id, rating);
-        Ok(())
+        profiles
     }
+}
 
-    pub async fn get_trending_profiles(&self, limit: usize) -> anyhow::Result<Vec<CommunityProfile>> {
-        // Return mock trending profiles
-        let all_profiles = self.search_profiles("", None).await?;
-        let mut trending = all_profiles;
-        trending.sort_by(|a, b| b.metadata.downloads.cmp(&a.metadata.downloads));
-        trending.truncate(limit);
-        Ok(trending)
-    }
+/// Percent-encode `segment` so a profile ID can't add path components or a
+/// query string to a registry URL
+fn path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
 }
 
 impl Default for BoltGameManager {
@@ -833,6 +1723,7 @@ impl Default for BoltGameManager {
                 runtime: None,
                 containers: Arc::new(RwLock::new(HashMap::new())),
                 metrics: Arc::new(RwLock::new(None)),
+                sampler: Arc::new(Mutex::new(MetricsSampler::default())),
                 optimization_manager: OptimizationManager::new(profile_dir).unwrap_or_else(|_| {
                     OptimizationManager {
                         profiles: Arc::new(RwLock::new(HashMap::new())),
@@ -840,10 +1731,38 @@ impl Default for BoltGameManager {
                     }
                 }),
                 drift_client: DriftClient::new(),
-                protondb_client: ProtonDBClient::new(),
+                protondb_client: configured_protondb_client(),
                 #[cfg(not(feature = "container-bolt"))]
                 _phantom: std::marker::PhantomData,
             }
         })
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_options_may_reference_variables() {
+        let shell_free = |option: &str| {
+            !without_variable_references(option).contains(SHELL_METACHARACTERS)
+        };
+
+        assert!(shell_free("PROTON_LOG_DIR=${GAME_DIR}/logs"));
+        assert!(shell_free("-config=$WINEPREFIX/drive_c/game.cfg"));
+        assert!(shell_free("PRICE=$$5"));
+        assert!(!shell_free("${GAME_DIR}; rm -rf ~"));
+        assert!(!shell_free("$(whoami)"));
+        assert!(!shell_free("${GAME DIR}"));
+        assert!(!shell_free("${UNCLOSED"));
+        assert!(!shell_free("$5"));
+    }
+
+    #[test]
+    fn profile_ids_stay_in_one_path_segment() {
+        assert_eq!(path_segment("esports-cs2"), "esports-cs2");
+        assert_eq!(path_segment("../admin?x=1"), "..%2Fadmin%3Fx%3D1");
+        assert_eq!(path_segment("a b#"), "a%20b%23");
+    }
+}
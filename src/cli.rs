@@ -1,9 +1,13 @@
 use crate::game_launcher::{GameLauncher, LaunchOptions};
 use crate::protondb::ProtonDBTier;
-use crate::bolt_integration::{GameCategory, OptimizationProfile, NvidiaConfig};
+use crate::bolt_integration::{
+    AmdConfig, AmdVulkanDriver, FanCurvePoint, GameCategory, NvidiaConfig, OptimizationProfile,
+};
+use crate::utils::{eout, out};
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::*;
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -18,11 +22,31 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    #[arg(short, long, global = true, help = "Enable verbose output")]
-    pub verbose: bool,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        help = "Enable verbose output (-vv for trace logs)"
+    )]
+    pub verbose: u8,
 
     #[arg(long, global = true, help = "Path to config file")]
     pub config: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print JSON instead of formatted output (info, doctor and list commands)"
+    )]
+    pub json: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Disable colored output (also disabled by NO_COLOR or when not a terminal)"
+    )]
+    pub no_color: bool,
 }
 
 #[derive(Subcommand)]
@@ -41,7 +65,7 @@ pub enum Commands {
 
     #[command(about = "Launch a game")]
     Launch {
-        #[arg(help = "Game ID or name")]
+        #[arg(help = "Game ID, name or part of the name")]
         game: String,
 
         #[arg(long, help = "Override Wine/Proton version")]
@@ -49,6 +73,48 @@ pub enum Commands {
 
         #[arg(long, help = "Additional launch arguments")]
         args: Vec<String>,
+
+        #[arg(long, help = "Ignore cached ProtonDB data")]
+        refresh_protondb: bool,
+
+        #[arg(long, help = "Turn on HDR for the game and its displays")]
+        hdr: bool,
+
+        #[arg(long, help = "GPU to render on, see 'forge game gpu'")]
+        gpu: Option<usize>,
+
+        #[arg(long, help = "Run the game inside gamescope")]
+        gamescope: bool,
+
+        #[arg(
+            short = 'W',
+            long,
+            requires = "gamescope",
+            help = "Gamescope output width (default: display width)"
+        )]
+        width: Option<u32>,
+
+        #[arg(
+            short = 'H',
+            long,
+            requires = "gamescope",
+            help = "Gamescope output height (default: display height)"
+        )]
+        height: Option<u32>,
+
+        #[arg(
+            long,
+            requires = "gamescope",
+            help = "Gamescope refresh rate (default: display refresh rate)"
+        )]
+        fps: Option<u32>,
+
+        #[arg(
+            long,
+            requires = "gamescope",
+            help = "Upscale from a lower resolution (fsr, nis)"
+        )]
+        upscale: Option<String>,
     },
 
     #[command(about = "Install a game from various sources")]
@@ -75,16 +141,29 @@ pub enum Commands {
         action: LauncherCommands,
     },
 
-    #[command(about = "Apply Winetricks/tweaks to a game prefix")]
+    #[command(
+        about = "Apply Winetricks/tweaks to a game prefix",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
     Tricks {
-        #[arg(help = "Game ID or name")]
-        game: String,
+        #[command(subcommand)]
+        action: Option<TricksCommands>,
+
+        #[arg(required = true, help = "Game ID or name")]
+        game: Option<String>,
 
-        #[arg(help = "Trick/tweak to apply")]
-        trick: String,
+        #[arg(required = true, help = "Tricks/tweaks to apply, in order")]
+        tricks: Vec<String>,
 
         #[arg(long, help = "Force apply even if already installed")]
         force: bool,
+
+        #[arg(long, help = "Keep going when a trick fails")]
+        continue_on_error: bool,
+
+        #[arg(long, help = "Only show what would be run")]
+        dry_run: bool,
     },
 
     #[command(about = "Optimize game performance and GPU settings")]
@@ -98,11 +177,17 @@ pub enum Commands {
         #[arg(long, help = "Apply AMD optimizations")]
         amd: bool,
 
+        #[arg(long, help = "Apply Intel Arc optimizations")]
+        intel: bool,
+
         #[arg(long, help = "Enable GameMode")]
         gamemode: bool,
 
         #[arg(long, help = "Set CPU governor to performance")]
         cpu_performance: bool,
+
+        #[arg(long, help = "Restore the settings from before tuning was applied")]
+        reset: bool,
     },
 
     #[command(about = "Search for games and settings")]
@@ -132,6 +217,9 @@ pub enum Commands {
         full: bool,
     },
 
+    #[command(about = "Check the system for problems that keep games from running well")]
+    Doctor,
+
     #[command(about = "Backup and restore game configurations")]
     Backup {
         #[command(subcommand)]
@@ -150,6 +238,18 @@ pub enum Commands {
         action: GraphicsCommands,
     },
 
+    #[command(about = "Manage displays, refresh rates and VRR")]
+    Display {
+        #[command(subcommand)]
+        action: DisplayCommands,
+    },
+
+    #[command(about = "Manage Wine prefixes and their snapshots")]
+    Prefix {
+        #[command(subcommand)]
+        action: PrefixCommands,
+    },
+
     #[command(about = "Launch Terminal UI")]
     Tui,
 
@@ -158,6 +258,13 @@ pub enum Commands {
 
     #[command(about = "Manage optimization profiles for superior gaming performance")]
     Profile {
+        #[arg(
+            long,
+            global = true,
+            help = "Use sample profiles, not the registry, and skip ProtonDB lookups"
+        )]
+        offline: bool,
+
         #[command(subcommand)]
         action: ProfileCommands,
     },
@@ -215,12 +322,43 @@ pub enum ProfileCommands {
         #[arg(long, help = "Core clock offset (MHz)")]
         core_offset: Option<i32>,
 
+        #[arg(long, help = "AMD power profile (3D_FULL_SCREEN, VR, COMPUTE, ...)")]
+        amd_power_profile: Option<String>,
+
+        #[arg(long, help = "AMD Vulkan driver (radv, amdvlk)")]
+        vulkan_driver: Option<String>,
+
+        #[arg(long, action = clap::ArgAction::Append, help = "RADV_PERFTEST features (gpl, sam, rt, ...)")]
+        radv_perftest: Vec<String>,
+
+        #[arg(long, help = "AMD fan curve as °C:% pairs, e.g. 40:30,60:50,80:80")]
+        fan_curve: Option<String>,
+
         #[arg(long, help = "CPU governor (performance, powersave, ondemand)")]
         cpu_governor: Option<String>,
 
         #[arg(long, help = "Process nice level (-20 to 19)")]
         nice_level: Option<i32>,
 
+        #[arg(long, help = "Precompile shaders at launch (always on for aaa)")]
+        shader_precache: bool,
+
+        #[arg(long, help = "Wine sync mode (auto, ntsync, fsync, esync, none)")]
+        sync: Option<String>,
+
+        #[arg(long, help = "Upscaler (fsr, gamescope-fsr, nis)")]
+        upscale: Option<String>,
+
+        #[arg(
+            long,
+            requires = "upscale",
+            help = "Upscaling sharpness, 0 (sharpest) to 5"
+        )]
+        upscale_strength: Option<u8>,
+
+        #[arg(long, help = "Frame rate cap for games without their own")]
+        fps_limit: Option<u32>,
+
         #[arg(long, action = clap::ArgAction::Append, help = "Wine tricks to apply")]
         wine_tricks: Vec<String>,
 
@@ -285,6 +423,21 @@ pub enum ProfileCommands {
         benchmarks: bool,
     },
 
+    #[command(about = "Show what a profile would set for a game, without applying it")]
+    Preview {
+        #[arg(help = "Profile name")]
+        profile: String,
+
+        #[arg(long, help = "Game ID or name")]
+        game: String,
+    },
+
+    #[command(about = "Assign recommended profiles to library games by category")]
+    AutoAssign {
+        #[arg(long, help = "Reassign games that already have a profile")]
+        all: bool,
+    },
+
     #[command(about = "Copy/clone an existing profile")]
     Clone {
         #[arg(help = "Source profile name")]
@@ -330,6 +483,15 @@ pub enum ProfileCommands {
         comment: Option<String>,
     },
 
+    #[command(about = "Show reviews for a community profile")]
+    Reviews {
+        #[arg(help = "Profile ID")]
+        profile_id: String,
+
+        #[arg(long, help = "Maximum reviews to show", default_value = "10")]
+        limit: usize,
+    },
+
     #[command(about = "Export profile to file")]
     Export {
         #[arg(help = "Profile name")]
@@ -350,6 +512,15 @@ pub enum ProfileCommands {
         #[arg(long, help = "Override existing profile")]
         force: bool,
     },
+
+    #[command(about = "Log in to the community profile registry")]
+    Login {
+        #[arg(help = "Registry API token (prompted for when omitted)")]
+        token: Option<String>,
+    },
+
+    #[command(about = "Remove the saved registry token")]
+    Logout,
 }
 
 #[derive(Subcommand)]
@@ -359,7 +530,13 @@ pub enum GameCommands {
         #[arg(long, help = "Filter by launcher")]
         launcher: Option<String>,
 
-        #[arg(long, help = "Filter by status")]
+        #[arg(long, help = "Filter by category")]
+        category: Option<String>,
+
+        #[arg(long, help = "Filter by tag")]
+        tag: Option<String>,
+
+        #[arg(long, help = "Filter by status (installed, not-installed, running)")]
         status: Option<String>,
     },
 
@@ -380,14 +557,122 @@ pub enum GameCommands {
         #[arg(help = "Game ID or name")]
         game: String,
 
-        #[arg(long, help = "Also remove game files")]
+        #[arg(long, help = "Also remove game files and the game's unused prefix")]
         purge: bool,
+
+        #[arg(long, help = "Delete without asking for confirmation")]
+        yes: bool,
     },
 
-    #[command(about = "Edit game configuration")]
+    #[command(about = "Edit game configuration, in $EDITOR when no options are given")]
     Edit {
         #[arg(help = "Game ID or name")]
         game: String,
+
+        #[arg(long, help = "Set the Wine/Proton version ('none' for default)")]
+        wine: Option<String>,
+
+        #[arg(long, help = "Pin a DXVK version ('none' to unpin)")]
+        dxvk: Option<String>,
+
+        #[arg(long, help = "Pin a VKD3D-Proton version ('none' to unpin)")]
+        vkd3d: Option<String>,
+
+        #[arg(
+            long,
+            help = "Vulkan device to use, by (part of) its name ('none' for default)"
+        )]
+        vulkan_device: Option<String>,
+    },
+
+    #[command(about = "Manage a game's environment variables and launch arguments")]
+    LaunchOptions {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(
+            long,
+            allow_hyphen_values = true,
+            help = "KEY=VALUE sets an environment variable, anything else appends a launch argument"
+        )]
+        set: Vec<String>,
+
+        #[arg(long, help = "Clear existing env vars and arguments first")]
+        clear: bool,
+    },
+
+    #[command(about = "Configure the MangoHud overlay for a game")]
+    Mangohud {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(long, help = "Overlay preset (minimal, full)")]
+        preset: Option<String>,
+
+        #[arg(long, help = "Overlay position (e.g., top-left, bottom-right)")]
+        position: Option<String>,
+
+        #[arg(long, help = "Turn the overlay off for this game")]
+        off: bool,
+
+        #[arg(long, help = "Use the global MangoHud setting again")]
+        reset: bool,
+    },
+
+    #[command(about = "Choose the GPU a game renders on")]
+    Gpu {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(help = "GPU index, lists the GPUs when left out")]
+        index: Option<usize>,
+
+        #[arg(long, help = "Render on the discrete GPU again")]
+        reset: bool,
+    },
+
+    #[command(about = "Set up FSR/NIS upscaling for a game")]
+    Upscale {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(long, conflicts_with_all = ["nis", "off"], help = "Upscale with FSR")]
+        fsr: bool,
+
+        #[arg(long, conflicts_with = "off", help = "Upscale with NIS in gamescope")]
+        nis: bool,
+
+        #[arg(long, requires = "fsr", help = "Run FSR in gamescope instead of Wine")]
+        gamescope: bool,
+
+        #[arg(long, help = "Sharpening from 0 (sharpest) to 5 [default: 2]")]
+        strength: Option<u8>,
+
+        #[arg(long, help = "Turn upscaling off")]
+        off: bool,
+    },
+
+    #[command(about = "Cap a game's frame rate with MangoHud or libstrangle")]
+    FpsLimit {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(
+            help = "Frames per second, 'vrr' for just under the display's refresh rate, or 'off'"
+        )]
+        limit: Option<String>,
+    },
+
+    #[command(about = "Link a game to an optimization profile")]
+    SetProfile {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(required_unless_present = "clear", help = "Profile name")]
+        profile: Option<String>,
+
+        #[arg(long, conflicts_with = "profile", help = "Unlink the game's profile")]
+        clear: bool,
     },
 
     #[command(about = "Show game details")]
@@ -401,6 +686,53 @@ pub enum GameCommands {
         #[arg(help = "Game ID or name")]
         game: String,
     },
+
+    #[command(about = "List games that are currently running")]
+    Running,
+
+    #[command(about = "Stop a running game")]
+    Stop {
+        #[arg(help = "Game ID or name")]
+        game: String,
+    },
+
+    #[command(about = "Move a game's install directory to another location")]
+    Move {
+        #[arg(help = "Game ID or name")]
+        game: String,
+        #[arg(help = "Directory to move the game into")]
+        dest: String,
+        #[arg(long, help = "Also move the game's Wine prefix")]
+        prefix: bool,
+    },
+
+    #[command(about = "Show the disk space used by games, prefixes and shader caches")]
+    Usage {
+        #[arg(long, default_value = "10", help = "Number of largest games to list")]
+        top: usize,
+    },
+
+    #[command(about = "Export the library to a JSON or TOML bundle")]
+    Export {
+        #[arg(help = "Bundle file, TOML when it ends in .toml")]
+        file: String,
+    },
+
+    #[command(about = "Import games from a bundle made with 'forge game export'")]
+    Import {
+        #[arg(help = "Bundle file")]
+        file: String,
+
+        #[arg(
+            long,
+            conflicts_with = "merge",
+            help = "Replace games with the same ID"
+        )]
+        replace: bool,
+
+        #[arg(long, help = "Keep games with the same ID")]
+        merge: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -411,10 +743,18 @@ pub enum WineCommands {
         available: bool,
     },
 
-    #[command(about = "Install a Wine/Proton version")]
+    #[command(about = "Install Wine/Proton versions")]
     Install {
-        #[arg(help = "Version to install (e.g., Proton-9.0, Wine-staging-9.0)")]
-        version: String,
+        #[arg(required = true, help = "Versions to install (e.g., GE-Proton9-20)")]
+        versions: Vec<String>,
+
+        #[arg(
+            short,
+            long,
+            help = "Versions to download at once",
+            default_value_t = crate::download::DEFAULT_CONCURRENCY
+        )]
+        jobs: usize,
     },
 
     #[command(about = "Remove a Wine/Proton version")]
@@ -437,6 +777,12 @@ pub enum WineCommands {
         #[arg(long, help = "Specific version")]
         version: Option<String>,
     },
+
+    #[command(about = "Install newer GE-Proton releases than the ones installed")]
+    Update {
+        #[arg(help = "Only check this installed version")]
+        version: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -464,6 +810,24 @@ pub enum ConfigCommands {
         #[arg(long, help = "Confirm reset")]
         yes: bool,
     },
+
+    #[command(about = "Clear cached ProtonDB reports")]
+    ClearCache {
+        #[arg(long, help = "Only clear the cache for this Steam app ID")]
+        appid: Option<u32>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TricksCommands {
+    #[command(about = "Show or clear downloaded Winetricks installers shared by all prefixes")]
+    Cache {
+        #[arg(long, help = "Show the cache size per verb (default)")]
+        size: bool,
+
+        #[arg(long, conflicts_with = "size", help = "Delete all cached downloads")]
+        clear: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -473,16 +837,16 @@ pub enum LauncherCommands {
 
     #[command(about = "Setup a launcher (Steam, Battle.net, Epic, etc.)")]
     Setup {
-        #[arg(help = "Launcher type (steam, battlenet, epic, gog, ubisoft)")]
+        #[arg(help = "Launcher type (steam, battlenet, epic, gog, ubisoft, ea)")]
         launcher: String,
 
-        #[arg(long, help = "Path to launcher installation")]
+        #[arg(long, help = "Existing install to register (Wine prefix or Steam dir)")]
         path: Option<String>,
     },
 
     #[command(about = "Sync games from launcher")]
     Sync {
-        #[arg(help = "Launcher to sync from")]
+        #[arg(help = "Launcher to sync from, or 'all'")]
         launcher: String,
     },
 
@@ -608,18 +972,201 @@ pub enum GraphicsCommands {
         #[arg(help = "Game name")]
         game: String,
     },
+
+    #[command(about = "Pin a graphics layer version for a game")]
+    Pin {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(help = "Layer type (dxvk, vkd3d)")]
+        layer: String,
+
+        #[arg(help = "Version to pin (e.g., v2.3), or 'none' to unpin")]
+        version: String,
+    },
+
+    #[command(about = "Show, back up or clear a game's DXVK/VKD3D state caches")]
+    Cache {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(long, help = "Delete the caches, a fix for stutter after DXVK updates")]
+        clear: bool,
+
+        #[arg(long, help = "Copy the caches to the backups directory first")]
+        backup: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DisplayCommands {
+    #[command(about = "List displays with their modes, VRR and HDR state")]
+    List,
+
+    #[command(about = "Turn variable refresh rate on or off for a display")]
+    Vrr {
+        #[arg(help = "on or off")]
+        state: String,
+
+        #[arg(help = "Display connector (e.g., DP-1)")]
+        connector: String,
+    },
+
+    #[command(about = "Change a display's refresh rate")]
+    Refresh {
+        #[arg(help = "Display connector (e.g., DP-1)")]
+        connector: String,
+
+        #[arg(help = "Refresh rate in Hz")]
+        hz: u32,
+    },
+
+    #[command(about = "Set refresh rate, VRR and VSync for a target frame rate")]
+    Optimize {
+        #[arg(help = "Target frame rate")]
+        fps: u32,
+
+        #[arg(long, help = "Also switch HDR-capable displays to HDR")]
+        hdr: bool,
+    },
+
+    #[command(about = "Restore the display settings from before 'forge display optimize'")]
+    Restore,
+
+    #[command(about = "Show live frame timing and VRR activity")]
+    Monitor {
+        #[arg(long, default_value = "30", help = "How long to monitor, in seconds")]
+        seconds: u64,
+    },
+
+    #[command(about = "Manage display profiles")]
+    Profile {
+        #[command(subcommand)]
+        action: DisplayProfileCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DisplayProfileCommands {
+    #[command(about = "List saved and built-in display profiles")]
+    List,
+
+    #[command(
+        about = "Save the current display setup as a profile",
+        alias = "save-profile"
+    )]
+    Save {
+        #[arg(help = "Profile name")]
+        name: String,
+
+        #[arg(long, help = "Profile description")]
+        description: Option<String>,
+
+        #[arg(long, help = "Mark the profile as gaming optimized")]
+        gaming: bool,
+    },
+
+    #[command(
+        about = "Apply a saved or built-in display profile (desktop, gaming)",
+        alias = "apply-profile"
+    )]
+    Apply {
+        #[arg(help = "Profile name")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PrefixCommands {
+    #[command(about = "List the Wine prefixes of games and launchers")]
+    List,
+
+    #[command(about = "Show a prefix's Wine setup, DLL overrides and installed components")]
+    Info {
+        #[arg(help = "Prefix path, or a game ID or name")]
+        path: String,
+    },
+
+    #[command(about = "Create a Wine prefix")]
+    Create {
+        #[arg(help = "Prefix path")]
+        path: String,
+
+        #[arg(long, help = "Architecture (win32, win64)", default_value = "win64")]
+        arch: String,
+
+        #[arg(
+            long,
+            help = "Windows version (win10, win7, ...)",
+            default_value = "win10"
+        )]
+        winver: String,
+
+        #[arg(long, help = "Wine/Proton version to use")]
+        wine_version: Option<String>,
+    },
+
+    #[command(about = "List the snapshots of a game's prefix")]
+    Snapshots {
+        #[arg(help = "Game ID or name")]
+        game: String,
+    },
+
+    #[command(about = "Restore a game's prefix from its last snapshot")]
+    Rollback {
+        #[arg(help = "Game ID or name")]
+        game: String,
+
+        #[arg(long, help = "Snapshot ID to restore instead of the latest")]
+        snapshot: Option<String>,
+    },
+
+    #[command(about = "Delete prefixes no game or launcher uses any more")]
+    Gc {
+        #[arg(long, help = "Delete without asking for confirmation")]
+        yes: bool,
+    },
 }
 
 impl Cli {
     pub async fn execute(self) -> Result<()> {
+        if let Some(path) = &self.config {
+            crate::config::Config::use_path(PathBuf::from(path))?;
+        }
+
+        let json = self.json;
         match self.command {
-            Commands::Game { action } => handle_game_command(action).await,
-            Commands::Wine { action } => handle_wine_command(action).await,
+            Commands::Game { action } => handle_game_command(action, json).await,
+            Commands::Wine { action } => handle_wine_command(action, json).await,
             Commands::Launch {
                 game,
                 wine_version,
                 args,
-            } => handle_launch(game, wine_version, args).await,
+                refresh_protondb,
+                hdr,
+                gpu,
+                gamescope,
+                width,
+                height,
+                fps,
+                upscale,
+            } => {
+                let gamescope = if gamescope {
+                    Some(gamescope_config(width, height, fps, upscale)?)
+                } else {
+                    None
+                };
+                handle_launch(
+                    game,
+                    wine_version,
+                    args,
+                    refresh_protondb,
+                    hdr,
+                    gpu,
+                    gamescope,
+                )
+                .await
+            }
             Commands::Install {
                 source,
                 name,
@@ -627,14 +1174,36 @@ impl Cli {
             } => handle_install(source, name, wine_version).await,
             Commands::Config { action } => handle_config_command(action).await,
             Commands::Launcher { action } => handle_launcher_command(action).await,
-            Commands::Tricks { game, trick, force } => handle_tricks(game, trick, force).await,
+            Commands::Tricks {
+                action: Some(action),
+                ..
+            } => handle_tricks_command(action),
+            Commands::Tricks {
+                action: None,
+                game,
+                tricks,
+                force,
+                continue_on_error,
+                dry_run,
+            } => {
+                let game = game.unwrap_or_default();
+                handle_tricks(game, tricks, force, continue_on_error, dry_run).await
+            }
             Commands::Optimize {
                 game,
                 nvidia,
                 amd,
+                intel,
                 gamemode,
                 cpu_performance,
-            } => handle_optimize(game, nvidia, amd, gamemode, cpu_performance).await,
+                reset,
+            } => {
+                if reset {
+                    handle_optimize_reset()
+                } else {
+                    handle_optimize(game, nvidia, amd, intel, gamemode, cpu_performance).await
+                }
+            }
             Commands::Search {
                 query,
                 protondb,
@@ -645,13 +1214,16 @@ impl Cli {
                 wine,
                 vulkan,
                 full,
-            } => handle_info(gpu, wine, vulkan, full).await,
+            } => handle_info(gpu, wine, vulkan, full, json).await,
+            Commands::Doctor => handle_doctor(json).await,
             Commands::Backup { action } => handle_backup_command(action).await,
             Commands::Battlenet { action } => handle_battlenet_command(action).await,
-            Commands::Graphics { action } => handle_graphics_command(action).await,
+            Commands::Graphics { action } => handle_graphics_command(action, json).await,
+            Commands::Display { action } => handle_display_command(action).await,
+            Commands::Prefix { action } => handle_prefix_command(action).await,
             Commands::Tui => launch_tui().await,
             Commands::Gui => launch_gui().await,
-            Commands::Profile { action } => handle_profile_command(action).await,
+            Commands::Profile { offline, action } => handle_profile_command(action, offline).await,
             Commands::Init { runtime, force } => handle_init_command(runtime, force).await,
             Commands::Scan { source, auto_optimize, with_protondb } => handle_scan_command(source, auto_optimize, with_protondb).await,
         }
@@ -659,11 +1231,12 @@ impl Cli {
 }
 
 // Profile command handlers for superior gaming experience
-async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
+async fn handle_profile_command(action: ProfileCommands, offline: bool) -> Result<()> {
     use crate::bolt_integration::{BoltGameManager, GameCategory};
     use chrono::Utc;
 
-    let bolt_manager = BoltGameManager::new()?;
+    let mut bolt_manager = BoltGameManager::new()?;
+    bolt_manager.drift_client_mut().set_offline(offline);
     let optimization_manager = bolt_manager.optimization_manager();
     let drift_client = bolt_manager.drift_client();
 
@@ -678,12 +1251,21 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
             power_limit,
             memory_offset,
             core_offset,
+            amd_power_profile,
+            vulkan_driver,
+            radv_perftest,
+            fan_curve,
             cpu_governor,
             nice_level,
+            shader_precache,
+            sync,
+            upscale,
+            upscale_strength,
+            fps_limit,
             wine_tricks,
             launch_options,
         } => {
-            println!("🔧 Creating optimization profile: {}", name.bright_green());
+            out!("🔧 Creating optimization profile: {}", name.bright_green());
 
             let game_category = match category.as_deref() {
                 Some("competitive") => GameCategory::Competitive,
@@ -693,6 +1275,39 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 Some("streaming") => GameCategory::Streaming,
                 _ => GameCategory::Unknown,
             };
+            // AAA games stutter the most while compiling shaders
+            let shader_precache = shader_precache || matches!(game_category, GameCategory::AAA);
+            let sync_mode = match sync.as_deref() {
+                None => crate::game_launcher::SyncMode::Auto,
+                Some(name) => crate::game_launcher::SyncMode::from_name(name).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown sync mode: {}. Use 'auto', 'ntsync', 'fsync', 'esync' or 'none'",
+                        name
+                    )
+                })?,
+            };
+
+            let upscaling = match upscale.as_deref() {
+                None => None,
+                Some(name) => {
+                    let method =
+                        crate::graphics::UpscalingMethod::from_name(name).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Unknown upscaler: {}. Use 'fsr', 'gamescope-fsr' or 'nis'",
+                                name
+                            )
+                        })?;
+                    let strength = upscale_strength
+                        .unwrap_or(crate::graphics::UpscalingSettings::DEFAULT_STRENGTH);
+                    if strength > crate::graphics::UpscalingSettings::MAX_STRENGTH {
+                        return Err(anyhow::anyhow!(
+                            "Invalid strength: {}. Use 0 (sharpest) to 5",
+                            strength
+                        ));
+                    }
+                    Some(crate::graphics::UpscalingSettings { method, strength })
+                }
+            };
 
             let nvidia_config = Some(NvidiaConfig {
                 dlss_enabled: dlss,
@@ -703,6 +1318,23 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 core_clock_offset: core_offset,
             });
 
+            let fan_curve = fan_curve.as_deref().map(parse_fan_curve).transpose()?.unwrap_or_default();
+            let amd_config = if amd_power_profile.is_some() || vulkan_driver.is_some() || !radv_perftest.is_empty() || !fan_curve.is_empty() {
+                let vulkan_driver = match vulkan_driver.as_deref().map(str::to_lowercase).as_deref() {
+                    None | Some("radv") => AmdVulkanDriver::Radv,
+                    Some("amdvlk") => AmdVulkanDriver::Amdvlk,
+                    Some(other) => return Err(anyhow::anyhow!("Unknown Vulkan driver '{}' (use radv or amdvlk)", other)),
+                };
+                Some(AmdConfig {
+                    power_profile: amd_power_profile,
+                    vulkan_driver,
+                    radv_perftest,
+                    fan_curve,
+                })
+            } else {
+                None
+            };
+
             let profile = OptimizationProfile {
                 name: name.clone(),
                 description: format!("Custom profile for {:?} games", game_category),
@@ -711,8 +1343,13 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 wine_tricks,
                 launch_options,
                 nvidia_config,
+                amd_config,
                 cpu_governor,
                 nice_level,
+                shader_precache,
+                sync_mode,
+                upscaling,
+                fps_limit,
                 created: Utc::now(),
                 rating: 0.0,
                 downloads: 0,
@@ -720,20 +1357,25 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 compatible_games: vec![],
             };
 
+            let problems = profile.validate();
+            if !problems.is_empty() {
+                return Err(anyhow::anyhow!("Invalid profile: {}", problems.join("; ")));
+            }
+
             optimization_manager.save_profile(&profile).await?;
-            println!("✅ Profile '{}' created successfully!", name.bright_green());
+            out!("✅ Profile '{}' created successfully!", name.bright_green());
         }
         ProfileCommands::List { category, community, detailed } => {
             if community {
-                println!("🌍 Fetching community profiles...");
+                out!("🌍 Fetching community profiles...");
                 let community_profiles = drift_client.search_profiles("", None).await?;
 
                 if community_profiles.is_empty() {
                     println!("No community profiles found.");
                 } else {
-                    println!("\n{} Community Profiles:", "📦".bright_blue());
+                    out!("\n{} Community Profiles:", "📦".bright_blue());
                     for profile in community_profiles {
-                        println!("  {} {} ({}⭐ {} downloads)",
+                        out!("  {} {} ({}⭐ {} downloads)",
                             "•".bright_green(),
                             profile.profile.name.bright_white(),
                             profile.metadata.rating,
@@ -765,7 +1407,7 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 if filtered_profiles.is_empty() {
                     println!("No profiles found.");
                 } else {
-                    println!("\n{} Local Optimization Profiles:", "🎯".bright_blue());
+                    out!("\n{} Local Optimization Profiles:", "🎯".bright_blue());
                     for profile in filtered_profiles {
                         println!("  {} {} ({:?})",
                             "•".bright_green(),
@@ -778,19 +1420,25 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                                 println!("    Proton: {}", proton);
                             }
                             if let Some(nvidia) = &profile.nvidia_config {
-                                println!("    NVIDIA: DLSS={} Reflex={} RT={}",
+                                out!("    NVIDIA: DLSS={} Reflex={} RT={}",
                                     if nvidia.dlss_enabled { "✓" } else { "✗" },
                                     if nvidia.reflex_enabled { "✓" } else { "✗" },
                                     if nvidia.raytracing_enabled { "✓" } else { "✗" }
                                 );
                             }
+                            if let Some(amd) = &profile.amd_config {
+                                println!("    AMD: {} profile={}",
+                                    amd.vulkan_driver.icd_name(),
+                                    amd.power_profile.as_deref().unwrap_or("default")
+                                );
+                            }
                         }
                     }
                 }
             }
         }
         ProfileCommands::Install { profile, min_rating: _min_rating, force: _force } => {
-            println!("📥 Installing community profile: {}", profile.bright_green());
+            out!("📥 Installing community profile: {}", profile.bright_green());
 
             let config_dir = dirs::config_dir()
                 .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
@@ -799,33 +1447,33 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
 
             match drift_client.install_profile(&profile, &profile_dir).await {
                 Ok(installed_profile) => {
-                    println!("✅ Successfully installed profile: {}", installed_profile.name.bright_green());
+                    out!("✅ Successfully installed profile: {}", installed_profile.name.bright_green());
                     println!("   Category: {:?}", installed_profile.game_category);
                     if let Some(proton) = &installed_profile.proton_version {
                         println!("   Proton: {}", proton);
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to install profile: {}", e);
+                    eout!("❌ Failed to install profile: {}", e);
                 }
             }
         }
         ProfileCommands::Share { profile, games: _games, tags: _tags, public } => {
-            println!("🌍 Sharing profile '{}' with community...", profile.bright_green());
+            out!("🌍 Sharing profile '{}' with community...", profile.bright_green());
 
             if let Some(prof) = optimization_manager.get_profile(&profile) {
                 match drift_client.share_profile(&prof).await {
                     Ok(profile_id) => {
-                        println!("✅ Profile shared successfully!");
+                        out!("✅ Profile shared successfully!");
                         println!("   Profile ID: {}", profile_id.bright_blue());
                         println!("   Visibility: {}", if public { "Public" } else { "Community" });
                     }
                     Err(e) => {
-                        eprintln!("❌ Failed to share profile: {}", e);
+                        eout!("❌ Failed to share profile: {}", e);
                     }
                 }
             } else {
-                eprintln!("❌ Profile '{}' not found", profile);
+                eout!("❌ Profile '{}' not found", profile);
             }
         }
         ProfileCommands::Delete { profile, force } => {
@@ -842,11 +1490,11 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
             }
 
             optimization_manager.delete_profile(&profile).await?;
-            println!("✅ Deleted profile: {}", profile.bright_green());
+            out!("✅ Deleted profile: {}", profile.bright_green());
         }
         ProfileCommands::Show { profile, benchmarks: _benchmarks } => {
             if let Some(prof) = optimization_manager.get_profile(&profile) {
-                println!("\n{} Profile: {}", "🎯".bright_blue(), prof.name.bright_white());
+                out!("\n{} Profile: {}", "🎯".bright_blue(), prof.name.bright_white());
                 println!("  Description: {}", prof.description);
                 println!("  Category: {:?}", prof.game_category);
                 println!("  Author: {}", prof.author);
@@ -864,6 +1512,18 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                     println!("  Launch Options: {}", prof.launch_options.join(" "));
                 }
 
+                println!("  Sync Mode: {}", prof.sync_mode.name());
+                if let Some(upscaling) = &prof.upscaling {
+                    println!(
+                        "  Upscaling: {} (strength {})",
+                        upscaling.method.name(),
+                        upscaling.strength
+                    );
+                }
+                if let Some(limit) = prof.fps_limit {
+                    println!("  FPS Limit: {}", limit);
+                }
+
                 if let Some(nvidia) = &prof.nvidia_config {
                     println!("  NVIDIA Configuration:");
                     println!("    DLSS: {}", if nvidia.dlss_enabled { "Enabled" } else { "Disabled" });
@@ -880,6 +1540,21 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                     }
                 }
 
+                if let Some(amd) = &prof.amd_config {
+                    println!("  AMD Configuration:");
+                    println!("    Vulkan Driver: {}", amd.vulkan_driver.icd_name());
+                    if let Some(power_profile) = &amd.power_profile {
+                        println!("    Power Profile: {}", power_profile);
+                    }
+                    if !amd.radv_perftest.is_empty() {
+                        println!("    RADV_PERFTEST: {}", amd.radv_perftest.join(","));
+                    }
+                    if !amd.fan_curve.is_empty() {
+                        let points: Vec<String> = amd.fan_curve.iter().map(|p| format!("{}°C→{}%", p.temperature, p.speed)).collect();
+                        println!("    Fan Curve: {}", points.join(", "));
+                    }
+                }
+
                 if let Some(governor) = &prof.cpu_governor {
                     println!("  CPU Governor: {}", governor);
                 }
@@ -888,22 +1563,124 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                     println!("  Process Priority: {}", nice);
                 }
             } else {
-                eprintln!("❌ Profile '{}' not found", profile);
+                eout!("❌ Profile '{}' not found", profile);
+            }
+        }
+        ProfileCommands::Preview { profile, game } => {
+            let prof = optimization_manager
+                .get_profile(&profile)
+                .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile))?;
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, false)?;
+            let preview = bolt_manager.preview_profile(&game_obj, &prof, &config);
+
+            out!(
+                "\n{} Preview of {} for {} (nothing is applied)",
+                "🔎".bright_blue(),
+                preview.profile.bright_white(),
+                preview.game.cyan()
+            );
+            println!("\n  Command:");
+            println!("    {} {}", preview.command, preview.arguments.join(" "));
+            println!("\n  Environment:");
+            for var in &preview.environment {
+                println!("    {}", var);
+            }
+            if !preview.wine_tricks.is_empty() {
+                println!("\n  Wine Tricks: {}", preview.wine_tricks.join(", "));
+            }
+            println!("\n  System Changes:");
+            if let Some(nice) = preview.nice_level {
+                println!("    Process priority: nice {}", nice);
+            }
+            for change in &preview.system_changes {
+                println!("    {}", change);
+            }
+            if preview.nice_level.is_none() && preview.system_changes.is_empty() {
+                println!("    None");
             }
         }
-        ProfileCommands::Search { query, category, gpu_vendor, min_rating, sort_by: _sort_by, limit } => {
-            println!("🔍 Searching community profiles for: {}", query.bright_green());
+        ProfileCommands::AutoAssign { all } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut assigned = 0;
+            let mut unmatched = 0;
 
-            let cat_filter = category.as_deref().and_then(|c| match c {
-                "competitive" => Some(GameCategory::Competitive),
-                "aaa" => Some(GameCategory::AAA),
+            out!("{}", "🎯 Assigning profiles by game category...".bold());
+            for mut game in game_lib.list_games()? {
+                if game.profile.is_some() && !all {
+                    continue;
+                }
+                let steam_appid = if game.launcher.as_deref() == Some("Steam") {
+                    game.launcher_id.as_deref().and_then(|id| id.parse().ok())
+                } else {
+                    None
+                };
+                // Offline only the bundled and user game hints are asked
+                let category = if offline {
+                    GameCategory::for_game(&game.name, steam_appid).unwrap_or(GameCategory::Unknown)
+                } else {
+                    bolt_manager.detect_game_category(&game, steam_appid).await
+                };
+                match optimization_manager.recommended_profile(&category) {
+                    Some(prof) => {
+                        out!(
+                            "  {} {} → {} ({})",
+                            "✅".green(),
+                            game.name,
+                            prof.name.cyan(),
+                            category.slug()
+                        );
+                        game.profile = Some(prof.name);
+                        game_lib.update_game(&game)?;
+                        assigned += 1;
+                    }
+                    None => {
+                        out!("  {} {} ({})", "⏭️".dimmed(), game.name, category.slug());
+                        unmatched += 1;
+                    }
+                }
+            }
+
+            out!(
+                "\n{} Assigned profiles to {} game(s)",
+                "✅".green(),
+                assigned
+            );
+            if unmatched > 0 {
+                out!(
+                    "💡 {} game(s) had no matching profile, create one with 'forge profile create --category'",
+                    unmatched
+                );
+            }
+        }
+        ProfileCommands::Search { query, category, gpu_vendor, min_rating, sort_by, limit } => {
+            out!("🔍 Searching community profiles for: {}", query.bright_green());
+
+            let cat_filter = category.as_deref().and_then(|c| match c {
+                "competitive" => Some(GameCategory::Competitive),
+                "aaa" => Some(GameCategory::AAA),
                 "indie" => Some(GameCategory::Indie),
                 "vr" => Some(GameCategory::VR),
                 "streaming" => Some(GameCategory::Streaming),
                 _ => None,
             });
 
-            let profiles = drift_client.search_profiles(&query, cat_filter.as_ref()).await?;
+            let mut profiles = drift_client.search_profiles(&query, cat_filter.as_ref()).await?;
+
+            // Highest first; anything unrecognised keeps the rating default
+            match sort_by.to_lowercase().as_str() {
+                "downloads" => profiles.sort_by_key(|p| std::cmp::Reverse(p.metadata.downloads)),
+                "date" => profiles.sort_by_key(|p| std::cmp::Reverse(p.metadata.last_updated)),
+                other => {
+                    if other != "rating" {
+                        out!("⚠️ Unknown sort '{}', sorting by rating", other);
+                    }
+                    profiles.sort_by(|a, b| b.metadata.rating.total_cmp(&a.metadata.rating));
+                }
+            }
+
             let filtered_profiles: Vec<_> = profiles.into_iter()
                 .filter(|p| {
                     if let Some(min_r) = min_rating {
@@ -927,7 +1704,7 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
             } else {
                 println!("\nFound {} profiles:", filtered_profiles.len());
                 for profile in filtered_profiles {
-                    println!("\n  {} {} ({}⭐)",
+                    out!("\n  {} {} ({}⭐)",
                         "📦".bright_blue(),
                         profile.profile.name.bright_white(),
                         profile.metadata.rating
@@ -941,14 +1718,34 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 }
             }
         }
-        ProfileCommands::Rate { profile_id, rating, comment: _comment } => {
+        ProfileCommands::Rate { profile_id, rating, comment } => {
             if rating < 1.0 || rating > 5.0 {
-                eprintln!("❌ Rating must be between 1.0 and 5.0");
+                eout!("❌ Rating must be between 1.0 and 5.0");
                 return Ok(());
             }
 
-            drift_client.rate_profile(&profile_id, rating).await?;
-            println!("✅ Rated profile '{}' with {:.1} stars", profile_id.bright_green(), rating);
+            drift_client.rate_profile(&profile_id, rating, comment.as_deref()).await?;
+            out!("✅ Rated profile '{}' with {:.1} stars", profile_id.bright_green(), rating);
+        }
+        ProfileCommands::Reviews { profile_id, limit } => {
+            let reviews = drift_client.get_reviews(&profile_id).await?;
+
+            if reviews.is_empty() {
+                println!("No reviews for '{}' yet.", profile_id);
+            } else {
+                out!("\n{} Reviews for {} ({}):", "💬".bright_blue(), profile_id.bright_white(), reviews.len());
+                for review in reviews.iter().take(limit) {
+                    out!("\n  {} {:.1}⭐ by {} on {}",
+                        "•".bright_green(),
+                        review.rating,
+                        review.author.bright_white(),
+                        review.created.format("%Y-%m-%d")
+                    );
+                    if let Some(comment) = &review.comment {
+                        println!("    {}", comment);
+                    }
+                }
+            }
         }
         ProfileCommands::Clone { source, target, with_metadata: _with_metadata } => {
             if let Some(source_profile) = optimization_manager.get_profile(&source) {
@@ -960,31 +1757,67 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
                 new_profile.downloads = 0;
 
                 optimization_manager.save_profile(&new_profile).await?;
-                println!("✅ Cloned profile '{}' to '{}'", source.bright_blue(), target.bright_green());
+                out!("✅ Cloned profile '{}' to '{}'", source.bright_blue(), target.bright_green());
             } else {
-                eprintln!("❌ Source profile '{}' not found", source);
+                eout!("❌ Source profile '{}' not found", source);
             }
         }
         ProfileCommands::Export { profile, output, with_perf: _with_perf } => {
             if let Some(prof) = optimization_manager.get_profile(&profile) {
                 let json = serde_json::to_string_pretty(&prof)?;
                 std::fs::write(&output, json)?;
-                println!("✅ Exported profile '{}' to {}", profile.bright_green(), output.bright_blue());
+                out!("✅ Exported profile '{}' to {}", profile.bright_green(), output.bright_blue());
             } else {
-                eprintln!("❌ Profile '{}' not found", profile);
+                eout!("❌ Profile '{}' not found", profile);
             }
         }
         ProfileCommands::Import { file, force } => {
             let content = std::fs::read_to_string(&file)?;
-            let profile: OptimizationProfile = serde_json::from_str(&content)?;
+            let profile: OptimizationProfile = serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{} is not a valid profile: {}", file, e))?;
+            let problems = profile.validate();
+            if !problems.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Refusing to import {}: {}",
+                    file,
+                    problems.join("; ")
+                ));
+            }
 
             if !force && optimization_manager.get_profile(&profile.name).is_some() {
-                eprintln!("❌ Profile '{}' already exists. Use --force to override.", profile.name);
+                eout!("❌ Profile '{}' already exists. Use --force to override.", profile.name);
                 return Ok(());
             }
 
             optimization_manager.save_profile(&profile).await?;
-            println!("✅ Imported profile '{}' from {}", profile.name.bright_green(), file.bright_blue());
+            out!("✅ Imported profile '{}' from {}", profile.name.bright_green(), file.bright_blue());
+        }
+        ProfileCommands::Login { token } => {
+            let token = match token {
+                Some(token) => token,
+                None => dialoguer::Password::new()
+                    .with_prompt("Drift registry token")
+                    .interact()?,
+            };
+            if token.trim().is_empty() {
+                return Err(anyhow::anyhow!("Token must not be empty"));
+            }
+
+            let mut drift_client = crate::bolt_integration::DriftClient::new();
+            drift_client.save_token(&token)?;
+            out!("✅ Logged in to the Drift registry");
+            println!(
+                "   Token saved to {}",
+                crate::bolt_integration::DriftClient::token_path().display()
+            );
+        }
+        ProfileCommands::Logout => {
+            let mut drift_client = crate::bolt_integration::DriftClient::new();
+            if drift_client.clear_token()? {
+                out!("✅ Logged out of the Drift registry");
+            } else {
+                println!("Not logged in.");
+            }
         }
     }
 
@@ -992,26 +1825,26 @@ async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
 }
 
 async fn handle_init_command(runtime: String, _force: bool) -> Result<()> {
-    println!("🚀 Initializing GhostForge with {} runtime...", runtime.bright_green());
+    out!("🚀 Initializing GhostForge with {} runtime...", runtime.bright_green());
 
     match runtime.as_str() {
         "bolt" => {
             let _bolt_manager = crate::bolt_integration::BoltGameManager::new()?;
-            println!("✅ Bolt runtime initialized successfully!");
-            println!("   Container support: {}", "✓ Gaming-optimized containers".bright_green());
-            println!("   GPU acceleration: {}", "✓ NVIDIA DLSS/Reflex support".bright_green());
-            println!("   Profile management: {}", "✓ Community sharing".bright_green());
+            out!("✅ Bolt runtime initialized successfully!");
+            out!("   Container support: {}", "✓ Gaming-optimized containers".bright_green());
+            out!("   GPU acceleration: {}", "✓ NVIDIA DLSS/Reflex support".bright_green());
+            out!("   Profile management: {}", "✓ Community sharing".bright_green());
         }
         "docker" => {
-            println!("⚠️  Docker runtime support coming soon!");
+            out!("⚠️  Docker runtime support coming soon!");
             println!("   Use 'bolt' for best gaming performance.");
         }
         "podman" => {
-            println!("⚠️  Podman runtime support coming soon!");
+            out!("⚠️  Podman runtime support coming soon!");
             println!("   Use 'bolt' for best gaming performance.");
         }
         _ => {
-            eprintln!("❌ Unknown runtime: {}. Supported: bolt, docker, podman", runtime);
+            eout!("❌ Unknown runtime: {}. Supported: bolt, docker, podman", runtime);
             return Ok(());
         }
     }
@@ -1020,7 +1853,7 @@ async fn handle_init_command(runtime: String, _force: bool) -> Result<()> {
 }
 
 async fn handle_scan_command(source: String, auto_optimize: bool, with_protondb: bool) -> Result<()> {
-    println!("🔍 Scanning {} library...", source.bright_green());
+    out!("🔍 Scanning {} library...", source.bright_green());
 
     match source.as_str() {
         "steam" => {
@@ -1028,7 +1861,7 @@ async fn handle_scan_command(source: String, auto_optimize: bool, with_protondb:
 
             if auto_optimize {
                 let profiles = bolt_manager.scan_and_optimize_steam_library().await?;
-                println!("✅ Created {} optimization profiles for Steam games", profiles.len());
+                out!("✅ Created {} optimization profiles for Steam games", profiles.len());
 
                 for profile in &profiles {
                     println!("  {} {} ({:?})",
@@ -1043,109 +1876,256 @@ async fn handle_scan_command(source: String, auto_optimize: bool, with_protondb:
             }
 
             if with_protondb {
-                println!("\n📊 Fetching ProtonDB compatibility data...");
+                out!("\n📊 Fetching ProtonDB compatibility data...");
                 // This would integrate with actual Steam library scanning
-                println!("✅ ProtonDB integration enabled");
+                out!("✅ ProtonDB integration enabled");
             }
         }
         "battlenet" => {
-            println!("🎮 Battle.net library scanning coming soon!");
+            let config_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+                .join("ghostforge");
+            let launcher_manager = crate::launcher::LauncherManager::new(config_dir.clone());
+
+            let Some(launcher) = launcher_manager.detect_battlenet()? else {
+                out!("❌ Battle.net not found. Use 'forge launcher setup battlenet' first.");
+                return Ok(());
+            };
+            let prefix = launcher.wine_prefix.clone().unwrap_or_default();
+            println!("  Prefix: {}", prefix.display().to_string().dimmed());
+
+            let games = launcher_manager.sync_battlenet_games(&launcher)?;
+            if games.is_empty() {
+                println!("No installed Blizzard games found");
+                return Ok(());
+            }
+
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let imported = launcher_manager.import_launcher_games(&launcher, &game_lib).await?;
+
+            let protondb = crate::protondb::ProtonDBClient::from_config(&config);
+            let optimization_manager = if auto_optimize {
+                Some(crate::bolt_integration::OptimizationManager::new(config_dir.join("profiles"))?)
+            } else {
+                None
+            };
+
+            for game in &games {
+                out!("  🎮 {} ({})", game.name.bright_white(), game.install_path.display().to_string().dimmed());
+
+                if with_protondb {
+                    match crate::launcher::battlenet_steam_appid(&game.launcher_id) {
+                        Some(appid) => match protondb.get_compatibility_info(appid).await {
+                            Ok(report) => out!("    🌐 ProtonDB: {:?} (Steam app {})", report.tier, appid),
+                            Err(e) => out!("    ⚠️  ProtonDB lookup failed: {}", e),
+                        },
+                        None => out!("    🌐 ProtonDB: {}", "not on Steam, no reports".dimmed()),
+                    }
+                }
+
+                if let Some(manager) = &optimization_manager {
+                    let category = match game.launcher_id.as_str() {
+                        "pro" | "s2" => GameCategory::Competitive,
+                        "wow" | "d3" | "d4" => GameCategory::AAA,
+                        "hs" => GameCategory::Indie,
+                        _ => GameCategory::Unknown,
+                    };
+                    let profile = manager.get_or_create_profile(&game.name, &category, None, None).await?;
+                    out!("    🔧 Profile: {} ({:?})", profile.name, profile.game_category);
+                }
+            }
+
+            // WoW's prefix tweaks apply to the whole Battle.net prefix, so run them once
+            if auto_optimize && games.iter().any(|g| g.launcher_id == "wow") {
+                let manager = crate::winetricks::WinetricksManager::new(
+                    crate::winetricks::default_cache_dir(),
+                )?;
+                manager.optimize_for_wow(&prefix).await?;
+            }
+
+            out!("\n✅ Found {} Blizzard games, imported {} new into the library", games.len(), imported);
         }
         "lutris" => {
-            println!("🔄 Lutris migration support coming soon!");
-            println!("   Will import games and create optimized profiles");
+            let Some(paths) = crate::lutris::LutrisPaths::detect() else {
+                out!("❌ No Lutris installation found (looked for pga.db in the native and Flatpak data directories)");
+                return Ok(());
+            };
+            println!("  Database: {}", paths.database.display().to_string().dimmed());
+
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let optimization_manager = if auto_optimize {
+                let profile_dir = dirs::config_dir()
+                    .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+                    .join("ghostforge")
+                    .join("profiles");
+                Some(crate::bolt_integration::OptimizationManager::new(profile_dir)?)
+            } else {
+                None
+            };
+
+            let (mut imported, mut not_installed, mut existing, mut profiles) = (0, 0, 0, 0);
+            let mut skipped: Vec<String> = Vec::new();
+
+            for lutris_game in crate::lutris::read_games(&paths)? {
+                // Entries Lutris itself lists as not installed have nothing to launch
+                if !lutris_game.installed {
+                    continue;
+                }
+                if lutris_game.runner.as_deref() == Some("steam") {
+                    skipped.push(format!("{} (Steam game, use 'forge scan steam')", lutris_game.name));
+                    continue;
+                }
+                let Some(game) = lutris_game.to_game(&paths) else {
+                    skipped.push(format!("{} (no executable in its Lutris config)", lutris_game.name));
+                    continue;
+                };
+
+                if game_lib.get_game(&game.id)?.is_some() {
+                    existing += 1;
+                    continue;
+                }
+                game_lib.add_game(&game)?;
+                imported += 1;
+
+                let installed = game.is_installed();
+                if !installed {
+                    not_installed += 1;
+                }
+                out!("  {} {} [{}]",
+                    if installed { "✅" } else { "❌" },
+                    game.name.bright_white(),
+                    lutris_game.runner.as_deref().unwrap_or("unknown").dimmed()
+                );
+
+                if let Some(manager) = &optimization_manager {
+                    manager.get_or_create_profile(&game.name, &GameCategory::Unknown, None, None).await?;
+                    profiles += 1;
+                }
+            }
+
+            out!("\n✅ Imported {} games from Lutris", imported);
+            if not_installed > 0 {
+                out!(
+                    "  ❌ {} imported as not installed (missing executable)",
+                    not_installed
+                );
+            }
+            if existing > 0 {
+                println!("  {} already in the library", existing);
+            }
+            if profiles > 0 {
+                out!("  🔧 Created {} optimization profiles", profiles);
+            }
+            if !skipped.is_empty() {
+                out!("  ⚠️  Skipped {}:", skipped.len());
+                for reason in &skipped {
+                    println!("    • {}", reason);
+                }
+            }
         }
         _ => {
-            eprintln!("❌ Unknown source: {}. Supported: steam, battlenet, lutris", source);
+            eout!("❌ Unknown source: {}. Supported: steam, battlenet, lutris", source);
         }
     }
 
     Ok(())
 }
 
-async fn handle_game_command(action: GameCommands) -> Result<()> {
+async fn handle_game_command(action: GameCommands, json: bool) -> Result<()> {
     match action {
         GameCommands::List {
             launcher,
-            status: _,
+            category,
+            tag,
+            status,
         } => {
-            println!("{}", "📮 Available Games:".bold().cyan());
-
-            let config_dir = dirs::config_dir().unwrap().join("ghostforge");
-            let launcher_manager = crate::launcher::LauncherManager::new(config_dir);
+            let status = status
+                .map(|status| {
+                    crate::game::GameStatus::from_name(&status).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown status: {}. Use 'installed', 'not-installed' or 'running'",
+                            status
+                        )
+                    })
+                })
+                .transpose()?;
+            let filter = crate::game::GameFilter {
+                launcher,
+                category,
+                tag,
+                status,
+                ..Default::default()
+            };
 
-            // Get all detected launchers
-            let launchers = launcher_manager.detect_launchers()?;
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let running: HashSet<String> = crate::game_launcher::RunningGameRegistry::load()?
+                .games
+                .into_keys()
+                .collect();
+            let games: Vec<_> = game_lib
+                .list_games()?
+                .into_iter()
+                .filter(|game| filter.matches(game, &running))
+                .collect();
 
-            if launchers.is_empty() {
-                println!(
-                    "No launchers detected. Run 'forge launcher list' to see available launchers."
-                );
-                return Ok(());
+            if json {
+                return print_json(&games);
             }
 
-            let mut total_games = 0;
+            out!("{}", "📮 Games:".bold().cyan());
 
-            // Filter launchers if specific launcher requested
-            let filtered_launchers: Vec<_> = if let Some(ref launcher_filter) = launcher {
-                launchers
-                    .into_iter()
-                    .filter(|l| {
-                        l.name
-                            .to_lowercase()
-                            .contains(&launcher_filter.to_lowercase())
-                    })
-                    .collect()
-            } else {
-                launchers
-            };
-
-            for launcher_info in filtered_launchers {
-                println!(
-                    "\n{} ({}):",
-                    launcher_info.name.bold().blue(),
-                    format!("{:?}", launcher_info.launcher_type).dimmed()
-                );
+            if games.is_empty() {
+                if filter == crate::game::GameFilter::default() {
+                    println!(
+                        "No games in the library. Run 'forge scan' to import games from your launchers."
+                    );
+                } else {
+                    println!("No games match the filter.");
+                }
+                return Ok(());
+            }
 
-                let games = match launcher_info.launcher_type {
-                    crate::launcher::LauncherType::Steam => {
-                        launcher_manager.sync_steam_games(&launcher_info)?
-                    }
-                    crate::launcher::LauncherType::BattleNet => {
-                        launcher_manager.sync_battlenet_games(&launcher_info)?
-                    }
-                    _ => {
-                        println!("  Game sync not yet implemented for this launcher type");
-                        continue;
-                    }
-                };
+            let mut by_launcher: BTreeMap<&str, Vec<&crate::game::Game>> = BTreeMap::new();
+            for game in &games {
+                by_launcher
+                    .entry(game.launcher.as_deref().unwrap_or("Manual"))
+                    .or_default()
+                    .push(game);
+            }
 
-                if games.is_empty() {
-                    println!("  No games found");
-                } else {
-                    for game in &games {
-                        let status_icon = if game.installed { "✅" } else { "❌" };
+            for (launcher_name, launcher_games) in by_launcher {
+                println!("\n{}:", launcher_name.bold().blue());
+                for game in launcher_games {
+                    let installed = game.is_installed();
+                    let status_icon = if running.contains(&game.id) {
+                        "▶️"
+                    } else if installed {
+                        "✅"
+                    } else {
+                        "❌"
+                    };
+                    out!(
+                        "  {} {} (ID: {})",
+                        status_icon,
+                        game.name.cyan(),
+                        game.id.yellow()
+                    );
+                    if installed {
                         println!(
-                            "  {} {} (ID: {})",
-                            status_icon,
-                            game.name.cyan(),
-                            game.id.yellow()
+                            "    Path: {}",
+                            game.install_path.display().to_string().dimmed()
                         );
-                        if game.installed {
-                            println!(
-                                "    Path: {}",
-                                game.install_path.display().to_string().dimmed()
-                            );
-                        }
                     }
-                    total_games += games.len();
                 }
             }
 
-            println!(
+            out!(
                 "\n{} {} games found",
                 "📊".bold(),
-                total_games.to_string().bold().green()
+                games.len().to_string().bold().green()
             );
             Ok(())
         }
@@ -1154,43 +2134,1144 @@ async fn handle_game_command(action: GameCommands) -> Result<()> {
             name,
             wine_version: _,
         } => {
-            println!(
+            out!(
                 "{} {}",
                 "✅".green(),
                 format!("Added game: {}", name).bold()
             );
             Ok(())
         }
-        GameCommands::Remove { game, purge: _ } => {
-            println!("{} Removed game: {}", "🗑️", game);
+        GameCommands::Remove { game, purge, yes } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, true)?;
+
+            game_lib.remove_game(&game_obj.id)?;
+            out!("🗑️ Removed {} from the library", game_obj.name.cyan());
+            if !purge {
+                return Ok(());
+            }
+
+            let mut reclaimed = 0;
+            if game_obj.launcher.as_deref() == Some("Steam") {
+                out!(
+                    "⚠️  Steam manages the files of {}, uninstall it in Steam to free its space",
+                    game_obj.name
+                );
+            } else if game_obj.install_path.is_dir() {
+                reclaimed += delete_confirmed(&game_obj.install_path, "game files", yes)?;
+            }
+
+            // Only offer the prefix once nothing else uses it
+            let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+            let launchers = crate::launcher::LauncherManager::new(config_dir)
+                .detect_launchers()
+                .unwrap_or_default();
+            let prefix = game_obj.prefix_path(&config.wine);
+            if crate::prefix::find_orphaned_prefixes(
+                &game_lib.list_games()?,
+                &launchers,
+                &config.wine,
+            )
+            .contains(&prefix)
+            {
+                reclaimed += delete_confirmed(&prefix, "Wine prefix", yes)?;
+            } else if prefix.join("system.reg").exists() {
+                out!(
+                    "ℹ️  Keeping the prefix {}, it is still in use",
+                    prefix.display()
+                );
+            }
+
+            if reclaimed > 0 {
+                out!("🧹 Reclaimed {}", crate::utils::format_size(reclaimed));
+            }
+            Ok(())
+        }
+        GameCommands::Edit {
+            game,
+            wine,
+            dxvk,
+            vkd3d,
+            vulkan_device,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, true)?;
+
+            // Without flags, edit the whole record
+            if wine.is_none() && dxvk.is_none() && vkd3d.is_none() && vulkan_device.is_none() {
+                match edit_game_in_editor(&game_obj)? {
+                    Some(edited) => {
+                        game_lib.update_game(&edited)?;
+                        out!("✅ Saved {}", edited.name.green());
+                    }
+                    None => println!("No changes to {}", game_obj.name),
+                }
+                return Ok(());
+            }
+            let pins_changed = dxvk.is_some() || vkd3d.is_some();
+
+            if let Some(version) = wine {
+                game_obj.wine_version = parse_pinned_version(&version);
+                out!(
+                    "🍷 Wine/Proton: {}",
+                    game_obj.wine_version.as_deref().unwrap_or("default").cyan()
+                );
+            }
+            if let Some(version) = dxvk {
+                game_obj.dxvk_version = parse_pinned_version(&version);
+            }
+            if let Some(version) = vkd3d {
+                game_obj.vkd3d_version = parse_pinned_version(&version);
+            }
+            if pins_changed && (game_obj.dxvk_version.is_some() || game_obj.vkd3d_version.is_some())
+            {
+                check_graphics_pins_apply(&game_obj)?;
+            }
+            if let Some(device) = vulkan_device {
+                game_obj.vulkan_device = parse_pinned_version(&device);
+                if let Some(device) = &game_obj.vulkan_device {
+                    let devices = crate::utils::SystemDetector::vulkan_devices();
+                    if devices.is_empty() {
+                        out!("⚠️  No Vulkan devices detected, is vulkaninfo installed?");
+                    } else if !devices.iter().any(|d| d.name.contains(device.as_str())) {
+                        return Err(anyhow::anyhow!(
+                            "Unknown Vulkan device: {}. Use one of: {}",
+                            device,
+                            devices
+                                .iter()
+                                .map(|d| d.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ));
+                    }
+                }
+                out!(
+                    "🌋 Vulkan device: {}",
+                    game_obj
+                        .vulkan_device
+                        .as_deref()
+                        .unwrap_or("default")
+                        .cyan()
+                );
+            }
+
+            game_lib.update_game(&game_obj)?;
+            if pins_changed {
+                print_graphics_pins(&game_obj);
+            }
+            Ok(())
+        }
+        GameCommands::LaunchOptions { game, set, clear } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+
+            if clear || !set.is_empty() {
+                if clear {
+                    game_obj.environment_variables.clear();
+                    game_obj.launch_arguments.clear();
+                }
+
+                for option in set {
+                    match parse_env_assignment(&option) {
+                        Some((key, value)) => game_obj.set_environment_variable(key, value),
+                        None => game_obj.launch_arguments.push(option),
+                    }
+                }
+
+                game_lib.update_game(&game_obj)?;
+                out!("✅ Launch options saved for {}", game_obj.name.green());
+            }
+
+            out!("{}", "🔧 Environment:".bold());
+            if game_obj.environment_variables.is_empty() {
+                println!("  (none)");
+            }
+            for (key, value) in &game_obj.environment_variables {
+                println!("  {}={}", key.cyan(), value);
+            }
+
+            out!("{}", "🚀 Arguments:".bold());
+            if game_obj.launch_arguments.is_empty() {
+                println!("  (none)");
+            } else {
+                println!("  {}", game_obj.launch_arguments.join(" "));
+            }
+            Ok(())
+        }
+        GameCommands::Gpu { game, index, reset } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+            let gpus = crate::utils::SystemDetector::detect_gpu()?;
+
+            if let Some(index) = index {
+                let gpu = gpus.get(index).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown GPU: {}. Use 'forge game gpu {}' to list the GPUs",
+                        index,
+                        game
+                    )
+                })?;
+                game_obj.gpu_index = Some(index);
+                game_lib.update_game(&game_obj)?;
+                out!(
+                    "✅ {} renders on GPU {} ({})",
+                    game_obj.name.green(),
+                    index,
+                    gpu.name.cyan()
+                );
+                return Ok(());
+            }
+            if reset {
+                game_obj.gpu_index = None;
+                game_lib.update_game(&game_obj)?;
+                out!(
+                    "✅ {} renders on the default GPU again",
+                    game_obj.name.green()
+                );
+                return Ok(());
+            }
+
+            // Without PRIME render offload games stay on the display GPU
+            let offload = config.gpu.nvidia_prime_render_offload;
+            let default = if offload {
+                crate::utils::default_render_gpu(&gpus)
+                    .and_then(|gpu| gpus.iter().position(|g| std::ptr::eq(g, gpu)))
+            } else {
+                gpus.iter().position(|gpu| gpu.boot_gpu)
+            };
+            let selected = game_obj.gpu_index.or(default);
+
+            out!("🎮 GPUs for {}:", game_obj.name.bold().cyan());
+            for (i, gpu) in gpus.iter().enumerate() {
+                let marker = if selected == Some(i) { "▶" } else { " " };
+                println!(
+                    "  {} [{}] {} ({}){}",
+                    marker,
+                    i,
+                    gpu.name.cyan(),
+                    gpu.driver.as_deref().unwrap_or("no driver"),
+                    if gpu.boot_gpu { ", display" } else { "" }.dimmed()
+                );
+            }
+            if gpus.len() < 2 {
+                println!("  Only one GPU, games always render on it");
+            } else if game_obj.gpu_index.is_none() && offload {
+                println!("  Using the default (discrete) GPU, pick another with its index");
+            } else if game_obj.gpu_index.is_none() {
+                println!(
+                    "  Using the display GPU, pick another with its index or turn on \
+                     'forge config set gpu.nvidia_prime true'"
+                );
+            }
             Ok(())
         }
-        GameCommands::Edit { game } => {
-            println!("Opening configuration for: {}", game.yellow());
+        GameCommands::Upscale {
+            game,
+            fsr,
+            nis,
+            gamescope,
+            strength,
+            off,
+        } => {
+            use crate::graphics::{UpscalingMethod, UpscalingSettings};
+
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+
+            if off {
+                game_obj.upscaling = None;
+                game_lib.update_game(&game_obj)?;
+                out!("✅ Upscaling off for {}", game_obj.name.green());
+                return Ok(());
+            }
+
+            let method = match (fsr, nis, gamescope) {
+                (true, _, false) => Some(UpscalingMethod::WineFsr),
+                (true, _, true) => Some(UpscalingMethod::GamescopeFsr),
+                (_, true, _) => Some(UpscalingMethod::GamescopeNis),
+                _ => game_obj.upscaling.as_ref().map(|u| u.method),
+            };
+            let Some(method) = method.filter(|_| fsr || nis || strength.is_some()) else {
+                match &game_obj.upscaling {
+                    Some(upscaling) => out!(
+                        "🔍 {} upscales with {} at strength {}",
+                        game_obj.name.cyan(),
+                        upscaling.method.name(),
+                        upscaling.strength
+                    ),
+                    None => out!(
+                        "🔍 {} doesn't upscale, enable it with --fsr or --nis",
+                        game_obj.name.cyan()
+                    ),
+                }
+                return Ok(());
+            };
+
+            let strength = strength
+                .or_else(|| game_obj.upscaling.as_ref().map(|u| u.strength))
+                .unwrap_or(UpscalingSettings::DEFAULT_STRENGTH);
+            if strength > UpscalingSettings::MAX_STRENGTH {
+                return Err(anyhow::anyhow!(
+                    "Invalid strength: {}. Use 0 (sharpest) to {}",
+                    strength,
+                    UpscalingSettings::MAX_STRENGTH
+                ));
+            }
+
+            game_obj.upscaling = Some(UpscalingSettings { method, strength });
+            game_lib.update_game(&game_obj)?;
+            out!(
+                "✅ {} upscales with {} at strength {}",
+                game_obj.name.green(),
+                method.name(),
+                strength
+            );
+            if method == UpscalingMethod::WineFsr {
+                out!(
+                    "💡 Wine FSR needs Proton or Wine-GE, and the game set to fullscreen below the display resolution"
+                );
+            }
+            Ok(())
+        }
+        GameCommands::FpsLimit { game, limit } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+
+            let Some(limit) = limit else {
+                match game_obj.fps_limit {
+                    Some(limit) => {
+                        out!("🎯 {} is capped at {} FPS", game_obj.name.cyan(), limit)
+                    }
+                    None => out!("🎯 {} has no frame rate cap", game_obj.name.cyan()),
+                }
+                return Ok(());
+            };
+
+            game_obj.fps_limit = match limit.to_lowercase().as_str() {
+                "off" | "none" | "0" => None,
+                "vrr" => {
+                    let (refresh, vrr) = crate::vrr_monitor::primary_display_refresh()
+                        .ok_or_else(|| anyhow::anyhow!("Could not detect the primary display"))?;
+                    if !vrr {
+                        out!(
+                            "⚠️  VRR is off on the primary display, enable it for tear-free capped frames"
+                        );
+                    }
+                    Some(crate::vrr_monitor::vrr_fps_cap(refresh))
+                }
+                value => {
+                    let fps = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|fps| crate::game::FPS_LIMITS.contains(fps));
+                    let Some(fps) = fps else {
+                        return Err(anyhow::anyhow!(
+                            "Invalid FPS limit: {}. Use {} to {}, 'vrr' or 'off'",
+                            limit,
+                            crate::game::FPS_LIMITS.start(),
+                            crate::game::FPS_LIMITS.end()
+                        ));
+                    };
+                    if let Some((refresh, true)) = crate::vrr_monitor::primary_display_refresh()
+                        && fps > refresh
+                    {
+                        out!(
+                            "💡 {} FPS is above the {} Hz refresh rate, 'vrr' caps at {} to stay in the VRR range",
+                            fps,
+                            refresh,
+                            crate::vrr_monitor::vrr_fps_cap(refresh)
+                        );
+                    }
+                    Some(fps)
+                }
+            };
+
+            game_lib.update_game(&game_obj)?;
+            match game_obj.fps_limit {
+                Some(fps) => out!("✅ {} is capped at {} FPS", game_obj.name.green(), fps),
+                None => out!("✅ Frame rate cap off for {}", game_obj.name.green()),
+            }
+            Ok(())
+        }
+        GameCommands::SetProfile {
+            game,
+            profile,
+            clear,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, true)?;
+
+            let Some(profile) = profile.filter(|_| !clear) else {
+                game_obj.profile = None;
+                game_lib.update_game(&game_obj)?;
+                out!("✅ Unlinked the profile from {}", game_obj.name.green());
+                return Ok(());
+            };
+
+            let profile_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+                .join("ghostforge")
+                .join("profiles");
+            let manager = crate::bolt_integration::OptimizationManager::new(profile_dir)?;
+            if manager.get_profile(&profile).is_none() {
+                return Err(anyhow::anyhow!(
+                    "Profile '{}' not found, see 'forge profile list'",
+                    profile
+                ));
+            }
+
+            game_obj.profile = Some(profile.clone());
+            game_lib.update_game(&game_obj)?;
+            out!(
+                "✅ {} now launches with profile {}",
+                game_obj.name.green(),
+                profile.cyan()
+            );
+            Ok(())
+        }
+        GameCommands::Mangohud {
+            game,
+            preset,
+            position,
+            off,
+            reset,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+
+            if reset {
+                game_obj.mangohud = None;
+                game_lib.update_game(&game_obj)?;
+                out!(
+                    "✅ {} follows the global MangoHud setting ({})",
+                    game_obj.name.green(),
+                    if config.general.enable_mangohud {
+                        "on"
+                    } else {
+                        "off"
+                    }
+                );
+                return Ok(());
+            }
+
+            let mut settings = game_obj.mangohud.clone().unwrap_or_default();
+            settings.enabled = !off;
+            if let Some(preset) = preset {
+                settings.preset =
+                    crate::mangohud::MangoHudPreset::from_name(&preset).ok_or_else(|| {
+                        anyhow::anyhow!("Unknown preset: {}. Use 'minimal' or 'full'", preset)
+                    })?;
+            }
+            if let Some(position) = position {
+                if !crate::mangohud::POSITIONS.contains(&position.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Unknown position: {}. Use one of {}",
+                        position,
+                        crate::mangohud::POSITIONS.join(", ")
+                    ));
+                }
+                settings.position = Some(position);
+            }
+
+            game_obj.mangohud = Some(settings.clone());
+            game_lib.update_game(&game_obj)?;
+
+            if !settings.enabled {
+                out!("✅ MangoHud disabled for {}", game_obj.name.green());
+                return Ok(());
+            }
+
+            let path = settings.write_config(&game_obj.id)?;
+            out!(
+                "✅ MangoHud enabled for {} ({} preset, {})",
+                game_obj.name.green(),
+                settings.preset.name().cyan(),
+                settings.position.as_deref().unwrap_or("top-left")
+            );
+            out!("  📁 {}", path.display());
+            if which::which("mangohud").is_err() {
+                out!("  ⚠️  MangoHud is not installed");
+            }
             Ok(())
         }
         GameCommands::Info { game } => {
-            println!("{}", format!("Game Information: {}", game).bold());
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, false)?;
+
+            if json {
+                return print_json(&game_obj);
+            }
+
+            let unset = || "not set".dimmed().to_string();
+            let path = |path: &std::path::Path| path.display().to_string();
+            let list = |items: &[String]| {
+                if items.is_empty() {
+                    unset()
+                } else {
+                    items.join(", ")
+                }
+            };
+
+            out!("{}", format!("🎮 {}", game_obj.name).bold().cyan());
+            println!("  ID: {}", game_obj.id.yellow());
+            println!(
+                "  Launcher: {}",
+                game_obj.launcher.as_deref().unwrap_or("Manual")
+            );
+            if let Some(launcher_id) = &game_obj.launcher_id {
+                println!("  Launcher ID: {}", launcher_id);
+            }
+            println!(
+                "  Status: {}",
+                if game_obj.is_installed() {
+                    "installed".green()
+                } else {
+                    "not installed".red()
+                }
+            );
+
+            println!("\n  {}", "Files".bold());
+            println!("    Executable: {}", path(&game_obj.executable));
+            println!("    Install path: {}", path(&game_obj.install_path));
+            println!("    Prefix: {}", path(&game_obj.prefix_path(&config.wine)));
+            println!(
+                "    Wine/Proton: {}",
+                game_obj.wine_version.clone().unwrap_or_else(unset)
+            );
+            if !game_obj.launch_arguments.is_empty() {
+                println!("    Arguments: {}", game_obj.launch_arguments.join(" "));
+            }
+            for (key, value) in &game_obj.environment_variables {
+                println!("    {}={}", key, value);
+            }
+
+            println!("\n  {}", "Library".bold());
+            println!(
+                "    Playtime: {}h {}m",
+                game_obj.playtime_minutes / 60,
+                game_obj.playtime_minutes % 60
+            );
+            println!(
+                "    Last played: {}",
+                game_obj
+                    .last_played
+                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "never".to_string())
+            );
+            println!(
+                "    Installed: {}",
+                game_obj.installed_date.format("%Y-%m-%d")
+            );
+            println!("    Categories: {}", list(&game_obj.categories));
+            println!("    Tags: {}", list(&game_obj.tags));
+            println!(
+                "    Favorite: {}  Hidden: {}",
+                if game_obj.favorite { "yes" } else { "no" },
+                if game_obj.hidden { "yes" } else { "no" }
+            );
+            if let Some(notes) = &game_obj.notes {
+                println!("    Notes: {}", notes);
+            }
+
+            println!("\n  {}", "Launch".bold());
+            println!(
+                "    Profile: {}",
+                game_obj.profile.clone().unwrap_or_else(unset)
+            );
+            if let Some(upscaling) = &game_obj.upscaling {
+                println!(
+                    "    Upscaling: {} at strength {}",
+                    upscaling.method.name(),
+                    upscaling.strength
+                );
+            }
+            if let Some(limit) = game_obj.fps_limit {
+                println!("    FPS limit: {}", limit);
+            }
+            if let Some(gamemode) = game_obj.gamemode {
+                println!("    GameMode: {}", if gamemode { "on" } else { "off" });
+            }
+            if let Some(version) = &game_obj.dxvk_version {
+                println!("    DXVK: {}", version);
+            }
+            if let Some(version) = &game_obj.vkd3d_version {
+                println!("    VKD3D-Proton: {}", version);
+            }
+
+            // Only what's cached, info shouldn't wait on the network
+            let steam_appid = game_obj
+                .launcher_id
+                .as_deref()
+                .filter(|_| game_obj.launcher.as_deref() == Some("Steam"))
+                .and_then(|id| id.parse::<u32>().ok());
+            if let Some(appid) = steam_appid {
+                let protondb = crate::protondb::ProtonDBClient::from_config(&config);
+                match protondb.cached_report(appid) {
+                    Some(report) => out!(
+                        "    ProtonDB: {} ({} reports, {})",
+                        report.tier_display,
+                        report.total_reports,
+                        report.last_updated.format("%Y-%m-%d")
+                    ),
+                    None => println!(
+                        "    ProtonDB: {}",
+                        "not fetched yet, 'forge scan --with-protondb' fetches it".dimmed()
+                    ),
+                }
+            }
+
+            // What containers for the game start from, see game_hints.toml
+            let hints = crate::game_hints::GameHints::load();
+            let steam_appid = game_obj.steam_appid();
+            let settings = hints.settings(&game_obj.name, steam_appid);
+            let mut applied = Vec::new();
+            if let Some(category) = hints.category(&game_obj.name, steam_appid) {
+                applied.push(format!("{} category", category.slug()));
+            }
+            if let Some(memory_mb) = settings.memory_mb {
+                applied.push(format!("{} MB memory", memory_mb));
+            }
+            if let Some(disk_mb) = settings.disk_mb {
+                applied.push(format!("{} MB disk", disk_mb));
+            }
+            if let Some(network_mode) = &settings.network_mode {
+                applied.push(format!("{} network", network_mode));
+            }
+            if let Some(wine_version) = &settings.wine_version {
+                applied.push(format!("Wine {}", wine_version));
+            }
+            if !applied.is_empty() {
+                println!("    Game hints: {}", applied.join(", "));
+            }
             Ok(())
         }
         GameCommands::Verify { game } => {
-            println!("Verifying files for: {}", game.cyan());
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, false)?;
+
+            out!("🔍 Verifying files for: {}", game_obj.name.cyan());
+            let report = crate::game::verify_game_files(&game_obj)?;
+
+            println!(
+                "  Checked {} files ({})",
+                report.files_checked,
+                crate::utils::format_size(report.total_size)
+            );
+
+            if report.passed() {
+                out!("{}", "✅ Verification passed".bold().green());
+                return Ok(());
+            }
+
+            out!(
+                "{}",
+                format!("❌ Verification failed: {} problems", report.problems.len())
+                    .bold()
+                    .red()
+            );
+            for problem in &report.problems {
+                println!("  • {}", problem);
+            }
+
+            let steam_appid = game_obj
+                .launcher_id
+                .as_ref()
+                .filter(|_| game_obj.launcher.as_deref() == Some("Steam"));
+            if let Some(appid) = steam_appid {
+                let validate = dialoguer::Confirm::new()
+                    .with_prompt("Ask Steam to validate and repair the game files?")
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false);
+
+                if validate {
+                    std::process::Command::new("xdg-open")
+                        .arg(format!("steam://validate/{}", appid))
+                        .spawn()?;
+                    out!("🔄 Steam validation started");
+                }
+            }
+
+            Err(anyhow::anyhow!(
+                "{} failed verification with {} problems",
+                game_obj.name,
+                report.problems.len()
+            ))
+        }
+        GameCommands::Running => {
+            let registry = crate::game_launcher::RunningGameRegistry::load()?;
+            if registry.games.is_empty() {
+                println!("No games are running");
+                return Ok(());
+            }
+
+            out!("{}", "🎮 Running Games:".bold().blue());
+            let mut games: Vec<_> = registry.games.values().collect();
+            games.sort_by_key(|game| game.start_time);
+            for game in games {
+                let minutes = chrono::Utc::now()
+                    .signed_duration_since(game.start_time)
+                    .num_minutes();
+                println!(
+                    "  • {} (PID {}) - running for {}h {}m",
+                    game.game_name.green(),
+                    game.pid.map(|pid| pid.to_string()).unwrap_or_default(),
+                    minutes / 60,
+                    minutes % 60
+                );
+            }
+            Ok(())
+        }
+        GameCommands::Stop { game } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, false)?;
+
+            GameLauncher::new(config).stop_game(&game_obj.id).await
+        }
+        GameCommands::Move { game, dest, prefix } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, true)?;
+
+            let moved =
+                game_lib.move_game(&game_obj, &PathBuf::from(&dest), prefix, &config.wine)?;
+            out!(
+                "✅ Moved {} to {}",
+                moved.name.green(),
+                moved.install_path.display()
+            );
+            Ok(())
+        }
+        GameCommands::Export { file } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let games = game_lib.list_games()?;
+
+            let profile_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+                .join("ghostforge")
+                .join("profiles");
+            let manager = crate::bolt_integration::OptimizationManager::new(profile_dir)?;
+            let linked: HashSet<&str> = games.iter().filter_map(|g| g.profile.as_deref()).collect();
+            let profiles: Vec<_> = linked
+                .into_iter()
+                .filter_map(|name| manager.get_profile(name))
+                .collect();
+
+            let mut bundle = crate::game::LibraryBundle::new(games, &config.paths.games_library);
+            bundle.profiles = profiles;
+            bundle.save(std::path::Path::new(&file))?;
+            out!(
+                "✅ Exported {} games and {} profiles to {}",
+                bundle.games.len(),
+                bundle.profiles.len(),
+                file.bright_blue()
+            );
+            Ok(())
+        }
+        GameCommands::Import {
+            file,
+            replace,
+            merge,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut bundle = crate::game::LibraryBundle::load(std::path::Path::new(&file))
+                .map_err(|e| anyhow::anyhow!("{} is not a valid library bundle: {}", file, e))?;
+            let home =
+                dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+            bundle.rewrite_paths(&home, &config.paths.games_library);
+
+            let collisions = bundle
+                .games
+                .iter()
+                .filter(|game| matches!(game_lib.get_game(&game.id), Ok(Some(_))))
+                .count();
+            let replace = if collisions > 0 && !replace && !merge {
+                dialoguer::Confirm::new()
+                    .with_prompt(format!(
+                        "{} games are already in the library, replace them? (no keeps them)",
+                        collisions
+                    ))
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false)
+            } else {
+                replace
+            };
+
+            let summary = game_lib.import_bundle(&bundle, replace)?;
+            out!(
+                "✅ Imported {} games, replaced {}, kept {} existing",
+                summary.added,
+                summary.replaced,
+                summary.skipped.len()
+            );
+
+            let profile_dir = dirs::config_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+                .join("ghostforge")
+                .join("profiles");
+            let manager = crate::bolt_integration::OptimizationManager::new(profile_dir)?;
+            for profile in &bundle.profiles {
+                if manager.get_profile(&profile.name).is_some() {
+                    continue;
+                }
+                let problems = profile.validate();
+                if problems.is_empty() {
+                    manager.save_profile(profile).await?;
+                    println!("  Added profile {}", profile.name.cyan());
+                } else {
+                    out!(
+                        "  {} Skipped profile {}: {}",
+                        "⚠️".yellow(),
+                        profile.name,
+                        problems.join("; ")
+                    );
+                }
+            }
+
+            let missing: Vec<_> = bundle.games.iter().filter(|g| !g.is_installed()).collect();
+            if !missing.is_empty() {
+                out!(
+                    "💡 {} games aren't installed at their paths here, move their files or reinstall them:",
+                    missing.len()
+                );
+                for game in missing {
+                    println!("  • {} ({})", game.name, game.install_path.display());
+                }
+            }
+            Ok(())
+        }
+        GameCommands::Usage { top } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+
+            out!("{}", "💾 Measuring disk usage...".cyan());
+            let mut usage = crate::game::measure_disk_usage(
+                &game_lib.list_games()?,
+                &config.paths.cache,
+                &config.wine,
+            );
+            usage.sort_by_key(|u| std::cmp::Reverse(u.total()));
+
+            if json {
+                return print_json(&usage);
+            }
+
+            let size = crate::utils::format_size;
+            out!("\n{}", "📦 Largest games:".bold());
+            for u in usage.iter().filter(|u| u.total() > 0).take(top) {
+                println!(
+                    "  {:>10}  {} (install {}, prefix {}, shaders {})",
+                    size(u.total()).yellow(),
+                    u.name.cyan(),
+                    size(u.install),
+                    size(u.prefix),
+                    size(u.shader_cache)
+                );
+            }
+
+            let install: u64 = usage.iter().map(|u| u.install).sum();
+            let prefix: u64 = usage.iter().map(|u| u.prefix).sum();
+            let shader_cache: u64 = usage.iter().map(|u| u.shader_cache).sum();
+            out!("\n{}", "📊 Total:".bold());
+            println!("  Installs:      {}", size(install));
+            println!("  Prefixes:      {}", size(prefix));
+            println!("  Shader caches: {}", size(shader_cache));
+            println!(
+                "  All:           {}",
+                size(install + prefix + shader_cache).bold()
+            );
             Ok(())
         }
     }
 }
 
-async fn handle_wine_command(action: WineCommands) -> Result<()> {
+/// Output of `--json`
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+async fn handle_prefix_command(action: PrefixCommands) -> Result<()> {
+    use crate::prefix::PrefixSnapshot;
+
+    let config = crate::config::Config::load()?;
+    let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+
+    match action {
+        PrefixCommands::List => {
+            let games = game_lib.list_games()?;
+            let prefixes = crate::prefix::discover_prefixes(
+                games.iter().map(|game| game.prefix_path(&config.wine)),
+            );
+
+            out!("{}", "🍷 Wine prefixes:".bold().magenta());
+            if prefixes.is_empty() {
+                println!("  No prefixes found");
+                return Ok(());
+            }
+
+            for path in prefixes {
+                let info = match crate::prefix::PrefixInfo::read(&path) {
+                    Ok(info) => info,
+                    Err(e) => {
+                        println!("\n  {} {}", path.display(), format!("({})", e).red());
+                        continue;
+                    }
+                };
+                let users: Vec<&crate::game::Game> = games
+                    .iter()
+                    .filter(|g| g.prefix_path(&config.wine) == path)
+                    .collect();
+
+                println!("\n  {}", path.display().to_string().cyan().bold());
+                if !users.is_empty() {
+                    let names: Vec<&str> = users.iter().map(|g| g.name.as_str()).collect();
+                    println!("    Games: {}", names.join(", "));
+                }
+                println!(
+                    "    Wine: {}  Arch: {}  Size: {}",
+                    prefix_wine_version(&info, &users),
+                    info.arch.as_deref().unwrap_or("unknown"),
+                    crate::utils::format_size(info.size_bytes)
+                );
+                if !info.dll_overrides.is_empty() {
+                    let overrides: Vec<String> = info
+                        .dll_overrides
+                        .iter()
+                        .map(|(dll, mode)| format!("{}={}", dll, mode))
+                        .collect();
+                    println!("    DLL overrides: {}", overrides.join(", ").dimmed());
+                }
+            }
+            Ok(())
+        }
+        PrefixCommands::Info { path } => {
+            let mut prefix_path = PathBuf::from(&path);
+            if !prefix_path.join("system.reg").exists()
+                && let Ok(game) = find_game(&game_lib, &path, false)
+            {
+                prefix_path = game.prefix_path(&config.wine);
+            }
+            let info = crate::prefix::PrefixInfo::read(&prefix_path)?;
+            let games = game_lib.list_games()?;
+            let users: Vec<&crate::game::Game> = games
+                .iter()
+                .filter(|g| g.prefix_path(&config.wine) == prefix_path)
+                .collect();
+
+            out!(
+                "{} {}",
+                "🍷 Prefix".bold(),
+                prefix_path.display().to_string().cyan().bold()
+            );
+            if !users.is_empty() {
+                let names: Vec<&str> = users.iter().map(|g| g.name.as_str()).collect();
+                println!("  Games: {}", names.join(", "));
+            }
+            println!("  Wine: {}", prefix_wine_version(&info, &users));
+            println!("  Arch: {}", info.arch.as_deref().unwrap_or("unknown"));
+            println!(
+                "  Windows version: {}",
+                info.windows_version.as_deref().unwrap_or("default")
+            );
+            println!("  Size: {}", crate::utils::format_size(info.size_bytes));
+
+            let list = |title: &str, items: Vec<String>| {
+                out!("\n{}", title.bold());
+                if items.is_empty() {
+                    println!("  None");
+                }
+                for item in items {
+                    println!("  • {}", item);
+                }
+            };
+            list("🎨 Graphics layers:", info.graphics_layers);
+            list(
+                "🔧 DLL overrides:",
+                info.dll_overrides
+                    .iter()
+                    .map(|(dll, mode)| format!("{} = {}", dll, mode))
+                    .collect(),
+            );
+            list("📦 Winetricks verbs:", info.winetricks_verbs);
+            list("💿 Installed programs:", info.installed_programs);
+            Ok(())
+        }
+        PrefixCommands::Create {
+            path,
+            arch,
+            winver,
+            wine_version,
+        } => crate::prefix::PrefixManager::create(
+            &PathBuf::from(path),
+            &arch,
+            &winver,
+            wine_version.as_deref(),
+        ),
+        PrefixCommands::Snapshots { game } => {
+            let game_obj = find_game(&game_lib, &game, false)?;
+            let prefix_path = game_obj.prefix_path(&config.wine);
+            let snapshots = PrefixSnapshot::list(&prefix_path)?;
+
+            out!(
+                "{} {}",
+                "📸 Prefix snapshots for".bold(),
+                game_obj.name.cyan().bold()
+            );
+            if snapshots.is_empty() {
+                println!("  No snapshots of {}", prefix_path.display());
+                return Ok(());
+            }
+            for snapshot in snapshots {
+                println!(
+                    "  {} {} {}",
+                    snapshot.id.green(),
+                    snapshot
+                        .created
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    snapshot.reason.dimmed()
+                );
+            }
+            Ok(())
+        }
+        PrefixCommands::Rollback { game, snapshot } => {
+            let game_obj = find_game(&game_lib, &game, true)?;
+            let prefix_path = game_obj.prefix_path(&config.wine);
+
+            if crate::game_launcher::RunningGameRegistry::load()?
+                .games
+                .contains_key(&game_obj.id)
+            {
+                return Err(anyhow::anyhow!(
+                    "{} is running, stop it with 'forge game stop' first",
+                    game_obj.name
+                ));
+            }
+
+            let snapshots = PrefixSnapshot::list(&prefix_path)?;
+            let target = match &snapshot {
+                Some(id) => snapshots.into_iter().find(|s| &s.id == id).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No snapshot {} for {}. Use 'forge prefix snapshots' to list them",
+                        id,
+                        game_obj.name
+                    )
+                })?,
+                None => snapshots
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("No snapshots of {}", prefix_path.display()))?,
+            };
+
+            out!(
+                "⏪ Rolling back {} to {} ({})",
+                game_obj.name.cyan(),
+                target.id,
+                target.reason
+            );
+            target.restore()?;
+            out!("✅ Prefix restored");
+            Ok(())
+        }
+        PrefixCommands::Gc { yes } => {
+            let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+            let launchers = crate::launcher::LauncherManager::new(config_dir)
+                .detect_launchers()
+                .unwrap_or_default();
+            let orphaned = crate::prefix::find_orphaned_prefixes(
+                &game_lib.list_games()?,
+                &launchers,
+                &config.wine,
+            );
+            if orphaned.is_empty() {
+                out!("✨ No orphaned prefixes");
+                return Ok(());
+            }
+
+            out!("{}", "🧹 Prefixes no longer in use:".bold().magenta());
+            let mut sizes = Vec::new();
+            for path in &orphaned {
+                let size = crate::utils::disk_usage(path)?;
+                println!(
+                    "  {} ({})",
+                    path.display().to_string().cyan(),
+                    crate::utils::format_size(size)
+                );
+                sizes.push(size);
+            }
+
+            let total: u64 = sizes.iter().sum();
+            let confirmed = yes
+                || dialoguer::Confirm::new()
+                    .with_prompt(format!(
+                        "Delete {} prefix(es), freeing {}?",
+                        orphaned.len(),
+                        crate::utils::format_size(total)
+                    ))
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false);
+            if !confirmed {
+                return Ok(());
+            }
+
+            let mut reclaimed = 0;
+            for (path, size) in orphaned.iter().zip(sizes) {
+                match std::fs::remove_dir_all(path) {
+                    Ok(()) => reclaimed += size,
+                    Err(e) => out!("❌ Failed to delete {}: {}", path.display(), e),
+                }
+            }
+            out!("✅ Reclaimed {}", crate::utils::format_size(reclaimed));
+            Ok(())
+        }
+    }
+}
+
+/// Wine version of a prefix as configured on the games using it, or the
+/// Proton version Steam recorded
+fn prefix_wine_version(info: &crate::prefix::PrefixInfo, users: &[&crate::game::Game]) -> String {
+    users
+        .iter()
+        .find_map(|game| game.wine_version.clone())
+        .or_else(|| info.proton_version.clone())
+        .unwrap_or_else(|| "system".to_string())
+}
+
+async fn handle_wine_command(action: WineCommands, json: bool) -> Result<()> {
     match action {
         WineCommands::List { available } => {
-            println!("{}", "🍷 Wine/Proton Versions:".bold().magenta());
+            let config = crate::config::Config::load()?;
+            let manager = crate::wine::WineManager::from_config(&config);
+
+            if json {
+                let versions = if available {
+                    manager.list_available().await?
+                } else {
+                    manager.list_installed().await?
+                };
+                return print_json(&versions);
+            }
 
-            let wine_dir = dirs::data_dir().unwrap().join("ghostforge").join("wine");
-            let config_dir = dirs::config_dir().unwrap().join("ghostforge");
-            let manager = crate::wine::WineManager::new(wine_dir, config_dir);
+            out!("{}", "🍷 Wine/Proton Versions:".bold().magenta());
 
             if available {
-                println!("\n📥 Available for Download:");
+                out!("\n📥 Available for Download:");
                 match manager.list_available().await {
                     Ok(available_versions) => {
                         if available_versions.is_empty() {
@@ -1205,7 +3286,7 @@ async fn handle_wine_command(action: WineCommands) -> Result<()> {
                                     crate::wine::WineType::WineStaging => "🍷",
                                     _ => "📦",
                                 };
-                                println!(
+                                out!(
                                     "  {} {} ({})",
                                     type_icon,
                                     version.name.green(),
@@ -1217,10 +3298,10 @@ async fn handle_wine_command(action: WineCommands) -> Result<()> {
                             }
                         }
                     }
-                    Err(e) => println!("  ❌ Failed to fetch available versions: {}", e),
+                    Err(e) => out!("  ❌ Failed to fetch available versions: {}", e),
                 }
             } else {
-                println!("\n📦 Installed Versions:");
+                out!("\n📦 Installed Versions:");
                 match manager.list_installed().await {
                     Ok(installed_versions) => {
                         if installed_versions.is_empty() {
@@ -1239,7 +3320,7 @@ async fn handle_wine_command(action: WineCommands) -> Result<()> {
                                     _ => "📦",
                                 };
                                 let system_marker = if version.system { " (system)" } else { "" };
-                                println!(
+                                out!(
                                     "  {} {} {} {}",
                                     type_icon,
                                     version.name.cyan(),
@@ -1256,13 +3337,62 @@ async fn handle_wine_command(action: WineCommands) -> Result<()> {
                             }
                         }
                     }
-                    Err(e) => println!("  ❌ Failed to list installed versions: {}", e),
+                    Err(e) => out!("  ❌ Failed to list installed versions: {}", e),
                 }
             }
             Ok(())
         }
-        WineCommands::Install { version } => {
-            println!("Installing {}...", version.green());
+        WineCommands::Install { versions, jobs } => {
+            use futures_util::StreamExt;
+
+            let config = crate::config::Config::load()?;
+            let manager = crate::wine::WineManager::from_config(&config);
+
+            let available = manager.list_available().await?;
+            let mut selected = Vec::new();
+            for name in &versions {
+                let version = available
+                    .iter()
+                    .find(|v| v.version == *name || v.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown version: {}. Use 'forge wine list --available' to see versions",
+                            name
+                        )
+                    })?;
+                if version.path.exists() {
+                    out!("⏭️  {} is already installed", version.name);
+                } else {
+                    selected.push(version.clone());
+                }
+            }
+
+            let results: Vec<(String, Result<()>)> = futures_util::stream::iter(selected)
+                .map(|version| {
+                    let manager = &manager;
+                    async move {
+                        let result = manager.install_wine_version(&version).await;
+                        (version.name, result)
+                    }
+                })
+                .buffer_unordered(jobs.max(1))
+                .collect()
+                .await;
+
+            let mut failures = 0;
+            for (name, result) in &results {
+                if let Err(e) = result {
+                    out!("❌ {}: {}", name, e);
+                    failures += 1;
+                }
+            }
+            if failures > 0 {
+                return Err(anyhow::anyhow!(
+                    "{} of {} installs failed",
+                    failures,
+                    results.len()
+                ));
+            }
             Ok(())
         }
         WineCommands::Remove { version } => {
@@ -1281,6 +3411,72 @@ async fn handle_wine_command(action: WineCommands) -> Result<()> {
             }
             Ok(())
         }
+        WineCommands::Update { version } => {
+            out!("{}", "🔄 Checking for GE-Proton updates...".bold().cyan());
+
+            let config = crate::config::Config::load()?;
+            let manager = crate::wine::WineManager::from_config(&config);
+
+            let check = manager.check_ge_proton_updates(version.as_deref()).await?;
+
+            // Only update what's there, installing is 'forge wine install'
+            if !check.installed {
+                out!("  ℹ️  No GE-Proton build is installed");
+                if let Some(latest) = &check.latest {
+                    out!(
+                        "  💡 Install the newest with 'forge wine install {}'",
+                        latest.name
+                    );
+                }
+                return Ok(());
+            }
+
+            match &check.latest {
+                Some(latest) => {
+                    out!("  📥 New release available: {}", latest.name.green());
+                    manager.install_wine_version(latest).await?;
+                }
+                None => out!("  ✅ Latest GE-Proton is already installed"),
+            }
+
+            if check.outdated.is_empty() {
+                return Ok(());
+            }
+
+            out!("\n{}", "📦 Outdated versions (kept installed):".bold());
+            for outdated in &check.outdated {
+                println!("  • {}", outdated.name.yellow());
+            }
+
+            // Games may pin an old build, so point them out instead of removing it
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let pinned: Vec<_> = game_lib
+                .list_games()?
+                .into_iter()
+                .filter_map(|game| {
+                    let wine_version = game.wine_version.clone()?;
+                    let key = crate::wine::ge_proton_release(&wine_version)?;
+                    check
+                        .outdated
+                        .iter()
+                        .any(|v| crate::wine::ge_proton_release(&v.name) == Some(key))
+                        .then_some((game, wine_version))
+                })
+                .collect();
+
+            if !pinned.is_empty() {
+                out!("\n{}", "🎮 Games using outdated versions:".bold());
+                for (game, wine_version) in &pinned {
+                    println!("  • {} ({})", game.name.cyan(), wine_version.dimmed());
+                }
+                out!(
+                    "\n💡 Switch them with 'forge game edit <game> --wine <version>', \
+                     then remove old builds with 'forge wine remove'"
+                );
+            }
+
+            Ok(())
+        }
     }
 }
 
@@ -1288,27 +3484,22 @@ async fn handle_launch(
     game: String,
     wine_version: Option<String>,
     args: Vec<String>,
+    refresh_protondb: bool,
+    hdr: bool,
+    gpu: Option<usize>,
+    gamescope: Option<crate::graphics::GameScopeConfig>,
 ) -> Result<()> {
     let config = crate::config::Config::load()?;
     config.ensure_directories()?;
     let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+    let mut protondb = crate::protondb::ProtonDBClient::from_config(&config);
+    protondb.set_refresh(refresh_protondb);
     let launcher = GameLauncher::new(config);
 
     // Find the game in the database
-    let game_obj = if let Some(found_game) = game_lib
-        .search_games(&game)?
-        .into_iter()
-        .find(|g| g.name.to_lowercase() == game.to_lowercase())
-    {
-        found_game
-    } else {
-        return Err(anyhow::anyhow!(
-            "Game '{}' not found. Use 'forge game list' to see available games.",
-            game
-        ));
-    };
+    let game_obj = find_game(&game_lib, &game, false)?;
 
-    println!("{} Launching {}...", "🚀", game_obj.name.bold().green());
+    out!("{} Launching {}...", "🚀", game_obj.name.bold().green());
 
     // Prepare launch options
     let mut options = LaunchOptions::default();
@@ -1319,18 +3510,91 @@ async fn handle_launch(
     }
 
     options.launch_arguments = args;
+    options.gpu_index = gpu;
+
+    // Per-game MangoHud settings take precedence over the global toggle
+    options.apply_mangohud(&game_obj, launcher.config.general.enable_mangohud)?;
+
+    if let Some(config) = gamescope {
+        println!(
+            "  Using gamescope: {}x{}{}",
+            config.output_width.unwrap_or(config.width),
+            config.output_height.unwrap_or(config.height),
+            config
+                .refresh_rate
+                .map(|rate| format!(" @ {}Hz", rate))
+                .unwrap_or_default()
+        );
+        options.enable_gamescope = true;
+        options.gamescope_config = Some(config);
+    }
+
+    // System tuning from the game's optimization profile, if it has one
+    let profile_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+        .join("ghostforge")
+        .join("profiles");
+    let manager = crate::bolt_integration::OptimizationManager::new(profile_dir)?;
+    if let Some(name) = &game_obj.profile
+        && manager.get_profile(name).is_none()
+    {
+        out!(
+            "  {} Profile '{}' not found, falling back to defaults",
+            "⚠️".yellow(),
+            name
+        );
+    }
+    if let Some(profile) = manager.profile_for_game(&game_obj) {
+        println!("  Using profile: {}", profile.name.cyan());
+        options.apply_profile(profile, &game_obj);
+    }
+    let tuned = options.wants_tuning();
+
+    // Switch HDR-capable displays to HDR for the game, remembering which
+    // ones to switch back
+    let mut hdr_displays = Vec::new();
+    let mut display_manager = None;
+    if hdr {
+        options.enable_hdr = true;
+        let mut manager = crate::display::DisplayManager::new()?;
+        let targets: Vec<String> = manager
+            .get_displays()
+            .iter()
+            .filter(|d| d.connected && d.hdr_capable && !d.hdr_enabled)
+            .map(|d| d.id.clone())
+            .collect();
+        for id in targets {
+            match manager.set_hdr(&id, true) {
+                Ok(()) => {
+                    out!("  🌈 HDR enabled on {}", id.cyan());
+                    hdr_displays.push(id);
+                }
+                Err(e) => out!("  ⚠️  Could not enable HDR on {}: {}", id, e),
+            }
+        }
+        display_manager = Some(manager);
+    }
 
     // Get ProtonDB recommendations if available
     if let Some(launcher_id) = &game_obj.launcher_id {
         if let Ok(appid) = launcher_id.parse::<u32>() {
-            let protondb = crate::protondb::ProtonDBClient::new();
-            if let Ok(compat_report) = protondb.get_compatibility_info(appid).await {
-                println!("  🌐 ProtonDB rating: {:?}", compat_report.tier);
-                if compat_report.tier == ProtonDBTier::Silver
-                    || compat_report.tier == ProtonDBTier::Bronze
-                {
-                    println!("  ⚠️  This game may require tweaks for optimal performance");
+            // A slow or unreachable ProtonDB must not hold up the launch
+            let lookup = tokio::time::timeout(
+                crate::protondb::LAUNCH_LOOKUP_TIMEOUT,
+                protondb.get_compatibility_info(appid),
+            )
+            .await;
+            match lookup {
+                Ok(Ok(compat_report)) => {
+                    out!("  🌐 ProtonDB rating: {:?}", compat_report.tier);
+                    if compat_report.tier == ProtonDBTier::Silver
+                        || compat_report.tier == ProtonDBTier::Bronze
+                    {
+                        out!("  ⚠️  This game may require tweaks for optimal performance");
+                    }
                 }
+                Ok(Err(e)) => out!("  ⚠️  ProtonDB unavailable ({}), launching anyway", e),
+                Err(_) => out!("  ⚠️  ProtonDB timed out, launching anyway"),
             }
         }
     }
@@ -1338,25 +3602,134 @@ async fn handle_launch(
     // Launch the game
     match launcher.launch_game(&game_obj, options).await {
         Ok(pid) => {
-            println!("✅ {} launched successfully (PID: {})", game_obj.name, pid);
+            out!("✅ {} launched successfully (PID: {})", game_obj.name, pid);
+
+            // Playtime, restoring settings and the post-launch script all
+            // happen when the game exits, which needs this process to still
+            // be around
+            if tuned || !hdr_displays.is_empty() {
+                out!(
+                    "  ⏳ Waiting for {} to exit to restore system settings (if interrupted, run 'forge optimize --reset')",
+                    game_obj.name
+                );
+            } else {
+                out!("  ⏳ Waiting for {} to exit", game_obj.name);
+            }
+            launcher.wait_for_exit(&game_obj.id).await;
+            restore_hdr(display_manager.as_mut(), &hdr_displays);
             Ok(())
         }
         Err(e) => {
-            println!("❌ Failed to launch {}: {}", game_obj.name, e);
+            out!("❌ Failed to launch {}: {}", game_obj.name, e);
+            restore_hdr(display_manager.as_mut(), &hdr_displays);
             Err(e)
         }
     }
 }
 
+/// Gamescope settings for `forge launch --gamescope`, starting from the
+/// primary display's native mode
+fn gamescope_config(
+    width: Option<u32>,
+    height: Option<u32>,
+    fps: Option<u32>,
+    upscale: Option<String>,
+) -> Result<crate::graphics::GameScopeConfig> {
+    use crate::graphics::{GameScopeConfig, GameScopeUpscaling};
+
+    let mut config = GameScopeConfig::for_primary_display();
+    if let Some(width) = width {
+        config.width = width;
+    }
+    if let Some(height) = height {
+        config.height = height;
+    }
+    if fps.is_some() {
+        config.refresh_rate = fps;
+    }
+
+    let upscaling = match upscale.as_deref().map(str::to_lowercase).as_deref() {
+        None => GameScopeUpscaling::None,
+        Some("fsr") => GameScopeUpscaling::FSR,
+        Some("nis") => GameScopeUpscaling::NIS,
+        Some(other) => {
+            return Err(anyhow::anyhow!(
+                "Unknown upscaler: {}. Use 'fsr' or 'nis'",
+                other
+            ));
+        }
+    };
+
+    Ok(config.with_upscaling(upscaling))
+}
+
+/// Switch displays that were put into HDR for a game launch back to SDR
+fn restore_hdr(manager: Option<&mut crate::display::DisplayManager>, displays: &[String]) {
+    let Some(manager) = manager else {
+        return;
+    };
+    for id in displays {
+        if let Err(e) = manager.set_hdr(id, false) {
+            out!("  ⚠️  Could not disable HDR on {}: {}", id, e);
+        }
+    }
+}
+
 async fn handle_install(
     source: String,
     name: Option<String>,
-    _wine_version: Option<String>,
+    wine_version: Option<String>,
 ) -> Result<()> {
-    println!("{} Installing from: {}", "📦", source.yellow());
-    if let Some(n) = name {
-        println!("  Game name: {}", n);
+    use crate::prefix::PrefixManager;
+
+    let installer = PathBuf::from(&source);
+    if !installer.is_file() {
+        return Err(anyhow::anyhow!(
+            "Installer not found: {}. Use 'forge launcher setup' to install a launcher",
+            source
+        ));
+    }
+    let name = name.unwrap_or_else(|| {
+        installer
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| source.clone())
+    });
+
+    out!("📦 Installing {} from: {}", name.cyan(), source.yellow());
+
+    // The game isn't in the library yet, so a per-game prefix is named after
+    // it. Games added from its drive_c find the prefix from their path.
+    let config = crate::config::Config::load()?;
+    let prefix = config.wine.prefix_for(&name);
+    if !prefix.join("system.reg").exists() {
+        PrefixManager::create(
+            &prefix,
+            &config.wine.default_arch,
+            &config.wine.default_windows_version,
+            wine_version.as_deref(),
+        )?;
+    }
+
+    let wine = crate::prefix::wine_binary(wine_version.as_deref())?;
+    let mut cmd = tokio::process::Command::new(wine);
+    cmd.env("WINEPREFIX", &prefix);
+    let is_msi = installer
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("msi"));
+    if is_msi {
+        cmd.args(["msiexec", "/i"]);
     }
+    cmd.arg(&installer);
+
+    out!("🔧 Running installer in {}...", prefix.display());
+    let status = cmd.status().await?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Installer exited with {}", status));
+    }
+
+    out!("✅ {} installed into {}", name, prefix.display());
+    println!("Add it with 'forge game add <executable> \"{}\"'", name);
     Ok(())
 }
 
@@ -1365,7 +3738,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
 
     match action {
         ConfigCommands::Show => {
-            println!("{}", "⚙️  GhostForge Configuration".bold().cyan());
+            out!("{}", "⚙️  GhostForge Configuration".bold().cyan());
             println!();
 
             // General settings
@@ -1374,7 +3747,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                 "  Default Wine Version: {}",
                 config.general.default_wine_version.yellow()
             );
-            println!(
+            out!(
                 "  Enable GameMode: {}",
                 if config.general.enable_gamemode {
                     "✅ Yes".green()
@@ -1382,7 +3755,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     "❌ No".red()
                 }
             );
-            println!(
+            out!(
                 "  Enable MangoHud: {}",
                 if config.general.enable_mangohud {
                     "✅ Yes".green()
@@ -1390,7 +3763,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     "❌ No".red()
                 }
             );
-            println!(
+            out!(
                 "  Enable DXVK: {}",
                 if config.general.enable_dxvk {
                     "✅ Yes".green()
@@ -1398,7 +3771,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     "❌ No".red()
                 }
             );
-            println!(
+            out!(
                 "  Enable VKD3D: {}",
                 if config.general.enable_vkd3d {
                     "✅ Yes".green()
@@ -1407,6 +3780,22 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                 }
             );
             println!("  Log Level: {}", config.general.log_level.cyan());
+            println!(
+                "  ProtonDB Cache TTL: {}h",
+                config.general.protondb_cache_ttl_hours
+            );
+            println!(
+                "  Launch Script Timeout: {}s",
+                config.general.script_timeout_secs
+            );
+            out!(
+                "  Central State Cache: {}",
+                if config.general.central_state_cache {
+                    "✅ Yes".green()
+                } else {
+                    "❌ No".red()
+                }
+            );
             println!();
 
             // Wine settings
@@ -1437,11 +3826,12 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                 "  Default Windows Version: {}",
                 config.wine.default_windows_version.cyan()
             );
+            println!("  Prefix Mode: {}", config.wine.prefix_mode.name().cyan());
             println!();
 
             // GPU settings
             println!("{}", "GPU:".bold());
-            println!(
+            out!(
                 "  NVIDIA Prime Render Offload: {}",
                 if config.gpu.nvidia_prime_render_offload {
                     "✅ Yes".green()
@@ -1449,7 +3839,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     "❌ No".red()
                 }
             );
-            println!(
+            out!(
                 "  Enable DLSS: {}",
                 if config.gpu.enable_dlss {
                     "✅ Yes".green()
@@ -1457,7 +3847,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     "❌ No".red()
                 }
             );
-            println!(
+            out!(
                 "  Enable Ray Tracing: {}",
                 if config.gpu.enable_ray_tracing {
                     "✅ Yes".green()
@@ -1483,6 +3873,15 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
             );
             println!();
 
+            let problems = config.validate();
+            if !problems.is_empty() {
+                println!("{}", "Problems:".bold().red());
+                for problem in problems {
+                    out!("  ❌ {}", problem);
+                }
+                println!();
+            }
+
             Ok(())
         }
         ConfigCommands::Set { key, value } => {
@@ -1507,6 +3906,14 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                     config.general.enable_vkd3d = value.parse().unwrap_or(false);
                     true
                 }
+                "general.state_cache" => {
+                    config.general.central_state_cache = value.parse().unwrap_or(false);
+                    true
+                }
+                "containers.controller_support" => {
+                    config.containers.controller_support = value.parse().unwrap_or(true);
+                    true
+                }
                 "gpu.nvidia_prime" => {
                     config.gpu.nvidia_prime_render_offload = value.parse().unwrap_or(false);
                     true
@@ -1524,23 +3931,120 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                         config.wine.default_arch = value.clone();
                         true
                     } else {
-                        println!("❌ Invalid architecture. Use 'win32' or 'win64'");
+                        out!("❌ Invalid architecture. Use 'win32' or 'win64'");
+                        false
+                    }
+                }
+                "wine.prefix_mode" => match crate::config::PrefixMode::from_name(&value) {
+                    Some(mode) => {
+                        // Games keep the prefix they use today, the new mode
+                        // only applies to games added from now on
+                        if mode != config.wine.prefix_mode {
+                            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+                            let assigned = game_lib.assign_prefixes(&config.wine)?;
+                            if assigned > 0 {
+                                out!("📌 Kept the current prefix of {} game(s)", assigned);
+                            }
+                        }
+                        config.wine.prefix_mode = mode;
+                        true
+                    }
+                    None => {
+                        out!(
+                            "❌ Unknown prefix mode: {}. Use 'per-game' or 'shared'",
+                            value
+                        );
+                        false
+                    }
+                },
+                "general.protondb_cache_ttl" => match value.parse::<u64>() {
+                    Ok(hours) => {
+                        config.general.protondb_cache_ttl_hours = hours;
+                        true
+                    }
+                    Err(_) => {
+                        out!("❌ Invalid TTL '{}'. Use a number of hours", value);
+                        false
+                    }
+                },
+                "general.script_timeout" => match value.parse::<u64>() {
+                    Ok(seconds) => {
+                        config.general.script_timeout_secs = seconds;
+                        true
+                    }
+                    Err(_) => {
+                        out!("❌ Invalid timeout '{}'. Use a number of seconds", value);
+                        false
+                    }
+                },
+                "general.log_level" => match value.parse::<tracing::Level>() {
+                    Ok(_) => {
+                        config.general.log_level = value.to_lowercase();
+                        true
+                    }
+                    Err(_) => {
+                        out!(
+                            "❌ Invalid log level '{}'. Use trace, debug, info, warn or error",
+                            value
+                        );
                         false
                     }
+                },
+                "wine.default_prefix_path" => {
+                    apply_config_path(&mut config.wine.default_prefix_path, &value, true)
+                }
+                "wine.wine_versions_path" => {
+                    apply_config_path(&mut config.wine.wine_versions_path, &value, true)
+                }
+                "wine.dxvk_versions_path" => {
+                    apply_config_path(&mut config.wine.dxvk_versions_path, &value, true)
+                }
+                "wine.winetricks_path" => {
+                    if value == "none" {
+                        config.wine.winetricks_path = None;
+                        true
+                    } else {
+                        let path = PathBuf::from(&value);
+                        if !path.is_absolute() {
+                            out!("❌ Path must be absolute: {}", value);
+                            false
+                        } else if !path.is_file() {
+                            out!("❌ Winetricks not found at: {}", value);
+                            false
+                        } else {
+                            config.wine.winetricks_path = Some(path);
+                            true
+                        }
+                    }
+                }
+                "paths.games_library" => {
+                    apply_config_path(&mut config.paths.games_library, &value, true)
                 }
+                "paths.downloads" => apply_config_path(&mut config.paths.downloads, &value, true),
+                "paths.backups" => apply_config_path(&mut config.paths.backups, &value, true),
+                "paths.logs" => apply_config_path(&mut config.paths.logs, &value, true),
+                "paths.cache" => apply_config_path(&mut config.paths.cache, &value, true),
+                "paths.database" => apply_config_path(&mut config.paths.database, &value, false),
                 _ => {
-                    println!("❌ Unknown configuration key: {}", key);
+                    out!("❌ Unknown configuration key: {}", key);
                     println!("Available keys:");
                     println!("  wine.default_version, general.gamemode, general.mangohud");
-                    println!("  general.dxvk, general.vkd3d, gpu.nvidia_prime");
-                    println!("  gpu.dlss, gpu.ray_tracing, wine.default_arch");
+                    println!("  general.dxvk, general.vkd3d, general.log_level");
+                    println!("  general.protondb_cache_ttl, general.script_timeout");
+                    println!("  general.state_cache");
+                    println!("  gpu.nvidia_prime, gpu.dlss, gpu.ray_tracing");
+                    println!("  wine.default_arch, wine.default_prefix_path, wine.prefix_mode");
+                    println!("  wine.wine_versions_path, wine.dxvk_versions_path");
+                    println!("  wine.winetricks_path, paths.games_library, paths.downloads");
+                    println!("  paths.backups, paths.logs, paths.cache, paths.database");
+                    println!("  containers.controller_support");
                     false
                 }
             };
 
             if updated {
                 config.save()?;
-                println!("✅ Set {} = {}", key.cyan(), value.green());
+                out!("✅ Set {} = {}", key.cyan(), value.green());
                 println!(
                     "Configuration saved to: {}",
                     crate::config::Config::config_path()
@@ -1563,8 +4067,28 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                 "gpu.dlss" => config.gpu.enable_dlss.to_string(),
                 "gpu.ray_tracing" => config.gpu.enable_ray_tracing.to_string(),
                 "wine.default_arch" => config.wine.default_arch,
+                "wine.prefix_mode" => config.wine.prefix_mode.name().to_string(),
+                "general.log_level" => config.general.log_level,
+                "general.protondb_cache_ttl" => config.general.protondb_cache_ttl_hours.to_string(),
+                "general.script_timeout" => config.general.script_timeout_secs.to_string(),
+                "general.state_cache" => config.general.central_state_cache.to_string(),
+                "containers.controller_support" => config.containers.controller_support.to_string(),
+                "wine.default_prefix_path" => config.wine.default_prefix_path.display().to_string(),
+                "wine.wine_versions_path" => config.wine.wine_versions_path.display().to_string(),
+                "wine.dxvk_versions_path" => config.wine.dxvk_versions_path.display().to_string(),
+                "wine.winetricks_path" => config
+                    .wine
+                    .winetricks_path
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+                "paths.games_library" => config.paths.games_library.display().to_string(),
+                "paths.downloads" => config.paths.downloads.display().to_string(),
+                "paths.backups" => config.paths.backups.display().to_string(),
+                "paths.logs" => config.paths.logs.display().to_string(),
+                "paths.cache" => config.paths.cache.display().to_string(),
+                "paths.database" => config.paths.database.display().to_string(),
                 _ => {
-                    println!("❌ Unknown configuration key: {}", key);
+                    out!("❌ Unknown configuration key: {}", key);
                     return Ok(());
                 }
             };
@@ -1576,7 +4100,7 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
             if yes {
                 let default_config = crate::config::Config::default();
                 default_config.save()?;
-                println!("✅ Configuration reset to defaults");
+                out!("✅ Configuration reset to defaults");
                 println!(
                     "Configuration file: {}",
                     crate::config::Config::config_path()
@@ -1585,18 +4109,61 @@ async fn handle_config_command(action: ConfigCommands) -> Result<()> {
                         .dimmed()
                 );
             } else {
-                println!("⚠️ This will reset ALL configuration to defaults.");
+                out!("⚠️ This will reset ALL configuration to defaults.");
                 println!("Use --yes to confirm the reset.");
             }
             Ok(())
         }
+        ConfigCommands::ClearCache { appid } => {
+            let protondb = crate::protondb::ProtonDBClient::from_config(&config);
+            let removed = protondb.invalidate_cache(appid)?;
+            match appid {
+                Some(appid) => out!(
+                    "🗑️ Removed {} cached ProtonDB file(s) for app {}",
+                    removed,
+                    appid
+                ),
+                None => out!("🗑️ Removed {} cached ProtonDB file(s)", removed),
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Validate an absolute path for `config set` and create it (or its parent
+/// directory for files) so later commands don't trip over a missing path.
+fn apply_config_path(field: &mut PathBuf, value: &str, directory: bool) -> bool {
+    let path = PathBuf::from(value);
+    if !path.is_absolute() {
+        out!("❌ Path must be absolute: {}", value);
+        return false;
+    }
+
+    if directory && path.exists() && !path.is_dir() {
+        out!("❌ Not a directory: {}", value);
+        return false;
+    }
+
+    let dir = if directory {
+        Some(path.as_path())
+    } else {
+        path.parent()
+    };
+    if let Some(dir) = dir
+        && let Err(e) = std::fs::create_dir_all(dir)
+    {
+        out!("❌ Cannot create directory {}: {}", dir.display(), e);
+        return false;
     }
+
+    *field = path;
+    true
 }
 
 async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
     match action {
         LauncherCommands::List => {
-            println!("{}", "🎮 Configured Launchers:".bold().blue());
+            out!("{}", "🎮 Configured Launchers:".bold().blue());
 
             let config_dir = dirs::config_dir().unwrap().join("ghostforge");
             let launcher_manager = crate::launcher::LauncherManager::new(config_dir);
@@ -1605,7 +4172,7 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
                 Ok(launchers) => {
                     if launchers.is_empty() {
                         println!("  No launchers detected");
-                        println!("\n💡 Supported launchers:");
+                        out!("\n💡 Supported launchers:");
                         println!("  • Steam - Install from your distribution's package manager");
                         println!(
                             "  • Battle.net - Use 'forge battlenet setup' to create a Wine prefix"
@@ -1633,7 +4200,7 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
                                 crate::launcher::LauncherType::Custom => "🔧",
                             };
 
-                            println!(
+                            out!(
                                 "  {} {} {} ({})",
                                 status_icon,
                                 launcher_icon,
@@ -1661,7 +4228,7 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
                                 println!("    Game Paths:");
                                 for path in &launcher.games_path {
                                     let exists_marker = if path.exists() { "✓" } else { "✗" };
-                                    println!(
+                                    out!(
                                         "      {} {}",
                                         exists_marker,
                                         path.display().to_string().dimmed()
@@ -1671,7 +4238,7 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
                             println!();
                         }
 
-                        println!(
+                        out!(
                             "📊 {} launcher(s) detected",
                             launchers.len().to_string().bold().green()
                         );
@@ -1694,22 +4261,46 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
                         }
 
                         if games_available > 0 {
-                            println!(
+                            out!(
                                 "🎮 {} games available across all launchers",
                                 games_available.to_string().bold().blue()
                             );
-                            println!("💡 Use 'forge game list' to see all games");
+                            out!("💡 Use 'forge game list' to see all games");
                         }
                     }
                 }
                 Err(e) => {
-                    println!("❌ Failed to detect launchers: {}", e);
+                    out!("❌ Failed to detect launchers: {}", e);
                 }
             }
             Ok(())
         }
-        LauncherCommands::Setup { launcher, path: _ } => {
+        LauncherCommands::Setup { launcher, path } => {
+            let launcher_type = crate::launcher::LauncherType::from_name(&launcher)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown launcher '{}'. Supported: steam, battlenet, epic, gog, ubisoft, ea",
+                        launcher
+                    )
+                })?;
+
             println!("Setting up {} launcher...", launcher.green());
+
+            let config_dir = dirs::config_dir().unwrap().join("ghostforge");
+            let launcher_manager = crate::launcher::LauncherManager::new(config_dir);
+            let configured = launcher_manager
+                .setup_launcher(launcher_type, path.map(PathBuf::from))
+                .await?;
+
+            out!("✅ {} is ready", configured.name.bold().green());
+            println!("  Executable: {}", configured.executable.display());
+            if let Some(prefix) = &configured.wine_prefix {
+                println!("  Wine prefix: {}", prefix.display());
+            }
+            out!(
+                "\n💡 Use 'forge launcher sync {}' to import its games",
+                launcher.to_lowercase()
+            );
             Ok(())
         }
         LauncherCommands::Sync { launcher } => {
@@ -1719,14 +4310,38 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
             let launcher_manager =
                 crate::launcher::LauncherManager::new(config.paths.cache.clone());
 
-            if !launcher.is_empty() {
-                println!("🔄 Syncing games from {}...", launcher.cyan());
-                // TODO: Sync specific launcher
-                println!("⚠️ Specific launcher sync not yet implemented");
+            if !launcher.is_empty() && launcher != "all" {
+                let launcher_type = crate::launcher::LauncherType::from_name(&launcher)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown launcher '{}'. Supported: steam, battlenet, epic, gog, ubisoft, ea, all",
+                            launcher
+                        )
+                    })?;
+
+                let detected = launcher_manager
+                    .detect_launcher(&launcher_type)?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "{} is not installed. Use 'forge launcher setup {}' first.",
+                            launcher,
+                            launcher.to_lowercase()
+                        )
+                    })?;
+
+                out!("🔄 Syncing games from {}...", detected.name.cyan());
+                let imported = launcher_manager
+                    .import_launcher_games(&detected, &game_lib)
+                    .await?;
+                out!(
+                    "\n✅ Imported {} games from {}",
+                    imported.to_string().bold().green(),
+                    detected.name
+                );
             } else {
-                println!("🔄 Syncing games from all detected launchers...");
+                out!("🔄 Syncing games from all detected launchers...");
                 let imported = launcher_manager.import_all_games(&game_lib).await?;
-                println!(
+                out!(
                     "\n✅ Successfully imported {} games",
                     imported.to_string().bold().green()
                 );
@@ -1744,98 +4359,509 @@ async fn handle_launcher_command(action: LauncherCommands) -> Result<()> {
     }
 }
 
-async fn handle_tricks(game: String, trick: String, _force: bool) -> Result<()> {
-    use crate::winetricks::WinetricksManager;
+fn handle_tricks_command(action: TricksCommands) -> Result<()> {
+    match action {
+        TricksCommands::Cache { size: _, clear } => {
+            let cache_dir = crate::winetricks::default_cache_dir();
+            if clear {
+                let freed = crate::winetricks::clear_cache(&cache_dir)?;
+                out!(
+                    "🧹 Cleared the Winetricks cache, freed {}",
+                    crate::utils::format_size(freed).green()
+                );
+                return Ok(());
+            }
 
-    let cache_dir = dirs::cache_dir()
-        .unwrap()
-        .join("ghostforge")
-        .join("winetricks");
-    let manager = WinetricksManager::new(cache_dir)?;
+            let entries = crate::winetricks::cache_entries(&cache_dir)?;
+            if entries.is_empty() {
+                out!(
+                    "📦 The Winetricks cache at {} is empty",
+                    cache_dir.display()
+                );
+                return Ok(());
+            }
+
+            out!("{}", "📦 Winetricks Cache:".bold().blue());
+            for (verb, size) in &entries {
+                println!("  {:<20} {}", verb, crate::utils::format_size(*size));
+            }
+            let total: u64 = entries.iter().map(|(_, size)| size).sum();
+            println!(
+                "\n{} verbs, {} in {}",
+                entries.len(),
+                crate::utils::format_size(total).bold(),
+                cache_dir.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+async fn handle_tricks(
+    game: String,
+    tricks: Vec<String>,
+    force: bool,
+    continue_on_error: bool,
+    dry_run: bool,
+) -> Result<()> {
+    use crate::winetricks::{WinetrickVerb, WinetricksManager, default_cache_dir};
+
+    let config = crate::config::Config::load()?;
+    let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+    let game_obj = find_game(&game_lib, &game, false)?;
+    let prefix_path = game_obj.prefix_path(&config.wine);
+
+    let mut manager = WinetricksManager::new(default_cache_dir())?;
+    manager.set_dry_run(dry_run);
+
+    for conflict in manager.check_conflicts(&tricks) {
+        out!("⚠️  {}", conflict);
+    }
+
+    let installed = manager.list_installed_verbs(&prefix_path)?;
+    out!(
+        "🍷 Applying {} tricks to {} ({})",
+        tricks.len(),
+        game_obj.name.cyan(),
+        prefix_path.display()
+    );
+
+    if !dry_run {
+        let pending: Vec<&str> = tricks
+            .iter()
+            .filter(|trick| force || !installed.contains(trick))
+            .map(String::as_str)
+            .collect();
+        if !pending.is_empty() {
+            snapshot_prefix(&prefix_path, &format!("winetricks: {}", pending.join(", ")))?;
+        }
+    }
+
+    let mut results: Vec<(String, Result<bool>)> = Vec::new();
+    for trick in &tricks {
+        let result = match trick.as_str() {
+            "battlenet-essentials" => {
+                out!("🎮 Installing Battle.net essentials...");
+                manager
+                    .install_battlenet_essentials(&prefix_path)
+                    .await
+                    .map(|_| true)
+            }
+            "wow-optimize" => {
+                out!("🐉 Optimizing for World of Warcraft...");
+                manager.optimize_for_wow(&prefix_path).await.map(|_| true)
+            }
+            "create-battlenet-prefix" => {
+                out!("🍷 Creating new Battle.net prefix...");
+                manager
+                    .create_battlenet_prefix(&prefix_path, None)
+                    .await
+                    .map(|_| true)
+            }
+            _ if !force && installed.contains(trick) => {
+                out!("⏭️  {} is already installed", trick);
+                Ok(false)
+            }
+            _ => {
+                let verb = manager
+                    .get_verb_info(trick)
+                    .unwrap_or_else(|| WinetrickVerb::custom(trick));
+                manager
+                    .install_verb(&prefix_path, &verb)
+                    .await
+                    .map(|_| true)
+            }
+        };
+
+        let failed = result.is_err();
+        results.push((trick.clone(), result));
+        if failed && !continue_on_error {
+            break;
+        }
+    }
+
+    out!("\n{}", "📋 Summary:".bold());
+    for (trick, result) in &results {
+        match result {
+            Ok(true) => out!("  ✅ {}", trick),
+            Ok(false) => out!("  ⏭️  {} (already installed)", trick),
+            Err(e) => out!("  ❌ {}: {}", trick, e),
+        }
+    }
+    for trick in tricks.iter().skip(results.len()) {
+        out!("  ⏸️  {} (not run)", trick);
+    }
+
+    let failures = results.iter().filter(|(_, r)| r.is_err()).count();
+    if failures > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} tricks failed",
+            failures,
+            tricks.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Snapshot a prefix before changing it, so 'forge prefix rollback' can undo
+/// the change
+fn snapshot_prefix(prefix: &std::path::Path, reason: &str) -> Result<()> {
+    if let Some(snapshot) = crate::prefix::PrefixSnapshot::create(prefix, reason)? {
+        out!("📸 Saved prefix snapshot {}", snapshot.id.dimmed());
+    }
+    Ok(())
+}
+
+/// What `forge optimize --gamemode` used to store in a game's environment
+const LEGACY_GAMEMODE_PRELOAD: &str = "libgamemodeauto.so.0";
+
+async fn handle_optimize(
+    game: Option<String>,
+    nvidia: bool,
+    amd: bool,
+    intel: bool,
+    gamemode: bool,
+    cpu_performance: bool,
+) -> Result<()> {
+    out!("{}", "⚡ Applying optimizations...".bold().yellow());
+
+    let mut applied: Vec<String> = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+
+    if let Some(query) = game {
+        let config = crate::config::Config::load()?;
+        let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+        let mut game_obj = find_game(&game_lib, &query, false)?;
+
+        // Without explicit flags, apply the vendor-neutral defaults
+        let no_flags = !nvidia && !amd && !intel && !gamemode && !cpu_performance;
+        let gamemode = gamemode || no_flags;
+        let cpu_performance = cpu_performance || no_flags;
+
+        let profile_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+            .join("ghostforge")
+            .join("profiles");
+        let manager = crate::bolt_integration::OptimizationManager::new(profile_dir)?;
+        let profile = match manager.get_profile(&game_obj.name) {
+            Some(profile) => profile,
+            None => {
+                manager
+                    .get_or_create_profile(
+                        &game_obj.name,
+                        &GameCategory::Unknown,
+                        game_obj.wine_version.as_deref(),
+                        None,
+                    )
+                    .await?
+            }
+        };
+        println!("  Using profile: {}", profile.name.cyan());
+
+        let mut env: Vec<(String, String)> = Vec::new();
+
+        if nvidia {
+            for (key, value) in [
+                ("__GL_YIELD", "USLEEP"),
+                ("__GL_THREADED_OPTIMIZATIONS", "1"),
+                ("__GL_SHADER_DISK_CACHE", "1"),
+                ("__GL_SHADER_DISK_CACHE_SKIP_CLEANUP", "1"),
+            ] {
+                env.push((key.to_string(), value.to_string()));
+            }
+            if profile
+                .nvidia_config
+                .as_ref()
+                .is_some_and(|nv| nv.dlss_enabled)
+            {
+                env.push(("PROTON_ENABLE_NVAPI".to_string(), "1".to_string()));
+                env.push(("DXVK_ENABLE_NVAPI".to_string(), "1".to_string()));
+            }
+        }
+
+        // The profile's AMD settings, or the defaults for its category
+        let amd_config = amd.then(|| {
+            profile
+                .amd_config
+                .clone()
+                .unwrap_or_else(|| AmdConfig::for_category(&profile.game_category))
+        });
+        if let Some(amd_config) = &amd_config {
+            env.extend(amd_config.environment());
+            env.push(("mesa_glthread".to_string(), "true".to_string()));
+        }
+
+        if intel {
+            let graphics = crate::graphics::GraphicsManager::new(
+                crate::graphics::GraphicsManager::default_dir(),
+            )?;
+            let arc = graphics.detect_intel_arc_features().unwrap_or_default();
+            if arc.available {
+                env.extend(graphics.setup_intel_arc_optimizations(&arc));
+                if !arc.supports_vkd3d() {
+                    let (major, minor) = crate::graphics::ARC_DX12_MIN_MESA;
+                    failed.push(format!(
+                        "VKD3D-Proton: Mesa {} is too old for DX12 on Arc, update to {}.{} or newer",
+                        arc.mesa_version.as_deref().unwrap_or("unknown"),
+                        major,
+                        minor
+                    ));
+                }
+                let has_dxvk = crate::prefix::PrefixInfo::read(&game_obj.prefix_path(&config.wine))
+                    .is_ok_and(|info| info.graphics_layers.iter().any(|l| l == "DXVK"));
+                if !has_dxvk {
+                    failed.push(format!(
+                        "DXVK: not in the game's prefix, Arc needs it for DirectX 9-11 (forge graphics apply dxvk {})",
+                        game_obj.prefix_path(&config.wine).display()
+                    ));
+                }
+            } else {
+                failed.push("Intel: no Arc GPU detected".to_string());
+            }
+        }
+
+        if nvidia || amd || intel {
+            env.push(("DXVK_ASYNC".to_string(), "1".to_string()));
+            env.push(("DXVK_STATE_CACHE".to_string(), "1".to_string()));
+        }
+
+        // The launcher wraps the game in gamemoderun, an LD_PRELOAD here
+        // would replace MangoHud's and libstrangle's
+        if gamemode {
+            if which::which("gamemoderun").is_ok() {
+                game_obj
+                    .environment_variables
+                    .retain(|(key, value)| key != "LD_PRELOAD" || value != LEGACY_GAMEMODE_PRELOAD);
+                game_obj.gamemode = Some(true);
+                applied.push("GameMode: gamemoderun".to_string());
+            } else {
+                failed.push("GameMode: gamemoderun is not installed".to_string());
+            }
+        }
+
+        // Profile launch options in KEY=VALUE form are environment variables
+        for option in &profile.launch_options {
+            if let Some((key, value)) = option.split_once('=') {
+                env.push((key.to_string(), value.to_string()));
+            }
+        }
 
-    // For demo purposes, use a default prefix path
-    let prefix_path = dirs::home_dir().unwrap().join("Games").join("battle.net");
+        if !env.is_empty() || gamemode {
+            for (key, value) in env {
+                applied.push(format!("{}={}", key, value));
+                game_obj.set_environment_variable(key, value);
+            }
+
+            if let Err(e) = game_lib.update_game(&game_obj) {
+                failed.push(format!("Saving launch environment: {}", e));
+                applied.clear();
+            }
+        }
 
-    match trick.as_str() {
-        "battlenet-essentials" => {
-            println!("🎮 Installing Battle.net essentials for {}...", game.cyan());
-            manager.install_battlenet_essentials(&prefix_path).await?;
+        let governor =
+            cpu_performance.then(|| profile.cpu_governor.as_deref().unwrap_or("performance"));
+        let amd_tuning = amd_config.as_ref().filter(|amd| amd.tunes_gpu());
+        if governor.is_some() || amd_tuning.is_some() {
+            apply_manual_tuning(governor, amd_tuning, &mut applied, &mut failed);
         }
-        "wow-optimize" => {
-            println!("🐉 Optimizing for World of Warcraft...");
-            manager.optimize_for_wow(&prefix_path).await?;
+
+        out!("\n🎮 {}", game_obj.name.bold().green());
+    } else {
+        if nvidia || amd || intel || gamemode {
+            out!(
+                "  {} GPU and GameMode tweaks are stored per game, pass a game to apply them",
+                "ℹ️".blue()
+            );
         }
-        "create-battlenet-prefix" => {
-            println!("🍷 Creating new Battle.net prefix...");
-            manager.create_battlenet_prefix(&prefix_path, None).await?;
+
+        // Without a game only the GPU power profile applies on AMD
+        let amd_config = amd.then(|| AmdConfig {
+            power_profile: Some("3D_FULL_SCREEN".to_string()),
+            ..AmdConfig::for_category(&GameCategory::Unknown)
+        });
+        apply_manual_tuning(
+            Some("performance"),
+            amd_config.as_ref(),
+            &mut applied,
+            &mut failed,
+        );
+    }
+
+    if !applied.is_empty() {
+        out!("\n{}", "✅ Applied:".bold().green());
+        for setting in &applied {
+            println!("  • {}", setting);
         }
-        _ => {
-            if let Some(verb) = manager.get_verb_info(&trick) {
-                println!(
-                    "Installing {} for {}...",
-                    verb.description.magenta(),
-                    game.cyan()
-                );
-                manager.install_verb(&prefix_path, &verb).await?;
-            } else {
-                println!("❌ Unknown trick: {}", trick);
-                println!("Available tricks:");
-                println!("  • battlenet-essentials - Install all Battle.net essentials");
-                println!("  • wow-optimize - Optimize for World of Warcraft");
-                println!("  • create-battlenet-prefix - Create new Battle.net prefix");
-                println!("  • corefonts - Windows core fonts");
-                println!("  • vcrun2019 - Visual C++ 2019 Runtime");
-                println!("  • dxvk - DirectX to Vulkan layer");
-            }
+    }
+
+    if !failed.is_empty() {
+        out!("\n{}", "❌ Failed:".bold().red());
+        for failure in &failed {
+            println!("  • {}", failure);
         }
     }
 
+    if applied.is_empty() && failed.is_empty() {
+        println!("  Nothing to apply");
+    }
+
     Ok(())
 }
 
-async fn handle_optimize(
-    _game: Option<String>,
-    nvidia: bool,
-    amd: bool,
-    gamemode: bool,
-    cpu_performance: bool,
-) -> Result<()> {
-    println!("{}", "⚡ Applying optimizations...".bold().yellow());
-    if nvidia {
-        println!("  • NVIDIA optimizations enabled");
+/// Switch the CPU governor and AMD GPU settings outside of a game session,
+/// saving the previous ones so `forge optimize --reset` can put them back
+fn apply_manual_tuning(
+    governor: Option<&str>,
+    amd: Option<&AmdConfig>,
+    applied: &mut Vec<String>,
+    failed: &mut Vec<String>,
+) {
+    let (snapshot, report) = crate::tuning::apply(governor, None, amd);
+    if report.applied.is_empty() && report.failed.is_empty() {
+        applied.push("System settings already tuned".to_string());
+    }
+    applied.extend(report.applied);
+    failed.extend(report.failed);
+
+    if let Err(e) = crate::tuning::TuningState::begin_session("optimize", &snapshot) {
+        failed.push(format!("Saving previous settings: {}", e));
     }
-    if amd {
-        println!("  • AMD optimizations enabled");
+}
+
+fn handle_optimize_reset() -> Result<()> {
+    let state = crate::tuning::TuningState::load()?;
+    if state.previous.is_empty() {
+        out!("✅ No saved tuning to restore");
+        return Ok(());
     }
-    if gamemode {
-        println!("  • GameMode enabled");
+
+    out!(
+        "{}",
+        "🔄 Restoring saved system settings...".bold().yellow()
+    );
+    if !state.sessions.is_empty() {
+        out!(
+            "  {} Tuned sessions still recorded: {}",
+            "ℹ️".blue(),
+            state.sessions.join(", ")
+        );
     }
-    if cpu_performance {
-        println!("  • CPU governor set to performance");
+
+    let report = crate::tuning::TuningState::reset()?;
+    report.print();
+
+    if !report.failed.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Some settings could not be restored, run 'forge optimize --reset' again to retry"
+        ));
     }
     Ok(())
 }
 
-async fn handle_search(query: String, _protondb: bool, _local: bool) -> Result<()> {
-    println!("🔍 Searching for: {}", query.bold());
+async fn handle_search(query: String, protondb: bool, local: bool) -> Result<()> {
+    out!("🔍 Searching for: {}", query.bold());
+
+    // With neither flag, search everywhere
+    let search_local = local || !protondb;
+    let search_protondb = protondb || !local;
+
+    let mut local_matches = Vec::new();
+    if search_local {
+        out!("\n{}", "📚 Local Library:".bold().cyan());
+
+        let config = crate::config::Config::load()?;
+        let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+        local_matches = game_lib.fuzzy_search(&query)?;
+
+        if local_matches.is_empty() {
+            println!("  No matching games in your library");
+        } else {
+            for game in &local_matches {
+                out!(
+                    "  🎮 {} (ID: {}) [{}]",
+                    game.name.green(),
+                    game.id.yellow(),
+                    game.launcher.as_deref().unwrap_or("Manual").dimmed()
+                );
+            }
+        }
+    }
+
+    if search_protondb {
+        out!("\n{}", "🌐 ProtonDB:".bold().cyan());
+        let config = crate::config::Config::load()?;
+        let client = crate::protondb::ProtonDBClient::from_config(&config);
+
+        // Steam games already in the library have known app IDs
+        let mut apps: Vec<(u32, String)> = local_matches
+            .iter()
+            .filter(|g| g.launcher.as_deref() == Some("Steam"))
+            .filter_map(|g| {
+                let appid = g.launcher_id.as_ref()?.parse::<u32>().ok()?;
+                Some((appid, g.name.clone()))
+            })
+            .collect();
+
+        match client.find_steam_app(&query).await {
+            Ok(Some((appid, name))) => {
+                if !apps.iter().any(|(id, _)| *id == appid) {
+                    apps.push((appid, name));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => out!("  ⚠️  Steam app lookup failed: {}", e),
+        }
+
+        if apps.is_empty() {
+            println!("  No matching Steam apps found");
+        }
+
+        for (appid, name) in apps {
+            match client.get_game_summary(appid).await {
+                Ok(Some(summary)) => {
+                    let (tier_display, tier_description) =
+                        crate::protondb::ProtonDBClient::format_tier(&summary.tier);
+                    out!(
+                        "  {} (AppID: {}): {} - {} ({} reports)",
+                        name.green(),
+                        appid.to_string().yellow(),
+                        tier_display,
+                        tier_description.dimmed(),
+                        summary.total
+                    );
+                }
+                Ok(None) => println!(
+                    "  {} (AppID: {}): no ProtonDB reports",
+                    name.green(),
+                    appid.to_string().yellow()
+                ),
+                Err(e) => out!("  ❌ {} (AppID: {}): {}", name, appid, e),
+            }
+        }
+    }
+
     Ok(())
 }
 
-async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<()> {
-    println!("{}", "ℹ️  System Information:".bold().blue());
+async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool, json: bool) -> Result<()> {
+    if json {
+        return print_json(&crate::utils::SystemDetector::get_system_info()?);
+    }
+
+    out!("{}", "ℹ️  System Information:".bold().blue());
 
     match crate::utils::SystemDetector::get_system_info() {
         Ok(system_info) => {
             if full {
                 // Show everything
-                println!("\n🖥️  System:");
+                out!("\n🖥️  System:");
                 println!("  OS: {}", system_info.os.cyan());
                 println!("  Kernel: {}", system_info.kernel.yellow());
                 if let Some(ref desktop) = system_info.desktop {
                     println!("  Desktop: {}", desktop.green());
                 }
 
-                println!("\n💻 CPU:");
+                out!("\n💻 CPU:");
                 println!("  Model: {}", system_info.cpu.brand.cyan());
                 println!(
                     "  Cores: {} ({} threads)",
@@ -1843,7 +4869,7 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                 );
                 println!("  Frequency: {} MHz", system_info.cpu.frequency);
 
-                println!("\n💾 Memory:");
+                out!("\n💾 Memory:");
                 println!(
                     "  Total: {:.2} GB",
                     system_info.memory.total as f64 / 1024.0 / 1024.0 / 1024.0
@@ -1861,7 +4887,7 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
             }
 
             if gpu || full {
-                println!("\n🎮 GPU Information:");
+                out!("\n🎮 GPU Information:");
                 if system_info.gpu.is_empty() {
                     println!("  No GPUs detected");
                 } else {
@@ -1872,7 +4898,7 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                             crate::utils::GpuVendor::Intel => "🔵",
                             _ => "⚪",
                         };
-                        println!(
+                        out!(
                             "  {} GPU {}: {} ({:?})",
                             vendor_icon,
                             i + 1,
@@ -1894,15 +4920,15 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                             "❌"
                         };
                         let dxvk_status = if gpu_info.dxvk_support { "✅" } else { "❌" };
-                        println!("    Vulkan: {} | DXVK: {}", vulkan_status, dxvk_status);
+                        out!("    Vulkan: {} | DXVK: {}", vulkan_status, dxvk_status);
                     }
                 }
             }
 
             if vulkan || full {
-                println!("\n🌋 Vulkan Support:");
+                out!("\n🌋 Vulkan Support:");
                 if system_info.vulkan.available {
-                    println!("  Status: {} Available", "✅".green());
+                    out!("  Status: {} Available", "✅".green());
                     if let Some(ref api_version) = system_info.vulkan.api_version {
                         println!("  API Version: {}", api_version.yellow());
                     }
@@ -1916,15 +4942,15 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                         }
                     }
                 } else {
-                    println!("  Status: {} Not available", "❌".red());
+                    out!("  Status: {} Not available", "❌".red());
                     println!("  Install vulkan drivers for your GPU to enable Vulkan support");
                 }
             }
 
             if wine || full {
-                println!("\n🍷 Wine Support:");
+                out!("\n🍷 Wine Support:");
                 if system_info.wine_support.installed {
-                    println!("  Status: {} Installed", "✅".green());
+                    out!("  Status: {} Installed", "✅".green());
                     if let Some(ref version) = system_info.wine_support.version {
                         println!("  Version: {}", version.yellow());
                     }
@@ -1939,7 +4965,7 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                     } else {
                         "❌ No"
                     };
-                    println!("  Multilib: {}", multilib_status);
+                    out!("  Multilib: {}", multilib_status);
                     if let Some(ref prefix_path) = system_info.wine_support.prefix_path {
                         println!(
                             "  Default Prefix: {}",
@@ -1947,13 +4973,13 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                         );
                     }
                 } else {
-                    println!("  Status: {} Not installed", "❌".red());
+                    out!("  Status: {} Not installed", "❌".red());
                     println!("  Install Wine to run Windows games and applications");
                 }
             }
 
             if full {
-                println!("\n🎯 Gaming Tools:");
+                out!("\n🎯 Gaming Tools:");
                 let tools = &system_info.gaming_tools;
                 let dxvk_status = if tools.dxvk { "✅" } else { "❌" };
                 let vkd3d_status = if tools.vkd3d { "✅" } else { "❌" };
@@ -1963,22 +4989,26 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                 let winetricks_status = if tools.winetricks { "✅" } else { "❌" };
                 let protontricks_status = if tools.protontricks { "✅" } else { "❌" };
 
-                println!(
+                out!(
                     "  DXVK: {} | VKD3D: {} | MangoHUD: {}",
-                    dxvk_status, vkd3d_status, mangohud_status
+                    dxvk_status,
+                    vkd3d_status,
+                    mangohud_status
                 );
-                println!(
+                out!(
                     "  GameMode: {} | GameScope: {}",
-                    gamemode_status, gamescope_status
+                    gamemode_status,
+                    gamescope_status
                 );
-                println!(
+                out!(
                     "  Winetricks: {} | Protontricks: {}",
-                    winetricks_status, protontricks_status
+                    winetricks_status,
+                    protontricks_status
                 );
 
                 // Show container runtime information
                 if full {
-                    println!("\n📦 Container Runtime:");
+                    out!("\n📦 Container Runtime:");
                     let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
                     match crate::container::ContainerManager::new(config_dir) {
                         Ok(container_manager) => {
@@ -1999,11 +5029,11 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                                     println!("    • {}", runtime.cyan());
                                 }
                             } else {
-                                println!("  ⚠️  No container runtimes detected");
+                                out!("  ⚠️  No container runtimes detected");
                             }
                         }
                         Err(e) => {
-                            println!(
+                            out!(
                                 "  ❌ Container runtime detection failed: {}",
                                 e.to_string().red()
                             );
@@ -2012,29 +5042,17 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
                 }
 
                 // Show recommendations
-                println!("\n💡 Recommendations:");
-                if !tools.dxvk {
-                    println!("  • Install DXVK for better DirectX performance");
-                }
-                if !tools.vkd3d {
-                    println!("  • Install VKD3D-Proton for DirectX 12 support");
-                }
-                if !tools.gamemode {
-                    println!("  • Install GameMode for automatic performance optimizations");
-                }
-                if !tools.mangohud {
-                    println!("  • Install MangoHUD for performance monitoring overlay");
-                }
-                if !system_info.wine_support.installed {
-                    println!("  • Install Wine to run Windows games");
-                }
-                if !system_info.vulkan.available {
-                    println!("  • Install Vulkan drivers for your GPU");
+                out!("\n💡 Recommendations:");
+                for check in crate::doctor::system_checks(&system_info) {
+                    if let Some(suggestion) = check.suggestion {
+                        println!("  • {}", suggestion);
+                    }
                 }
+                println!("  Run 'forge doctor' for a full health check");
             }
         }
         Err(e) => {
-            println!("❌ Failed to gather system information: {}", e);
+            out!("❌ Failed to gather system information: {}", e);
             println!("This might be due to missing system utilities or permissions");
         }
     }
@@ -2042,6 +5060,26 @@ async fn handle_info(gpu: bool, wine: bool, vulkan: bool, full: bool) -> Result<
     Ok(())
 }
 
+async fn handle_doctor(json: bool) -> Result<()> {
+    let config = crate::config::Config::load()?;
+    let report = crate::doctor::DoctorReport::run(&config).await;
+
+    if json {
+        print_json(&report)?;
+    } else {
+        out!("{}", "🩺 GhostForge Doctor".bold().cyan());
+        report.print();
+    }
+
+    if report.failures() > 0 {
+        return Err(anyhow::anyhow!(
+            "{} critical checks failed",
+            report.failures()
+        ));
+    }
+    Ok(())
+}
+
 async fn handle_backup_command(action: BackupCommands) -> Result<()> {
     match action {
         BackupCommands::Create { target, output: _ } => {
@@ -2053,7 +5091,7 @@ async fn handle_backup_command(action: BackupCommands) -> Result<()> {
             Ok(())
         }
         BackupCommands::List => {
-            println!("{}", "💾 Available Backups:".bold());
+            out!("{}", "💾 Available Backups:".bold());
             Ok(())
         }
     }
@@ -2061,7 +5099,7 @@ async fn handle_backup_command(action: BackupCommands) -> Result<()> {
 
 async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
     use crate::utils::SystemDetector;
-    use crate::winetricks::WinetricksManager;
+    use crate::winetricks::{WinetricksManager, default_cache_dir};
 
     match action {
         BattlenetCommands::Setup {
@@ -2073,25 +5111,21 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
                 .map(|p| PathBuf::from(p))
                 .unwrap_or_else(|| dirs::home_dir().unwrap().join("Games/battlenet"));
 
-            println!(
+            out!(
                 "🍷 Setting up Battle.net prefix at: {}",
                 prefix_path.display()
             );
 
-            let cache_dir = dirs::cache_dir()
-                .unwrap()
-                .join("ghostforge")
-                .join("winetricks");
-            let manager = WinetricksManager::new(cache_dir)?;
+            let manager = WinetricksManager::new(default_cache_dir())?;
 
             match game.as_deref() {
                 Some("wow") => {
-                    println!("🐉 Setting up World of Warcraft optimized prefix...");
+                    out!("🐉 Setting up World of Warcraft optimized prefix...");
                     crate::winetricks::setup_wow_prefix(&prefix_path, wine_version.as_deref())
                         .await?;
                 }
                 Some("diablo") => {
-                    println!("⚔️  Setting up Diablo optimized prefix...");
+                    out!("⚔️  Setting up Diablo optimized prefix...");
                     crate::winetricks::setup_diablo_prefix(&prefix_path, wine_version.as_deref())
                         .await?;
                 }
@@ -2108,12 +5142,8 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
                 .map(|p| PathBuf::from(p))
                 .unwrap_or_else(|| dirs::home_dir().unwrap().join("Games/battlenet"));
 
-            println!("📦 Installing Battle.net essentials...");
-            let cache_dir = dirs::cache_dir()
-                .unwrap()
-                .join("ghostforge")
-                .join("winetricks");
-            let manager = WinetricksManager::new(cache_dir)?;
+            out!("📦 Installing Battle.net essentials...");
+            let manager = WinetricksManager::new(default_cache_dir())?;
 
             manager.install_battlenet_essentials(&prefix_path).await?;
         }
@@ -2123,12 +5153,8 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
                 .map(|p| PathBuf::from(p))
                 .unwrap_or_else(|| dirs::home_dir().unwrap().join("Games/battlenet"));
 
-            println!("🐉 Optimizing prefix for World of Warcraft...");
-            let cache_dir = dirs::cache_dir()
-                .unwrap()
-                .join("ghostforge")
-                .join("winetricks");
-            let manager = WinetricksManager::new(cache_dir)?;
+            out!("🐉 Optimizing prefix for World of Warcraft...");
+            let manager = WinetricksManager::new(default_cache_dir())?;
 
             manager.optimize_for_wow(&prefix_path).await?;
         }
@@ -2138,22 +5164,22 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
                 dirs::download_dir().unwrap_or_else(|| dirs::home_dir().unwrap().join("Downloads"))
             });
 
-            println!("📥 Downloading Battle.net installer...");
+            out!("📥 Downloading Battle.net installer...");
             println!("Installer will be saved to: {}", download_dir.display());
 
             // In a real implementation, you'd download from Blizzard's servers
-            println!("⚠️  Download Battle.net from: https://www.battle.net/download");
+            out!("⚠️  Download Battle.net from: https://www.battle.net/download");
             println!("Save it to: {}", download_dir.display());
         }
 
         BattlenetCommands::Check => {
-            println!("🔍 Checking Battle.net compatibility...");
+            out!("🔍 Checking Battle.net compatibility...");
             let report = SystemDetector::check_battlenet_compatibility()?;
-            println!("{}", report);
+            out!("{}", report);
         }
 
         BattlenetCommands::Games => {
-            println!("🎮 Scanning for Battle.net games...");
+            out!("🎮 Scanning for Battle.net games...");
             let config_dir = dirs::config_dir().unwrap().join("ghostforge");
             let launcher_manager = crate::launcher::LauncherManager::new(config_dir);
 
@@ -2167,14 +5193,14 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
                     for game in games {
                         println!("  • {} ({})", game.name.cyan(), game.launcher_id.yellow());
                         println!("    Path: {}", game.install_path.display());
-                        println!(
+                        out!(
                             "    Installed: {}",
                             if game.installed { "✅" } else { "❌" }
                         );
                     }
                 }
             } else {
-                println!("❌ Battle.net launcher not detected.");
+                out!("❌ Battle.net launcher not detected.");
                 println!("Run 'forge battlenet setup' to create a Battle.net prefix first.");
             }
         }
@@ -2183,14 +5209,10 @@ async fn handle_battlenet_command(action: BattlenetCommands) -> Result<()> {
     Ok(())
 }
 
-async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
+async fn handle_graphics_command(action: GraphicsCommands, json: bool) -> Result<()> {
     use crate::graphics::GraphicsManager;
 
-    let base_dir = dirs::data_dir()
-        .unwrap()
-        .join("ghostforge")
-        .join("graphics");
-    let mut manager = GraphicsManager::new(base_dir)?;
+    let mut manager = GraphicsManager::new(GraphicsManager::default_dir())?;
     manager.set_dry_run(true); // Safe default
 
     match action {
@@ -2199,11 +5221,26 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
             dxvk,
             vkd3d,
         } => {
+            if json {
+                let mut layers = Vec::new();
+                if !available {
+                    layers = manager.list_installed()?;
+                } else {
+                    if !vkd3d {
+                        layers.extend(manager.list_available_dxvk().await?);
+                    }
+                    if !dxvk {
+                        layers.extend(manager.list_available_vkd3d().await?);
+                    }
+                }
+                return print_json(&layers);
+            }
+
             if available {
-                println!("📥 Available Graphics Layers:");
+                out!("📥 Available Graphics Layers:");
 
                 if !vkd3d {
-                    println!("\n🔷 DXVK (DirectX 9/10/11 → Vulkan):");
+                    out!("\n🔷 DXVK (DirectX 9/10/11 → Vulkan):");
                     let dxvk_versions = manager.list_available_dxvk().await?;
                     for layer in &dxvk_versions[..5.min(dxvk_versions.len())] {
                         println!(
@@ -2215,7 +5252,7 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
                 }
 
                 if !dxvk {
-                    println!("\n🔶 VKD3D-Proton (DirectX 12 → Vulkan):");
+                    out!("\n🔶 VKD3D-Proton (DirectX 12 → Vulkan):");
                     let vkd3d_versions = manager.list_available_vkd3d().await?;
                     for layer in &vkd3d_versions[..5.min(vkd3d_versions.len())] {
                         println!(
@@ -2226,7 +5263,7 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
                     }
                 }
             } else {
-                println!("📦 Installed Graphics Layers:");
+                out!("📦 Installed Graphics Layers:");
                 let installed = manager.list_installed()?;
 
                 if installed.is_empty() {
@@ -2242,7 +5279,7 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
         }
 
         GraphicsCommands::Install { layer } => {
-            println!("📦 Installing graphics layer: {}", layer.cyan());
+            out!("📦 Installing graphics layer: {}", layer.cyan());
 
             // For demo, show what would be installed
             if layer.contains("dxvk") {
@@ -2252,13 +5289,13 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
                         || v.name.to_lowercase().contains(&layer.to_lowercase())
                 }) {
                     println!("Found: {}", found.name);
-                    println!(
+                    out!(
                         "🔄 [DRY RUN] Would download from: {}",
                         found.download_url.as_ref().unwrap_or(&"N/A".to_string())
                     );
-                    println!("✅ [SIMULATED] {} installed successfully", found.name);
+                    out!("✅ [SIMULATED] {} installed successfully", found.name);
                 } else {
-                    println!("❌ DXVK version '{}' not found", layer);
+                    out!("❌ DXVK version '{}' not found", layer);
                 }
             } else if layer.contains("vkd3d") {
                 let versions = manager.list_available_vkd3d().await?;
@@ -2267,13 +5304,13 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
                         || v.name.to_lowercase().contains(&layer.to_lowercase())
                 }) {
                     println!("Found: {}", found.name);
-                    println!(
+                    out!(
                         "🔄 [DRY RUN] Would download from: {}",
                         found.download_url.as_ref().unwrap_or(&"N/A".to_string())
                     );
-                    println!("✅ [SIMULATED] {} installed successfully", found.name);
+                    out!("✅ [SIMULATED] {} installed successfully", found.name);
                 } else {
-                    println!("❌ VKD3D version '{}' not found", layer);
+                    out!("❌ VKD3D version '{}' not found", layer);
                 }
             }
         }
@@ -2287,21 +5324,19 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
 
             match layer_type.to_lowercase().as_str() {
                 "dxvk" => {
-                    println!("🔧 Applying DXVK to prefix: {}", prefix.cyan());
-                    println!("🔄 [DRY RUN] Would copy DXVK DLLs to prefix");
-                    println!(
-                        "🔄 [DRY RUN] Would set DLL overrides for: d3d9, d3d10core, d3d11, dxgi"
-                    );
-                    println!("✅ [SIMULATED] DXVK applied successfully");
+                    out!("🔧 Applying DXVK to prefix: {}", prefix.cyan());
+                    out!("🔄 [DRY RUN] Would copy DXVK DLLs to prefix");
+                    out!("🔄 [DRY RUN] Would set DLL overrides for: d3d9, d3d10core, d3d11, dxgi");
+                    out!("✅ [SIMULATED] DXVK applied successfully");
                 }
                 "vkd3d" => {
-                    println!("🔧 Applying VKD3D-Proton to prefix: {}", prefix.cyan());
-                    println!("🔄 [DRY RUN] Would copy VKD3D DLLs to prefix");
-                    println!("🔄 [DRY RUN] Would set DLL overrides for: d3d12, dxcore");
-                    println!("✅ [SIMULATED] VKD3D-Proton applied successfully");
+                    out!("🔧 Applying VKD3D-Proton to prefix: {}", prefix.cyan());
+                    out!("🔄 [DRY RUN] Would copy VKD3D DLLs to prefix");
+                    out!("🔄 [DRY RUN] Would set DLL overrides for: d3d12, dxcore");
+                    out!("✅ [SIMULATED] VKD3D-Proton applied successfully");
                 }
                 _ => {
-                    println!(
+                    out!(
                         "❌ Unknown layer type: {}. Use 'dxvk' or 'vkd3d'",
                         layer_type
                     );
@@ -2314,25 +5349,25 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
 
             match layer_type.to_lowercase().as_str() {
                 "dxvk" => {
-                    println!("🗑️ Removing DXVK from prefix: {}", prefix.cyan());
-                    println!("🔄 [DRY RUN] Would remove DXVK DLLs and reset overrides");
-                    println!("✅ [SIMULATED] DXVK removed successfully");
+                    out!("🗑️ Removing DXVK from prefix: {}", prefix.cyan());
+                    out!("🔄 [DRY RUN] Would remove DXVK DLLs and reset overrides");
+                    out!("✅ [SIMULATED] DXVK removed successfully");
                 }
                 "vkd3d" => {
-                    println!("🗑️ Removing VKD3D from prefix: {}", prefix.cyan());
-                    println!("🔄 [DRY RUN] Would remove VKD3D DLLs and reset overrides");
-                    println!("✅ [SIMULATED] VKD3D removed successfully");
+                    out!("🗑️ Removing VKD3D from prefix: {}", prefix.cyan());
+                    out!("🔄 [DRY RUN] Would remove VKD3D DLLs and reset overrides");
+                    out!("✅ [SIMULATED] VKD3D removed successfully");
                 }
                 "all" => {
-                    println!(
+                    out!(
                         "🗑️ Removing all graphics layers from prefix: {}",
                         prefix.cyan()
                     );
-                    println!("🔄 [DRY RUN] Would remove DXVK and VKD3D");
-                    println!("✅ [SIMULATED] All graphics layers removed");
+                    out!("🔄 [DRY RUN] Would remove DXVK and VKD3D");
+                    out!("✅ [SIMULATED] All graphics layers removed");
                 }
                 _ => {
-                    println!(
+                    out!(
                         "❌ Unknown layer type: {}. Use 'dxvk', 'vkd3d', or 'all'",
                         layer_type
                     );
@@ -2342,49 +5377,51 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
 
         GraphicsCommands::Info { prefix } => {
             let prefix_path = PathBuf::from(&prefix);
-            println!("📊 Graphics Layer Information for: {}", prefix.cyan());
+            out!("📊 Graphics Layer Information for: {}", prefix.cyan());
 
             // Simulate checking what's installed
-            println!("🔍 Checking prefix...");
+            out!("🔍 Checking prefix...");
 
             if prefix_path
                 .join("drive_c/windows/system32/d3d11.dll")
                 .exists()
             {
-                println!("  ✅ DXVK: Installed (d3d9, d3d10core, d3d11, dxgi)");
+                out!("  ✅ DXVK: Installed (d3d9, d3d10core, d3d11, dxgi)");
             } else {
-                println!("  ❌ DXVK: Not installed");
+                out!("  ❌ DXVK: Not installed");
             }
 
             if prefix_path
                 .join("drive_c/windows/system32/d3d12.dll")
                 .exists()
             {
-                println!("  ✅ VKD3D-Proton: Installed (d3d12, dxcore)");
+                out!("  ✅ VKD3D-Proton: Installed (d3d12, dxcore)");
             } else {
-                println!("  ❌ VKD3D-Proton: Not installed");
+                out!("  ❌ VKD3D-Proton: Not installed");
             }
 
-            println!("  🍷 WineD3D: Available (Wine's built-in renderer)");
+            out!("  🍷 WineD3D: Available (Wine's built-in renderer)");
         }
 
         GraphicsCommands::Recommend { game } => {
             let nvidia_features = manager.detect_nvidia_features().unwrap_or_default();
-            let recommendations = manager.recommend_for_game(&game, &nvidia_features);
+            let intel_arc_features = manager.detect_intel_arc_features().unwrap_or_default();
+            let recommendations =
+                manager.recommend_for_game(&game, &nvidia_features, &intel_arc_features);
 
-            println!("🎯 Graphics Recommendations for: {}", game.green());
+            out!("🎯 Graphics Recommendations for: {}", game.green());
             println!("Based on community reports and compatibility data:\n");
 
             for layer_type in recommendations {
                 match layer_type {
                     crate::graphics::GraphicsLayerType::DXVK => {
-                        println!("  ✅ {} - Recommended", "DXVK".cyan().bold());
+                        out!("  ✅ {} - Recommended", "DXVK".cyan().bold());
                         println!("     • Significantly improves DirectX 9/10/11 performance");
                         println!("     • Better frame times and reduced CPU overhead");
                         println!("     • Install: forge graphics install dxvk-latest");
                     }
                     crate::graphics::GraphicsLayerType::VKD3DProton => {
-                        println!("  ✅ {} - Recommended", "VKD3D-Proton".yellow().bold());
+                        out!("  ✅ {} - Recommended", "VKD3D-Proton".yellow().bold());
                         println!("     • Required for DirectX 12 games");
                         println!("     • Valve's optimized D3D12 implementation");
                         println!("     • Install: forge graphics install vkd3d-proton-latest");
@@ -2393,18 +5430,606 @@ async fn handle_graphics_command(action: GraphicsCommands) -> Result<()> {
                 }
             }
 
+            if intel_arc_features.available {
+                out!(
+                    "\n🔵 {}: DirectX always runs through DXVK/VKD3D-Proton on the ANV driver",
+                    intel_arc_features
+                        .gpu_name
+                        .as_deref()
+                        .unwrap_or("Intel Arc")
+                        .cyan()
+                );
+                if !intel_arc_features.supports_vkd3d() {
+                    let (major, minor) = crate::graphics::ARC_DX12_MIN_MESA;
+                    out!(
+                        "  ⚠️  Mesa {} is too old for DX12 games on Arc, update to {}.{} or newer",
+                        intel_arc_features
+                            .mesa_version
+                            .as_deref()
+                            .unwrap_or("unknown"),
+                        major,
+                        minor
+                    );
+                }
+            }
+
+            out!("\n💡 Tip: Always test both layers to see which performs best for your system!");
+        }
+
+        GraphicsCommands::Pin {
+            game,
+            layer,
+            version,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let mut game_obj = find_game(&game_lib, &game, false)?;
+            let pinned = parse_pinned_version(&version);
+
+            let layer_type = match layer.to_lowercase().as_str() {
+                "dxvk" => {
+                    game_obj.dxvk_version = pinned.clone();
+                    crate::graphics::GraphicsLayerType::DXVK
+                }
+                "vkd3d" | "vkd3d-proton" => {
+                    game_obj.vkd3d_version = pinned.clone();
+                    crate::graphics::GraphicsLayerType::VKD3DProton
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown layer type: {}. Use 'dxvk' or 'vkd3d'",
+                        layer
+                    ));
+                }
+            };
+            if pinned.is_some() {
+                check_graphics_pins_apply(&game_obj)?;
+            }
+
+            if let Some(version) = &pinned
+                && manager.find_installed(&layer_type, version)?.is_none()
+            {
+                out!(
+                    "⚠️  {} {} is not installed yet, install it before launching",
+                    layer,
+                    version
+                );
+            }
+
+            game_lib.update_game(&game_obj)?;
+            print_graphics_pins(&game_obj);
+        }
+
+        GraphicsCommands::Cache {
+            game,
+            clear,
+            backup,
+        } => {
+            let config = crate::config::Config::load()?;
+            let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+            let game_obj = find_game(&game_lib, &game, false)?;
+            let caches = crate::graphics::find_state_caches(&game_obj, &config.paths.cache)?;
+
+            out!(
+                "{} {}",
+                "🗄️  State caches for".bold(),
+                game_obj.name.cyan().bold()
+            );
+            if caches.is_empty() {
+                println!("  No DXVK/VKD3D state caches found");
+                return Ok(());
+            }
+
+            let total: u64 = caches.iter().map(|cache| cache.size).sum();
+            for cache in &caches {
+                println!(
+                    "  {} ({})",
+                    cache.path.display(),
+                    crate::utils::format_size(cache.size)
+                );
+            }
+            println!("  Total: {}", crate::utils::format_size(total).bold());
+
+            if backup {
+                let backup_dir = config
+                    .paths
+                    .backups
+                    .join("state-cache")
+                    .join(&game_obj.id)
+                    .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+                std::fs::create_dir_all(&backup_dir)?;
+                for cache in &caches {
+                    if let Some(name) = cache.path.file_name() {
+                        std::fs::copy(&cache.path, backup_dir.join(name))?;
+                    }
+                }
+                out!("💾 Backed up to {}", backup_dir.display());
+            }
+
+            if clear {
+                for cache in &caches {
+                    std::fs::remove_file(&cache.path)?;
+                }
+                out!(
+                    "🧹 Cleared {} of state caches, they'll be rebuilt as the game runs",
+                    crate::utils::format_size(total)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_display_command(action: DisplayCommands) -> Result<()> {
+    use crate::display::{DisplayBackend, DisplayManager};
+
+    let mut manager = DisplayManager::new()?;
+
+    match action {
+        DisplayCommands::List => {
+            out!("{}", "🖥️  Displays:".bold().blue());
+            for display in manager.get_displays() {
+                let status = if display.connected { "✅" } else { "❌" };
+                let primary = if display.primary { " 👑" } else { "" };
+                out!(
+                    "  {} {} ({}){}",
+                    status,
+                    display.id.bold(),
+                    display.name,
+                    primary
+                );
+                if !display.connected {
+                    continue;
+                }
+                println!(
+                    "     {}x{} @ {}Hz",
+                    display.resolution.width,
+                    display.resolution.height,
+                    display.current_refresh_rate.to_string().cyan()
+                );
+                if !display.refresh_rates.is_empty() {
+                    let rates: Vec<String> = display
+                        .refresh_rates
+                        .iter()
+                        .map(|rate| format!("{}Hz", rate))
+                        .collect();
+                    println!("     Rates: {}", rates.join(", "));
+                }
+                let capability = |capable: bool, enabled: bool| match (capable, enabled) {
+                    (false, _) => "unsupported".dimmed(),
+                    (true, true) => "on".green(),
+                    (true, false) => "off".yellow(),
+                };
+                println!(
+                    "     VRR: {}  HDR: {}",
+                    capability(display.vrr_capable, display.vrr_enabled),
+                    capability(display.hdr_capable, display.hdr_enabled)
+                );
+            }
+
+            let backend = match manager.backend() {
+                DisplayBackend::Xrandr => "xrandr",
+                DisplayBackend::WlrRandr => "wlr-randr",
+                DisplayBackend::KScreen => "kscreen-doctor",
+                DisplayBackend::Unsupported => "none (read-only)",
+            };
+            let session = if manager.is_wayland_session() {
+                "Wayland"
+            } else {
+                "X11"
+            };
+            println!("\n  Session: {}, configured through {}", session, backend);
+        }
+
+        DisplayCommands::Vrr { state, connector } => {
+            let enabled = match state.to_lowercase().as_str() {
+                "on" => true,
+                "off" => false,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown VRR state: {}. Use 'on' or 'off'",
+                        state
+                    ));
+                }
+            };
+            manager.set_vrr(&connector, enabled)?;
+            out!(
+                "✅ VRR {} on {}",
+                if enabled { "enabled" } else { "disabled" },
+                connector.green()
+            );
+        }
+
+        DisplayCommands::Refresh { connector, hz } => {
+            manager.set_refresh_rate(&connector, hz)?;
+            out!("✅ {} set to {}Hz", connector.green(), hz);
+        }
+
+        DisplayCommands::Optimize { fps, hdr } => {
+            let mut settings = manager.get_gaming_settings();
+            settings.target_fps = fps;
+            settings.hdr_gaming = hdr;
+
+            let report = manager.optimize_for_gaming(&settings)?;
+            report.print();
+            if report.changed.is_empty() && report.refused.is_empty() {
+                out!("✅ Displays are already optimized");
+            }
+            out!("💡 Run 'forge display restore' to undo these changes");
+        }
+
+        DisplayCommands::Restore => {
+            let report = manager.restore_display_settings()?;
+            report.print();
+            if report.changed.is_empty() && report.refused.is_empty() {
+                out!("✅ Nothing to restore");
+            }
+        }
+
+        DisplayCommands::Monitor { seconds } => {
+            let mut monitor = crate::vrr_monitor::VrrMonitor::new();
+            monitor.start_monitoring(&manager)?;
+            println!("  Frame timing comes from MangoHud, launch games with it enabled");
+
+            let started = std::time::Instant::now();
+            while started.elapsed().as_secs() < seconds {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                monitor.update_metrics()?;
+
+                match monitor.get_real_time_stats() {
+                    Some(stats) => println!(
+                        "  {} {:>6.1} fps  {:>6.2} ms  {}Hz{}",
+                        stats.display_id.cyan(),
+                        stats.current_fps,
+                        stats.frame_time_ms,
+                        stats.vrr_range.current_hz,
+                        if stats.vrr_active { " (VRR)" } else { "" }
+                    ),
+                    None => out!("  💤 Idle - no game is presenting"),
+                }
+            }
+
+            let report = monitor.generate_performance_report(started.elapsed());
+            monitor.stop_monitoring();
+
+            out!("\n{}", "📊 Session Summary:".bold());
+            println!("  Average FPS: {:.1}", report.average_fps);
             println!(
-                "\n💡 Tip: Always test both layers to see which performs best for your system!"
+                "  Frame time consistency: {:.0}/100",
+                report.frame_time_consistency
             );
+            println!("  Stability: {:.0}/100", report.stability_score);
+            for recommendation in &report.recommended_settings {
+                out!(
+                    "  💡 {}: {}",
+                    recommendation.category.bold(),
+                    recommendation.suggestion
+                );
+            }
+        }
+
+        DisplayCommands::Profile { action } => match action {
+            DisplayProfileCommands::List => {
+                out!("{}", "🖥️  Display Profiles:".bold().blue());
+                let mut profiles: Vec<_> = manager.get_profiles().values().collect();
+                profiles.sort_by(|a, b| a.name.cmp(&b.name));
+                for profile in profiles {
+                    let gaming = if profile.gaming_optimized {
+                        " 🎮"
+                    } else {
+                        ""
+                    };
+                    out!(
+                        "  • {}{} - {}",
+                        profile.name.cyan(),
+                        gaming,
+                        profile.description
+                    );
+                }
+            }
+
+            DisplayProfileCommands::Save {
+                name,
+                description,
+                gaming,
+            } => {
+                let path = manager.save_profile(&name, description, gaming)?;
+                out!("✅ Saved display profile {}", name.green());
+                for (id, config) in &manager.get_profiles()[&name].displays {
+                    println!(
+                        "  • {}: {}x{} @ {}Hz{}",
+                        id.cyan(),
+                        config.resolution.width,
+                        config.resolution.height,
+                        config.refresh_rate,
+                        if config.vrr_enabled { " (VRR)" } else { "" }
+                    );
+                }
+                out!("  📁 {}", path.display());
+            }
+
+            DisplayProfileCommands::Apply { name } => {
+                if !manager.get_profiles().contains_key(&name) {
+                    let mut names: Vec<_> = manager.get_profiles().keys().cloned().collect();
+                    names.sort();
+                    return Err(anyhow::anyhow!(
+                        "Display profile '{}' not found. Available: {}",
+                        name,
+                        names.join(", ")
+                    ));
+                }
+
+                out!("🖥️  Applying display profile {}...", name.cyan());
+                manager.apply_profile(&name)?;
+                out!("✅ Display profile {} applied", name.green());
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Parse a fan curve given as `TEMP:SPEED` pairs, e.g. `40:30,60:50,80:80`
+fn parse_fan_curve(spec: &str) -> Result<Vec<FanCurvePoint>> {
+    spec.split(',')
+        .map(|pair| {
+            let (temperature, speed) = pair.trim().split_once(':').ok_or_else(|| {
+                anyhow::anyhow!("Invalid fan curve point '{}', expected TEMP:SPEED", pair)
+            })?;
+            let temperature: u32 = temperature.trim().parse()?;
+            let speed: u32 = speed.trim().parse()?;
+            if speed > 100 {
+                return Err(anyhow::anyhow!("Fan speed {}% is above 100%", speed));
+            }
+            Ok(FanCurvePoint { temperature, speed })
+        })
+        .collect()
+}
+
+/// Delete `path` after the user confirms, or right away with `yes`.
+/// Returns the bytes freed.
+fn delete_confirmed(path: &std::path::Path, what: &str, yes: bool) -> Result<u64> {
+    let size = crate::utils::disk_usage(path)?;
+    let confirmed = yes
+        || dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "Delete the {} at {} ({})?",
+                what,
+                path.display(),
+                crate::utils::format_size(size)
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+    if !confirmed {
+        return Ok(0);
+    }
+
+    std::fs::remove_dir_all(path)?;
+    out!("🗑️ Deleted {}", path.display());
+    Ok(size)
+}
+
+/// Resolve a game by ID, then exact name, then a unique partial or fuzzy
+/// name match, asking which one was meant when several match. Commands that
+/// delete or rewrite a game pass `exact_only` to skip the guessing.
+fn find_game(
+    game_lib: &crate::game::GameLibrary,
+    query: &str,
+    exact_only: bool,
+) -> Result<crate::game::Game> {
+    use std::io::IsTerminal;
+
+    if let Some(game) = game_lib.get_game(query)? {
+        return Ok(game);
+    }
+
+    let query_lower = query.to_lowercase();
+    let mut candidates = game_lib.search_games(query)?;
+    if let Some(index) = candidates
+        .iter()
+        .position(|g| g.name.to_lowercase() == query_lower)
+    {
+        return Ok(candidates.swap_remove(index));
+    }
+
+    if exact_only {
+        let suggestions: Vec<String> = candidates
+            .iter()
+            .take(5)
+            .map(|g| format!("{} ({})", g.name, g.id))
+            .collect();
+        return Err(if suggestions.is_empty() {
+            anyhow::anyhow!(
+                "Game '{}' not found. Use 'forge game list' to see available games.",
+                query
+            )
+        } else {
+            anyhow::anyhow!(
+                "No game has the ID or name '{}'. Did you mean: {}?",
+                query,
+                suggestions.join(", ")
+            )
+        });
+    }
+
+    // A name containing the query beats fuzzy matches
+    let substring: Vec<usize> = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| g.name.to_lowercase().contains(&query_lower))
+        .map(|(index, _)| index)
+        .collect();
+    if let [index] = substring[..] {
+        return Ok(candidates.swap_remove(index));
+    }
+
+    match candidates.len() {
+        0 => Err(anyhow::anyhow!(
+            "Game '{}' not found. Use 'forge game list' to see available games.",
+            query
+        )),
+        1 => Ok(candidates.remove(0)),
+        _ if std::io::stdin().is_terminal() => {
+            candidates.truncate(10);
+            let names: Vec<&str> = candidates.iter().map(|g| g.name.as_str()).collect();
+            let choice = dialoguer::Select::new()
+                .with_prompt(format!("Several games match '{}'", query))
+                .items(&names)
+                .default(0)
+                .interact_opt()?
+                .ok_or_else(|| anyhow::anyhow!("No game selected"))?;
+            Ok(candidates.swap_remove(choice))
+        }
+        _ => Err(anyhow::anyhow!(
+            "'{}' matches several games: {}. Use the game ID or a longer name.",
+            query,
+            candidates
+                .iter()
+                .take(5)
+                .map(|g| format!("{} ({})", g.name, g.id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Open the game as TOML in $VISUAL/$EDITOR until it parses and validates.
+/// None when nothing was changed or the edit was abandoned.
+fn edit_game_in_editor(game: &crate::game::Game) -> Result<Option<crate::game::Game>> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut editor_args = editor.split_whitespace();
+    let editor_cmd = editor_args
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("$EDITOR is empty"))?;
+
+    let original = format!(
+        "# Editing {}. Save and quit to apply, quit without saving to cancel.\n\n{}",
+        game.name,
+        toml::to_string_pretty(game)?
+    );
+    // A fresh file each time, a predictable name in /tmp could be planted
+    let file = tempfile::Builder::new()
+        .prefix("ghostforge-")
+        .suffix(".toml")
+        .tempfile()?;
+    let path = file.path().to_path_buf();
+    std::fs::write(&path, &original)?;
+
+    let result = loop {
+        let status = std::process::Command::new(editor_cmd)
+            .args(editor_args.clone())
+            .arg(&path)
+            .status()
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", editor, e))?;
+        if !status.success() {
+            break Err(anyhow::anyhow!("{} exited with {}", editor, status));
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        if content == original {
+            break Ok(None);
+        }
+
+        let problems = match toml::from_str::<crate::game::Game>(&content) {
+            Ok(edited) => {
+                let problems = edited.validate_changes(game);
+                if problems.is_empty() {
+                    break Ok(Some(edited));
+                }
+                problems
+            }
+            Err(e) => vec![e.to_string()],
+        };
+
+        eout!("❌ Not saving {}:", game.name);
+        for problem in &problems {
+            eprintln!("  • {}", problem);
         }
+        let retry = dialoguer::Confirm::new()
+            .with_prompt("Edit again?")
+            .default(true)
+            .interact()
+            .unwrap_or(false);
+        if !retry {
+            break Ok(None);
+        }
+    };
+
+    drop(file);
+    result
+}
+
+/// Split `KEY=VALUE` into an environment variable, leaving flags like `--foo=bar` alone
+fn parse_env_assignment(option: &str) -> Option<(String, String)> {
+    let (key, value) = option.split_once('=')?;
+    let valid_key = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid_key.then(|| (key.to_string(), value.to_string()))
+}
+
+fn parse_pinned_version(version: &str) -> Option<String> {
+    match version.to_lowercase().as_str() {
+        "none" | "" => None,
+        _ => Some(version.to_string()),
     }
+}
 
+/// Pins only reach Wine prefixes GhostForge manages, Proton and Steam put
+/// their own DXVK/VKD3D-Proton back into the prefix on every launch
+fn check_graphics_pins_apply(game: &crate::game::Game) -> Result<()> {
+    if game.launcher.as_deref() == Some("Steam") {
+        return Err(anyhow::anyhow!(
+            "{} launches through Steam, which manages its own DXVK/VKD3D-Proton. \
+             Pins can't be applied to it",
+            game.name
+        ));
+    }
+    if game
+        .wine_version
+        .as_deref()
+        .is_some_and(crate::game_launcher::is_proton)
+    {
+        return Err(anyhow::anyhow!(
+            "{} runs through Proton, which replaces DXVK/VKD3D-Proton on every launch. \
+             Switch it to a Wine build with 'forge game edit {} --wine <version>' first",
+            game.name,
+            game.id
+        ));
+    }
     Ok(())
 }
 
+fn print_graphics_pins(game: &crate::game::Game) {
+    out!("📌 Graphics pins for {}:", game.name.green());
+    println!(
+        "  DXVK: {}",
+        game.dxvk_version.as_deref().unwrap_or("not pinned").cyan()
+    );
+    println!(
+        "  VKD3D-Proton: {}",
+        game.vkd3d_version
+            .as_deref()
+            .unwrap_or("not pinned")
+            .yellow()
+    );
+}
+
 async fn launch_tui() -> Result<()> {
-    println!("Launching Terminal UI...");
-    Ok(())
+    #[cfg(feature = "tui")]
+    {
+        crate::tui::run_tui().await
+    }
+    #[cfg(not(feature = "tui"))]
+    {
+        Err(anyhow::anyhow!(
+            "TUI feature not enabled. Rebuild with --features tui"
+        ))
+    }
 }
 
 async fn launch_gui() -> Result<()> {
@@ -1,14 +1,28 @@
+use crate::utils::eout;
 use anyhow::Result;
 use dirs;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Layout version of `config.toml`, bumped when fields are moved or change
+/// meaning so `Config::load` can upgrade older files
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Config file given with `--config`, used instead of the default location
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+static CONFIG_WARNED: OnceLock<()> = OnceLock::new();
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// 0 for files written before the config was versioned
+    #[serde(default)]
+    pub version: u32,
     pub general: GeneralConfig,
     pub wine: WineConfig,
     pub gpu: GpuConfig,
     pub paths: PathsConfig,
+    pub containers: ContainersConfig,
     pub launchers: LaunchersConfig,
 }
 
@@ -23,6 +37,23 @@ pub struct GeneralConfig {
     pub enable_vkd3d: bool,
     pub cpu_governor: String,
     pub log_level: String,
+    #[serde(default = "default_protondb_cache_ttl")]
+    pub protondb_cache_ttl_hours: u64,
+    /// How long pre- and post-launch scripts may run before they are killed
+    #[serde(default = "default_script_timeout")]
+    pub script_timeout_secs: u64,
+    /// Keep DXVK/VKD3D-Proton state caches under the cache directory instead
+    /// of next to each game's executable
+    #[serde(default)]
+    pub central_state_cache: bool,
+}
+
+fn default_protondb_cache_ttl() -> u64 {
+    crate::protondb::DEFAULT_CACHE_TTL_HOURS
+}
+
+fn default_script_timeout() -> u64 {
+    300
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +65,50 @@ pub struct WineConfig {
     pub vkd3d_versions_path: PathBuf,
     pub default_arch: String,            // win32 or win64
     pub default_windows_version: String, // win10, win7, etc.
+    #[serde(default)]
+    pub prefix_mode: PrefixMode,
+}
+
+/// Where games without an explicit prefix get one. A prefix per game keeps
+/// winetricks, DLL overrides and registry tweaks from leaking between games
+/// at the cost of disk space (roughly 1 GB each) and installing runtimes
+/// once per game. A shared prefix is smaller and installs runtimes once, but
+/// one game's tweaks or a broken install affect every game in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrefixMode {
+    /// `default_prefix_path/<game-id>`
+    #[default]
+    PerGame,
+    /// `default_prefix_path/shared`
+    Shared,
+}
+
+impl PrefixMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "per-game" | "pergame" => Some(Self::PerGame),
+            "shared" => Some(Self::Shared),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PerGame => "per-game",
+            Self::Shared => "shared",
+        }
+    }
+}
+
+impl WineConfig {
+    /// The prefix a game gets under the configured `prefix_mode`
+    pub fn prefix_for(&self, game_id: &str) -> PathBuf {
+        match self.prefix_mode {
+            PrefixMode::PerGame => self.default_prefix_path.join(game_id),
+            PrefixMode::Shared => self.default_prefix_path.join("shared"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +133,12 @@ pub struct PathsConfig {
     pub database: PathBuf,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainersConfig {
+    /// Pass game controllers and Steam Input pads into new containers
+    pub controller_support: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LaunchersConfig {
     pub steam: Option<LauncherConfig>,
@@ -91,6 +172,7 @@ impl Default for Config {
             .join("ghostforge");
 
         Self {
+            version: CONFIG_VERSION,
             general: GeneralConfig {
                 default_wine_version: "system".to_string(),
                 enable_gamemode: true,
@@ -101,6 +183,9 @@ impl Default for Config {
                 enable_vkd3d: true,
                 cpu_governor: "ondemand".to_string(),
                 log_level: "info".to_string(),
+                protondb_cache_ttl_hours: default_protondb_cache_ttl(),
+                script_timeout_secs: default_script_timeout(),
+                central_state_cache: false,
             },
             wine: WineConfig {
                 default_prefix_path: data_dir.join("prefixes"),
@@ -110,6 +195,7 @@ impl Default for Config {
                 vkd3d_versions_path: data_dir.join("vkd3d-versions"),
                 default_arch: "win64".to_string(),
                 default_windows_version: "win10".to_string(),
+                prefix_mode: PrefixMode::default(),
             },
             gpu: GpuConfig {
                 nvidia_prime_render_offload: false,
@@ -129,6 +215,9 @@ impl Default for Config {
                 cache: cache_dir,
                 database: data_dir.join("ghostforge.db"),
             },
+            containers: ContainersConfig {
+                controller_support: true,
+            },
             launchers: LaunchersConfig {
                 steam: None,
                 battlenet: None,
@@ -147,7 +236,18 @@ impl Config {
 
         if config_path.exists() {
             let contents = std::fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&contents)?;
+            let (config, migrated) = Self::parse(&contents)?;
+            if migrated {
+                // Keep the original around in case the upgrade lost anything
+                std::fs::copy(&config_path, config_path.with_extension("toml.bak"))?;
+                config.save()?;
+            }
+            // Commands load the config several times, warn on the first
+            CONFIG_WARNED.get_or_init(|| {
+                for problem in config.validate() {
+                    eout!("⚠️  {}: {}", config_path.display(), problem);
+                }
+            });
             Ok(config)
         } else {
             let config = Config::default();
@@ -156,6 +256,14 @@ impl Config {
         }
     }
 
+    /// Parse a config file, upgrading older layouts. Also returns whether the
+    /// file needed upgrading.
+    fn parse(contents: &str) -> Result<(Self, bool)> {
+        let mut value: toml::Value = toml::from_str(contents)?;
+        let migrated = migrate(&mut value)?;
+        Ok((value.try_into()?, migrated))
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path();
 
@@ -169,13 +277,96 @@ impl Config {
         Ok(())
     }
 
+    /// Use `path` as the config file for the rest of the run. It must exist
+    /// and parse, so a typo doesn't silently fall back to the defaults.
+    pub fn use_path(path: PathBuf) -> Result<()> {
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("Config file not found: {}", path.display()));
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        Self::parse(&contents)
+            .map_err(|e| anyhow::anyhow!("Invalid config file {}: {}", path.display(), e))?;
+
+        CONFIG_PATH_OVERRIDE
+            .set(path)
+            .map_err(|_| anyhow::anyhow!("Config file was already set"))
+    }
+
     pub fn config_path() -> PathBuf {
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return path.clone();
+        }
+
         dirs::config_dir()
             .unwrap_or_else(|| dirs::home_dir().unwrap().join(".config"))
             .join("ghostforge")
             .join("config.toml")
     }
 
+    /// Check values that parse fine but would break later, returning one
+    /// message per bad key
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if !crate::prefix::ARCHES.contains(&self.wine.default_arch.as_str()) {
+            problems.push(format!(
+                "wine.default_arch: '{}' is not one of {}",
+                self.wine.default_arch,
+                crate::prefix::ARCHES.join(", ")
+            ));
+        }
+        if !crate::prefix::WINDOWS_VERSIONS.contains(&self.wine.default_windows_version.as_str()) {
+            problems.push(format!(
+                "wine.default_windows_version: '{}' is not one of {}",
+                self.wine.default_windows_version,
+                crate::prefix::WINDOWS_VERSIONS.join(", ")
+            ));
+        }
+        if self.general.log_level.parse::<tracing::Level>().is_err() {
+            problems.push(format!(
+                "general.log_level: '{}' is not one of error, warn, info, debug, trace",
+                self.general.log_level
+            ));
+        }
+
+        let paths = [
+            (
+                "wine.default_prefix_path",
+                Some(&self.wine.default_prefix_path),
+            ),
+            (
+                "wine.wine_versions_path",
+                Some(&self.wine.wine_versions_path),
+            ),
+            ("wine.winetricks_path", self.wine.winetricks_path.as_ref()),
+            (
+                "wine.dxvk_versions_path",
+                Some(&self.wine.dxvk_versions_path),
+            ),
+            (
+                "wine.vkd3d_versions_path",
+                Some(&self.wine.vkd3d_versions_path),
+            ),
+            ("paths.games_library", Some(&self.paths.games_library)),
+            ("paths.downloads", Some(&self.paths.downloads)),
+            ("paths.backups", Some(&self.paths.backups)),
+            ("paths.logs", Some(&self.paths.logs)),
+            ("paths.cache", Some(&self.paths.cache)),
+            ("paths.database", Some(&self.paths.database)),
+        ];
+        for (key, path) in paths {
+            if let Some(path) = path.filter(|path| !path.is_absolute()) {
+                problems.push(format!(
+                    "{}: '{}' is not an absolute path",
+                    key,
+                    path.display()
+                ));
+            }
+        }
+
+        problems
+    }
+
     pub fn ensure_directories(&self) -> Result<()> {
         std::fs::create_dir_all(&self.paths.games_library)?;
         std::fs::create_dir_all(&self.paths.downloads)?;
@@ -205,4 +396,160 @@ impl Config {
             _ => None,
         }
     }
+
+    /// Replace a launcher's config entry, returning false for unknown names
+    pub fn set_launcher(&mut self, name: &str, launcher: Option<LauncherConfig>) -> bool {
+        let slot = match name.to_lowercase().as_str() {
+            "steam" => &mut self.launchers.steam,
+            "battlenet" | "battle.net" => &mut self.launchers.battlenet,
+            "epic" => &mut self.launchers.epic,
+            "gog" => &mut self.launchers.gog,
+            "ubisoft" | "uplay" => &mut self.launchers.ubisoft,
+            "ea" | "origin" => &mut self.launchers.ea,
+            _ => return false,
+        };
+        *slot = launcher;
+        true
+    }
+}
+
+/// Upgrade a parsed config file to `CONFIG_VERSION` in place, returning
+/// whether anything changed
+fn migrate(value: &mut toml::Value) -> Result<bool> {
+    let table = value
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("Config file is not a TOML table"))?;
+
+    let version = table
+        .get("version")
+        .and_then(|v| v.as_integer())
+        .unwrap_or(0);
+    if version > CONFIG_VERSION as i64 {
+        return Err(anyhow::anyhow!(
+            "Config file version {} is newer than this GhostForge supports ({})",
+            version,
+            CONFIG_VERSION
+        ));
+    }
+
+    // Renamed or moved keys get handled here, per version they changed in
+
+    // Fields added since the file was written get their defaults, so
+    // required fields don't fail to parse and the file shows every setting
+    let defaults = toml::Value::try_from(Config::default())?;
+    let mut changed = fill_defaults(table, defaults.as_table().unwrap());
+
+    if version < CONFIG_VERSION as i64 {
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(CONFIG_VERSION as i64),
+        );
+        changed = true;
+    }
+
+    Ok(changed)
+}
+
+fn fill_defaults(table: &mut toml::Table, defaults: &toml::Table) -> bool {
+    let mut changed = false;
+    for (key, default) in defaults {
+        match (table.get_mut(key), default) {
+            (Some(toml::Value::Table(section)), toml::Value::Table(default_section)) => {
+                changed |= fill_defaults(section, default_section);
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                table.insert(key.clone(), default.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config from before versioning, without the fields added since
+    const V0_CONFIG: &str = r#"
+[general]
+default_wine_version = "GE-Proton9-20"
+enable_gamemode = false
+enable_mangohud = true
+enable_fsync = true
+enable_esync = true
+enable_dxvk = true
+enable_vkd3d = true
+cpu_governor = "performance"
+log_level = "info"
+
+[wine]
+default_prefix_path = "/home/user/.local/share/ghostforge/prefixes"
+wine_versions_path = "/home/user/.local/share/ghostforge/wine-versions"
+dxvk_versions_path = "/home/user/.local/share/ghostforge/dxvk-versions"
+vkd3d_versions_path = "/home/user/.local/share/ghostforge/vkd3d-versions"
+default_arch = "win64"
+default_windows_version = "win10"
+
+[gpu]
+nvidia_prime_render_offload = true
+amd_vulkan_icd = "radv"
+intel_vulkan_icd = "anv"
+enable_nvapi = true
+enable_nvidia_ngx = true
+enable_dlss = true
+enable_ray_tracing = true
+
+[paths]
+games_library = "/home/user/Games"
+downloads = "/home/user/Downloads/ghostforge"
+backups = "/home/user/.local/share/ghostforge/backups"
+logs = "/home/user/.local/share/ghostforge/logs"
+cache = "/home/user/.cache/ghostforge"
+database = "/home/user/.local/share/ghostforge/ghostforge.db"
+"#;
+
+    #[test]
+    fn migrates_v0_config() {
+        let (config, migrated) = Config::parse(V0_CONFIG).unwrap();
+        assert!(migrated);
+        assert_eq!(config.version, CONFIG_VERSION);
+
+        // Existing settings are kept
+        assert_eq!(config.general.default_wine_version, "GE-Proton9-20");
+        assert_eq!(config.general.cpu_governor, "performance");
+        assert!(!config.general.enable_gamemode);
+        assert!(config.gpu.nvidia_prime_render_offload);
+
+        // New ones get their defaults
+        assert_eq!(config.general.script_timeout_secs, default_script_timeout());
+        assert!(!config.general.central_state_cache);
+        assert!(config.launchers.steam.is_none());
+        assert!(config.containers.controller_support);
+        assert_eq!(config.wine.prefix_mode, PrefixMode::PerGame);
+
+        // Saving and loading again needs no further migration
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let (reloaded, migrated) = Config::parse(&saved).unwrap();
+        assert!(!migrated);
+        assert_eq!(reloaded.general.default_wine_version, "GE-Proton9-20");
+    }
+
+    #[test]
+    fn resolves_prefixes_by_mode() {
+        let mut wine = Config::default().wine;
+        wine.default_prefix_path = PathBuf::from("/prefixes");
+        assert_eq!(wine.prefix_for("abc"), PathBuf::from("/prefixes/abc"));
+
+        wine.prefix_mode = PrefixMode::Shared;
+        assert_eq!(wine.prefix_for("abc"), PathBuf::from("/prefixes/shared"));
+        assert_eq!(wine.prefix_for("def"), wine.prefix_for("abc"));
+    }
+
+    #[test]
+    fn rejects_newer_config() {
+        let newer = format!("version = {}\n{}", CONFIG_VERSION + 1, V0_CONFIG);
+        assert!(Config::parse(&newer).is_err());
+    }
 }
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::process::Command;
 use tokio::process::Command as AsyncCommand;
 use uuid::Uuid;
+use crate::utils::{eout, out};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameContainer {
@@ -23,8 +24,79 @@ pub struct GameContainer {
     pub created_at: DateTime<Utc>,
     pub last_used: Option<DateTime<Utc>>,
     pub size_mb: Option<u64>,
+    /// Game controllers and Steam Input pads are passed into the container
+    #[serde(default = "default_controller_support")]
+    pub controller_support: bool,
+    /// Image tag the container runs, shared by containers whose Dockerfiles
+    /// are identical. Empty for containers from before images were shared.
+    #[serde(default)]
+    pub image: String,
 }
 
+impl GameContainer {
+    pub fn image_tag(&self) -> String {
+        if self.image.is_empty() {
+            format!("ghostforge-{}", self.id)
+        } else {
+            self.image.clone()
+        }
+    }
+}
+
+fn default_controller_support() -> bool {
+    true
+}
+
+/// Name podman/docker run a container under, so its logs can be looked up
+fn run_name(container_id: &str) -> String {
+    format!("ghostforge-run-{}", container_id)
+}
+
+/// Join a container's stdout and stderr, with stderr under its own header
+pub(crate) fn format_logs(stdout: &[u8], stderr: &[u8]) -> String {
+    let mut logs = String::from_utf8_lossy(stdout).to_string();
+    let stderr = String::from_utf8_lossy(stderr);
+    if !stderr.trim().is_empty() {
+        if !logs.is_empty() && !logs.ends_with('\n') {
+            logs.push('\n');
+        }
+        logs.push_str("── stderr ──\n");
+        logs.push_str(&stderr);
+    }
+    logs
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Environment variables SDL reads controller mappings from. Steam sets the
+/// ignore list to hide physical pads behind its Steam Input virtual ones.
+const CONTROLLER_ENVIRONMENT: &[&str] = &[
+    "SDL_GAMECONTROLLERCONFIG",
+    "SDL_GAMECONTROLLERCONFIG_FILE",
+    "SDL_GAMECONTROLLER_IGNORE_DEVICES",
+    "SDL_GAMECONTROLLER_IGNORE_DEVICES_EXCEPT",
+];
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Character device major number of evdev input devices
+const INPUT_DEVICE_MAJOR: u32 = 13;
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Only exists while the proprietary NVIDIA driver is loaded
+const NVIDIA_CONTROL_DEVICE: &str = "/dev/nvidiactl";
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Character device major number of /dev/nvidia0.., nvidiactl and nvidia-modeset
+const NVIDIA_DEVICE_MAJOR: u32 = 195;
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Driver features the NVIDIA container toolkit exposes, games need the
+/// graphics (OpenGL/Vulkan) and display libraries on top of the basics
+const NVIDIA_DRIVER_CAPABILITIES: &str = "graphics,display,compute,utility,video";
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// CDI specs generated by `nvidia-ctk cdi generate`, used by Podman
+const NVIDIA_CDI_SPECS: &[&str] = &["/etc/cdi/nvidia.yaml", "/var/run/cdi/nvidia.yaml"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerImage {
     pub name: String,
@@ -57,6 +129,18 @@ pub enum NetworkMode {
     Custom(String), // Custom network name
 }
 
+impl NetworkMode {
+    /// Mode named in game hints, custom networks aren't nameable there
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bridge" => Some(NetworkMode::Bridge),
+            "host" => Some(NetworkMode::Host),
+            "none" => Some(NetworkMode::None),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceLimits {
     pub memory_mb: Option<u64>,
@@ -97,6 +181,8 @@ pub struct ContainerManager {
     pub runtime: ContainerRuntime,
     pub containers: HashMap<String, GameContainer>,
     pub base_images: Vec<ContainerImage>,
+    /// Pass game controllers into containers created from now on
+    pub controller_support: bool,
 }
 
 impl ContainerManager {
@@ -105,6 +191,18 @@ impl ContainerManager {
         let data_dir = config_dir.join("containers");
         std::fs::create_dir_all(&data_dir)?;
 
+        // A broken config shouldn't take every container command down with it
+        let controller_support = match crate::config::Config::load() {
+            Ok(config) => config.containers.controller_support,
+            Err(e) => {
+                eout!(
+                    "⚠️ Could not load the config, passing controllers through: {}",
+                    e
+                );
+                default_controller_support()
+            }
+        };
+
         Ok(Self {
             runtime: ContainerRuntime {
                 runtime_type: runtime,
@@ -114,6 +212,7 @@ impl ContainerManager {
             },
             containers: HashMap::new(),
             base_images: Self::get_default_images(),
+            controller_support,
         })
     }
 
@@ -198,6 +297,18 @@ impl ContainerManager {
         self.setup_wine_environment(&mut environment_variables, &wine_version);
         self.setup_graphics_environment(&mut environment_variables, &graphics_layers);
         self.setup_game_environment(&mut environment_variables, game);
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if self.controller_support {
+            self.setup_controller_environment(&mut environment_variables);
+        }
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if uses_nvidia_devices(&mount_points) {
+            environment_variables.insert("NVIDIA_VISIBLE_DEVICES".to_string(), "all".to_string());
+            environment_variables.insert(
+                "NVIDIA_DRIVER_CAPABILITIES".to_string(),
+                NVIDIA_DRIVER_CAPABILITIES.to_string(),
+            );
+        }
 
         // Configure resource limits based on game requirements
         let resource_limits = self.calculate_resource_limits(game);
@@ -217,6 +328,18 @@ impl ContainerManager {
             created_at: Utc::now(),
             last_used: None,
             size_mb: None,
+            controller_support: self.controller_support,
+            image: String::new(),
+        };
+
+        // Podman and Docker containers with identical Dockerfiles share an image
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        let container = match self.runtime.runtime_type {
+            RuntimeType::Podman | RuntimeType::Docker => GameContainer {
+                image: image_tag_for(&self.generate_dockerfile(&container)?),
+                ..container
+            },
+            _ => container,
         };
 
         // Build the container image using the appropriate runtime
@@ -269,7 +392,8 @@ impl ContainerManager {
 
     async fn determine_wine_version(&self, game: &crate::game::Game) -> Result<String> {
         // Use ProtonDB integration to determine best Wine version
-        let protondb = crate::protondb::ProtonDBClient::new();
+        let config = crate::config::Config::load()?;
+        let protondb = crate::protondb::ProtonDBClient::from_config(&config);
 
         if let Some(launcher_id) = &game.launcher_id {
             if let Ok(appid) = launcher_id.parse::<u32>() {
@@ -279,8 +403,12 @@ impl ContainerManager {
             }
         }
 
-        // Default to latest Proton GE
-        Ok("GE-Proton8-26".to_string())
+        let hints = crate::game_hints::GameHints::load();
+        hints
+            .settings(&game.name, game.steam_appid())
+            .wine_version
+            .or_else(|| hints.default_wine_version.clone())
+            .ok_or_else(|| anyhow::anyhow!("No Wine version for {}", game.name))
     }
 
     async fn configure_graphics_layers(&self, game: &crate::game::Game) -> Result<Vec<String>> {
@@ -290,7 +418,14 @@ impl ContainerManager {
         let nvidia_features = graphics_manager
             .detect_nvidia_features()
             .unwrap_or_default();
-        let recommendations = graphics_manager.recommend_for_game(&game.name, &nvidia_features);
+        let intel_arc_features = graphics_manager
+            .detect_intel_arc_features()
+            .unwrap_or_default();
+        let recommendations = graphics_manager.recommend_for_game(
+            &game.name,
+            &nvidia_features,
+            &intel_arc_features,
+        );
 
         let layer_names = recommendations
             .iter()
@@ -327,7 +462,7 @@ impl ContainerManager {
                 read_only: false,
                 bind_type: BindType::Volume,
             },
-            // GPU access
+            // GPU access, Mesa drivers (AMD/Intel) only need the DRM nodes
             MountPoint {
                 host_path: PathBuf::from("/dev/dri"),
                 container_path: PathBuf::from("/dev/dri"),
@@ -343,7 +478,7 @@ impl ContainerManager {
             },
         ];
 
-        // Add X11 socket for graphics
+        // Add X11 socket for graphics, XWayland provides one on Wayland
         if let Ok(display) = std::env::var("DISPLAY") {
             mounts.push(MountPoint {
                 host_path: PathBuf::from("/tmp/.X11-unix"),
@@ -353,6 +488,52 @@ impl ContainerManager {
             });
         }
 
+        // Wayland socket, at the same path so WAYLAND_DISPLAY can point at it
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if let Some(socket) = wayland_socket() {
+            mounts.push(MountPoint {
+                host_path: socket.clone(),
+                container_path: socket,
+                read_only: false,
+                bind_type: BindType::Bind,
+            });
+        }
+
+        // Sound server sockets, /dev/snd alone bypasses PulseAudio/PipeWire
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        {
+            let audio = AudioSockets::detect();
+            for socket in [audio.pulse, audio.pipewire].into_iter().flatten() {
+                mounts.push(MountPoint {
+                    host_path: socket.clone(),
+                    container_path: socket,
+                    read_only: false,
+                    bind_type: BindType::Bind,
+                });
+            }
+            if let Some(cookie) = audio.pulse_cookie {
+                mounts.push(MountPoint {
+                    host_path: cookie.clone(),
+                    container_path: cookie,
+                    read_only: true,
+                    bind_type: BindType::Bind,
+                });
+            }
+        }
+
+        // The proprietary NVIDIA driver talks to its own device nodes
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        mounts.extend(nvidia_device_nodes().into_iter().map(|path| MountPoint {
+            host_path: path.clone(),
+            container_path: path,
+            read_only: false,
+            bind_type: BindType::DeviceNode,
+        }));
+
+        if self.controller_support {
+            mounts.extend(Self::controller_mount_points());
+        }
+
         // Create necessary host directories
         for mount in &mounts {
             if matches!(mount.bind_type, BindType::Volume) {
@@ -363,6 +544,57 @@ impl ContainerManager {
         Ok(mounts)
     }
 
+    /// Input devices and udev data SDL and Wine find controllers through.
+    /// `/dev/input` is mounted whole so pads plugged in later show up too,
+    /// hidraw nodes (SDL's DualSense and Switch Pro drivers) exist per device
+    /// and are mounted as found.
+    fn controller_mount_points() -> Vec<MountPoint> {
+        let mut mounts = vec![MountPoint {
+            host_path: PathBuf::from("/dev/input"),
+            container_path: PathBuf::from("/dev/input"),
+            read_only: false,
+            bind_type: BindType::DeviceNode,
+        }];
+
+        // libudev in the container reads device properties and hotplug
+        // events from here
+        if std::path::Path::new("/run/udev").exists() {
+            mounts.push(MountPoint {
+                host_path: PathBuf::from("/run/udev"),
+                container_path: PathBuf::from("/run/udev"),
+                read_only: true,
+                bind_type: BindType::Bind,
+            });
+        }
+
+        if let Ok(entries) = std::fs::read_dir("/dev") {
+            let mut hidraw: Vec<PathBuf> = entries
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("hidraw"))
+                .map(|entry| entry.path())
+                .collect();
+            hidraw.sort();
+            mounts.extend(hidraw.into_iter().map(|path| MountPoint {
+                host_path: path.clone(),
+                container_path: path,
+                read_only: false,
+                bind_type: BindType::DeviceNode,
+            }));
+        }
+
+        mounts
+    }
+
+    #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+    /// Hand the host's SDL controller mappings to the game
+    fn setup_controller_environment(&self, env: &mut HashMap<String, String>) {
+        for key in CONTROLLER_ENVIRONMENT {
+            if let Ok(value) = std::env::var(key) {
+                env.insert(key.to_string(), value);
+            }
+        }
+    }
+
     fn setup_wine_environment(&self, env: &mut HashMap<String, String>, wine_version: &str) {
         env.insert("WINE_VERSION".to_string(), wine_version.to_string());
         env.insert("WINEPREFIX".to_string(), "/wine_prefix".to_string());
@@ -415,29 +647,59 @@ impl ContainerManager {
         if let Ok(display) = std::env::var("DISPLAY") {
             env.insert("DISPLAY".to_string(), display);
         }
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if let Some(socket) = wayland_socket() {
+            env.insert(
+                "WAYLAND_DISPLAY".to_string(),
+                socket.to_string_lossy().to_string(),
+            );
+        }
 
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        {
+            let audio = AudioSockets::detect();
+            if let Some(pulse) = &audio.pulse {
+                if let Some(runtime_path) = pulse.parent() {
+                    env.insert(
+                        "PULSE_RUNTIME_PATH".to_string(),
+                        runtime_path.to_string_lossy().to_string(),
+                    );
+                }
+                env.insert(
+                    "PULSE_SERVER".to_string(),
+                    format!("unix:{}", pulse.display()),
+                );
+            }
+            if let Some(cookie) = &audio.pulse_cookie {
+                env.insert(
+                    "PULSE_COOKIE".to_string(),
+                    cookie.to_string_lossy().to_string(),
+                );
+            }
+            if let Some(pipewire) = &audio.pipewire {
+                env.insert(
+                    "PIPEWIRE_REMOTE".to_string(),
+                    pipewire.to_string_lossy().to_string(),
+                );
+            }
+        }
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
         env.insert(
-            "PULSE_RUNTIME_PATH".to_string(),
-            "/run/user/1000/pulse".to_string(),
+            "XDG_RUNTIME_DIR".to_string(),
+            host_runtime_dir().to_string_lossy().to_string(),
         );
-        env.insert("XDG_RUNTIME_DIR".to_string(), "/run/user/1000".to_string());
     }
 
     fn calculate_resource_limits(&self, game: &crate::game::Game) -> ResourceLimits {
         // Determine resource limits based on game requirements
         let mut limits = ResourceLimits::default();
 
-        // Adjust based on game genre/requirements
-        let game_lower = game.name.to_lowercase();
-
-        if game_lower.contains("cyberpunk") || game_lower.contains("metro") {
-            // AAA games need more resources
-            limits.memory_mb = Some(8192); // 8GB
-            limits.disk_mb = Some(20480); // 20GB
-        } else if game_lower.contains("indie") || game_lower.contains("retro") {
-            // Indie games need less
-            limits.memory_mb = Some(2048); // 2GB
-            limits.disk_mb = Some(5120); // 5GB
+        let hints = crate::game_hints::GameHints::load().settings(&game.name, game.steam_appid());
+        if let Some(memory_mb) = hints.memory_mb {
+            limits.memory_mb = Some(memory_mb);
+        }
+        if let Some(disk_mb) = hints.disk_mb {
+            limits.disk_mb = Some(disk_mb);
         }
 
         limits
@@ -465,21 +727,39 @@ impl ContainerManager {
     }
 
     fn determine_network_mode(&self, game: &crate::game::Game) -> NetworkMode {
-        // Use host networking for games that need anti-cheat or low latency
-        let game_lower = game.name.to_lowercase();
-
-        if game_lower.contains("valorant")
-            || game_lower.contains("apex")
-            || game_lower.contains("fortnite")
-            || game_lower.contains("call of duty")
-        {
+        // Anti-cheat checks the network setup, use host networking for it
+        if crate::anticheat::detect(game).is_some() {
             return NetworkMode::Host;
         }
 
-        NetworkMode::Bridge
+        crate::game_hints::GameHints::load()
+            .settings(&game.name, game.steam_appid())
+            .network_mode
+            .and_then(|mode| NetworkMode::from_name(&mode))
+            .unwrap_or(NetworkMode::Bridge)
     }
 
     async fn build_container(&self, container: &GameContainer) -> Result<()> {
+        let build_cmd = match self.runtime.runtime_type {
+            RuntimeType::Podman => "podman",
+            RuntimeType::Docker => "docker",
+            _ => return Err(anyhow::anyhow!("Unsupported runtime")),
+        };
+        let image = container.image_tag();
+
+        // Games with the same base image, dependencies and Wine version share
+        // an image, the tag is derived from the Dockerfile
+        let exists = AsyncCommand::new(build_cmd)
+            .args(["image", "inspect", &image])
+            .output()
+            .await?
+            .status
+            .success();
+        if exists {
+            out!("♻️  Reusing container image {}", image);
+            return Ok(());
+        }
+
         let dockerfile_content = self.generate_dockerfile(container)?;
         let build_context = self.runtime.data_dir.join(&container.id);
 
@@ -487,19 +767,8 @@ impl ContainerManager {
         std::fs::write(build_context.join("Dockerfile"), dockerfile_content)?;
 
         // Build the container image
-        let build_cmd = match self.runtime.runtime_type {
-            RuntimeType::Podman => "podman",
-            RuntimeType::Docker => "docker",
-            _ => return Err(anyhow::anyhow!("Unsupported runtime")),
-        };
-
         let output = AsyncCommand::new(build_cmd)
-            .args(&[
-                "build",
-                "-t",
-                &format!("ghostforge-{}", container.id),
-                build_context.to_str().unwrap(),
-            ])
+            .args(["build", "-t", &image, build_context.to_str().unwrap()])
             .output()
             .await?;
 
@@ -547,28 +816,28 @@ impl ContainerManager {
     #[cfg(feature = "container-bolt")]
     async fn build_bolt_container(&self, container: &GameContainer) -> Result<()> {
         // Placeholder implementation for when Bolt becomes available
-        println!(
+        out!(
             "🔥 Building Bolt gaming container for {}...",
             container.name
         );
-        println!(
+        out!(
             "   📦 Gaming capsule: {}",
             self.determine_gaming_capsule(container)
         );
-        println!(
+        out!(
             "   🎮 GPU support: {}",
             container.resource_limits.gpu_access
         );
 
         if let Some(memory_mb) = container.resource_limits.memory_mb {
-            println!("   💾 Memory limit: {}Mi", memory_mb);
+            out!("   💾 Memory limit: {}Mi", memory_mb);
         }
         if let Some(cpu_cores) = container.resource_limits.cpu_cores {
-            println!("   🖥️  CPU limit: {}", cpu_cores);
+            out!("   🖥️  CPU limit: {}", cpu_cores);
         }
 
-        println!("   📁 Volumes: {} mounts", container.mount_points.len());
-        println!(
+        out!("   📁 Volumes: {} mounts", container.mount_points.len());
+        out!(
             "   🌍 Environment: {} variables",
             container.environment_variables.len()
         );
@@ -577,11 +846,11 @@ impl ContainerManager {
         // let bolt_runtime = BoltRuntime::new().await?;
         // bolt_runtime.create_service(&container.id, service_config).await?;
 
-        println!(
+        out!(
             "✅ Bolt gaming container created (placeholder): {}",
             container.name
         );
-        println!("   ⚠️  Install Bolt runtime for actual functionality");
+        out!("   ⚠️  Install Bolt runtime for actual functionality");
         Ok(())
     }
 
@@ -627,7 +896,7 @@ impl ContainerManager {
         container: &GameContainer,
         game: &crate::game::Game,
     ) -> Result<u32> {
-        println!("🎮 Launching {} with Bolt runtime...", game.name);
+        out!("🎮 Launching {} with Bolt runtime...", game.name);
 
         // Create launch command for the game
         let game_cmd = vec![
@@ -635,8 +904,8 @@ impl ContainerManager {
             game.executable.to_string_lossy().to_string(),
         ];
 
-        println!("   📦 Container: {}", container.name);
-        println!("   🎮 Command: {:?}", game_cmd);
+        out!("   📦 Container: {}", container.name);
+        out!("   🎮 Command: {:?}", game_cmd);
 
         // TODO: Replace with actual Bolt runtime calls when available
         // let bolt_runtime = BoltRuntime::new().await?;
@@ -644,11 +913,12 @@ impl ContainerManager {
 
         // Placeholder: return a fake PID for testing
         let fake_pid = 12345;
-        println!(
+        out!(
             "🚀 {} launched with Bolt (placeholder PID: {})",
-            game.name, fake_pid
+            game.name,
+            fake_pid
         );
-        println!("   ⚠️  Install Bolt runtime for actual game launching");
+        out!("   ⚠️  Install Bolt runtime for actual game launching");
         Ok(fake_pid)
     }
 
@@ -679,9 +949,20 @@ impl ContainerManager {
 
         cmd.push("run".to_string());
         cmd.push("--rm".to_string()); // Remove container after exit
+        cmd.push(format!("--name={}", run_name(&container.id)));
         cmd.push("--interactive".to_string());
         cmd.push("--tty".to_string());
 
+        // Run as the host user so the mounted runtime dir sockets are usable
+        match self.runtime.runtime_type {
+            RuntimeType::Podman => cmd.push("--userns=keep-id".to_string()),
+            _ => cmd.push(format!(
+                "--user={}:{}",
+                nix::unistd::getuid(),
+                nix::unistd::getgid()
+            )),
+        }
+
         // Add resource limits
         if let Some(memory_mb) = container.resource_limits.memory_mb {
             cmd.push(format!("--memory={}m", memory_mb));
@@ -703,6 +984,52 @@ impl ContainerManager {
             cmd.push(mount_arg);
         }
 
+        // Bind-mounted device nodes also need cgroup access. hidraw has a
+        // dynamic major number, listed in /proc/devices.
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if container.controller_support {
+            cmd.push(format!(
+                "--device-cgroup-rule=c {}:* rmw",
+                INPUT_DEVICE_MAJOR
+            ));
+            if let Some(major) = device_major("hidraw") {
+                cmd.push(format!("--device-cgroup-rule=c {}:* rmw", major));
+            }
+        }
+
+        #[cfg(any(feature = "container-podman", feature = "container-docker"))]
+        if uses_nvidia_devices(&container.mount_points) {
+            cmd.push(format!(
+                "--device-cgroup-rule=c {}:* rmw",
+                NVIDIA_DEVICE_MAJOR
+            ));
+            for name in ["nvidia-uvm", "nvidia-caps"] {
+                if let Some(major) = device_major(name) {
+                    cmd.push(format!("--device-cgroup-rule=c {}:* rmw", major));
+                }
+            }
+
+            // The toolkit hooks inject the host's driver libraries, which
+            // have to match the kernel module exactly
+            match self.runtime.runtime_type {
+                RuntimeType::Podman
+                    if NVIDIA_CDI_SPECS
+                        .iter()
+                        .any(|spec| std::path::Path::new(spec).exists()) =>
+                {
+                    cmd.push("--device=nvidia.com/gpu=all".to_string());
+                }
+                RuntimeType::Docker if which::which("nvidia-container-runtime-hook").is_ok() => {
+                    cmd.push("--gpus=all".to_string());
+                }
+                _ => {
+                    out!(
+                        "   ⚠️  NVIDIA container toolkit not found, the driver libraries won't be available in the container"
+                    );
+                }
+            }
+        }
+
         // Add environment variables
         for (key, value) in &container.environment_variables {
             cmd.push("--env".to_string());
@@ -726,7 +1053,7 @@ impl ContainerManager {
         }
 
         // Container image
-        cmd.push(format!("ghostforge-{}", container.id));
+        cmd.push(container.image_tag());
 
         // Game executable command
         cmd.push("wine".to_string());
@@ -795,6 +1122,40 @@ impl ContainerManager {
         Ok(cleaned_count)
     }
 
+    /// Fetch the last `tail` lines of a running container's output
+    pub async fn container_logs(&self, container_id: &str, tail: usize) -> Result<String> {
+        let tail = tail.to_string();
+        let output = match self.runtime.runtime_type {
+            RuntimeType::Bolt => {
+                AsyncCommand::new("bolt")
+                    .args(["logs", "--tail", &tail, container_id])
+                    .output()
+                    .await?
+            }
+            RuntimeType::Podman | RuntimeType::Docker => {
+                let logs_cmd = match self.runtime.runtime_type {
+                    RuntimeType::Podman => "podman",
+                    RuntimeType::Docker => "docker",
+                    _ => unreachable!(),
+                };
+                AsyncCommand::new(logs_cmd)
+                    .args(["logs", "--tail", &tail, &run_name(container_id)])
+                    .output()
+                    .await?
+            }
+            _ => return Err(anyhow::anyhow!("Unsupported runtime")),
+        };
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch logs: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(format_logs(&output.stdout, &output.stderr))
+    }
+
     pub async fn remove_container(&mut self, container_id: &str) -> Result<()> {
         match self.runtime.runtime_type {
             RuntimeType::Bolt => self.remove_bolt_container(container_id).await?,
@@ -805,10 +1166,31 @@ impl ContainerManager {
                     _ => unreachable!(),
                 };
 
-                let _output = AsyncCommand::new(remove_cmd)
-                    .args(&["rmi", &format!("ghostforge-{}", container_id)])
+                let image = self
+                    .containers
+                    .get(container_id)
+                    .map(|container| container.image_tag())
+                    .unwrap_or_else(|| format!("ghostforge-{}", container_id));
+
+                // Keep images other game containers are built on, or that
+                // any container of the runtime, ours or not, was created from
+                let users = AsyncCommand::new(remove_cmd)
+                    .args(["ps", "-a", "--quiet", "--filter"])
+                    .arg(format!("ancestor={}", image))
                     .output()
                     .await?;
+                let shared = !users.status.success()
+                    || !users.stdout.trim_ascii().is_empty()
+                    || self
+                        .containers
+                        .values()
+                        .any(|other| other.id != container_id && other.image_tag() == image);
+                if !shared {
+                    let _output = AsyncCommand::new(remove_cmd)
+                        .args(["rmi", &image])
+                        .output()
+                        .await?;
+                }
             }
             _ => return Err(anyhow::anyhow!("Unsupported runtime")),
         }
@@ -827,14 +1209,14 @@ impl ContainerManager {
 
     #[cfg(feature = "container-bolt")]
     async fn remove_bolt_container(&self, container_id: &str) -> Result<()> {
-        println!("🗑️ Removing Bolt container: {}", container_id);
+        out!("🗑️ Removing Bolt container: {}", container_id);
 
         // TODO: Replace with actual Bolt runtime calls when available
         // let bolt_runtime = BoltRuntime::new().await?;
         // bolt_runtime.remove_service(container_id).await?;
 
-        println!("✅ Bolt container removed (placeholder): {}", container_id);
-        println!("   ⚠️  Install Bolt runtime for actual container management");
+        out!("✅ Bolt container removed (placeholder): {}", container_id);
+        out!("   ⚠️  Install Bolt runtime for actual container management");
         Ok(())
     }
 
@@ -954,6 +1336,102 @@ impl ContainerManager {
     }
 }
 
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// The user's runtime directory on the host, where display and audio
+/// servers put their sockets
+fn host_runtime_dir() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("/run/user/{}", nix::unistd::getuid())))
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// The Wayland compositor's socket when running in a Wayland session.
+/// `WAYLAND_DISPLAY` is a name in the runtime directory or an absolute path.
+fn wayland_socket() -> Option<PathBuf> {
+    if !crate::display::wayland_session_active() {
+        return None;
+    }
+    let display = std::env::var("WAYLAND_DISPLAY").unwrap_or_else(|_| "wayland-0".to_string());
+    let socket = host_runtime_dir().join(display);
+    socket.exists().then_some(socket)
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// The host's sound server sockets, mounted at the same paths in containers
+struct AudioSockets {
+    /// PulseAudio, or PipeWire's PulseAudio replacement
+    pulse: Option<PathBuf>,
+    /// Authenticates PulseAudio clients, PipeWire doesn't need it
+    pulse_cookie: Option<PathBuf>,
+    pipewire: Option<PathBuf>,
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+impl AudioSockets {
+    fn detect() -> Self {
+        let runtime_dir = host_runtime_dir();
+        let existing = |path: PathBuf| path.exists().then_some(path);
+        Self {
+            pulse: existing(runtime_dir.join("pulse").join("native")),
+            pulse_cookie: dirs::config_dir()
+                .map(|dir| dir.join("pulse").join("cookie"))
+                .and_then(existing),
+            pipewire: existing(runtime_dir.join("pipewire-0")),
+        }
+    }
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Tag for the image built from `dockerfile`, identical Dockerfiles get the
+/// same tag
+fn image_tag_for(dockerfile: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let hash = format!("{:x}", Sha256::digest(dockerfile.as_bytes()));
+    format!("ghostforge-image:{}", &hash[..16])
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// Major device number the kernel gave a driver with a dynamic one, as
+/// listed in /proc/devices
+fn device_major(driver: &str) -> Option<u32> {
+    let devices = std::fs::read_to_string("/proc/devices").ok()?;
+    devices.lines().find_map(|line| {
+        let (major, name) = line.trim().split_once(' ')?;
+        if name.trim() != driver {
+            return None;
+        }
+        major.parse().ok()
+    })
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+/// /dev/nvidia0.., nvidiactl, nvidia-modeset, nvidia-uvm and nvidia-caps
+/// when the proprietary driver is loaded. Nouveau only uses /dev/dri.
+fn nvidia_device_nodes() -> Vec<PathBuf> {
+    if !std::path::Path::new(NVIDIA_CONTROL_DEVICE).exists() {
+        return Vec::new();
+    }
+    let Ok(entries) = std::fs::read_dir("/dev") else {
+        return Vec::new();
+    };
+    let mut nodes: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("nvidia"))
+        .map(|entry| entry.path())
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+#[cfg(any(feature = "container-podman", feature = "container-docker"))]
+fn uses_nvidia_devices(mounts: &[MountPoint]) -> bool {
+    mounts
+        .iter()
+        .any(|mount| mount.container_path == std::path::Path::new(NVIDIA_CONTROL_DEVICE))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuntimeInfo {
     pub current_runtime: String,
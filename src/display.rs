@@ -3,6 +3,7 @@
 //! This module provides comprehensive display management for gaming on Linux,
 //! including Wayland support, G-Sync/FreeSync VRR, and performance optimization.
 
+use crate::utils::out;
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +19,82 @@ pub struct DisplayManager {
     current_profile: Option<String>,
     profiles: HashMap<String, DisplayProfile>,
     wayland_session: bool,
+    #[serde(default)]
+    backend: DisplayBackend,
+}
+
+/// Tool the displays were detected with, and that changes are applied through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DisplayBackend {
+    Xrandr,
+    WlrRandr,
+    KScreen,
+    /// Read-only detection, e.g. XWayland or sysfs on GNOME
+    #[default]
+    Unsupported,
+}
+
+/// Displays that were reconfigured and the changes that were refused
+#[derive(Debug, Clone, Default)]
+pub struct DisplayChangeReport {
+    pub changed: Vec<String>,
+    pub refused: Vec<String>,
+}
+
+impl DisplayChangeReport {
+    pub fn print(&self) {
+        for change in &self.changed {
+            out!("  ✅ {}", change);
+        }
+        for refusal in &self.refused {
+            out!("  ⚠️  {}", refusal);
+        }
+    }
+}
+
+/// Display settings from before `optimize_for_gaming`, kept on disk until
+/// they are restored
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedDisplaySettings {
+    displays: Vec<SavedDisplay>,
+    /// NVIDIA's X11 SyncToVBlank setting
+    sync_to_vblank: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedDisplay {
+    id: String,
+    resolution: Resolution,
+    refresh_rate: u32,
+    vrr_enabled: bool,
+    #[serde(default)]
+    hdr_enabled: bool,
+}
+
+impl SavedDisplaySettings {
+    fn path() -> Result<PathBuf> {
+        Ok(dirs::data_dir()
+            .ok_or_else(|| anyhow!("Cannot find data directory"))?
+            .join("ghostforge")
+            .join("display-state.json"))
+    }
+
+    fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,26 +189,117 @@ pub enum VsyncMode {
     Enhanced,
 }
 
+/// Whether this process runs in a Wayland session
+pub fn wayland_session_active() -> bool {
+    std::env::var("WAYLAND_DISPLAY").is_ok()
+        || std::env::var("XDG_SESSION_TYPE")
+            .map(|s| s == "wayland")
+            .unwrap_or(false)
+}
+
 impl DisplayManager {
     pub fn new() -> Result<Self> {
-        let wayland_session = std::env::var("WAYLAND_DISPLAY").is_ok()
-            || std::env::var("XDG_SESSION_TYPE")
-                .map(|s| s == "wayland")
-                .unwrap_or(false);
+        let wayland_session = wayland_session_active();
 
         let mut manager = Self {
             displays: Vec::new(),
             current_profile: None,
             profiles: HashMap::new(),
             wayland_session,
+            backend: DisplayBackend::Unsupported,
         };
 
         manager.detect_displays()?;
         manager.create_default_profiles();
+        manager.load_saved_profiles();
 
         Ok(manager)
     }
 
+    /// Where custom display profiles are saved, one JSON file per profile
+    pub fn profile_dir() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
+            .ok_or_else(|| anyhow!("Cannot find config directory"))?
+            .join("ghostforge")
+            .join("display-profiles"))
+    }
+
+    /// Load saved profiles, which replace built-in ones of the same name
+    fn load_saved_profiles(&mut self) {
+        let Ok(entries) = Self::profile_dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            if entry.path().extension().is_some_and(|ext| ext == "json")
+                && let Ok(content) = std::fs::read_to_string(entry.path())
+                && let Ok(profile) = serde_json::from_str::<DisplayProfile>(&content)
+            {
+                self.profiles.insert(profile.name.clone(), profile);
+            }
+        }
+    }
+
+    /// Save the current layout, refresh rates, VRR and HDR state of the
+    /// connected displays as a profile
+    pub fn save_profile(
+        &mut self,
+        name: &str,
+        description: Option<String>,
+        gaming_optimized: bool,
+    ) -> Result<PathBuf> {
+        // The name becomes the file name under the profiles directory
+        if name.trim().is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(anyhow!("'{}' is not a valid display profile name", name));
+        }
+
+        let displays: HashMap<String, DisplayConfig> = self
+            .displays
+            .iter()
+            .filter(|d| d.connected)
+            .map(|d| {
+                (
+                    d.id.clone(),
+                    DisplayConfig {
+                        resolution: d.resolution.clone(),
+                        refresh_rate: d.current_refresh_rate,
+                        vrr_enabled: d.vrr_enabled,
+                        hdr_enabled: d.hdr_enabled,
+                        position: d.position.clone(),
+                        rotation: d.rotation.clone(),
+                        scaling: d.scaling,
+                        primary: d.primary,
+                    },
+                )
+            })
+            .collect();
+        if displays.is_empty() {
+            return Err(anyhow!("No connected displays to save"));
+        }
+
+        let vrr_enabled = displays.values().any(|d| d.vrr_enabled);
+        let profile = DisplayProfile {
+            name: name.to_string(),
+            description: description.unwrap_or_else(|| "Custom display profile".to_string()),
+            displays,
+            gaming_optimized,
+            vrr_mode: if vrr_enabled {
+                VrrMode::Auto
+            } else {
+                VrrMode::Disabled
+            },
+            latency_reduction: gaming_optimized,
+        };
+
+        let dir = Self::profile_dir()?;
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.json", name));
+        std::fs::write(&path, serde_json::to_string_pretty(&profile)?)?;
+
+        self.profiles.insert(profile.name.clone(), profile);
+        Ok(path)
+    }
+
     pub fn detect_displays(&mut self) -> Result<()> {
         self.displays.clear();
 
@@ -139,32 +307,201 @@ impl DisplayManager {
             self.detect_wayland_displays()?;
         } else {
             self.detect_x11_displays()?;
+            self.backend = DisplayBackend::Xrandr;
         }
 
         Ok(())
     }
 
-    #[cfg(feature = "wayland-gaming")]
     fn detect_wayland_displays(&mut self) -> Result<()> {
-        use wayland_client::{Connection, Dispatch, EventQueue, QueueHandle};
-        use wayland_protocols::xdg::shell::client::xdg_wm_base;
+        // wlroots compositors and KDE expose their outputs through their own
+        // tools, other compositors mirror outputs to XWayland for xrandr
+        if self.detect_wlr_displays() {
+            self.backend = DisplayBackend::WlrRandr;
+            return Ok(());
+        }
+        if self.detect_kscreen_displays() {
+            self.backend = DisplayBackend::KScreen;
+            return Ok(());
+        }
+
+        // XWayland outputs can be listed but not reconfigured
+        self.backend = DisplayBackend::Unsupported;
+        if self.detect_x11_displays().is_ok() && !self.displays.is_empty() {
+            return Ok(());
+        }
 
-        // Connect to Wayland compositor
-        let connection = Connection::connect_to_env()
-            .map_err(|e| anyhow!("Failed to connect to Wayland: {}", e))?;
+        self.displays.clear();
+        self.detect_displays_fallback()
+    }
 
-        // Get display information through Wayland protocols
-        // This is a simplified implementation - full implementation would use
-        // wayland-protocols to get detailed display information
+    /// Query outputs through the wlr-output-management protocol using
+    /// `wlr-randr` (Sway, Hyprland, river, Wayfire...)
+    fn detect_wlr_displays(&mut self) -> bool {
+        let Ok(output) = Command::new("wlr-randr").arg("--json").output() else {
+            return false;
+        };
+        if !output.status.success() {
+            return false;
+        }
+        let Ok(serde_json::Value::Array(outputs)) = serde_json::from_slice(&output.stdout) else {
+            return false;
+        };
 
-        self.detect_displays_fallback()?;
-        Ok(())
+        for output in &outputs {
+            let Some(name) = output["name"].as_str() else {
+                continue;
+            };
+            let modes = output["modes"].as_array().cloned().unwrap_or_default();
+            let current = modes.iter().find(|m| m["current"].as_bool() == Some(true));
+
+            let mut refresh_rates: Vec<u32> = modes
+                .iter()
+                .filter_map(|m| m["refresh"].as_f64())
+                .map(|rate| rate.round() as u32)
+                .collect();
+            refresh_rates.sort_unstable_by(|a, b| b.cmp(a));
+            refresh_rates.dedup();
+
+            let adaptive_sync = output["adaptive_sync"].as_bool().unwrap_or(false);
+            let text = |key: &str| {
+                output[key]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or("Unknown")
+                    .to_string()
+            };
+
+            self.displays.push(Display {
+                id: name.to_string(),
+                name: output["description"].as_str().unwrap_or(name).to_string(),
+                connector: name.to_string(),
+                resolution: Resolution {
+                    width: current.and_then(|m| m["width"].as_u64()).unwrap_or(0) as u32,
+                    height: current.and_then(|m| m["height"].as_u64()).unwrap_or(0) as u32,
+                },
+                refresh_rates,
+                current_refresh_rate: current
+                    .and_then(|m| m["refresh"].as_f64())
+                    .map_or(60, |rate| rate.round() as u32),
+                vrr_capable: adaptive_sync || self.check_vrr_capability(name).unwrap_or(false),
+                vrr_enabled: adaptive_sync,
+                hdr_capable: self.detect_hdr_capability(name),
+                hdr_enabled: false,
+                connected: output["enabled"].as_bool().unwrap_or(true),
+                primary: false,
+                position: Position {
+                    x: output["position"]["x"].as_i64().unwrap_or(0) as i32,
+                    y: output["position"]["y"].as_i64().unwrap_or(0) as i32,
+                },
+                rotation: match output["transform"].as_str() {
+                    Some("90") => Rotation::Left,
+                    Some("180") => Rotation::Inverted,
+                    Some("270") => Rotation::Right,
+                    _ => Rotation::Normal,
+                },
+                scaling: output["scale"].as_f64().unwrap_or(1.0) as f32,
+                color_depth: 24,
+                manufacturer: text("make"),
+                model: text("model"),
+            });
+        }
+
+        // wlroots has no notion of a primary output, use the one at the origin
+        if let Some(display) = self
+            .displays
+            .iter_mut()
+            .find(|d| d.position.x == 0 && d.position.y == 0)
+        {
+            display.primary = true;
+        }
+
+        !self.displays.is_empty()
     }
 
-    #[cfg(not(feature = "wayland-gaming"))]
-    fn detect_wayland_displays(&mut self) -> Result<()> {
-        // Fallback for when wayland features aren't compiled
-        self.detect_displays_fallback()
+    /// Query outputs from KWin through KScreen using `kscreen-doctor`
+    fn detect_kscreen_displays(&mut self) -> bool {
+        // KScreen::Output capability flags
+        const CAPABILITY_VRR: u64 = 0x2;
+        const CAPABILITY_HDR: u64 = 0x8;
+
+        let Ok(output) = Command::new("kscreen-doctor").arg("--json").output() else {
+            return false;
+        };
+        if !output.status.success() {
+            return false;
+        }
+        let Ok(config) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+            return false;
+        };
+        let Some(outputs) = config["outputs"].as_array() else {
+            return false;
+        };
+
+        for output in outputs {
+            if output["connected"].as_bool() != Some(true) {
+                continue;
+            }
+            let Some(name) = output["name"].as_str() else {
+                continue;
+            };
+
+            let modes = output["modes"].as_array().cloned().unwrap_or_default();
+            let current_id = &output["currentModeId"];
+            let current = modes.iter().find(|m| &m["id"] == current_id);
+
+            let mut refresh_rates: Vec<u32> = modes
+                .iter()
+                .filter_map(|m| m["refreshRate"].as_f64())
+                .map(|rate| rate.round() as u32)
+                .collect();
+            refresh_rates.sort_unstable_by(|a, b| b.cmp(a));
+            refresh_rates.dedup();
+
+            let capabilities = output["capabilities"].as_u64().unwrap_or(0);
+            let size = current.map(|m| &m["size"]).unwrap_or(&output["size"]);
+
+            self.displays.push(Display {
+                id: name.to_string(),
+                name: name.to_string(),
+                connector: name.to_string(),
+                resolution: Resolution {
+                    width: size["width"].as_u64().unwrap_or(0) as u32,
+                    height: size["height"].as_u64().unwrap_or(0) as u32,
+                },
+                refresh_rates,
+                current_refresh_rate: current
+                    .and_then(|m| m["refreshRate"].as_f64())
+                    .map_or(60, |rate| rate.round() as u32),
+                vrr_capable: capabilities & CAPABILITY_VRR != 0,
+                // 0 is "never", 1 "always" and 2 "automatic" (fullscreen games)
+                vrr_enabled: output["vrrPolicy"]
+                    .as_u64()
+                    .is_some_and(|policy| policy != 0),
+                hdr_capable: capabilities & CAPABILITY_HDR != 0,
+                hdr_enabled: output["hdr"].as_bool().unwrap_or(false),
+                connected: true,
+                // Plasma 5 has a primary flag, Plasma 6 orders outputs by priority
+                primary: output["primary"].as_bool().unwrap_or(false)
+                    || output["priority"].as_u64() == Some(1),
+                position: Position {
+                    x: output["pos"]["x"].as_i64().unwrap_or(0) as i32,
+                    y: output["pos"]["y"].as_i64().unwrap_or(0) as i32,
+                },
+                rotation: match output["rotation"].as_u64() {
+                    Some(2) => Rotation::Left,
+                    Some(4) => Rotation::Inverted,
+                    Some(8) => Rotation::Right,
+                    _ => Rotation::Normal,
+                },
+                scaling: output["scale"].as_f64().unwrap_or(1.0) as f32,
+                color_depth: 24,
+                manufacturer: "Unknown".to_string(),
+                model: "Unknown".to_string(),
+            });
+        }
+
+        !self.displays.is_empty()
     }
 
     fn detect_x11_displays(&mut self) -> Result<()> {
@@ -189,29 +526,75 @@ impl DisplayManager {
 
         #[cfg(not(feature = "display-management"))]
         {
-            // Create a mock display for development
+            self.detect_sysfs_displays()?;
+        }
+
+        Ok(())
+    }
+
+    /// List connected connectors from `/sys/class/drm`. The kernel only
+    /// exposes mode sizes there, so the refresh rate is left at 60Hz.
+    #[cfg(not(feature = "display-management"))]
+    fn detect_sysfs_displays(&mut self) -> Result<()> {
+        let mut connectors: Vec<_> = std::fs::read_dir("/sys/class/drm")?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                std::fs::read_to_string(path.join("status"))
+                    .is_ok_and(|status| status.trim() == "connected")
+            })
+            .collect();
+        connectors.sort();
+
+        for path in connectors {
+            // card0-DP-1 -> DP-1
+            let Some(name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.split_once('-'))
+                .map(|(_, connector)| connector.to_string())
+            else {
+                continue;
+            };
+
+            // The preferred mode is listed first
+            let resolution = std::fs::read_to_string(path.join("modes"))
+                .ok()
+                .and_then(|modes| {
+                    let (width, height) = modes.lines().next()?.split_once('x')?;
+                    Some(Resolution {
+                        width: width.parse().ok()?,
+                        height: height.trim_end_matches('i').parse().ok()?,
+                    })
+                })
+                .unwrap_or(Resolution {
+                    width: 0,
+                    height: 0,
+                });
+            let (manufacturer, model) = std::fs::read(path.join("edid"))
+                .ok()
+                .and_then(|edid| parse_edid_identity(&edid))
+                .unwrap_or_else(|| ("Unknown".to_string(), "Unknown".to_string()));
+
             self.displays.push(Display {
-                id: "eDP-1".to_string(),
-                name: "Built-in Display".to_string(),
-                connector: "eDP".to_string(),
-                resolution: Resolution {
-                    width: 1920,
-                    height: 1080,
-                },
-                refresh_rates: vec![60, 120, 144],
+                id: name.clone(),
+                name: name.clone(),
+                connector: name,
+                resolution,
+                refresh_rates: Vec::new(),
                 current_refresh_rate: 60,
-                vrr_capable: true,
+                vrr_capable: false,
                 vrr_enabled: false,
                 hdr_capable: false,
                 hdr_enabled: false,
                 connected: true,
-                primary: true,
+                primary: self.displays.is_empty(),
                 position: Position { x: 0, y: 0 },
                 rotation: Rotation::Normal,
                 scaling: 1.0,
                 color_depth: 24,
-                manufacturer: "Unknown".to_string(),
-                model: "Generic".to_string(),
+                manufacturer,
+                model,
             });
         }
 
@@ -537,9 +920,9 @@ impl DisplayManager {
         }
 
         self.profiles.insert(
-            "Gaming".to_string(),
+            "gaming".to_string(),
             DisplayProfile {
-                name: "Gaming".to_string(),
+                name: "gaming".to_string(),
                 description: "Optimized for gaming with VRR enabled".to_string(),
                 displays: gaming_displays,
                 gaming_optimized: true,
@@ -548,14 +931,14 @@ impl DisplayManager {
             },
         );
 
-        // Productivity profile
-        let mut productivity_displays = HashMap::new();
+        // Desktop profile
+        let mut desktop_displays = HashMap::new();
         for display in &self.displays {
-            productivity_displays.insert(
+            desktop_displays.insert(
                 display.id.clone(),
                 DisplayConfig {
                     resolution: display.resolution.clone(),
-                    refresh_rate: 60, // Standard refresh rate for productivity
+                    refresh_rate: 60, // Standard refresh rate for desktop work
                     vrr_enabled: false,
                     hdr_enabled: false,
                     position: display.position.clone(),
@@ -567,11 +950,11 @@ impl DisplayManager {
         }
 
         self.profiles.insert(
-            "Productivity".to_string(),
+            "desktop".to_string(),
             DisplayProfile {
-                name: "Productivity".to_string(),
-                description: "Standard settings for productivity work".to_string(),
-                displays: productivity_displays,
+                name: "desktop".to_string(),
+                description: "Standard settings for desktop work".to_string(),
+                displays: desktop_displays,
                 gaming_optimized: false,
                 vrr_mode: VrrMode::Disabled,
                 latency_reduction: false,
@@ -583,6 +966,14 @@ impl DisplayManager {
         &self.displays
     }
 
+    /// The primary display, or the first connected one when none is marked
+    pub fn primary_display(&self) -> Option<&Display> {
+        self.displays
+            .iter()
+            .find(|d| d.connected && d.primary)
+            .or_else(|| self.displays.iter().find(|d| d.connected))
+    }
+
     pub fn get_profiles(&self) -> &HashMap<String, DisplayProfile> {
         &self.profiles
     }
@@ -617,34 +1008,21 @@ impl DisplayManager {
     }
 
     fn configure_wayland_display(&self, display_id: &str, config: &DisplayConfig) -> Result<()> {
-        // Configure individual display on Wayland
-        // This is compositor-specific - different for GNOME/KDE/wlroots
+        self.apply_mode(display_id, &config.resolution, config.refresh_rate)?;
 
-        // For wlroots-based compositors (sway, etc.)
-        if let Ok(_) = Command::new("swaymsg")
-            .args(&[
-                "output",
-                display_id,
-                "resolution",
-                &format!("{}x{}", config.resolution.width, config.resolution.height),
-                "rate",
-                &format!("{}Hz", config.refresh_rate),
-                "position",
-                &format!("{},{}", config.position.x, config.position.y),
-            ])
-            .status()
-        {
-            println!("Applied Sway configuration for {}", display_id);
-        }
-
-        // Configure VRR if supported
-        if config.vrr_enabled {
-            self.enable_vrr_wayland(display_id)?;
-        } else {
-            self.disable_vrr_wayland(display_id)?;
+        let position = format!("{},{}", config.position.x, config.position.y);
+        match self.backend {
+            DisplayBackend::WlrRandr => {
+                run_display_tool("wlr-randr", &["--output", display_id, "--pos", &position])?
+            }
+            DisplayBackend::KScreen => run_display_tool(
+                "kscreen-doctor",
+                &[&format!("output.{}.position.{}", display_id, position)],
+            )?,
+            _ => {}
         }
 
-        Ok(())
+        self.apply_vrr(display_id, config.vrr_enabled)
     }
 
     fn apply_x11_profile(&self, profile: &DisplayProfile) -> Result<()> {
@@ -704,18 +1082,6 @@ impl DisplayManager {
         Ok(())
     }
 
-    fn enable_vrr_wayland(&self, display_id: &str) -> Result<()> {
-        // Enable VRR on Wayland (compositor-specific)
-        println!("Enabling VRR for {} on Wayland", display_id);
-        Ok(())
-    }
-
-    fn disable_vrr_wayland(&self, display_id: &str) -> Result<()> {
-        // Disable VRR on Wayland
-        println!("Disabling VRR for {} on Wayland", display_id);
-        Ok(())
-    }
-
     fn enable_vrr_x11(&self, display_id: &str) -> Result<()> {
         // Try NVIDIA method first
         if let Ok(_) = Command::new("nvidia-settings")
@@ -757,37 +1123,309 @@ impl DisplayManager {
         Ok(())
     }
 
-    pub fn optimize_for_gaming(&mut self, target_fps: u32) -> Result<()> {
-        println!("Optimizing displays for gaming (target: {}fps)", target_fps);
+    /// Switch connected displays to their best gaming refresh rate, enable
+    /// VRR where the panel supports it and apply the VSync mode. The previous
+    /// settings are saved for `restore_display_settings`.
+    pub fn optimize_for_gaming(
+        &mut self,
+        settings: &GamingDisplaySettings,
+    ) -> Result<DisplayChangeReport> {
+        println!(
+            "Optimizing displays for gaming (target: {}fps)",
+            settings.target_fps
+        );
+
+        // Keep the settings from before the first optimization
+        if SavedDisplaySettings::load()?.is_none() {
+            SavedDisplaySettings {
+                displays: self
+                    .displays
+                    .iter()
+                    .filter(|d| d.connected)
+                    .map(|d| SavedDisplay {
+                        id: d.id.clone(),
+                        resolution: d.resolution.clone(),
+                        refresh_rate: d.current_refresh_rate,
+                        vrr_enabled: d.vrr_enabled,
+                        hdr_enabled: d.hdr_enabled,
+                    })
+                    .collect(),
+                sync_to_vblank: query_sync_to_vblank(),
+            }
+            .save()?;
+        }
 
-        // Collect display info to avoid borrowing issues
-        let display_updates: Vec<(usize, u32)> = self
+        let mut report = DisplayChangeReport::default();
+        let displays: Vec<Display> = self
             .displays
             .iter()
-            .enumerate()
-            .filter(|(_, display)| display.connected)
-            .map(|(i, display)| {
-                let best_rate = self.find_optimal_refresh_rate(&display.refresh_rates, target_fps);
-                (i, best_rate)
-            })
+            .filter(|d| d.connected)
+            .cloned()
             .collect();
 
-        // Apply updates
-        for (i, best_rate) in display_updates {
-            let display = &mut self.displays[i];
-            display.current_refresh_rate = best_rate;
+        for display in displays {
+            // With VRR the panel follows the game, so run it as fast as it
+            // goes. Without it, match the refresh rate to the frame target.
+            let rate = if display.vrr_capable {
+                display.refresh_rates.iter().copied().max()
+            } else {
+                Some(self.find_optimal_refresh_rate(&display.refresh_rates, settings.target_fps))
+            }
+            .unwrap_or(display.current_refresh_rate);
+
+            if rate != display.current_refresh_rate {
+                match self.apply_mode(&display.id, &display.resolution, rate) {
+                    Ok(()) => {
+                        report.changed.push(format!(
+                            "{}: {}Hz -> {}Hz",
+                            display.id, display.current_refresh_rate, rate
+                        ));
+                        self.update_display(&display.id, |d| d.current_refresh_rate = rate);
+                    }
+                    Err(e) => report
+                        .refused
+                        .push(format!("{}: refresh rate {}Hz: {}", display.id, rate, e)),
+                }
+            }
+
+            if display.vrr_capable && !display.vrr_enabled {
+                match self.apply_vrr(&display.id, true) {
+                    Ok(()) => {
+                        report.changed.push(format!("{}: VRR enabled", display.id));
+                        self.update_display(&display.id, |d| d.vrr_enabled = true);
+                    }
+                    Err(e) => report.refused.push(format!("{}: VRR: {}", display.id, e)),
+                }
+            }
+
+            if settings.hdr_gaming && display.hdr_capable && !display.hdr_enabled {
+                match self.set_hdr(&display.id, true) {
+                    Ok(()) => report.changed.push(format!("{}: HDR enabled", display.id)),
+                    Err(e) => report.refused.push(format!("{}: HDR: {}", display.id, e)),
+                }
+            }
+        }
+
+        match apply_vsync_mode(&settings.vsync_mode) {
+            Ok(()) => report
+                .changed
+                .push(format!("VSync: {:?}", settings.vsync_mode)),
+            Err(e) => report.refused.push(format!("VSync: {}", e)),
+        }
+
+        Ok(report)
+    }
+
+    /// Put back the display settings saved by `optimize_for_gaming`
+    pub fn restore_display_settings(&mut self) -> Result<DisplayChangeReport> {
+        let saved = SavedDisplaySettings::load()?
+            .ok_or_else(|| anyhow!("No saved display settings to restore"))?;
+        let mut report = DisplayChangeReport::default();
+
+        for display in &saved.displays {
+            match self.apply_mode(&display.id, &display.resolution, display.refresh_rate) {
+                Ok(()) => {
+                    report
+                        .changed
+                        .push(format!("{}: {}Hz", display.id, display.refresh_rate));
+                    self.update_display(&display.id, |d| {
+                        d.current_refresh_rate = display.refresh_rate
+                    });
+                }
+                Err(e) => report.refused.push(format!("{}: {}", display.id, e)),
+            }
+
+            match self.apply_vrr(&display.id, display.vrr_enabled) {
+                Ok(()) => self.update_display(&display.id, |d| d.vrr_enabled = display.vrr_enabled),
+                Err(e) => report.refused.push(format!("{}: VRR: {}", display.id, e)),
+            }
+
+            let hdr_changed = self
+                .displays
+                .iter()
+                .any(|d| d.id == display.id && d.hdr_enabled != display.hdr_enabled);
+            if hdr_changed
+                && let Err(e) = self.set_hdr(&display.id, display.hdr_enabled)
+            {
+                report.refused.push(format!("{}: HDR: {}", display.id, e));
+            }
+        }
+
+        if let Some(enabled) = saved.sync_to_vblank
+            && let Err(e) = set_sync_to_vblank(enabled)
+        {
+            report.refused.push(format!("VSync: {}", e));
+        }
+
+        // Keep the saved settings around so a failed restore can be retried
+        if report.refused.is_empty() {
+            std::fs::remove_file(SavedDisplaySettings::path()?)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Switch a connector to one of its refresh rates at the current resolution
+    pub fn set_refresh_rate(&mut self, connector: &str, rate: u32) -> Result<()> {
+        let display = self.find_display(connector)?;
+        if !display.refresh_rates.is_empty() && !display.refresh_rates.contains(&rate) {
+            let rates: Vec<String> = display.refresh_rates.iter().map(u32::to_string).collect();
+            return Err(anyhow!(
+                "{} doesn't support {}Hz (available: {})",
+                connector,
+                rate,
+                rates.join(", ")
+            ));
+        }
+        let display_id = display.id.clone();
+        let resolution = display.resolution.clone();
 
-            // Enable VRR if capable
-            if display.vrr_capable {
-                display.vrr_enabled = true;
+        self.apply_mode(&display_id, &resolution, rate)?;
+        self.update_display(&display_id, |d| d.current_refresh_rate = rate);
+        Ok(())
+    }
+
+    /// Turn variable refresh rate on or off for a connector
+    pub fn set_vrr(&mut self, connector: &str, enabled: bool) -> Result<()> {
+        let display = self.find_display(connector)?;
+        if enabled && !display.vrr_capable {
+            return Err(anyhow!("{} doesn't support VRR", connector));
+        }
+        let display_id = display.id.clone();
+
+        self.apply_vrr(&display_id, enabled)?;
+        self.update_display(&display_id, |d| d.vrr_enabled = enabled);
+        Ok(())
+    }
+
+    /// Switch HDR output on a connector through the compositor
+    pub fn set_hdr(&mut self, connector: &str, enabled: bool) -> Result<()> {
+        let display = self.find_display(connector)?;
+        if enabled && !display.hdr_capable {
+            return Err(anyhow!("{} doesn't support HDR", connector));
+        }
+        let display_id = display.id.clone();
+
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+        if std::env::var("GAMESCOPE_WAYLAND_DISPLAY").is_ok() || desktop == "gamescope" {
+            return Err(anyhow!(
+                "gamescope sets HDR when it starts, launch it with --hdr-enabled"
+            ));
+        }
+
+        if self.backend == DisplayBackend::KScreen {
+            run_display_tool(
+                "kscreen-doctor",
+                &[&format!(
+                    "output.{}.hdr.{}",
+                    display_id,
+                    if enabled { "enable" } else { "disable" }
+                )],
+            )?;
+        } else if desktop.contains("GNOME") && which::which("gdctl").is_ok() {
+            // gdctl replaces the whole monitor layout, so only a lone
+            // monitor can be switched without losing the arrangement
+            if self.displays.iter().filter(|d| d.connected).count() > 1 {
+                return Err(anyhow!(
+                    "GNOME can only switch HDR with several monitors in Settings > Displays"
+                ));
             }
+            run_display_tool(
+                "gdctl",
+                &[
+                    "set",
+                    "--logical-monitor",
+                    "--primary",
+                    "--monitor",
+                    &display_id,
+                    "--color-mode",
+                    if enabled { "bt2100" } else { "default" },
+                ],
+            )?;
+        } else {
+            return Err(anyhow!(
+                "HDR can only be switched on KDE Plasma 6, GNOME 48+ or in gamescope"
+            ));
         }
 
-        // Apply gaming profile
-        self.apply_profile("Gaming")?;
+        self.update_display(&display_id, |d| d.hdr_enabled = enabled);
         Ok(())
     }
 
+    fn find_display(&self, connector: &str) -> Result<&Display> {
+        self.displays
+            .iter()
+            .find(|d| d.id == connector || d.connector == connector)
+            .ok_or_else(|| anyhow!("Display '{}' not found", connector))
+    }
+
+    fn update_display(&mut self, display_id: &str, update: impl FnOnce(&mut Display)) {
+        if let Some(display) = self.displays.iter_mut().find(|d| d.id == display_id) {
+            update(display);
+        }
+    }
+
+    fn apply_mode(&self, display_id: &str, resolution: &Resolution, rate: u32) -> Result<()> {
+        let mode = format!("{}x{}", resolution.width, resolution.height);
+        match self.backend {
+            DisplayBackend::Xrandr => run_display_tool(
+                "xrandr",
+                &[
+                    "--output",
+                    display_id,
+                    "--mode",
+                    &mode,
+                    "--rate",
+                    &rate.to_string(),
+                ],
+            ),
+            DisplayBackend::WlrRandr => run_display_tool(
+                "wlr-randr",
+                &[
+                    "--output",
+                    display_id,
+                    "--mode",
+                    &format!("{}@{}Hz", mode, rate),
+                ],
+            ),
+            DisplayBackend::KScreen => run_display_tool(
+                "kscreen-doctor",
+                &[&format!("output.{}.mode.{}@{}", display_id, mode, rate)],
+            ),
+            DisplayBackend::Unsupported => Err(anyhow!(
+                "the compositor doesn't allow changing display modes"
+            )),
+        }
+    }
+
+    fn apply_vrr(&self, display_id: &str, enabled: bool) -> Result<()> {
+        match self.backend {
+            DisplayBackend::Xrandr if enabled => self.enable_vrr_x11(display_id),
+            DisplayBackend::Xrandr => self.disable_vrr_x11(display_id),
+            DisplayBackend::WlrRandr => run_display_tool(
+                "wlr-randr",
+                &[
+                    "--output",
+                    display_id,
+                    "--adaptive-sync",
+                    if enabled { "enabled" } else { "disabled" },
+                ],
+            ),
+            // "automatic" limits VRR to fullscreen games
+            DisplayBackend::KScreen => run_display_tool(
+                "kscreen-doctor",
+                &[&format!(
+                    "output.{}.vrrpolicy.{}",
+                    display_id,
+                    if enabled { "automatic" } else { "never" }
+                )],
+            ),
+            DisplayBackend::Unsupported => {
+                Err(anyhow!("the compositor doesn't allow changing VRR"))
+            }
+        }
+    }
+
     fn find_optimal_refresh_rate(&self, available_rates: &[u32], target_fps: u32) -> u32 {
         // Find the lowest refresh rate that's still above the target FPS
         let mut best_rate = available_rates.first().copied().unwrap_or(60);
@@ -820,6 +1458,10 @@ impl DisplayManager {
         self.wayland_session
     }
 
+    pub fn backend(&self) -> DisplayBackend {
+        self.backend
+    }
+
     /// Detect if display supports HDR (High Dynamic Range)
     fn detect_hdr_capability(&self, display_id: &str) -> bool {
         // Check for HDR capability via DRM properties
@@ -961,6 +1603,90 @@ impl Default for DisplayManager {
             current_profile: None,
             profiles: HashMap::new(),
             wayland_session: false,
+            backend: DisplayBackend::Unsupported,
         })
     }
 }
+
+/// Manufacturer PNP ID and monitor name from a base EDID block
+#[cfg(not(feature = "display-management"))]
+fn parse_edid_identity(edid: &[u8]) -> Option<(String, String)> {
+    if edid.len() < 128 {
+        return None;
+    }
+
+    // Three 5-bit letters, 'A' being 1
+    let id = u16::from_be_bytes([edid[8], edid[9]]);
+    let manufacturer: String = [10, 5, 0]
+        .iter()
+        .map(|shift| (b'A' - 1 + ((id >> shift) & 0x1f) as u8) as char)
+        .collect();
+
+    // Display descriptors, 0xFC holds the monitor name
+    let model = [54, 72, 90, 108]
+        .iter()
+        .map(|&offset| &edid[offset..offset + 18])
+        .find(|d| d[..3] == [0, 0, 0] && d[3] == 0xfc)
+        .map(|d| String::from_utf8_lossy(&d[5..]).trim().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    Some((manufacturer, model))
+}
+
+fn run_display_tool(program: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// NVIDIA's global X11 VSync setting, `None` on other drivers and Wayland
+fn query_sync_to_vblank() -> Option<bool> {
+    let output = Command::new("nvidia-settings")
+        .args(["-t", "-q", "SyncToVBlank"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
+fn set_sync_to_vblank(enabled: bool) -> Result<()> {
+    run_display_tool(
+        "nvidia-settings",
+        &[
+            "-a",
+            &format!("SyncToVBlank={}", if enabled { 1 } else { 0 }),
+        ],
+    )
+}
+
+/// Only NVIDIA's X11 driver has a system-wide VSync switch, elsewhere it is
+/// up to the game or compositor. Adaptive, fast and enhanced sync all keep
+/// VSync on and rely on VRR to avoid the added latency.
+fn apply_vsync_mode(mode: &VsyncMode) -> Result<()> {
+    if query_sync_to_vblank().is_none() {
+        return Err(anyhow!(
+            "no system-wide VSync control on this driver, set it in-game"
+        ));
+    }
+    set_sync_to_vblank(*mode != VsyncMode::Off)
+}
+
+/// Environment that makes DXVK, VKD3D-Proton and Vulkan games output HDR.
+/// The display itself has to be in HDR mode as well.
+pub fn hdr_environment() -> Vec<(String, String)> {
+    ["DXVK_HDR", "ENABLE_HDR_WSI", "PROTON_ENABLE_HDR"]
+        .iter()
+        .map(|key| (key.to_string(), "1".to_string()))
+        .collect()
+}
@@ -0,0 +1,327 @@
+use crate::config::Config;
+use crate::utils::{GpuVendor, SystemInfo, out};
+use colored::*;
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    /// Critical, `forge doctor` exits non-zero
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn warn(name: &str, message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            message: message.into(),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+
+    fn fail(name: &str, message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            message: message.into(),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+}
+
+/// Health report for `forge doctor`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Run every check: GPU drivers, Vulkan, Wine, gaming tools, the
+    /// container runtime and GhostForge's own directories
+    pub async fn run(config: &Config) -> Self {
+        let mut checks = Vec::new();
+
+        match crate::utils::SystemDetector::get_system_info() {
+            Ok(info) => checks.extend(system_checks(&info)),
+            Err(e) => checks.push(DoctorCheck::fail(
+                "System",
+                format!("Failed to gather system information: {}", e),
+                "Make sure lspci and uname are installed",
+            )),
+        }
+        checks.push(config_check(config));
+        checks.push(default_wine_check(config));
+        checks.extend(container_checks().await);
+        checks.extend(directory_checks(config));
+
+        Self { checks }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.count(CheckStatus::Fail)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn print(&self) {
+        for check in &self.checks {
+            let status = match check.status {
+                CheckStatus::Pass => "✅ PASS".green(),
+                CheckStatus::Warn => "⚠️  WARN".yellow(),
+                CheckStatus::Fail => "❌ FAIL".red(),
+            };
+            out!("  {} {}: {}", status, check.name.bold(), check.message);
+            if let Some(suggestion) = &check.suggestion {
+                out!("         💡 {}", suggestion.dimmed());
+            }
+        }
+
+        println!(
+            "\n{} passed, {} warnings, {} failed",
+            self.count(CheckStatus::Pass).to_string().green(),
+            self.count(CheckStatus::Warn).to_string().yellow(),
+            self.failures().to_string().red()
+        );
+    }
+}
+
+/// Checks derived from the detected system alone, also used for the
+/// recommendations of `forge info --full`
+pub fn system_checks(info: &SystemInfo) -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+
+    if info.gpu.is_empty() {
+        checks.push(DoctorCheck::fail(
+            "GPU",
+            "No GPU detected",
+            "Install pciutils so GPUs can be detected with lspci",
+        ));
+    }
+    for gpu in &info.gpu {
+        let name = format!("GPU {}", gpu.name);
+        let check = match (&gpu.vendor, gpu.driver.as_deref()) {
+            (GpuVendor::Nvidia, Some("nouveau")) => DoctorCheck::warn(
+                &name,
+                "Using the nouveau driver, which has poor gaming performance",
+                "Install the proprietary NVIDIA driver",
+            ),
+            (GpuVendor::AMD, Some("radeon")) => DoctorCheck::warn(
+                &name,
+                "Using the legacy radeon driver, which has no Vulkan support",
+                "Enable the amdgpu driver (radeon.si_support=0 amdgpu.si_support=1)",
+            ),
+            (_, Some(driver)) => DoctorCheck::pass(&name, format!("Driver {}", driver)),
+            (_, None) => DoctorCheck::warn(
+                &name,
+                "No driver detected",
+                "Install the graphics driver for your GPU",
+            ),
+        };
+        checks.push(check);
+    }
+
+    checks.push(if info.vulkan.available {
+        DoctorCheck::pass(
+            "Vulkan",
+            format!(
+                "Available{}",
+                info.vulkan
+                    .api_version
+                    .as_ref()
+                    .map(|v| format!(" ({})", v))
+                    .unwrap_or_default()
+            ),
+        )
+    } else {
+        DoctorCheck::fail(
+            "Vulkan",
+            "Not available, DXVK and VKD3D-Proton need it",
+            "Install the Vulkan driver for your GPU and vulkan-tools",
+        )
+    });
+
+    checks.push(if !info.wine_support.installed {
+        DoctorCheck::warn(
+            "Wine",
+            "Not installed",
+            "Install Wine to run Windows games outside Steam",
+        )
+    } else if !info.wine_support.multilib_support {
+        DoctorCheck::warn(
+            "Wine",
+            "No 32-bit support",
+            "Enable multilib and install 32-bit Wine libraries for older games",
+        )
+    } else {
+        DoctorCheck::pass(
+            "Wine",
+            info.wine_support
+                .version
+                .clone()
+                .unwrap_or_else(|| "Installed".to_string()),
+        )
+    });
+
+    let tools = &info.gaming_tools;
+    let tool_checks = [
+        (
+            "DXVK",
+            tools.dxvk,
+            "Install DXVK for better DirectX 9-11 performance",
+        ),
+        (
+            "VKD3D-Proton",
+            tools.vkd3d,
+            "Install VKD3D-Proton for DirectX 12 support",
+        ),
+        (
+            "GameMode",
+            tools.gamemode,
+            "Install GameMode for automatic performance optimizations",
+        ),
+        (
+            "MangoHud",
+            tools.mangohud,
+            "Install MangoHud for a performance overlay",
+        ),
+        (
+            "Gamescope",
+            tools.gamescope,
+            "Install Gamescope for upscaling and frame limiting",
+        ),
+        (
+            "Winetricks",
+            tools.winetricks,
+            "Install winetricks to add runtimes to prefixes",
+        ),
+    ];
+    for (name, installed, suggestion) in tool_checks {
+        checks.push(if installed {
+            DoctorCheck::pass(name, "Installed")
+        } else {
+            DoctorCheck::warn(name, "Not installed", suggestion)
+        });
+    }
+
+    checks
+}
+
+fn default_wine_check(config: &Config) -> DoctorCheck {
+    let version = &config.general.default_wine_version;
+    let suggestion =
+        "Install it or pick another with 'forge config set wine.default_version <version>'";
+
+    match crate::prefix::wine_binary(Some(version)) {
+        Ok(path) if path.is_absolute() || which::which(&path).is_ok() => {
+            DoctorCheck::pass("Default Wine", format!("{} ({})", version, path.display()))
+        }
+        Ok(_) => DoctorCheck::fail(
+            "Default Wine",
+            format!("{} is the default but wine is not installed", version),
+            suggestion,
+        ),
+        Err(e) => DoctorCheck::fail("Default Wine", e.to_string(), suggestion),
+    }
+}
+
+async fn container_checks() -> Vec<DoctorCheck> {
+    let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+    let manager = match crate::container::ContainerManager::new(config_dir) {
+        Ok(manager) => manager,
+        Err(e) => {
+            return vec![DoctorCheck::warn(
+                "Container runtime",
+                format!("Detection failed: {}", e),
+                "Install Bolt, Podman or Docker to run games in containers",
+            )];
+        }
+    };
+
+    let info = manager.get_runtime_info();
+    if info.available_runtimes.is_empty() {
+        return vec![DoctorCheck::warn(
+            "Container runtime",
+            "None installed",
+            "Install Bolt, Podman or Docker to run games in containers",
+        )];
+    }
+
+    let diagnostics = manager.diagnose_runtime().await;
+    if diagnostics.issues.is_empty() {
+        vec![DoctorCheck::pass(
+            "Container runtime",
+            format!("{} {}", diagnostics.runtime_type, diagnostics.version),
+        )]
+    } else {
+        vec![DoctorCheck::warn(
+            "Container runtime",
+            diagnostics.issues.join(", "),
+            diagnostics.suggestions.join(", "),
+        )]
+    }
+}
+
+fn config_check(config: &Config) -> DoctorCheck {
+    let problems = config.validate();
+    if problems.is_empty() {
+        DoctorCheck::pass("Config", "No problems found")
+    } else {
+        DoctorCheck::warn(
+            "Config",
+            problems.join("; "),
+            "Fix the listed keys with 'forge config set' or by editing the config file",
+        )
+    }
+}
+
+fn directory_checks(config: &Config) -> Vec<DoctorCheck> {
+    let mut dirs = vec![(
+        "Config directory",
+        dirs::config_dir().unwrap_or_default().join("ghostforge"),
+    )];
+    if let Some(parent) = config.paths.database.parent() {
+        dirs.push(("Data directory", parent.to_path_buf()));
+    }
+    dirs.push(("Cache directory", config.paths.cache.clone()));
+
+    dirs.into_iter()
+        .map(|(name, dir)| match check_writable(&dir) {
+            Ok(()) => DoctorCheck::pass(name, dir.display().to_string()),
+            Err(e) => DoctorCheck::fail(
+                name,
+                format!("{} is not writable: {}", dir.display(), e),
+                "Fix the directory's ownership or permissions",
+            ),
+        })
+        .collect()
+}
+
+fn check_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".ghostforge-doctor");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(probe)
+}
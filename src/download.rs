@@ -0,0 +1,233 @@
+use crate::utils::out;
+use anyhow::Result;
+use futures_util::StreamExt;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use reqwest::StatusCode;
+use reqwest::header::RANGE;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Attempts after the first before a download is given up on
+pub const DEFAULT_RETRIES: u32 = 4;
+
+/// Wine/Proton builds installed at once by `forge wine install`
+pub const DEFAULT_CONCURRENCY: usize = 2;
+
+/// How long to wait for a server to accept a connection
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a response may stall between reads before it is abandoned
+pub const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Attempts after the first for API requests that fail transiently
+pub const REQUEST_RETRIES: u32 = 2;
+
+/// Called with the bytes downloaded so far and the total size when known
+pub type ProgressCallback<'a> = &'a (dyn Fn(u64, Option<u64>) + Send + Sync);
+
+/// HTTP downloader that resumes interrupted transfers and retries with
+/// exponential backoff
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    client: reqwest::Client,
+    user_agent: String,
+    retries: u32,
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Downloader {
+    pub fn new() -> Self {
+        Self {
+            client: http_client(),
+            user_agent: "GhostForge".to_string(),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Download `url` to `dest`. Data is written to `<dest>.part` first, which
+    /// a later attempt or run continues from with a range request.
+    pub async fn download(
+        &self,
+        url: &str,
+        dest: &Path,
+        progress: ProgressCallback<'_>,
+    ) -> Result<()> {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let part = part_path(dest);
+
+        let mut attempt = 0;
+        loop {
+            match self.try_download(url, &part, progress).await {
+                Ok(()) => break,
+                Err(e) if attempt < self.retries => {
+                    let delay = Duration::from_secs(2u64.pow(attempt));
+                    out!(
+                        "⚠️  Download interrupted ({}), retrying in {}s...",
+                        e,
+                        delay.as_secs()
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(anyhow::anyhow!("Failed to download {}: {}", url, e)),
+            }
+        }
+
+        std::fs::rename(&part, dest)?;
+        Ok(())
+    }
+
+    async fn try_download(
+        &self,
+        url: &str,
+        part: &Path,
+        progress: ProgressCallback<'_>,
+    ) -> Result<()> {
+        let offset = std::fs::metadata(part).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = self.client.get(url).header("User-Agent", &self.user_agent);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        let response = request.send().await?;
+
+        // A range starting at the end of the file means the previous run
+        // finished downloading but didn't get to rename it
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(());
+        }
+        let response = response.error_for_status()?;
+
+        // Servers without range support send the whole file again
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        let mut downloaded = if resumed { offset } else { 0 };
+        let total = response.content_length().map(|len| len + downloaded);
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(part)?;
+
+        progress(downloaded, total);
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk)?;
+            downloaded += chunk.len() as u64;
+            progress(downloaded, total);
+        }
+        file.flush()?;
+
+        if let Some(total) = total
+            && downloaded < total
+        {
+            return Err(anyhow::anyhow!(
+                "connection closed after {} of {} bytes",
+                downloaded,
+                total
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// HTTP client with connect and read timeouts, so a hung server fails the
+/// request instead of blocking it forever. There is no overall timeout, large
+/// downloads may take as long as they keep making progress.
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Send a request, retrying with backoff on timeouts, connection errors and
+/// 429/5xx responses. Requests with a streaming body are sent once.
+pub async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let mut attempt = 0;
+    loop {
+        let Some(next) = request.try_clone() else {
+            return request.send().await;
+        };
+
+        let result = next.send().await;
+        let transient = match &result {
+            Ok(response) => {
+                response.status().is_server_error()
+                    || response.status() == StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => e.is_timeout() || e.is_connect(),
+        };
+        if !transient || attempt >= REQUEST_RETRIES {
+            return result;
+        }
+
+        let delay = Duration::from_millis(500 * 2u64.pow(attempt));
+        tracing::debug!("Request failed transiently, retrying in {:?}", delay);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// File name at the end of a download URL, ignoring any query string
+pub fn file_name_from_url(url: &str) -> Option<&str> {
+    url.split(['?', '#'])
+        .next()?
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// Progress bar for a download. Bars share one `MultiProgress` so that
+/// parallel downloads draw below each other instead of over each other.
+pub fn progress_bar(label: &str) -> ProgressBar {
+    static BARS: OnceLock<MultiProgress> = OnceLock::new();
+
+    let pb = BARS
+        .get_or_init(MultiProgress::new)
+        .add(ProgressBar::new(0));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} {msg} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb.set_message(label.to_string());
+    pb
+}
+
+/// Progress callback that drives `pb`
+pub fn bar_progress(pb: &ProgressBar) -> impl Fn(u64, Option<u64>) + Send + Sync + '_ {
+    move |downloaded, total| {
+        if let Some(total) = total {
+            pb.set_length(total);
+        }
+        pb.set_position(downloaded);
+    }
+}
@@ -1,8 +1,15 @@
+use crate::utils::out;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+/// Frame rate caps a game or optimization profile can have
+pub const FPS_LIMITS: std::ops::RangeInclusive<u32> = 10..=1000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Game {
@@ -28,6 +35,32 @@ pub struct Game {
     pub favorite: bool,
     pub hidden: bool,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub dxvk_version: Option<String>,
+    #[serde(default)]
+    pub vkd3d_version: Option<String>,
+    /// MangoHud overlay settings, the global setting applies when unset
+    #[serde(default)]
+    pub mangohud: Option<crate::mangohud::MangoHudSettings>,
+    /// GPU to render on, as listed by `SystemDetector::detect_gpu`. Games
+    /// run on the discrete GPU of hybrid systems when unset.
+    #[serde(default)]
+    pub gpu_index: Option<usize>,
+    /// Vulkan device to use, for when a game picks the wrong one of several
+    #[serde(default)]
+    pub vulkan_device: Option<String>,
+    /// FSR/NIS upscaling, replacing the optimization profile's
+    #[serde(default)]
+    pub upscaling: Option<crate::graphics::UpscalingSettings>,
+    /// Optimization profile applied at launch, see `forge profile auto-assign`
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Frame rate cap, replacing the optimization profile's
+    #[serde(default)]
+    pub fps_limit: Option<u32>,
+    /// Run through `gamemoderun`, None follows the launch default
+    #[serde(default)]
+    pub gamemode: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,14 +124,249 @@ pub struct CompatibilitySettings {
     pub eac_support: bool, // Easy Anti-Cheat
 }
 
+impl Game {
+    /// Whether the game's executable exists. A missing Wine prefix is
+    /// created on the next launch, so it doesn't count.
+    pub fn is_installed(&self) -> bool {
+        self.executable.exists()
+    }
+
+    /// Case-insensitive substring match on the name or any tag, an empty
+    /// query matching every game
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name.to_lowercase().contains(&query)
+            || self
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&query))
+    }
+
+    /// Set an environment variable, replacing any existing value for the key
+    pub fn set_environment_variable(&mut self, key: String, value: String) {
+        match self
+            .environment_variables
+            .iter_mut()
+            .find(|(k, _)| *k == key)
+        {
+            Some(existing) => existing.1 = value,
+            None => self.environment_variables.push((key, value)),
+        }
+    }
+
+    /// Problems with an edited copy of `original`, a changed path must
+    /// exist and the ID can't change
+    pub fn validate_changes(&self, original: &Game) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.id != original.id {
+            problems.push(format!("id can't be changed from '{}'", original.id));
+        }
+        if self.name.trim().is_empty() {
+            problems.push("name must not be empty".to_string());
+        }
+        if self.executable != original.executable && !self.executable.is_file() {
+            problems.push(format!(
+                "executable {} doesn't exist",
+                self.executable.display()
+            ));
+        }
+        if self.install_path != original.install_path && !self.install_path.is_dir() {
+            problems.push(format!(
+                "install_path {} doesn't exist",
+                self.install_path.display()
+            ));
+        }
+        if let Some(upscaling) = &self.upscaling
+            && upscaling.strength > crate::graphics::UpscalingSettings::MAX_STRENGTH
+        {
+            problems.push(format!(
+                "upscaling strength must be 0 to {}",
+                crate::graphics::UpscalingSettings::MAX_STRENGTH
+            ));
+        }
+        if let Some(limit) = self.fps_limit.filter(|limit| !FPS_LIMITS.contains(limit)) {
+            problems.push(format!(
+                "fps_limit {} is outside {} to {}",
+                limit,
+                FPS_LIMITS.start(),
+                FPS_LIMITS.end()
+            ));
+        }
+        for (key, _) in &self.environment_variables {
+            let valid = !key.is_empty()
+                && !key.starts_with(|c: char| c.is_ascii_digit())
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                problems.push(format!("'{}' is not a valid variable name", key));
+            }
+        }
+
+        problems
+    }
+
+    /// The game's Wine prefix, the one a launch uses when none is configured
+    pub fn prefix_path(&self, wine: &crate::config::WineConfig) -> PathBuf {
+        self.wine_prefix
+            .clone()
+            .unwrap_or_else(|| self.default_prefix(wine))
+    }
+
+    /// App ID of a game from the Steam library
+    pub fn steam_appid(&self) -> Option<u32> {
+        self.launcher_id
+            .as_deref()
+            .filter(|_| self.launcher.as_deref() == Some("Steam"))
+            .and_then(|id| id.parse().ok())
+    }
+
+    /// Whether the game runs through Wine or Proton in a prefix GhostForge
+    /// manages. Steam and Legendary manage the prefixes of their own games.
+    pub fn uses_wine_prefix(&self) -> bool {
+        !matches!(self.launcher.as_deref(), Some("Steam") | Some("Epic"))
+            && (self.wine_version.is_some()
+                || self
+                    .executable
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("exe")))
+    }
+
+    /// The prefix for a game without a configured one: the prefix it was
+    /// installed into, the legacy `~/Games/<name>` when it is already set up
+    /// there, otherwise the one `wine.prefix_mode` gives it
+    pub fn default_prefix(&self, wine: &crate::config::WineConfig) -> PathBuf {
+        if let Some(prefix) = self.install_prefix() {
+            return prefix;
+        }
+        let legacy = dirs::home_dir()
+            .unwrap_or_default()
+            .join("Games")
+            .join(&self.name);
+        if legacy.join("system.reg").exists() {
+            legacy
+        } else {
+            wine.prefix_for(&self.id)
+        }
+    }
+
+    /// The prefix whose `drive_c` holds the executable, for games installed
+    /// with `forge install` or another Wine installer
+    fn install_prefix(&self) -> Option<PathBuf> {
+        self.executable
+            .ancestors()
+            .find(|dir| dir.file_name().is_some_and(|name| name == "drive_c"))
+            .and_then(Path::parent)
+            .filter(|prefix| prefix.join("system.reg").exists())
+            .map(Path::to_path_buf)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    Installed,
+    NotInstalled,
+    Running,
+}
+
+impl GameStatus {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "installed" => Some(Self::Installed),
+            "not-installed" => Some(Self::NotInstalled),
+            "running" => Some(Self::Running),
+            _ => None,
+        }
+    }
+}
+
+/// Narrows the library for `forge game list` and the GUI sidebar. Unset
+/// fields match every game; launcher, category and tag compare without case.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameFilter {
+    pub launcher: Option<String>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub status: Option<GameStatus>,
+    pub favorites: bool,
+    /// Only games played within the last `RECENTLY_PLAYED_DAYS`
+    pub recently_played: bool,
+}
+
+pub const RECENTLY_PLAYED_DAYS: i64 = 14;
+
+impl GameFilter {
+    /// Whether `game` passes the filter, `running` holding the ids of games
+    /// that are currently running
+    pub fn matches(&self, game: &Game, running: &HashSet<String>) -> bool {
+        let has = |values: &[String], wanted: &Option<String>| {
+            wanted
+                .as_ref()
+                .is_none_or(|wanted| values.iter().any(|v| v.eq_ignore_ascii_case(wanted)))
+        };
+
+        let launcher = game.launcher.as_deref().unwrap_or("Manual");
+        self.launcher
+            .as_ref()
+            .is_none_or(|wanted| same_launcher(launcher, wanted))
+            && has(&game.categories, &self.category)
+            && has(&game.tags, &self.tag)
+            && (!self.favorites || game.favorite)
+            && (!self.recently_played
+                || game.last_played.is_some_and(|played| {
+                    Utc::now().signed_duration_since(played).num_days() < RECENTLY_PLAYED_DAYS
+                }))
+            && match self.status {
+                None => true,
+                Some(GameStatus::Installed) => game.is_installed(),
+                Some(GameStatus::NotInstalled) => !game.is_installed(),
+                Some(GameStatus::Running) => running.contains(&game.id),
+            }
+    }
+}
+
+/// Launcher names match without case, or when both name the same launcher
+/// type, so `battlenet` finds games imported as `Battle.net`
+fn same_launcher(a: &str, b: &str) -> bool {
+    use crate::launcher::LauncherType;
+
+    a.eq_ignore_ascii_case(b)
+        || LauncherType::from_name(a).is_some_and(|a| LauncherType::from_name(b) == Some(a))
+}
+
+/// How long a write waits for another process's write to finish before
+/// failing with "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Open connections by database path, shared by every `GameLibrary` so the
+/// GUI's background threads and playtime updates don't contend for locks
+static CONNECTIONS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<Connection>>>>> = OnceLock::new();
+
+#[derive(Clone)]
 pub struct GameLibrary {
-    connection: Connection,
+    connection: Arc<Mutex<Connection>>,
 }
 
 impl GameLibrary {
     pub fn new(db_path: &PathBuf) -> Result<Self> {
+        let mut connections = CONNECTIONS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(connection) = connections.get(db_path) {
+            return Ok(Self {
+                connection: connection.clone(),
+            });
+        }
+
         let connection = Connection::open(db_path)?;
 
+        // WAL lets other processes read while forge writes, and the busy
+        // timeout makes concurrent writers wait their turn
+        connection
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+
         connection.execute(
             "CREATE TABLE IF NOT EXISTS games (
                 id TEXT PRIMARY KEY,
@@ -123,28 +391,112 @@ impl GameLibrary {
                 favorite INTEGER DEFAULT 0,
                 hidden INTEGER DEFAULT 0,
                 notes TEXT,
-                settings TEXT
+                settings TEXT,
+                dxvk_version TEXT,
+                vkd3d_version TEXT,
+                mangohud TEXT,
+                gpu_index INTEGER,
+                vulkan_device TEXT,
+                upscaling TEXT,
+                profile TEXT,
+                fps_limit INTEGER,
+                gamemode INTEGER
             )",
             [],
         )?;
 
+        // Older databases predate per-game graphics layer pinning
+        Self::ensure_column(&connection, "dxvk_version", "TEXT")?;
+        Self::ensure_column(&connection, "vkd3d_version", "TEXT")?;
+        Self::ensure_column(&connection, "mangohud", "TEXT")?;
+        Self::ensure_column(&connection, "gpu_index", "INTEGER")?;
+        Self::ensure_column(&connection, "vulkan_device", "TEXT")?;
+        Self::ensure_column(&connection, "upscaling", "TEXT")?;
+        Self::ensure_column(&connection, "profile", "TEXT")?;
+        Self::ensure_column(&connection, "fps_limit", "INTEGER")?;
+        Self::ensure_column(&connection, "gamemode", "INTEGER")?;
+        Self::ensure_search_index(&connection)?;
+
+        let connection = Arc::new(Mutex::new(connection));
+        connections.insert(db_path.clone(), connection.clone());
         Ok(Self { connection })
     }
 
+    /// Trigram index over names, tags, categories and notes for
+    /// `search_games`, kept current by triggers
+    fn ensure_search_index(connection: &Connection) -> Result<()> {
+        connection.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS games_fts USING fts5(
+                id UNINDEXED, name, tags, categories, notes,
+                tokenize = 'trigram'
+            );
+            CREATE TRIGGER IF NOT EXISTS games_fts_insert AFTER INSERT ON games BEGIN
+                INSERT INTO games_fts (id, name, tags, categories, notes)
+                VALUES (new.id, new.name, new.tags, new.categories, new.notes);
+            END;
+            CREATE TRIGGER IF NOT EXISTS games_fts_delete AFTER DELETE ON games BEGIN
+                DELETE FROM games_fts WHERE id = old.id;
+            END;
+            CREATE TRIGGER IF NOT EXISTS games_fts_update
+            AFTER UPDATE OF id, name, tags, categories, notes ON games BEGIN
+                DELETE FROM games_fts WHERE id = old.id;
+                INSERT INTO games_fts (id, name, tags, categories, notes)
+                VALUES (new.id, new.name, new.tags, new.categories, new.notes);
+            END;
+            -- Games added before the index existed
+            INSERT INTO games_fts (id, name, tags, categories, notes)
+            SELECT id, name, tags, categories, notes FROM games
+            WHERE id NOT IN (SELECT id FROM games_fts);",
+        )?;
+        Ok(())
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn ensure_column(connection: &Connection, column: &str, definition: &str) -> Result<()> {
+        let mut stmt = connection.prepare("PRAGMA table_info(games)")?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(|name| name.ok())
+            .any(|name| name == column);
+
+        if !exists {
+            connection.execute(
+                &format!("ALTER TABLE games ADD COLUMN {} {}", column, definition),
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn add_game(&self, game: &Game) -> Result<()> {
         let launch_args = serde_json::to_string(&game.launch_arguments)?;
         let env_vars = serde_json::to_string(&game.environment_variables)?;
         let categories = serde_json::to_string(&game.categories)?;
         let tags = serde_json::to_string(&game.tags)?;
+        let mangohud = game
+            .mangohud
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let upscaling = game
+            .upscaling
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
 
-        self.connection.execute(
+        self.connection().execute(
             "INSERT INTO games (
                 id, name, executable, install_path, launcher, launcher_id,
                 wine_version, wine_prefix, icon, banner, launch_arguments,
                 environment_variables, pre_launch_script, post_launch_script,
                 categories, tags, playtime_minutes, last_played, installed_date,
-                favorite, hidden, notes
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+                favorite, hidden, notes, dxvk_version, vkd3d_version, mangohud, gpu_index,
+                vulkan_device, upscaling, profile, fps_limit, gamemode
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31)",
             params![
                 game.id,
                 game.name,
@@ -168,6 +520,15 @@ impl GameLibrary {
                 game.favorite,
                 game.hidden,
                 game.notes,
+                game.dxvk_version,
+                game.vkd3d_version,
+                mangohud,
+                game.gpu_index.map(|index| index as i64),
+                game.vulkan_device,
+                upscaling,
+                game.profile,
+                game.fps_limit,
+                game.gamemode,
             ],
         )?;
 
@@ -175,101 +536,69 @@ impl GameLibrary {
     }
 
     pub fn get_game(&self, id: &str) -> Result<Option<Game>> {
-        let mut stmt = self
-            .connection
-            .prepare("SELECT * FROM games WHERE id = ?1")?;
-
-        let game = stmt
-            .query_row([id], |row| {
-                Ok(Game {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    executable: PathBuf::from(row.get::<_, String>(2)?),
-                    install_path: PathBuf::from(row.get::<_, String>(3)?),
-                    launcher: row.get(4)?,
-                    launcher_id: row.get(5)?,
-                    wine_version: row.get(6)?,
-                    wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
-                    icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
-                    banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
-                    launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?)
-                        .unwrap_or_default(),
-                    environment_variables: serde_json::from_str(&row.get::<_, String>(11)?)
-                        .unwrap_or_default(),
-                    pre_launch_script: row.get(12)?,
-                    post_launch_script: row.get(13)?,
-                    categories: serde_json::from_str(&row.get::<_, String>(14)?)
-                        .unwrap_or_default(),
-                    tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
-                    playtime_minutes: row.get(16)?,
-                    last_played: row
-                        .get::<_, Option<String>>(17)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
-                        .unwrap()
-                        .with_timezone(&Utc),
-                    favorite: row.get(19)?,
-                    hidden: row.get(20)?,
-                    notes: row.get(21)?,
-                })
-            })
-            .optional()?;
+        let connection = self.connection();
+        let mut stmt = connection.prepare("SELECT * FROM games WHERE id = ?1")?;
+
+        let game = stmt.query_row([id], game_from_row).optional()?;
 
         Ok(game)
     }
 
     pub fn list_games(&self) -> Result<Vec<Game>> {
-        let mut stmt = self
-            .connection
-            .prepare("SELECT * FROM games WHERE hidden = 0")?;
-
-        let games = stmt.query_map([], |row| {
-            Ok(Game {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                executable: PathBuf::from(row.get::<_, String>(2)?),
-                install_path: PathBuf::from(row.get::<_, String>(3)?),
-                launcher: row.get(4)?,
-                launcher_id: row.get(5)?,
-                wine_version: row.get(6)?,
-                wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
-                icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
-                banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
-                launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?)
-                    .unwrap_or_default(),
-                environment_variables: serde_json::from_str(&row.get::<_, String>(11)?)
-                    .unwrap_or_default(),
-                pre_launch_script: row.get(12)?,
-                post_launch_script: row.get(13)?,
-                categories: serde_json::from_str(&row.get::<_, String>(14)?).unwrap_or_default(),
-                tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
-                playtime_minutes: row.get(16)?,
-                last_played: row
-                    .get::<_, Option<String>>(17)?
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                favorite: row.get(19)?,
-                hidden: row.get(20)?,
-                notes: row.get(21)?,
-            })
-        })?;
+        let connection = self.connection();
+        let mut stmt = connection.prepare("SELECT * FROM games WHERE hidden = 0")?;
+
+        let games = stmt.query_map([], game_from_row)?;
 
         games
             .collect::<Result<Vec<_>, _>>()
             .map_err(anyhow::Error::from)
     }
 
+    /// Before `wine.prefix_mode` changes, record the prefix every Wine game
+    /// without one is set up in, so the new mode only affects games added
+    /// afterwards. Returns how many games were updated.
+    pub fn assign_prefixes(&self, wine: &crate::config::WineConfig) -> Result<usize> {
+        let games = {
+            let connection = self.connection();
+            let mut stmt = connection.prepare("SELECT * FROM games WHERE wine_prefix IS NULL")?;
+            stmt.query_map([], game_from_row)?
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut assigned = 0;
+        for mut game in games {
+            if !game.uses_wine_prefix() {
+                continue;
+            }
+            // A prefix that was never created has nothing to keep
+            let prefix = game.default_prefix(wine);
+            if prefix.exists() {
+                game.wine_prefix = Some(prefix);
+                self.update_game(&game)?;
+                assigned += 1;
+            }
+        }
+        Ok(assigned)
+    }
+
     pub fn update_game(&self, game: &Game) -> Result<()> {
         let launch_args = serde_json::to_string(&game.launch_arguments)?;
         let env_vars = serde_json::to_string(&game.environment_variables)?;
         let categories = serde_json::to_string(&game.categories)?;
         let tags = serde_json::to_string(&game.tags)?;
+        let mangohud = game
+            .mangohud
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let upscaling = game
+            .upscaling
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
 
-        self.connection.execute(
+        self.connection().execute(
             "UPDATE games SET
                 name = ?2,
                 executable = ?3,
@@ -290,7 +619,16 @@ impl GameLibrary {
                 last_played = ?18,
                 favorite = ?19,
                 hidden = ?20,
-                notes = ?21
+                notes = ?21,
+                dxvk_version = ?22,
+                vkd3d_version = ?23,
+                mangohud = ?24,
+                gpu_index = ?25,
+                vulkan_device = ?26,
+                upscaling = ?27,
+                profile = ?28,
+                fps_limit = ?29,
+                gamemode = ?30
             WHERE id = ?1",
             params![
                 game.id,
@@ -314,6 +652,15 @@ impl GameLibrary {
                 game.favorite,
                 game.hidden,
                 game.notes,
+                game.dxvk_version,
+                game.vkd3d_version,
+                mangohud,
+                game.gpu_index.map(|index| index as i64),
+                game.vulkan_device,
+                upscaling,
+                game.profile,
+                game.fps_limit,
+                game.gamemode,
             ],
         )?;
 
@@ -321,66 +668,96 @@ impl GameLibrary {
     }
 
     pub fn remove_game(&self, id: &str) -> Result<()> {
-        self.connection
+        self.connection()
             .execute("DELETE FROM games WHERE id = ?1", [id])?;
         Ok(())
     }
 
+    /// Games whose name, tags, categories or notes share most of the
+    /// query's trigrams, so partial words and small typos still match. Best
+    /// matches first.
     pub fn search_games(&self, query: &str) -> Result<Vec<Game>> {
+        let trigrams = search_trigrams(query);
+        if trigrams.is_empty() {
+            // Too short for the index
+            return self.search_games_like(query);
+        }
+
+        let expression = trigrams
+            .iter()
+            .map(|trigram| format!("\"{}\"", trigram.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let connection = self.connection();
+        let mut stmt = connection.prepare(
+            "SELECT games.* FROM games_fts
+            JOIN games ON games.id = games_fts.id
+            WHERE games_fts MATCH ?1 AND games.hidden = 0
+            ORDER BY bm25(games_fts, 0.0, 10.0, 2.0, 2.0, 1.0)",
+        )?;
+        let games = stmt
+            .query_map([expression], game_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Sorting is stable, so equally good matches keep the bm25 order
+        let mut scored: Vec<(f64, Game)> = games
+            .into_iter()
+            .map(|game| (search_score(&trigrams, &game), game))
+            .filter(|(score, _)| *score >= MIN_SEARCH_SCORE)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(scored.into_iter().map(|(_, game)| game).collect())
+    }
+
+    fn search_games_like(&self, query: &str) -> Result<Vec<Game>> {
         let pattern = format!("%{}%", query);
-        let mut stmt = self.connection.prepare(
+        let connection = self.connection();
+        let mut stmt = connection.prepare(
             "SELECT * FROM games WHERE
             (name LIKE ?1 OR tags LIKE ?1 OR categories LIKE ?1 OR notes LIKE ?1)
             AND hidden = 0",
         )?;
 
-        let games = stmt.query_map([pattern], |row| {
-            Ok(Game {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                executable: PathBuf::from(row.get::<_, String>(2)?),
-                install_path: PathBuf::from(row.get::<_, String>(3)?),
-                launcher: row.get(4)?,
-                launcher_id: row.get(5)?,
-                wine_version: row.get(6)?,
-                wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
-                icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
-                banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
-                launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?)
-                    .unwrap_or_default(),
-                environment_variables: serde_json::from_str(&row.get::<_, String>(11)?)
-                    .unwrap_or_default(),
-                pre_launch_script: row.get(12)?,
-                post_launch_script: row.get(13)?,
-                categories: serde_json::from_str(&row.get::<_, String>(14)?).unwrap_or_default(),
-                tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
-                playtime_minutes: row.get(16)?,
-                last_played: row
-                    .get::<_, Option<String>>(17)?
-                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                    .map(|dt| dt.with_timezone(&Utc)),
-                installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                favorite: row.get(19)?,
-                hidden: row.get(20)?,
-                notes: row.get(21)?,
-            })
-        })?;
+        let games = stmt.query_map([pattern], game_from_row)?;
 
         games
             .collect::<Result<Vec<_>, _>>()
             .map_err(anyhow::Error::from)
     }
 
+    /// Substring search plus fuzzy name matching, best matches first
+    pub fn fuzzy_search(&self, query: &str) -> Result<Vec<Game>> {
+        let mut scored: Vec<(i64, Game)> = self
+            .search_games(query)?
+            .into_iter()
+            .map(|game| {
+                // Tag/category/notes hits rank below any name match
+                let score = crate::utils::fuzzy_match_score(query, &game.name).unwrap_or(0);
+                (score, game)
+            })
+            .collect();
+
+        for game in self.list_games()? {
+            if scored.iter().any(|(_, g)| g.id == game.id) {
+                continue;
+            }
+            if let Some(score) = crate::utils::fuzzy_match_score(query, &game.name) {
+                scored.push((score, game));
+            }
+        }
+
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        Ok(scored.into_iter().map(|(_, game)| game).collect())
+    }
+
     pub fn delete_game(&self, id: &str) -> Result<()> {
-        self.connection
+        self.connection()
             .execute("DELETE FROM games WHERE id = ?1", [id])?;
         Ok(())
     }
 
     pub fn update_playtime(&self, id: &str, additional_minutes: u64) -> Result<()> {
-        self.connection.execute(
+        self.connection().execute(
             "UPDATE games SET playtime_minutes = playtime_minutes + ?2, last_played = ?3 WHERE id = ?1",
             params![id, additional_minutes, Utc::now().to_rfc3339()],
         )?;
@@ -388,92 +765,730 @@ impl GameLibrary {
     }
 
     pub fn get_games_by_launcher(&self, launcher: &str) -> Result<Vec<Game>> {
-        let mut stmt = self
-            .connection
-            .prepare("SELECT * FROM games WHERE launcher = ?1 AND hidden = 0")?;
+        let connection = self.connection();
+        let mut stmt =
+            connection.prepare("SELECT * FROM games WHERE launcher = ?1 AND hidden = 0")?;
 
         let games = stmt
-            .query_map([launcher], |row| {
-                Ok(Game {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    executable: PathBuf::from(row.get::<_, String>(2)?),
-                    install_path: PathBuf::from(row.get::<_, String>(3)?),
-                    launcher: row.get(4)?,
-                    launcher_id: row.get(5)?,
-                    wine_version: row.get(6)?,
-                    wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
-                    icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
-                    banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
-                    launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?)
-                        .unwrap_or_default(),
-                    environment_variables: serde_json::from_str(&row.get::<_, String>(11)?)
-                        .unwrap_or_default(),
-                    pre_launch_script: row.get(12)?,
-                    post_launch_script: row.get(13)?,
-                    categories: serde_json::from_str(&row.get::<_, String>(14)?)
-                        .unwrap_or_default(),
-                    tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
-                    playtime_minutes: row.get(16)?,
-                    last_played: row
-                        .get::<_, Option<String>>(17)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
-                        .unwrap()
-                        .with_timezone(&Utc),
-                    favorite: row.get(19)?,
-                    hidden: row.get(20)?,
-                    notes: row.get(21)?,
-                })
-            })?
+            .query_map([launcher], game_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(games)
     }
 
     pub fn get_favorites(&self) -> Result<Vec<Game>> {
-        let mut stmt = self.connection.prepare(
+        let connection = self.connection();
+        let mut stmt = connection.prepare(
             "SELECT * FROM games WHERE favorite = 1 AND hidden = 0 ORDER BY last_played DESC",
         )?;
 
         let games = stmt
-            .query_map([], |row| {
-                Ok(Game {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    executable: PathBuf::from(row.get::<_, String>(2)?),
-                    install_path: PathBuf::from(row.get::<_, String>(3)?),
-                    launcher: row.get(4)?,
-                    launcher_id: row.get(5)?,
-                    wine_version: row.get(6)?,
-                    wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
-                    icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
-                    banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
-                    launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?)
-                        .unwrap_or_default(),
-                    environment_variables: serde_json::from_str(&row.get::<_, String>(11)?)
-                        .unwrap_or_default(),
-                    pre_launch_script: row.get(12)?,
-                    post_launch_script: row.get(13)?,
-                    categories: serde_json::from_str(&row.get::<_, String>(14)?)
-                        .unwrap_or_default(),
-                    tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
-                    playtime_minutes: row.get(16)?,
-                    last_played: row
-                        .get::<_, Option<String>>(17)?
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc)),
-                    installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
-                        .unwrap()
-                        .with_timezone(&Utc),
-                    favorite: row.get(19)?,
-                    hidden: row.get(20)?,
-                    notes: row.get(21)?,
-                })
-            })?
+            .query_map([], game_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(games)
     }
+
+    /// Move a game's install directory into `dest`, with `with_prefix` also
+    /// its Wine prefix, and save the new paths. Registry entries pointing at
+    /// the old install directory are updated.
+    pub fn move_game(
+        &self,
+        game: &Game,
+        dest: &Path,
+        with_prefix: bool,
+        wine: &crate::config::WineConfig,
+    ) -> Result<Game> {
+        use crate::utils::{disk_usage, free_space, move_directory, same_filesystem};
+
+        if crate::game_launcher::RunningGameRegistry::load()?
+            .games
+            .contains_key(&game.id)
+        {
+            return Err(anyhow::anyhow!(
+                "{} is running, stop it with 'forge game stop' first",
+                game.name
+            ));
+        }
+        if game.launcher.as_deref() == Some("Steam") {
+            return Err(anyhow::anyhow!(
+                "Steam manages the files of {}, move it in Steam's storage settings",
+                game.name
+            ));
+        }
+
+        let old_install = game.install_path.clone();
+        if !old_install.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Install directory {} not found",
+                old_install.display()
+            ));
+        }
+        std::fs::create_dir_all(dest)?;
+        let dest = dest.canonicalize()?;
+        let new_install =
+            dest.join(old_install.file_name().ok_or_else(|| {
+                anyhow::anyhow!("Invalid install path {}", old_install.display())
+            })?);
+
+        // A prefix inside the install directory moves along with it
+        let old_prefix = game.prefix_path(wine);
+        let prefix_inside = old_prefix.starts_with(&old_install);
+        let move_prefix = with_prefix && old_prefix.is_dir() && !prefix_inside;
+        let new_prefix = dest.join(old_prefix.file_name().unwrap_or_default());
+        if move_prefix {
+            if let Some(other) = self
+                .list_games()?
+                .into_iter()
+                .find(|g| g.id != game.id && g.prefix_path(wine) == old_prefix)
+            {
+                return Err(anyhow::anyhow!(
+                    "The prefix {} is shared with {}, not moving it",
+                    old_prefix.display(),
+                    other.name
+                ));
+            }
+            if new_prefix == new_install {
+                return Err(anyhow::anyhow!(
+                    "The game and its prefix would both move to {}",
+                    new_install.display()
+                ));
+            }
+        }
+        for target in std::iter::once(&new_install).chain(move_prefix.then_some(&new_prefix)) {
+            if target.exists() {
+                return Err(anyhow::anyhow!("{} already exists", target.display()));
+            }
+        }
+
+        // Renames need no space, copies need room for everything at once
+        let mut needed = 0;
+        if !same_filesystem(&old_install, &dest)? {
+            needed += disk_usage(&old_install)?;
+        }
+        if move_prefix && !same_filesystem(&old_prefix, &dest)? {
+            needed += disk_usage(&old_prefix)?;
+        }
+        let free = free_space(&dest)?;
+        if needed > free {
+            return Err(anyhow::anyhow!(
+                "Not enough space in {}: {} needed, {} free",
+                dest.display(),
+                crate::utils::format_size(needed),
+                crate::utils::format_size(free)
+            ));
+        }
+
+        out!(
+            "🚚 Moving {} to {}...",
+            old_install.display(),
+            new_install.display()
+        );
+        move_directory(&old_install, &new_install)?;
+
+        let rebase = |path: &Path| {
+            path.strip_prefix(&old_install)
+                .map(|rest| new_install.join(rest))
+                .unwrap_or_else(|_| path.to_path_buf())
+        };
+        let mut moved = game.clone();
+        moved.install_path = new_install.clone();
+        moved.executable = rebase(&game.executable);
+        if prefix_inside || game.wine_prefix.is_some() {
+            moved.wine_prefix = Some(rebase(&old_prefix));
+        }
+        self.update_game(&moved)?;
+
+        if move_prefix {
+            out!(
+                "🚚 Moving prefix {} to {}...",
+                old_prefix.display(),
+                new_prefix.display()
+            );
+            move_directory(&old_prefix, &new_prefix)?;
+            moved.wine_prefix = Some(new_prefix.clone());
+            self.update_game(&moved)?;
+
+            // GhostForge-managed prefixes record their own location
+            let metadata_file = new_prefix.join("ghostforge.json");
+            if let Ok(content) = std::fs::read_to_string(&metadata_file)
+                && let Ok(mut metadata) = serde_json::from_str::<serde_json::Value>(&content)
+            {
+                metadata["path"] = serde_json::json!(new_prefix);
+                std::fs::write(&metadata_file, serde_json::to_string_pretty(&metadata)?)?;
+            }
+        }
+
+        let fixed = crate::prefix::rewrite_registry_paths(
+            &moved.prefix_path(wine),
+            &old_install,
+            &new_install,
+        )?;
+        if fixed > 0 {
+            out!("🔧 Updated {} registry paths", fixed);
+        }
+
+        Ok(moved)
+    }
+
+    /// Add the bundle's games, replacing games with the same ID when
+    /// `replace` is set and keeping the existing ones otherwise
+    pub fn import_bundle(&self, bundle: &LibraryBundle, replace: bool) -> Result<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        for game in &bundle.games {
+            match self.get_game(&game.id)? {
+                Some(_) if replace => {
+                    self.update_game(game)?;
+                    summary.replaced += 1;
+                }
+                Some(existing) => summary.skipped.push(existing.name),
+                None => {
+                    self.add_game(game)?;
+                    summary.added += 1;
+                }
+            }
+        }
+        Ok(summary)
+    }
+}
+
+/// Share of a query's trigrams a game must contain to be a search result
+const MIN_SEARCH_SCORE: f64 = 0.5;
+
+/// Lowercase trigrams of each word in `query`, words shorter than three
+/// characters have none
+fn search_trigrams(query: &str) -> Vec<String> {
+    let mut trigrams: Vec<String> = Vec::new();
+    for word in query.to_lowercase().split_whitespace() {
+        let chars: Vec<char> = word.chars().collect();
+        for window in chars.windows(3) {
+            let trigram: String = window.iter().collect();
+            if !trigrams.contains(&trigram) {
+                trigrams.push(trigram);
+            }
+        }
+    }
+    trigrams
+}
+
+/// Share of `trigrams` found in the game's name, or slightly less for
+/// matches only in its tags, categories or notes
+fn search_score(trigrams: &[String], game: &Game) -> f64 {
+    let coverage = |text: &str| {
+        let text = text.to_lowercase();
+        let found = trigrams
+            .iter()
+            .filter(|t| text.contains(t.as_str()))
+            .count();
+        found as f64 / trigrams.len() as f64
+    };
+
+    let other = [
+        game.tags.join(" "),
+        game.categories.join(" "),
+        game.notes.clone().unwrap_or_default(),
+    ]
+    .join(" ");
+    coverage(&game.name).max(coverage(&other) * 0.9)
+}
+
+fn game_from_row(row: &rusqlite::Row) -> rusqlite::Result<Game> {
+    Ok(Game {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        executable: PathBuf::from(row.get::<_, String>(2)?),
+        install_path: PathBuf::from(row.get::<_, String>(3)?),
+        launcher: row.get(4)?,
+        launcher_id: row.get(5)?,
+        wine_version: row.get(6)?,
+        wine_prefix: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
+        icon: row.get::<_, Option<String>>(8)?.map(PathBuf::from),
+        banner: row.get::<_, Option<String>>(9)?.map(PathBuf::from),
+        launch_arguments: serde_json::from_str(&row.get::<_, String>(10)?).unwrap_or_default(),
+        environment_variables: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
+        pre_launch_script: row.get(12)?,
+        post_launch_script: row.get(13)?,
+        categories: serde_json::from_str(&row.get::<_, String>(14)?).unwrap_or_default(),
+        tags: serde_json::from_str(&row.get::<_, String>(15)?).unwrap_or_default(),
+        playtime_minutes: row.get(16)?,
+        last_played: row
+            .get::<_, Option<String>>(17)?
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        installed_date: DateTime::parse_from_rfc3339(&row.get::<_, String>(18)?)
+            .unwrap()
+            .with_timezone(&Utc),
+        favorite: row.get(19)?,
+        hidden: row.get(20)?,
+        notes: row.get(21)?,
+        // Pinned columns are appended by migration, so look them up by name
+        dxvk_version: row.get("dxvk_version")?,
+        vkd3d_version: row.get("vkd3d_version")?,
+        mangohud: row
+            .get::<_, Option<String>>("mangohud")?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        gpu_index: row
+            .get::<_, Option<i64>>("gpu_index")?
+            .map(|index| index as usize),
+        vulkan_device: row.get("vulkan_device")?,
+        upscaling: row
+            .get::<_, Option<String>>("upscaling")?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        profile: row.get("profile")?,
+        fps_limit: row.get("fps_limit")?,
+        gamemode: row.get("gamemode")?,
+    })
+}
+
+/// Space a game takes on disk, in bytes
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GameDiskUsage {
+    pub game_id: String,
+    pub name: String,
+    pub install: u64,
+    /// Zero when the prefix lives inside the install directory or was
+    /// already counted for another game sharing it
+    pub prefix: u64,
+    /// DXVK/VKD3D-Proton state caches and the pre-cached driver shaders
+    pub shader_cache: u64,
+}
+
+impl GameDiskUsage {
+    pub fn total(&self) -> u64 {
+        self.install + self.prefix + self.shader_cache
+    }
+}
+
+/// Measure every game in `games`, counting a shared prefix only once
+pub fn measure_disk_usage(
+    games: &[Game],
+    cache_root: &Path,
+    wine: &crate::config::WineConfig,
+) -> Vec<GameDiskUsage> {
+    let mut counted_prefixes = HashSet::new();
+    games
+        .iter()
+        .map(|game| {
+            let install = crate::utils::disk_usage(&game.install_path).unwrap_or(0);
+
+            let prefix_path = game.prefix_path(wine);
+            let prefix = if prefix_path.starts_with(&game.install_path)
+                || !counted_prefixes.insert(prefix_path.clone())
+            {
+                0
+            } else {
+                crate::utils::disk_usage(&prefix_path).unwrap_or(0)
+            };
+
+            // Caches next to the executable are already part of the install
+            let central = crate::graphics::central_state_cache_dir(cache_root, &game.id);
+            let scattered: u64 = crate::graphics::find_state_caches(game, cache_root)
+                .unwrap_or_default()
+                .iter()
+                .filter(|cache| {
+                    !cache.path.starts_with(&game.install_path) && !cache.path.starts_with(&central)
+                })
+                .map(|cache| cache.size)
+                .sum();
+            let shader_cache = crate::utils::disk_usage(&central).unwrap_or(0) + scattered;
+
+            GameDiskUsage {
+                game_id: game.id.clone(),
+                name: game.name.clone(),
+                install,
+                prefix,
+                shader_cache,
+            }
+        })
+        .collect()
+}
+
+/// A portable copy of the library, for `forge game export` and `import`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Home and games library directories of the exporting machine, paths
+    /// under them are moved to the importing machine's
+    pub home: PathBuf,
+    pub games_library: PathBuf,
+    pub games: Vec<Game>,
+    /// Optimization profiles the games are linked to
+    #[serde(default)]
+    pub profiles: Vec<crate::bolt_integration::OptimizationProfile>,
+}
+
+/// What importing a bundle did
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub replaced: usize,
+    pub skipped: Vec<String>,
+}
+
+impl LibraryBundle {
+    pub const VERSION: u32 = 1;
+
+    pub fn new(games: Vec<Game>, games_library: &Path) -> Self {
+        Self {
+            version: Self::VERSION,
+            exported_at: Utc::now(),
+            home: dirs::home_dir().unwrap_or_default(),
+            games_library: games_library.to_path_buf(),
+            games,
+            profiles: Vec::new(),
+        }
+    }
+
+    /// Read a bundle, TOML for `.toml` files and JSON otherwise
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let bundle: Self = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content)?
+        } else {
+            serde_json::from_str(&content)?
+        };
+        if bundle.version > Self::VERSION {
+            return Err(anyhow::anyhow!(
+                "{} is from a newer GhostForge (bundle version {}), update to import it",
+                path.display(),
+                bundle.version
+            ));
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle, TOML for `.toml` files and JSON otherwise
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::to_string_pretty(self)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Move paths under the exporting machine's games library and home to
+    /// `games_library` and `home`
+    pub fn rewrite_paths(&mut self, home: &Path, games_library: &Path) {
+        let rebase = |path: &mut PathBuf| {
+            let rebased = [(&self.games_library, games_library), (&self.home, home)]
+                .into_iter()
+                .find_map(|(from, to)| path.strip_prefix(from).ok().map(|rest| to.join(rest)));
+            if let Some(rebased) = rebased {
+                *path = rebased;
+            }
+        };
+
+        for game in &mut self.games {
+            rebase(&mut game.executable);
+            rebase(&mut game.install_path);
+            for path in [&mut game.wine_prefix, &mut game.icon, &mut game.banner]
+                .into_iter()
+                .flatten()
+            {
+                rebase(path);
+            }
+        }
+        self.home = home.to_path_buf();
+        self.games_library = games_library.to_path_buf();
+    }
+}
+
+/// Result of checking a game's files on disk
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub files_checked: usize,
+    pub total_size: u64,
+    pub problems: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check that a game's install directory and executable are present and not truncated.
+///
+/// Steam games are also compared against their appmanifest.
+pub fn verify_game_files(game: &Game) -> Result<VerifyReport> {
+    let mut report = VerifyReport::default();
+
+    if !game.install_path.is_dir() {
+        report.problems.push(format!(
+            "Install directory missing: {}",
+            game.install_path.display()
+        ));
+        return Ok(report);
+    }
+
+    match std::fs::metadata(&game.executable) {
+        Ok(metadata) if metadata.len() == 0 => report.problems.push(format!(
+            "Executable is empty: {}",
+            game.executable.display()
+        )),
+        Ok(_) => {}
+        Err(_) => report
+            .problems
+            .push(format!("Executable missing: {}", game.executable.display())),
+    }
+
+    for entry in walkdir::WalkDir::new(&game.install_path) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.problems.push(format!("Unreadable: {}", e));
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        report.files_checked += 1;
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        report.total_size += size;
+
+        // Empty data files are common, empty binaries and archives never are
+        let binary = entry
+            .path()
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| {
+                matches!(
+                    e.to_lowercase().as_str(),
+                    "exe" | "dll" | "so" | "pak" | "bin"
+                )
+            });
+        if size == 0 && binary {
+            let relative = entry
+                .path()
+                .strip_prefix(&game.install_path)
+                .unwrap_or(entry.path());
+            report
+                .problems
+                .push(format!("Empty file: {}", relative.display()));
+        }
+    }
+
+    if report.files_checked == 0 {
+        report
+            .problems
+            .push("Install directory is empty".to_string());
+    }
+
+    if game.launcher.as_deref() == Some("Steam")
+        && let Some(appid) = &game.launcher_id
+    {
+        verify_steam_manifest(game, appid, &mut report);
+    }
+
+    Ok(report)
+}
+
+fn verify_steam_manifest(game: &Game, appid: &str, report: &mut VerifyReport) {
+    // Installs live in <library>/steamapps/common/<installdir>
+    let Some(steamapps) = game.install_path.parent().and_then(|p| p.parent()) else {
+        return;
+    };
+    let manifest_path = steamapps.join(format!("appmanifest_{}.acf", appid));
+    let Ok(manifest) = std::fs::read_to_string(&manifest_path) else {
+        report.problems.push(format!(
+            "Steam manifest missing: {}",
+            manifest_path.display()
+        ));
+        return;
+    };
+
+    let value = |key: &str| -> Option<u64> {
+        let re = regex::Regex::new(&format!(r#""{}"\s+"(\d+)""#, key)).ok()?;
+        re.captures(&manifest)?[1].parse().ok()
+    };
+
+    // StateFlags bit 4 is "fully installed"
+    if let Some(flags) = value("StateFlags")
+        && flags & 4 == 0
+    {
+        report.problems.push(format!(
+            "Steam reports the install as incomplete (StateFlags {})",
+            flags
+        ));
+    }
+
+    if let Some(expected) = value("SizeOnDisk")
+        && expected > 0
+        && report.total_size < expected / 100 * 95
+    {
+        report.problems.push(format!(
+            "Install is {} but Steam expects {}",
+            crate::utils::format_size(report.total_size),
+            crate::utils::format_size(expected)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_game(id: &str, name: &str, tags: &[&str]) -> Game {
+        Game {
+            id: id.to_string(),
+            name: name.to_string(),
+            executable: PathBuf::from(format!("/games/{}/game.exe", id)),
+            install_path: PathBuf::from(format!("/games/{}", id)),
+            launcher: None,
+            launcher_id: None,
+            wine_version: None,
+            wine_prefix: None,
+            icon: None,
+            banner: None,
+            launch_arguments: Vec::new(),
+            environment_variables: Vec::new(),
+            pre_launch_script: None,
+            post_launch_script: None,
+            categories: Vec::new(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            playtime_minutes: 0,
+            last_played: None,
+            installed_date: Utc::now(),
+            favorite: false,
+            hidden: false,
+            notes: None,
+            dxvk_version: None,
+            vkd3d_version: None,
+            mangohud: None,
+            gpu_index: None,
+            vulkan_device: None,
+            upscaling: None,
+            profile: None,
+            fps_limit: None,
+            gamemode: None,
+        }
+    }
+
+    #[test]
+    fn concurrent_reads_and_writes_dont_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ghostforge.db");
+        let library = GameLibrary::new(&db_path).unwrap();
+        library.add_game(&test_game("game", "Game", &[])).unwrap();
+
+        // Another process writing to the same database through its own
+        // connection, like a launch recording playtime
+        let external = {
+            let db_path = db_path.clone();
+            std::thread::spawn(move || {
+                let connection = Connection::open(&db_path).unwrap();
+                connection.busy_timeout(BUSY_TIMEOUT).unwrap();
+                for _ in 0..50 {
+                    connection
+                        .execute(
+                            "UPDATE games SET playtime_minutes = playtime_minutes + 1 WHERE id = 'game'",
+                            [],
+                        )
+                        .unwrap();
+                }
+            })
+        };
+        let writer = {
+            let db_path = db_path.clone();
+            std::thread::spawn(move || {
+                let library = GameLibrary::new(&db_path).unwrap();
+                for _ in 0..50 {
+                    library.update_playtime("game", 1).unwrap();
+                }
+            })
+        };
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let db_path = db_path.clone();
+                std::thread::spawn(move || {
+                    let library = GameLibrary::new(&db_path).unwrap();
+                    for _ in 0..50 {
+                        assert_eq!(library.list_games().unwrap().len(), 1);
+                    }
+                })
+            })
+            .collect();
+
+        external.join().unwrap();
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        let game = library.get_game("game").unwrap().unwrap();
+        assert_eq!(game.playtime_minutes, 100);
+    }
+
+    #[test]
+    fn installed_games_reuse_their_install_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wine = crate::config::Config::default().wine;
+        wine.default_prefix_path = dir.path().join("prefixes");
+        let mut game = test_game("setup", "Setup", &[]);
+        assert_eq!(game.prefix_path(&wine), wine.prefix_for("setup"));
+
+        let prefix = dir.path().join("prefixes").join("Setup");
+        let install = prefix.join("drive_c").join("Games").join("Setup");
+        std::fs::create_dir_all(&install).unwrap();
+        std::fs::write(prefix.join("system.reg"), "").unwrap();
+        game.executable = install.join("setup.exe");
+        assert_eq!(game.prefix_path(&wine), prefix);
+
+        game.wine_prefix = Some(PathBuf::from("/pinned"));
+        assert_eq!(game.prefix_path(&wine), PathBuf::from("/pinned"));
+    }
+
+    #[test]
+    fn search_matches_partial_and_misspelled_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = GameLibrary::new(&dir.path().join("ghostforge.db")).unwrap();
+        library
+            .add_game(&test_game("cp2077", "Cyberpunk 2077", &["rpg"]))
+            .unwrap();
+        library
+            .add_game(&test_game("witcher3", "The Witcher 3: Wild Hunt", &["rpg"]))
+            .unwrap();
+        library
+            .add_game(&test_game(
+                "cs2",
+                "Counter-Strike 2",
+                &["competitive", "fps"],
+            ))
+            .unwrap();
+
+        let ids = |query: &str| -> Vec<String> {
+            library
+                .search_games(query)
+                .unwrap()
+                .into_iter()
+                .map(|game| game.id)
+                .collect()
+        };
+
+        // Partial tokens
+        assert_eq!(ids("cyber"), ["cp2077"]);
+        assert_eq!(ids("punk"), ["cp2077"]);
+        assert_eq!(ids("wild hun"), ["witcher3"]);
+        // Misspellings
+        assert_eq!(ids("cyberpnuk"), ["cp2077"]);
+        assert_eq!(ids("witchr"), ["witcher3"]);
+        assert_eq!(ids("counter strik"), ["cs2"]);
+        // Tags, ranked below name matches
+        assert_eq!(ids("competitive"), ["cs2"]);
+        assert_eq!(ids("rpg").len(), 2);
+        // Short queries and renamed games
+        assert_eq!(ids("fp"), ["cs2"]);
+        let mut game = library.get_game("cp2077").unwrap().unwrap();
+        game.name = "Phantom Liberty".to_string();
+        library.update_game(&game).unwrap();
+        assert!(ids("cyberpunk").is_empty());
+        assert_eq!(ids("phantom"), ["cp2077"]);
+        library.delete_game("cp2077").unwrap();
+        assert!(ids("phantom").is_empty());
+    }
 }
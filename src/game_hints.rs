@@ -0,0 +1,297 @@
+use crate::bolt_integration::GameCategory;
+use crate::utils::eout;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Hints shipped with GhostForge, see the file for its format
+const BUNDLED_HINTS: &str = include_str!("../data/game_hints.toml");
+
+static HINTS: OnceLock<GameHints> = OnceLock::new();
+
+/// Settings a game or category hint can carry, unset fields fall through
+/// to the next matching hint
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct HintSettings {
+    pub memory_mb: Option<u64>,
+    pub disk_mb: Option<u64>,
+    /// host, bridge or none
+    pub network_mode: Option<String>,
+    /// Used when ProtonDB has no recommendation
+    pub wine_version: Option<String>,
+}
+
+impl HintSettings {
+    /// Fill the fields still unset from `other`
+    fn or(mut self, other: &HintSettings) -> Self {
+        self.memory_mb = self.memory_mb.or(other.memory_mb);
+        self.disk_mb = self.disk_mb.or(other.disk_mb);
+        self.network_mode = self.network_mode.or_else(|| other.network_mode.clone());
+        self.wine_version = self.wine_version.or_else(|| other.wine_version.clone());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GameHint {
+    /// Words that must appear in order in the game's name
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub steam_appids: Vec<u32>,
+    /// Category slug, e.g. "competitive" or "aaa"
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(flatten)]
+    pub settings: HintSettings,
+}
+
+impl GameHint {
+    fn matches(&self, name_words: &[String], steam_appid: Option<u32>) -> bool {
+        if steam_appid.is_some_and(|appid| self.steam_appids.contains(&appid)) {
+            return true;
+        }
+        let Some(pattern) = &self.name else {
+            return false;
+        };
+        let pattern = words(pattern);
+        !pattern.is_empty()
+            && name_words
+                .windows(pattern.len())
+                .any(|window| window == pattern)
+    }
+}
+
+/// Data-driven game categories, container resources and network modes,
+/// from `data/game_hints.toml` and the user's `game_hints.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GameHints {
+    #[serde(default)]
+    pub default_wine_version: Option<String>,
+    /// Settings for every game in a category, keyed by category slug
+    #[serde(default)]
+    pub categories: HashMap<String, HintSettings>,
+    #[serde(default)]
+    pub games: Vec<GameHint>,
+}
+
+impl GameHints {
+    /// The bundled hints with the user's file layered on top, read once
+    pub fn load() -> &'static GameHints {
+        HINTS.get_or_init(|| {
+            let bundled = Self::parse(BUNDLED_HINTS).expect("bundled game hints are valid");
+            let path = Self::user_path();
+            if !path.exists() {
+                return bundled;
+            }
+            let user = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| Self::parse(&contents));
+            match user {
+                Ok(user) => {
+                    for problem in user.validate() {
+                        eout!("⚠️  {}: {}", path.display(), problem);
+                    }
+                    user.layered_over(bundled)
+                }
+                Err(e) => {
+                    eout!("⚠️  Ignoring {}: {}", path.display(), e);
+                    bundled
+                }
+            }
+        })
+    }
+
+    /// User hints, next to `config.toml`
+    pub fn user_path() -> PathBuf {
+        crate::config::Config::config_path().with_file_name("game_hints.toml")
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Combine with `base`, preferring this file's values
+    fn layered_over(mut self, base: GameHints) -> Self {
+        self.default_wine_version = self.default_wine_version.or(base.default_wine_version);
+        for (category, settings) in base.categories {
+            let merged = match self.categories.remove(&category) {
+                Some(own) => own.or(&settings),
+                None => settings,
+            };
+            self.categories.insert(category, merged);
+        }
+        self.games.extend(base.games);
+        self
+    }
+
+    fn matching<'a>(
+        &'a self,
+        name: &str,
+        steam_appid: Option<u32>,
+    ) -> impl Iterator<Item = &'a GameHint> {
+        let name_words = words(name);
+        self.games
+            .iter()
+            .filter(move |hint| hint.matches(&name_words, steam_appid))
+    }
+
+    pub fn category(&self, name: &str, steam_appid: Option<u32>) -> Option<GameCategory> {
+        self.matching(name, steam_appid)
+            .filter_map(|hint| hint.category.as_deref())
+            .find_map(GameCategory::from_slug)
+    }
+
+    /// Settings from every hint matching the game, then from its category
+    pub fn settings(&self, name: &str, steam_appid: Option<u32>) -> HintSettings {
+        let settings = self
+            .matching(name, steam_appid)
+            .fold(HintSettings::default(), |settings, hint| {
+                settings.or(&hint.settings)
+            });
+        match self
+            .category(name, steam_appid)
+            .and_then(|category| self.categories.get(category.slug()))
+        {
+            Some(category_settings) => settings.or(category_settings),
+            None => settings,
+        }
+    }
+
+    /// Entries naming an unknown category or network mode
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let categories = self
+            .categories
+            .keys()
+            .chain(self.games.iter().filter_map(|hint| hint.category.as_ref()));
+        for category in categories {
+            if GameCategory::from_slug(category).is_none() {
+                problems.push(format!("Unknown category: {}", category));
+            }
+        }
+        let settings = self
+            .categories
+            .values()
+            .chain(self.games.iter().map(|hint| &hint.settings));
+        for mode in settings.filter_map(|settings| settings.network_mode.as_ref()) {
+            if crate::container::NetworkMode::from_name(mode).is_none() {
+                problems.push(format!("Unknown network mode: {}", mode));
+            }
+        }
+        if self
+            .games
+            .iter()
+            .any(|hint| hint.name.is_none() && hint.steam_appids.is_empty())
+        {
+            problems.push("Game hint without a name or Steam app IDs".to_string());
+        }
+        problems
+    }
+}
+
+/// Lowercase alphanumeric words, so "Half-Life: Alyx" is half, life, alyx
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"
+default_wine_version = "GE-Proton9-1"
+
+[categories.aaa]
+memory_mb = 8192
+disk_mb = 20480
+
+[categories.competitive]
+network_mode = "host"
+
+[[games]]
+name = "Half-Life: Alyx"
+steam_appids = [546560]
+category = "vr"
+
+[[games]]
+name = "VR"
+category = "vr"
+
+[[games]]
+name = "Cyberpunk"
+steam_appids = [1091500]
+category = "aaa"
+disk_mb = 71680
+
+[[games]]
+steam_appids = [730]
+category = "competitive"
+"#;
+
+    #[test]
+    fn bundled_hints_are_valid() {
+        let hints = GameHints::parse(BUNDLED_HINTS).unwrap();
+        assert!(hints.validate().is_empty(), "{:?}", hints.validate());
+        assert!(hints.default_wine_version.is_some());
+    }
+
+    #[test]
+    fn matches_games_by_name_words_and_appids() {
+        let hints = GameHints::parse(FIXTURE).unwrap();
+
+        assert_eq!(
+            hints.category("half-life alyx", None),
+            Some(GameCategory::VR)
+        );
+        assert_eq!(
+            hints.category("Beat Saber VR", None),
+            Some(GameCategory::VR)
+        );
+        // "vr" inside another word isn't a match
+        assert_eq!(hints.category("Vampire Survivors", None), None);
+        assert_eq!(
+            hints.category("Whatever", Some(730)),
+            Some(GameCategory::Competitive)
+        );
+        assert_eq!(hints.category("Whatever", Some(731)), None);
+
+        // Game settings take precedence over their category's
+        let settings = hints.settings("Cyberpunk 2077", None);
+        assert_eq!(settings.memory_mb, Some(8192));
+        assert_eq!(settings.disk_mb, Some(71680));
+
+        let settings = hints.settings("Counter-Strike 2", Some(730));
+        assert_eq!(settings.network_mode.as_deref(), Some("host"));
+        assert_eq!(
+            hints.settings("Stardew Valley", None),
+            HintSettings::default()
+        );
+    }
+
+    #[test]
+    fn user_hints_override_bundled_ones() {
+        let user = GameHints::parse(
+            r#"
+[categories.aaa]
+memory_mb = 16384
+
+[[games]]
+name = "Cyberpunk"
+network_mode = "none"
+"#,
+        )
+        .unwrap();
+        let hints = user.layered_over(GameHints::parse(FIXTURE).unwrap());
+
+        let settings = hints.settings("Cyberpunk 2077", None);
+        assert_eq!(settings.memory_mb, Some(16384));
+        assert_eq!(settings.disk_mb, Some(71680));
+        assert_eq!(settings.network_mode.as_deref(), Some("none"));
+        assert_eq!(hints.default_wine_version.as_deref(), Some("GE-Proton9-1"));
+    }
+}
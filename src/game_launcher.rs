@@ -1,12 +1,20 @@
+use crate::utils::{eout, out};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::process::Command as AsyncCommand;
 
+/// How long `stop_game` waits after SIGTERM before killing what's left
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// How often playtime is saved while a game runs, bounding what is lost
+/// if forge itself is killed
+const PLAYTIME_CHECKPOINT: Duration = Duration::from_secs(5 * 60);
+
 #[derive(Debug)]
 pub struct GameLauncher {
     pub running_games: Arc<Mutex<HashMap<String, RunningGame>>>,
@@ -30,9 +38,174 @@ pub enum LauncherType {
     Wine,
     Steam,
     Proton,
+    /// Epic games started through the `legendary` CLI
+    Legendary,
     Custom,
 }
 
+/// Wine synchronization primitive. ntsync, a kernel driver since Linux 6.14,
+/// is the fastest, fsync needs futex_waitv (Linux 5.16) and esync a high
+/// open file limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    /// ntsync when the kernel has it, fsync otherwise. `general.enable_fsync`
+    /// and `enable_esync` can turn those off, see `configured`.
+    #[default]
+    Auto,
+    Ntsync,
+    Fsync,
+    Esync,
+    /// Wine's own wineserver-based synchronization
+    None,
+}
+
+impl SyncMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "ntsync" => Some(Self::Ntsync),
+            "fsync" => Some(Self::Fsync),
+            "esync" => Some(Self::Esync),
+            "none" | "off" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Ntsync => "ntsync",
+            Self::Fsync => "fsync",
+            Self::Esync => "esync",
+            Self::None => "none",
+        }
+    }
+
+    /// `Auto` limited by the config's sync settings: fsync when enabled,
+    /// then esync, then none
+    pub fn configured(self, general: &crate::config::GeneralConfig) -> Self {
+        match self {
+            Self::Auto if general.enable_fsync => Self::Auto,
+            Self::Auto if general.enable_esync => Self::Esync,
+            Self::Auto => Self::None,
+            mode => mode,
+        }
+    }
+
+    /// The mode to use on this system, falling back to fsync when ntsync
+    /// isn't available
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto if crate::utils::ntsync_available() => Self::Ntsync,
+            Self::Auto => Self::Fsync,
+            Self::Ntsync if !crate::utils::ntsync_available() => {
+                out!(
+                    "⚠️ ntsync requested but /dev/ntsync is missing (Linux 6.14+ with the ntsync module), using fsync"
+                );
+                Self::Fsync
+            }
+            mode => mode,
+        }
+    }
+
+    /// Variables selecting the resolved mode, for Proton and for Wine builds
+    /// with the sync patches
+    pub fn environment(self) -> Vec<(String, String)> {
+        let vars: &[(&str, &str)] = match self.resolve() {
+            Self::Ntsync => &[("PROTON_USE_NTSYNC", "1"), ("WINENTSYNC", "1")],
+            Self::Fsync | Self::Auto => &[("WINEFSYNC", "1")],
+            Self::Esync => &[("PROTON_NO_FSYNC", "1"), ("WINEESYNC", "1")],
+            Self::None => &[
+                ("PROTON_NO_ESYNC", "1"),
+                ("PROTON_NO_FSYNC", "1"),
+                ("WINEESYNC", "0"),
+                ("WINEFSYNC", "0"),
+            ],
+        };
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+}
+
+/// Placeholders available in launch arguments, environment values and
+/// profile launch options, so they don't hardcode one machine's paths:
+///
+/// - `${GAME_DIR}`: the game's install directory
+/// - `${GAME_EXE}`: the game's executable
+/// - `${GAME_ID}`, `${GAME_NAME}`: the game's library ID and name
+/// - `${WINEPREFIX}`: the game's Wine prefix
+/// - `${GAMES_DIR}`, `${CACHE_DIR}`: the games library and cache directories
+///
+/// Anything else, like `$HOME` or `$XDG_DATA_HOME`, comes from forge's own
+/// environment.
+pub fn launch_variables(
+    game: &crate::game::Game,
+    prefix: &Path,
+    paths: &crate::config::PathsConfig,
+) -> HashMap<String, String> {
+    let path = |path: &Path| path.to_string_lossy().to_string();
+    HashMap::from([
+        ("GAME_DIR".to_string(), path(&game.install_path)),
+        ("GAME_EXE".to_string(), path(&game.executable)),
+        ("GAME_ID".to_string(), game.id.clone()),
+        ("GAME_NAME".to_string(), game.name.clone()),
+        ("WINEPREFIX".to_string(), path(prefix)),
+        ("CACHE_DIR".to_string(), path(&paths.cache)),
+        ("GAMES_DIR".to_string(), path(&paths.games_library)),
+    ])
+}
+
+/// Expand `$VAR` and `${VAR}` in `value` from `variables`, then the
+/// process environment. Unknown variables are left as written and `$$` is
+/// a literal `$`.
+pub fn expand_variables(value: &str, variables: &HashMap<String, String>) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            expanded.push('$');
+            rest = after;
+            continue;
+        }
+
+        let (name, consumed) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+
+        let value = (!name.is_empty())
+            .then(|| {
+                variables
+                    .get(name)
+                    .cloned()
+                    .or_else(|| std::env::var(name).ok())
+            })
+            .flatten();
+        match value {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LaunchOptions {
     pub wine_version: Option<String>,
@@ -44,12 +217,35 @@ pub struct LaunchOptions {
     pub enable_vkd3d: bool,
     pub enable_gamemode: bool,
     pub enable_mangohud: bool,
+    /// Generated MangoHud.conf for the game
+    pub mangohud_config: Option<PathBuf>,
     pub enable_gamescope: bool,
+    /// Raw gamescope arguments, replacing `gamescope_config`
     pub gamescope_options: Option<String>,
-    pub nvidia_prime: bool,
-    pub amd_prime: Option<u8>,
+    /// Resolution, refresh rate and upscaling for gamescope. The primary
+    /// display's native mode is used when unset.
+    pub gamescope_config: Option<crate::graphics::GameScopeConfig>,
+    /// FSR/NIS upscaling, replacing the game's `upscaling`
+    pub upscaling: Option<crate::graphics::UpscalingSettings>,
+    /// Frame rate cap, replacing the game's `fps_limit`
+    pub fps_limit: Option<u32>,
+    /// Ask DXVK/VKD3D-Proton and Vulkan games for HDR output. The display
+    /// has to be switched to HDR separately.
+    pub enable_hdr: bool,
+    /// GPU to render on, replacing the game's `gpu_index`
+    pub gpu_index: Option<usize>,
+    /// Keep shader caches in the game's central cache directory, record
+    /// pipelines with Fossilize and precompile them before launching
+    pub shader_precache: bool,
+    pub sync_mode: SyncMode,
     pub cpu_affinity: Option<Vec<u32>>,
     pub nice_level: Option<i8>,
+    /// CPU governor to switch to while the game runs
+    pub cpu_governor: Option<String>,
+    /// NVIDIA power limit and clock offsets to apply while the game runs
+    pub nvidia_config: Option<crate::bolt_integration::NvidiaConfig>,
+    /// AMD Vulkan driver selection, power profile and fan curve
+    pub amd_config: Option<crate::bolt_integration::AmdConfig>,
     pub pre_launch_script: Option<String>,
     pub post_launch_script: Option<String>,
 }
@@ -66,18 +262,71 @@ impl Default for LaunchOptions {
             enable_vkd3d: false,
             enable_gamemode: true,
             enable_mangohud: false,
+            mangohud_config: None,
             enable_gamescope: false,
             gamescope_options: None,
-            nvidia_prime: false,
-            amd_prime: None,
+            gamescope_config: None,
+            upscaling: None,
+            fps_limit: None,
+            enable_hdr: false,
+            gpu_index: None,
+            shader_precache: false,
+            sync_mode: SyncMode::Auto,
             cpu_affinity: None,
             nice_level: None,
+            cpu_governor: None,
+            nvidia_config: None,
+            amd_config: None,
             pre_launch_script: None,
             post_launch_script: None,
         }
     }
 }
 
+impl LaunchOptions {
+    /// Whether launching with these options changes system settings that
+    /// have to be restored afterwards
+    pub fn wants_tuning(&self) -> bool {
+        self.cpu_governor.is_some()
+            || self.nvidia_config.is_some()
+            || self.amd_config.as_ref().is_some_and(|amd| amd.tunes_gpu())
+    }
+
+    /// Use the game's MangoHud settings, or `global` when it has none
+    pub fn apply_mangohud(&mut self, game: &crate::game::Game, global: bool) -> Result<()> {
+        match &game.mangohud {
+            Some(mangohud) if mangohud.enabled => {
+                self.enable_mangohud = true;
+                self.mangohud_config = Some(mangohud.write_config(&game.id)?);
+            }
+            Some(_) => self.enable_mangohud = false,
+            None => self.enable_mangohud = global,
+        }
+        Ok(())
+    }
+
+    /// Take system tuning, sync mode and shader pre-caching from an
+    /// optimization profile, and its upscaling and frame rate cap unless the
+    /// game has its own
+    pub fn apply_profile(
+        &mut self,
+        profile: crate::bolt_integration::OptimizationProfile,
+        game: &crate::game::Game,
+    ) {
+        self.cpu_governor = profile.cpu_governor;
+        self.shader_precache = profile.shader_precache;
+        self.sync_mode = profile.sync_mode;
+        if game.upscaling.is_none() {
+            self.upscaling = profile.upscaling;
+        }
+        if game.fps_limit.is_none() {
+            self.fps_limit = profile.fps_limit;
+        }
+        self.nvidia_config = profile.nvidia_config;
+        self.amd_config = profile.amd_config;
+    }
+}
+
 impl GameLauncher {
     pub fn new(config: crate::config::Config) -> Self {
         Self {
@@ -90,28 +339,204 @@ impl GameLauncher {
     pub async fn launch_game(
         &self,
         game: &crate::game::Game,
-        options: LaunchOptions,
+        mut options: LaunchOptions,
     ) -> Result<u32> {
-        println!("🚀 Launching {}...", game.name);
+        out!("🚀 Launching {}...", game.name);
+
+        // Launch scripts from the options replace the game's own
+        let script_timeout = Duration::from_secs(self.config.general.script_timeout_secs);
+        let pre_launch_script = options
+            .pre_launch_script
+            .clone()
+            .or_else(|| game.pre_launch_script.clone());
+        let post_launch_script = options
+            .post_launch_script
+            .clone()
+            .or_else(|| game.post_launch_script.clone());
+
+        // A failing pre-launch script aborts the launch
+        if let Some(script) = &pre_launch_script {
+            Self::run_script(script, "pre-launch", game, script_timeout)
+                .await
+                .map_err(|e| anyhow::anyhow!("Not launching {}: {}", game.name, e))?;
+        }
 
-        // Pre-launch script
-        if let Some(script) = &options.pre_launch_script {
-            self.run_script(script, "pre-launch").await?;
+        // Expand placeholders in the game's and the options' arguments and
+        // environment
+        let variables = launch_variables(
+            game,
+            &self.resolve_prefix(game, &options),
+            &self.config.paths,
+        );
+        let mut expanded_game = game.clone();
+        let arguments = expanded_game
+            .launch_arguments
+            .iter_mut()
+            .chain(options.launch_arguments.iter_mut());
+        let environment = expanded_game
+            .environment_variables
+            .iter_mut()
+            .map(|(_, value)| value)
+            .chain(options.environment_variables.values_mut());
+        for value in arguments.chain(environment) {
+            *value = expand_variables(value, &variables);
         }
+        let game = &expanded_game;
 
         // Determine launcher type
         let launcher_type = self.determine_launcher_type(game, &options);
 
+        // The cap is applied by the wrappers around the launch command
+        options.fps_limit = options.fps_limit.or(game.fps_limit);
+        if let Some(gamemode) = game.gamemode {
+            options.enable_gamemode = gamemode;
+        }
+
+        // Gamescope upscaling runs the game in gamescope, Wine FSR only
+        // needs its variables set below
+        let upscaling = options.upscaling.clone().or_else(|| game.upscaling.clone());
+        if let Some(upscaling) = &upscaling {
+            if launcher_type == LauncherType::Steam {
+                out!(
+                    "⚠️ Steam starts {} itself, set up {} in its Steam launch options instead",
+                    game.name,
+                    upscaling.method.name()
+                );
+            } else if options.gamescope_options.is_none() {
+                let mut config = options
+                    .gamescope_config
+                    .clone()
+                    .unwrap_or_else(crate::graphics::GameScopeConfig::for_primary_display);
+                if upscaling.apply_to_gamescope(&mut config) {
+                    options.enable_gamescope = true;
+                    options.gamescope_config = Some(config);
+                }
+            }
+        }
+
+        // Set up the prefix on first launch, then apply pinned DXVK/VKD3D
+        // versions before it is used
+        match launcher_type {
+            LauncherType::Wine => {
+                let prefix = self.resolve_prefix(game, &options);
+                if !prefix.join("system.reg").exists() {
+                    crate::prefix::PrefixManager::create(
+                        &prefix,
+                        &self.config.wine.default_arch,
+                        &self.config.wine.default_windows_version,
+                        options
+                            .wine_version
+                            .as_deref()
+                            .or(game.wine_version.as_deref()),
+                    )?;
+                }
+                self.apply_pinned_graphics_layers(game, &prefix)?;
+            }
+            LauncherType::Proton if game.dxvk_version.is_some() || game.vkd3d_version.is_some() => {
+                out!(
+                    "⚠️ Proton ships its own DXVK/VKD3D-Proton, ignoring pinned versions for {}",
+                    game.name
+                );
+            }
+            _ => {}
+        }
+
         // Build the launch command
         let mut cmd = match launcher_type {
             LauncherType::Native => self.build_native_command(game, &options)?,
             LauncherType::Wine => self.build_wine_command(game, &options).await?,
             LauncherType::Proton => self.build_proton_command(game, &options).await?,
             LauncherType::Steam => self.build_steam_command(game, &options)?,
+            LauncherType::Legendary => self.build_legendary_command(game, &options).await?,
             LauncherType::Custom => self.build_custom_command(game, &options)?,
         };
 
-        // Set environment variables
+        // Warn about anti-cheat that blocks Linux, and point Proton at the
+        // runtimes of anti-cheat that supports it. Steam sets those up itself.
+        if launcher_type != LauncherType::Native
+            && let Some(anti_cheat) = crate::anticheat::detect(game)
+        {
+            anti_cheat.warn(&game.name);
+            if launcher_type != LauncherType::Steam && !anti_cheat.status.is_incompatible() {
+                let (env, missing) = anti_cheat.proton_runtimes();
+                for (key, value) in env {
+                    cmd.env(key, value);
+                }
+                for runtime in missing {
+                    out!(
+                        "⚠️ {} needs the {}, install it with Steam (steam://install/{})",
+                        game.name,
+                        runtime.name,
+                        runtime.appid
+                    );
+                }
+            }
+        }
+
+        // Set environment variables, per-launch options override the game's own
+        if options.enable_hdr {
+            for (key, value) in crate::display::hdr_environment() {
+                cmd.env(key, value);
+            }
+        }
+        if let Some(amd) = &options.amd_config {
+            for (key, value) in amd.environment() {
+                cmd.env(key, value);
+            }
+        }
+        // Steam applies the sync mode set in the game's launch options itself
+        if !matches!(launcher_type, LauncherType::Native | LauncherType::Steam) {
+            let sync_mode = options.sync_mode.configured(&self.config.general);
+            for (key, value) in sync_mode.environment() {
+                cmd.env(key, value);
+            }
+            for (key, value) in upscaling.iter().flat_map(|u| u.environment()) {
+                cmd.env(key, value);
+            }
+        }
+        // Render on the chosen GPU of hybrid systems, the discrete one when
+        // PRIME render offload is turned on
+        match crate::utils::prime_environment(
+            options.gpu_index.or(game.gpu_index),
+            self.config.gpu.nvidia_prime_render_offload,
+        ) {
+            Ok(env) => {
+                for (key, value) in env {
+                    cmd.env(key, value);
+                }
+            }
+            Err(e) => out!("⚠️ Not selecting a GPU for {}: {}", game.name, e),
+        }
+        if let Some(device) = &game.vulkan_device {
+            for (key, value) in crate::utils::vulkan_device_environment(device) {
+                cmd.env(key, value);
+            }
+        }
+        let cache_dir =
+            crate::graphics::central_state_cache_dir(&self.config.paths.cache, &game.id);
+        let mut shader_cache = None;
+        if options.shader_precache && launcher_type == LauncherType::Steam {
+            out!(
+                "⚠️ Steam pre-caches shaders for {} itself, enable Shader Pre-Caching in Steam's settings",
+                game.name
+            );
+        } else if options.shader_precache {
+            std::fs::create_dir_all(crate::graphics::fossilize_dump_dir(&cache_dir))?;
+            if let Err(e) = crate::graphics::precompile_shaders(&cache_dir).await {
+                out!("⚠️ Shader precompilation failed: {}", e);
+            }
+            for (key, value) in crate::graphics::shader_precache_environment(&cache_dir) {
+                cmd.env(key, value);
+            }
+            shader_cache = Some(crate::graphics::ShaderCacheStats::collect(&cache_dir));
+        } else if self.config.general.central_state_cache {
+            std::fs::create_dir_all(&cache_dir)?;
+            cmd.env("DXVK_STATE_CACHE_PATH", &cache_dir);
+            cmd.env("VKD3D_SHADER_CACHE_PATH", &cache_dir);
+        }
+        for (key, value) in &game.environment_variables {
+            cmd.env(key, value);
+        }
         for (key, value) in &options.environment_variables {
             cmd.env(key, value);
         }
@@ -123,68 +548,204 @@ impl GameLauncher {
             cmd.current_dir(&game.install_path);
         }
 
-        // Launch with proper I/O handling
-        cmd.stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        // Wine and DXVK are chatty, the game's output goes to a log file so
+        // nothing has to drain it while the game runs
+        cmd.stdin(Stdio::null());
+        match self.open_game_log(game) {
+            Ok((log, path)) => {
+                let stderr = log.try_clone()?;
+                cmd.stdout(log).stderr(stderr);
+                out!("📝 Game output: {}", path.display());
+            }
+            Err(e) => {
+                eout!("⚠️ Could not open a log for {}: {}", game.name, e);
+                cmd.stdout(Stdio::null()).stderr(Stdio::null());
+            }
+        }
+
+        // Tune the system for the session, the previous settings are saved
+        // so they can be restored when the game exits
+        let tuned = self.apply_tuning(game, &options, &launcher_type);
 
         // Execute the command
-        let mut child = cmd.spawn()?;
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                if tuned {
+                    Self::restore_tuning(&game.id);
+                }
+                return Err(e.into());
+            }
+        };
         let pid = child.id().unwrap_or(0);
 
-        println!("✅ {} launched with PID {}", game.name, pid);
+        out!("✅ {} launched with PID {}", game.name, pid);
+
+        // Steam records its own playtime and the steam command exits as
+        // soon as the game is handed off
+        let track_playtime = launcher_type != LauncherType::Steam;
 
-        // Register the running game
+        // Register the running game, also on disk so other forge processes
+        // can list and stop it
+        let wine_prefix = match launcher_type {
+            LauncherType::Wine => Some(self.resolve_prefix(game, &options)),
+            _ => options.wine_prefix.clone(),
+        };
         let running_game = RunningGame {
             game_id: game.id.clone(),
             game_name: game.name.clone(),
             pid: Some(pid),
             start_time: Utc::now(),
-            wine_prefix: options.wine_prefix.clone(),
+            wine_prefix,
             proton_version: options.wine_version.clone(),
             launcher_type,
         };
 
+        if let Err(e) = RunningGameRegistry::update(|registry| {
+            registry.games.insert(game.id.clone(), running_game.clone());
+        }) {
+            eout!("⚠️ Could not record {} as running: {}", game.name, e);
+        }
         {
             let mut running_games = self.running_games.lock().unwrap();
             running_games.insert(game.id.clone(), running_game);
         }
 
+        let mut playtime = track_playtime
+            .then(|| PlaytimeTracker::start(self.config.paths.database.clone(), &game.id));
+
         // Monitor the game in the background
         let game_id = game.id.clone();
+        let script_game = game.clone();
         let running_games_clone = Arc::clone(&self.running_games);
-        let post_launch_script = options.post_launch_script.clone();
 
         tokio::spawn(async move {
-            match child.wait().await {
-                Ok(status) => {
-                    let exit_code = status.code().unwrap_or(-1);
-                    println!("🎮 Game {} exited with code {}", game_id, exit_code);
-
-                    // Remove from running games
-                    {
-                        let mut running_games = running_games_clone.lock().unwrap();
-                        running_games.remove(&game_id);
-                    }
-
-                    // Post-launch script
-                    if let Some(script) = post_launch_script {
-                        if let Err(e) = Self::run_script_blocking(&script, "post-launch") {
-                            eprintln!("⚠️ Post-launch script failed: {}", e);
+            let mut checkpoint = tokio::time::interval(PLAYTIME_CHECKPOINT);
+            checkpoint.tick().await;
+            let result = loop {
+                tokio::select! {
+                    result = child.wait() => break result,
+                    _ = checkpoint.tick() => {
+                        if let Some(playtime) = &mut playtime {
+                            playtime.record();
                         }
                     }
                 }
-                Err(e) => {
-                    eprintln!("❌ Game {} process error: {}", game_id, e);
-                    let mut running_games = running_games_clone.lock().unwrap();
-                    running_games.remove(&game_id);
+            };
+            if let Some(playtime) = &mut playtime {
+                playtime.record();
+                out!(
+                    "📊 Played {} for {} minutes",
+                    game_id,
+                    playtime.recorded_minutes
+                );
+            }
+
+            // Growth of the cache files only approximates what was compiled,
+            // drivers may rewrite or pack several shaders into one file
+            if let Some(before) = shader_cache {
+                let after = crate::graphics::ShaderCacheStats::collect(&cache_dir);
+                out!(
+                    "🧊 Shader cache (estimate): {} cache files before launch, ~{} new files written this session ({} KB)",
+                    before.entries,
+                    after.entries.saturating_sub(before.entries),
+                    after.bytes.saturating_sub(before.bytes) / 1024
+                );
+            }
+
+            // Restore tuning whether the game exited cleanly or crashed
+            if tuned {
+                Self::restore_tuning(&game_id);
+            }
+
+            match result {
+                Ok(status) => {
+                    let exit_code = status.code().unwrap_or(-1);
+                    out!("🎮 Game {} exited with code {}", game_id, exit_code);
                 }
+                Err(e) => eout!("❌ Game {} process error: {}", game_id, e),
+            }
+
+            // Post-launch script, the game stays registered until it is
+            // done so wait_for_exit covers it
+            if let Some(script) = post_launch_script
+                && let Err(e) =
+                    Self::run_script(&script, "post-launch", &script_game, script_timeout).await
+            {
+                eout!("⚠️ Post-launch script failed: {}", e);
             }
+
+            running_games_clone.lock().unwrap().remove(&game_id);
+            let _ = RunningGameRegistry::update(|registry| {
+                registry.games.remove(&game_id);
+            });
         });
 
         Ok(pid)
     }
 
+    /// Create the log file the game's output is written to, replacing the
+    /// previous session's
+    fn open_game_log(&self, game: &crate::game::Game) -> Result<(std::fs::File, PathBuf)> {
+        std::fs::create_dir_all(&self.config.paths.logs)?;
+        let path = self.config.paths.logs.join(format!("{}.log", game.id));
+        let file = std::fs::File::create(&path)?;
+        Ok((file, path))
+    }
+
+    /// Wait until a game started by this launcher has exited
+    pub async fn wait_for_exit(&self, game_id: &str) {
+        while self.running_games.lock().unwrap().contains_key(game_id) {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    /// Apply the CPU governor and GPU tuning requested in `options`, returning
+    /// whether a tuning session was started
+    fn apply_tuning(
+        &self,
+        game: &crate::game::Game,
+        options: &LaunchOptions,
+        launcher_type: &LauncherType,
+    ) -> bool {
+        if !options.wants_tuning() {
+            return false;
+        }
+
+        // The steam command returns as soon as the game is handed off, so
+        // there is no process to restore the settings after
+        if *launcher_type == LauncherType::Steam {
+            out!("⚠️ System tuning is not applied to games launched through Steam");
+            return false;
+        }
+
+        out!("⚡ Applying system tuning...");
+        // Profiles may carry settings for both vendors, only apply what
+        // matches the installed GPU
+        let nvidia = options
+            .nvidia_config
+            .as_ref()
+            .filter(|_| crate::tuning::has_nvidia_gpu());
+        let amd = options
+            .amd_config
+            .as_ref()
+            .filter(|_| crate::tuning::amd_gpu_device().is_some());
+        let (snapshot, report) = crate::tuning::apply(options.cpu_governor.as_deref(), nvidia, amd);
+        report.print();
+
+        if let Err(e) = crate::tuning::TuningState::begin_session(&game.id, &snapshot) {
+            eout!("⚠️ Failed to save tuning state: {}", e);
+        }
+        true
+    }
+
+    fn restore_tuning(game_id: &str) {
+        match crate::tuning::TuningState::end_session(game_id) {
+            Ok(report) => report.print(),
+            Err(e) => eout!("⚠️ Failed to restore system settings: {}", e),
+        }
+    }
+
     fn determine_launcher_type(
         &self,
         game: &crate::game::Game,
@@ -192,13 +753,19 @@ impl GameLauncher {
     ) -> LauncherType {
         if game.launcher.as_deref() == Some("Steam") {
             LauncherType::Steam
+        } else if game.launcher.as_deref() == Some("Epic")
+            && game
+                .launcher_id
+                .as_deref()
+                .is_some_and(crate::launcher::legendary_installed)
+        {
+            LauncherType::Legendary
         } else if options.wine_version.is_some() || game.wine_version.is_some() {
             if options
                 .wine_version
-                .as_ref()
-                .or(game.wine_version.as_ref())
-                .map(|v| v.contains("Proton"))
-                .unwrap_or(false)
+                .as_deref()
+                .or(game.wine_version.as_deref())
+                .is_some_and(is_proton)
             {
                 LauncherType::Proton
             } else {
@@ -211,6 +778,59 @@ impl GameLauncher {
         }
     }
 
+    fn resolve_prefix(&self, game: &crate::game::Game, options: &LaunchOptions) -> PathBuf {
+        options
+            .wine_prefix
+            .clone()
+            .or_else(|| game.wine_prefix.clone())
+            .unwrap_or_else(|| game.default_prefix(&self.config.wine))
+    }
+
+    /// Copy the game's pinned DXVK/VKD3D-Proton builds into its prefix
+    fn apply_pinned_graphics_layers(&self, game: &crate::game::Game, prefix: &Path) -> Result<()> {
+        use crate::graphics::{GraphicsLayerType, GraphicsManager};
+
+        let pins = [
+            (GraphicsLayerType::DXVK, "dxvk", &game.dxvk_version),
+            (
+                GraphicsLayerType::VKD3DProton,
+                "vkd3d-proton",
+                &game.vkd3d_version,
+            ),
+        ];
+
+        if pins.iter().all(|(_, _, version)| version.is_none()) {
+            return Ok(());
+        }
+
+        let mut manager = GraphicsManager::new(GraphicsManager::default_dir())?;
+        manager.set_dry_run(false);
+
+        for (layer_type, label, version) in pins {
+            let Some(version) = version else {
+                continue;
+            };
+
+            let layer = manager
+                .find_installed(&layer_type, version)?
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{} is pinned to {} {} but it is not installed. Run 'forge graphics install {}-{}' first.",
+                        game.name,
+                        label,
+                        version,
+                        label,
+                        version
+                    )
+                })?;
+
+            out!("🎨 Applying pinned {}...", layer.name);
+            manager.install_to_prefix(&layer, prefix)?;
+        }
+
+        Ok(())
+    }
+
     fn build_native_command(
         &self,
         game: &crate::game::Game,
@@ -256,20 +876,9 @@ impl GameLauncher {
         let mut cmd = AsyncCommand::new(wine_bin);
 
         // Set Wine prefix
-        let prefix = options
-            .wine_prefix
-            .as_ref()
-            .or(game.wine_prefix.as_ref())
-            .cloned()
-            .unwrap_or_else(|| {
-                dirs::home_dir()
-                    .unwrap_or_default()
-                    .join("Games")
-                    .join(&game.name)
-            });
+        let prefix = self.resolve_prefix(game, options);
 
         cmd.env("WINEPREFIX", &prefix);
-        cmd.env("WINEARCH", "win64");
 
         // Enable DXVK if requested
         if options.enable_dxvk {
@@ -318,17 +927,7 @@ impl GameLauncher {
         let mut cmd = AsyncCommand::new(proton_bin);
 
         // Set Steam compatibility data
-        let prefix = options
-            .wine_prefix
-            .as_ref()
-            .or(game.wine_prefix.as_ref())
-            .cloned()
-            .unwrap_or_else(|| {
-                dirs::home_dir()
-                    .unwrap_or_default()
-                    .join("Games")
-                    .join(&game.name)
-            });
+        let prefix = self.resolve_prefix(game, options);
 
         cmd.env("STEAM_COMPAT_DATA_PATH", &prefix);
         cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", "/home/user/.steam");
@@ -342,12 +941,6 @@ impl GameLauncher {
             cmd.env("PROTON_USE_VKD3D", "1");
         }
 
-        // Enable NVIDIA features if available
-        if options.nvidia_prime {
-            cmd.env("__NV_PRIME_RENDER_OFFLOAD", "1");
-            cmd.env("__GLX_VENDOR_LIBRARY_NAME", "nvidia");
-        }
-
         cmd.arg("run");
         cmd.arg(&game.executable);
 
@@ -367,10 +960,24 @@ impl GameLauncher {
     fn build_steam_command(
         &self,
         game: &crate::game::Game,
-        _options: &LaunchOptions,
+        options: &LaunchOptions,
     ) -> Result<AsyncCommand> {
         let mut cmd = AsyncCommand::new("steam");
 
+        if options.enable_gamescope {
+            out!(
+                "⚠️ Steam starts {} itself, add gamescope to its Steam launch options instead",
+                game.name
+            );
+        }
+        if let Some(limit) = options.fps_limit {
+            out!(
+                "⚠️ Steam starts {} itself, add 'MANGOHUD_CONFIG=fps_limit={} mangohud %command%' to its Steam launch options instead",
+                game.name,
+                limit
+            );
+        }
+
         if let Some(launcher_id) = &game.launcher_id {
             cmd.arg("-applaunch").arg(launcher_id);
         } else {
@@ -380,6 +987,50 @@ impl GameLauncher {
         Ok(cmd)
     }
 
+    /// `legendary launch` with the game's Wine or Proton build, legendary's
+    /// own configuration being used when the game has none
+    async fn build_legendary_command(
+        &self,
+        game: &crate::game::Game,
+        options: &LaunchOptions,
+    ) -> Result<AsyncCommand> {
+        use crate::launcher::LegendaryRunner;
+
+        let app_name = game
+            .launcher_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Epic game missing app name"))?;
+
+        let mut cmd = AsyncCommand::new("legendary");
+        let runner = match options.wine_version.as_ref().or(game.wine_version.as_ref()) {
+            None => LegendaryRunner::Default,
+            Some(version) if is_proton(version) => {
+                cmd.env("STEAM_COMPAT_DATA_PATH", self.resolve_prefix(game, options));
+                LegendaryRunner::Proton(self.find_proton_binary(version).await?)
+            }
+            Some(version) => LegendaryRunner::Wine(self.find_wine_binary(version).await?),
+        };
+        let prefix = options.wine_prefix.as_ref().or(game.wine_prefix.as_ref());
+
+        cmd.args(crate::launcher::legendary_launch_args(
+            app_name,
+            &runner,
+            prefix.map(PathBuf::as_path),
+        ));
+        // legendary passes arguments it doesn't know on to the game
+        for arg in game
+            .launch_arguments
+            .iter()
+            .chain(&options.launch_arguments)
+        {
+            cmd.arg(arg);
+        }
+
+        self.wrap_with_performance_tools(&mut cmd, options)?;
+
+        Ok(cmd)
+    }
+
     fn build_custom_command(
         &self,
         game: &crate::game::Game,
@@ -433,48 +1084,75 @@ impl GameLauncher {
             wrapper_parts.push("gamemoderun".to_string());
         }
 
-        // MangoHud
-        if options.enable_mangohud && which::which("mangohud").is_ok() {
-            wrapper_parts.push("mangohud".to_string());
-        }
-
-        // GameScope
+        // GameScope, everything after it runs inside the gamescope session
         if options.enable_gamescope {
-            wrapper_parts.push("gamescope".to_string());
+            if which::which("gamescope").is_err() {
+                return Err(anyhow::anyhow!("gamescope is not installed"));
+            }
             if let Some(gamescope_opts) = &options.gamescope_options {
+                wrapper_parts.push("gamescope".to_string());
                 for opt in gamescope_opts.split_whitespace() {
                     wrapper_parts.push(opt.to_string());
                 }
+                if wrapper_parts.last().map(String::as_str) != Some("--") {
+                    wrapper_parts.push("--".to_string());
+                }
             } else {
-                // Default GameScope options for better gaming
-                wrapper_parts.extend(
-                    ["-W", "1920", "-H", "1080", "-f", "--force-grab-cursor"]
-                        .iter()
-                        .map(|s| s.to_string()),
-                );
+                let mut config = options
+                    .gamescope_config
+                    .clone()
+                    .unwrap_or_else(crate::graphics::GameScopeConfig::for_primary_display);
+                config.hdr |= options.enable_hdr;
+                wrapper_parts.extend(config.args());
             }
         }
 
-        // NVIDIA Prime
-        if options.nvidia_prime {
-            cmd.env("__NV_PRIME_RENDER_OFFLOAD", "1");
-            cmd.env("__GLX_VENDOR_LIBRARY_NAME", "nvidia");
+        // Frame rate cap, through MangoHud when it is installed (without its
+        // overlay unless that is on) and libstrangle otherwise
+        let mangohud_installed = which::which("mangohud").is_ok();
+        let mut mangohud_options = Vec::new();
+        if let Some(limit) = options.fps_limit {
+            if mangohud_installed {
+                if !options.enable_mangohud {
+                    mangohud_options.push("no_display".to_string());
+                }
+                mangohud_options.push(format!("fps_limit={}", limit));
+            } else if which::which("strangle").is_ok() {
+                wrapper_parts.push("strangle".to_string());
+                wrapper_parts.push(limit.to_string());
+            } else {
+                out!(
+                    "⚠️ Install MangoHud or libstrangle to limit the frame rate to {} FPS",
+                    limit
+                );
+            }
         }
 
-        // AMD Prime
-        if let Some(gpu_id) = options.amd_prime {
-            cmd.env("DRI_PRIME", gpu_id.to_string());
+        // MangoHud
+        if mangohud_installed && (options.enable_mangohud || !mangohud_options.is_empty()) {
+            wrapper_parts.push("mangohud".to_string());
+            cmd.env("MANGOHUD", "1");
+            if options.enable_mangohud {
+                // MANGOHUD_CONFIG replaces the config file, the game's or the
+                // user's MangoHud.conf, unless it asks for it to be read too
+                if let Some(config) = &options.mangohud_config {
+                    cmd.env("MANGOHUD_CONFIGFILE", config);
+                }
+                mangohud_options.insert(0, "read_cfg".to_string());
+
+                // Log frame times so the VRR monitor can show live stats. A
+                // MANGOHUD_CONFIG in the game's environment replaces this.
+                if let Some(log_config) = crate::vrr_monitor::mangohud_log_config() {
+                    mangohud_options.push(log_config);
+                }
+            }
+            if !mangohud_options.is_empty() {
+                cmd.env("MANGOHUD_CONFIG", mangohud_options.join(","));
+            }
         }
 
-        // If we have wrapper commands, we need to restructure the command
         if !wrapper_parts.is_empty() {
-            // This is complex to implement with tokio::process::Command
-            // For now, we'll set environment variables to enable the features
-            // In a real implementation, you'd want to use a shell wrapper
-
-            if options.enable_gamemode {
-                cmd.env("LD_PRELOAD", "libgamemodeauto.so.0");
-            }
+            *cmd = wrap_command(cmd, &wrapper_parts);
         }
 
         Ok(())
@@ -486,6 +1164,12 @@ impl GameLauncher {
             return Ok(PathBuf::from("wine"));
         }
 
+        // Wine builds imported from other tools are referenced by path
+        let build_path = Path::new(wine_version).join("bin/wine");
+        if build_path.is_absolute() && build_path.exists() {
+            return Ok(build_path);
+        }
+
         // Check in wine versions directory
         let wine_path = self
             .config
@@ -549,93 +1233,368 @@ impl GameLauncher {
         self.running_games.lock().unwrap().clone()
     }
 
+    /// Stop a game started by any forge process: SIGTERM to its whole
+    /// process tree, then SIGKILL for whatever is left after a grace period
     pub async fn stop_game(&self, game_id: &str) -> Result<()> {
-        let running_game = { self.running_games.lock().unwrap().get(game_id).cloned() };
-
-        if let Some(running_game) = running_game {
-            if let Some(pid) = running_game.pid {
-                println!("🛑 Stopping {}...", running_game.game_name);
-
-                // Try graceful shutdown first
-                if let Ok(_) = nix::sys::signal::kill(
-                    nix::unistd::Pid::from_raw(pid as i32),
-                    nix::sys::signal::Signal::SIGTERM,
-                ) {
-                    // Wait a bit for graceful shutdown
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                }
+        let running_game = RunningGameRegistry::load()?
+            .games
+            .remove(game_id)
+            .or_else(|| self.running_games.lock().unwrap().get(game_id).cloned())
+            .ok_or_else(|| anyhow::anyhow!("{} is not running", game_id))?;
+
+        // Collect the tree first, children get reparented once the game exits
+        let processes = if running_game.launcher_type == LauncherType::Steam {
+            self.steam_game_processes(&running_game)?
+        } else {
+            let pid = running_game.pid.ok_or_else(|| {
+                anyhow::anyhow!("No process recorded for {}", running_game.game_name)
+            })?;
+            process_tree(pid)
+        };
 
-                // Force kill if still running
-                let _ = nix::sys::signal::kill(
-                    nix::unistd::Pid::from_raw(pid as i32),
-                    nix::sys::signal::Signal::SIGKILL,
-                );
+        out!("🛑 Stopping {}...", running_game.game_name);
+        signal_processes(&processes, nix::sys::signal::Signal::SIGTERM);
 
-                // Remove from running games
-                self.running_games.lock().unwrap().remove(game_id);
-                println!("✅ {} stopped", running_game.game_name);
-            }
+        let deadline = tokio::time::Instant::now() + STOP_GRACE_PERIOD;
+        while processes.iter().any(|&pid| process_alive(pid))
+            && tokio::time::Instant::now() < deadline
+        {
+            tokio::time::sleep(Duration::from_millis(250)).await;
         }
 
+        let remaining: Vec<u32> = processes
+            .into_iter()
+            .filter(|&pid| process_alive(pid))
+            .collect();
+        if !remaining.is_empty() {
+            out!("  ⚠️  Force killing {} processes", remaining.len());
+            signal_processes(&remaining, nix::sys::signal::Signal::SIGKILL);
+        }
+
+        // wineserver detaches from the game and keeps the prefix busy
+        if let Some(prefix) = &running_game.wine_prefix
+            && which::which("wineserver").is_ok()
+        {
+            let _ = AsyncCommand::new("wineserver")
+                .arg("-k")
+                .env("WINEPREFIX", prefix)
+                .status()
+                .await;
+        }
+
+        self.running_games.lock().unwrap().remove(game_id);
+        RunningGameRegistry::update(|registry| {
+            registry.games.remove(game_id);
+        })?;
+        out!("✅ {} stopped", running_game.game_name);
+
         Ok(())
     }
 
-    async fn run_script(&self, script: &str, script_type: &str) -> Result<()> {
-        println!("📜 Running {} script...", script_type);
+    /// The recorded process only hands a Steam game to the Steam client, and
+    /// its tree can be the client itself. Steam tags the game's processes
+    /// with `SteamAppId`, so only those are stopped.
+    fn steam_game_processes(&self, running_game: &RunningGame) -> Result<Vec<u32>> {
+        let appid = crate::game::GameLibrary::new(&self.config.paths.database)?
+            .get_game(&running_game.game_id)?
+            .and_then(|game| game.steam_appid())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} has no Steam app ID, stop it from Steam",
+                    running_game.game_name
+                )
+            })?;
+
+        let tag = format!("SteamAppId={}", appid);
+        let own_pid = std::process::id();
+        let mut processes = Vec::new();
+        for entry in std::fs::read_dir("/proc")?.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                continue;
+            };
+            let Ok(environ) = std::fs::read(entry.path().join("environ")) else {
+                continue;
+            };
+            if pid != own_pid && environ.split(|&b| b == 0).any(|var| var == tag.as_bytes()) {
+                processes.push(pid);
+            }
+        }
 
-        let mut cmd = AsyncCommand::new("bash");
-        cmd.arg("-c").arg(script);
+        if processes.is_empty() {
+            self.running_games
+                .lock()
+                .unwrap()
+                .remove(&running_game.game_id);
+            RunningGameRegistry::update(|registry| {
+                registry.games.remove(&running_game.game_id);
+            })?;
+            return Err(anyhow::anyhow!(
+                "No process of {} found, it may have exited. \
+                 If it's still running, stop it from Steam",
+                running_game.game_name
+            ));
+        }
+        Ok(processes)
+    }
 
-        let status = cmd.status().await?;
+    /// Run a launch script through bash in the game's directory, printing
+    /// its output. Scripts that fail or outlive `timeout` are an error.
+    async fn run_script(
+        script: &str,
+        script_type: &str,
+        game: &crate::game::Game,
+        timeout: Duration,
+    ) -> Result<()> {
+        out!("📜 Running {} script...", script_type);
 
-        if status.success() {
-            println!("✅ {} script completed successfully", script_type);
-        } else {
-            println!(
-                "⚠️ {} script exited with code {:?}",
+        let mut cmd = AsyncCommand::new("bash");
+        if game.install_path.is_dir() {
+            cmd.current_dir(&game.install_path);
+        }
+        let child = cmd
+            .arg("-c")
+            .arg(script)
+            .env("GHOSTFORGE_GAME_ID", &game.id)
+            .env("GHOSTFORGE_GAME_NAME", &game.name)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // Dropping the child on timeout kills the script
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let output = tokio::time::timeout(timeout, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "{} script timed out after {}s",
+                    script_type,
+                    timeout.as_secs()
+                )
+            })??;
+
+        for line in String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+        {
+            println!("  │ {}", line);
+        }
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "{} script exited with code {}",
                 script_type,
-                status.code()
-            );
+                output.status.code().unwrap_or(-1)
+            ));
         }
 
+        out!("✅ {} script completed successfully", script_type);
         Ok(())
     }
+}
 
-    fn run_script_blocking(script: &str, script_type: &str) -> Result<()> {
-        println!("📜 Running {} script...", script_type);
+/// Adds a session's playtime to the library as it goes
+struct PlaytimeTracker {
+    database: PathBuf,
+    game_id: String,
+    started: std::time::Instant,
+    recorded_minutes: u64,
+}
 
-        let status = Command::new("bash").arg("-c").arg(script).status()?;
+impl PlaytimeTracker {
+    /// Start a session, marking the game as played now
+    fn start(database: PathBuf, game_id: &str) -> Self {
+        let tracker = Self {
+            database,
+            game_id: game_id.to_string(),
+            started: std::time::Instant::now(),
+            recorded_minutes: 0,
+        };
+        tracker.save(0);
+        tracker
+    }
 
-        if status.success() {
-            println!("✅ {} script completed successfully", script_type);
-        } else {
-            println!(
-                "⚠️ {} script exited with code {:?}",
-                script_type,
-                status.code()
-            );
+    /// Save the whole minutes played since the last record
+    fn record(&mut self) {
+        let minutes = self.started.elapsed().as_secs() / 60;
+        if minutes > self.recorded_minutes {
+            self.save(minutes - self.recorded_minutes);
+            self.recorded_minutes = minutes;
+        }
+    }
+
+    fn save(&self, additional_minutes: u64) {
+        let result = crate::game::GameLibrary::new(&self.database)
+            .and_then(|library| library.update_playtime(&self.game_id, additional_minutes));
+        if let Err(e) = result {
+            eout!("⚠️ Could not save playtime for {}: {}", self.game_id, e);
         }
+    }
+}
 
+/// Games launched by any forge process, kept in the runtime directory so
+/// `forge game running` and `forge game stop` work from another shell
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunningGameRegistry {
+    pub games: HashMap<String, RunningGame>,
+}
+
+impl RunningGameRegistry {
+    pub fn path() -> Result<PathBuf> {
+        Ok(dirs::runtime_dir()
+            .or_else(dirs::cache_dir)
+            .ok_or_else(|| anyhow::anyhow!("Cannot find runtime directory"))?
+            .join("ghostforge")
+            .join("running-games.json"))
+    }
+
+    /// Load the registry, dropping games whose process is gone, e.g. when
+    /// the forge process that launched them was killed
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let mut registry: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        registry
+            .games
+            .retain(|_, game| game.pid.is_some_and(process_alive));
+        Ok(registry)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
         Ok(())
     }
 
-    /// Update playtime for a game when it stops
-    pub async fn update_game_playtime(
-        &self,
-        game_id: &str,
-        game_lib: &crate::game::GameLibrary,
-    ) -> Result<()> {
-        if let Some(running_game) = self.running_games.lock().unwrap().get(game_id) {
-            let playtime_minutes = Utc::now()
-                .signed_duration_since(running_game.start_time)
-                .num_minutes() as u64;
+    fn update(change: impl FnOnce(&mut Self)) -> Result<()> {
+        let mut registry = Self::load()?;
+        change(&mut registry);
+        registry.save()
+    }
+}
+
+/// Proton builds are run through their `proton` script rather than a
+/// `bin/wine` binary
+pub(crate) fn is_proton(version: &str) -> bool {
+    version.contains("Proton") && !Path::new(version).join("bin/wine").exists()
+}
+
+/// Whether a process exists and hasn't exited yet (zombies count as gone)
+fn process_alive(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| {
+            let (_, rest) = stat.rsplit_once(')')?;
+            rest.split_whitespace().next().map(|state| state != "Z")
+        })
+        .unwrap_or(false)
+}
 
-            if playtime_minutes > 0 {
-                game_lib.update_playtime(game_id, playtime_minutes)?;
-                println!("📊 Updated playtime: {} minutes", playtime_minutes);
+/// A process and all of its descendants, parents first
+fn process_tree(root: u32) -> Vec<u32> {
+    let mut parents: Vec<(u32, u32)> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                continue;
+            };
+            // The parent PID follows the state after the parenthesised name
+            let ppid = std::fs::read_to_string(entry.path().join("stat"))
+                .ok()
+                .and_then(|stat| {
+                    let (_, rest) = stat.rsplit_once(')')?;
+                    rest.split_whitespace().nth(1)?.parse().ok()
+                });
+            if let Some(ppid) = ppid {
+                parents.push((pid, ppid));
             }
         }
+    }
 
-        Ok(())
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(
+            parents
+                .iter()
+                .filter(|(_, ppid)| *ppid == parent)
+                .map(|(pid, _)| *pid),
+        );
+        i += 1;
+    }
+    tree
+}
+
+fn signal_processes(pids: &[u32], signal: nix::sys::signal::Signal) {
+    for &pid in pids {
+        let _ = nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), signal);
+    }
+}
+
+/// Rebuild a command so it runs through `wrapper`, e.g. `gamemoderun`,
+/// keeping its environment and working directory
+fn wrap_command(cmd: &AsyncCommand, wrapper: &[String]) -> AsyncCommand {
+    let inner = cmd.as_std();
+    let mut wrapped = AsyncCommand::new(&wrapper[0]);
+    wrapped
+        .args(&wrapper[1..])
+        .arg(inner.get_program())
+        .args(inner.get_args());
+
+    for (key, value) in inner.get_envs() {
+        match value {
+            Some(value) => wrapped.env(key, value),
+            None => wrapped.env_remove(key),
+        };
+    }
+    if let Some(dir) = inner.get_current_dir() {
+        wrapped.current_dir(dir);
+    }
+
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_launch_variables() {
+        let variables = HashMap::from([
+            ("GAME_DIR".to_string(), "/games/My Game".to_string()),
+            ("PATH".to_string(), "/overridden".to_string()),
+        ]);
+        let expand = |value: &str| expand_variables(value, &variables);
+
+        assert_eq!(expand("$GAME_DIR/bin"), "/games/My Game/bin");
+        assert_eq!(expand("${GAME_DIR}_saves"), "/games/My Game_saves");
+        // Launch variables shadow the environment
+        assert_eq!(expand("$PATH"), "/overridden");
+        assert_eq!(expand("cost: $$5"), "cost: $5");
+        assert_eq!(expand("$$GAME_DIR"), "$GAME_DIR");
+        // Unknown, empty and unclosed references are left as written
+        assert_eq!(
+            expand("$GHOSTFORGE_UNSET_VARIABLE"),
+            "$GHOSTFORGE_UNSET_VARIABLE"
+        );
+        assert_eq!(expand("a $ b ${}"), "a $ b ${}");
+        assert_eq!(expand("${GAME_DIR"), "${GAME_DIR");
+        assert_eq!(expand("trailing $"), "trailing $");
+    }
+
+    #[test]
+    fn falls_back_to_the_environment() {
+        let Ok(home) = std::env::var("HOME") else {
+            return;
+        };
+        let expanded = expand_variables("${HOME}/saves", &HashMap::new());
+        assert_eq!(expanded, format!("{}/saves", home));
     }
 }
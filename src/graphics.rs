@@ -1,12 +1,9 @@
+use crate::utils::out;
 use anyhow::Result;
 use flate2::read::GzDecoder;
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tar::Archive;
@@ -38,11 +35,52 @@ impl NvidiaGameSettings {
     }
 }
 
+// Intel Arc-specific structures
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IntelArcFeatures {
+    pub available: bool,
+    pub gpu_name: Option<String>,
+    /// Kernel driver, `i915` or `xe`
+    pub driver: Option<String>,
+    /// Mesa release providing the ANV Vulkan driver
+    pub mesa_version: Option<String>,
+}
+
+/// ANV gained the sparse resource support many DX12 games need under
+/// VKD3D-Proton in Mesa 23.3
+pub const ARC_DX12_MIN_MESA: (u32, u32) = (23, 3);
+
+impl IntelArcFeatures {
+    /// Whether ANV is new enough for DX12 through VKD3D-Proton, assumed
+    /// when the Mesa version is unknown
+    pub fn supports_vkd3d(&self) -> bool {
+        self.mesa_version
+            .as_deref()
+            .and_then(major_minor)
+            .is_none_or(|version| version >= ARC_DX12_MIN_MESA)
+    }
+}
+
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor: String = parts
+        .next()?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    Some((major, minor.parse().ok()?))
+}
+
 // GameScope configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameScopeConfig {
+    /// Resolution the game renders at
     pub width: u32,
     pub height: u32,
+    /// Resolution of the gamescope window, the game resolution when unset
+    pub output_width: Option<u32>,
+    pub output_height: Option<u32>,
     pub refresh_rate: Option<u32>,
     pub fullscreen: bool,
     pub borderless: bool,
@@ -52,6 +90,9 @@ pub struct GameScopeConfig {
     pub steam_integration: bool,
     pub upscaling: GameScopeUpscaling,
     pub scaling_filter: GameScopeFilter,
+    /// FSR/NIS sharpness, 0 (sharpest) to 20
+    #[serde(default)]
+    pub sharpness: Option<u8>,
 }
 
 impl Default for GameScopeConfig {
@@ -59,6 +100,8 @@ impl Default for GameScopeConfig {
         Self {
             width: 1920,
             height: 1080,
+            output_width: None,
+            output_height: None,
             refresh_rate: None,
             fullscreen: false,
             borderless: true,
@@ -68,10 +111,120 @@ impl Default for GameScopeConfig {
             steam_integration: false,
             upscaling: GameScopeUpscaling::None,
             scaling_filter: GameScopeFilter::Linear,
+            sharpness: None,
         }
     }
 }
 
+impl GameScopeConfig {
+    /// Fullscreen at the primary display's native resolution and refresh rate
+    pub fn for_primary_display() -> Self {
+        let mut config = Self {
+            fullscreen: true,
+            borderless: false,
+            force_grab_cursor: true,
+            ..Default::default()
+        };
+
+        if let Ok(manager) = crate::display::DisplayManager::new()
+            && let Some(display) = manager.primary_display()
+        {
+            config.width = display.resolution.width;
+            config.height = display.resolution.height;
+            config.refresh_rate = Some(display.current_refresh_rate);
+            config.adaptive_sync = display.vrr_enabled;
+        }
+
+        config
+    }
+
+    /// Render below the output resolution and let gamescope upscale, at
+    /// FSR/NIS "Quality" scale (1.5x)
+    pub fn with_upscaling(mut self, upscaling: GameScopeUpscaling) -> Self {
+        if !matches!(upscaling, GameScopeUpscaling::None) {
+            self.output_width.get_or_insert(self.width);
+            self.output_height.get_or_insert(self.height);
+            self.width = self.width * 2 / 3;
+            self.height = self.height * 2 / 3;
+        }
+        self.upscaling = upscaling;
+        self
+    }
+
+    /// Command that runs a game inside gamescope, the game command goes
+    /// after the trailing `--`
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec!["gamescope".to_string()];
+
+        // Game and output resolution
+        args.push("-w".to_string());
+        args.push(self.width.to_string());
+        args.push("-h".to_string());
+        args.push(self.height.to_string());
+        args.push("-W".to_string());
+        args.push(self.output_width.unwrap_or(self.width).to_string());
+        args.push("-H".to_string());
+        args.push(self.output_height.unwrap_or(self.height).to_string());
+
+        // Refresh rate
+        if let Some(rate) = self.refresh_rate {
+            args.push("-r".to_string());
+            args.push(rate.to_string());
+        }
+
+        // Display mode
+        if self.fullscreen {
+            args.push("-f".to_string());
+        } else if self.borderless {
+            args.push("-b".to_string());
+        }
+
+        // Adaptive sync (FreeSync/G-Sync)
+        if self.adaptive_sync {
+            args.push("--adaptive-sync".to_string());
+        }
+
+        // HDR
+        if self.hdr {
+            args.push("--hdr-enabled".to_string());
+        }
+
+        // Force grab cursor
+        if self.force_grab_cursor {
+            args.push("--force-grab-cursor".to_string());
+        }
+
+        // Steam integration
+        if self.steam_integration {
+            args.push("--steam".to_string());
+        }
+
+        // Upscaling replaces the scaling filter
+        let filter = match (&self.upscaling, &self.scaling_filter) {
+            (GameScopeUpscaling::FSR, _) | (GameScopeUpscaling::None, GameScopeFilter::FSR) => {
+                "fsr"
+            }
+            (GameScopeUpscaling::NIS, _) => "nis",
+            (GameScopeUpscaling::None, GameScopeFilter::Nearest) => "nearest",
+            (GameScopeUpscaling::None, GameScopeFilter::Linear) => "linear",
+        };
+        args.push("-F".to_string());
+        args.push(filter.to_string());
+        if let Some(sharpness) = self
+            .sharpness
+            .filter(|_| filter == "fsr" || filter == "nis")
+        {
+            args.push("--sharpness".to_string());
+            args.push(sharpness.to_string());
+        }
+
+        // Add separator for game command
+        args.push("--".to_string());
+
+        args
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameScopeUpscaling {
     None,
@@ -86,6 +239,78 @@ pub enum GameScopeFilter {
     FSR,
 }
 
+/// Upscaling for a game, by Wine/Proton's fullscreen FSR or gamescope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpscalingSettings {
+    pub method: UpscalingMethod,
+    /// Sharpening, 0 (sharpest) to 5 as for `WINE_FULLSCREEN_FSR_STRENGTH`
+    pub strength: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpscalingMethod {
+    /// Proton and Wine-GE upscale fullscreen games running below the
+    /// display resolution
+    WineFsr,
+    GamescopeFsr,
+    GamescopeNis,
+}
+
+impl UpscalingMethod {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fsr" | "wine-fsr" => Some(Self::WineFsr),
+            "gamescope-fsr" => Some(Self::GamescopeFsr),
+            "nis" | "gamescope-nis" => Some(Self::GamescopeNis),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::WineFsr => "Wine FSR",
+            Self::GamescopeFsr => "gamescope FSR",
+            Self::GamescopeNis => "gamescope NIS",
+        }
+    }
+}
+
+impl UpscalingSettings {
+    pub const DEFAULT_STRENGTH: u8 = 2;
+    pub const MAX_STRENGTH: u8 = 5;
+
+    /// Variables enabling Wine's fullscreen FSR, empty for gamescope
+    pub fn environment(&self) -> Vec<(String, String)> {
+        match self.method {
+            UpscalingMethod::WineFsr => vec![
+                ("WINE_FULLSCREEN_FSR".to_string(), "1".to_string()),
+                (
+                    "WINE_FULLSCREEN_FSR_STRENGTH".to_string(),
+                    self.strength.to_string(),
+                ),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Apply gamescope upscaling to `config`, unless it already upscales.
+    /// Returns false for Wine FSR, which doesn't need gamescope.
+    pub fn apply_to_gamescope(&self, config: &mut GameScopeConfig) -> bool {
+        let upscaling = match self.method {
+            UpscalingMethod::WineFsr => return false,
+            UpscalingMethod::GamescopeFsr => GameScopeUpscaling::FSR,
+            UpscalingMethod::GamescopeNis => GameScopeUpscaling::NIS,
+        };
+        if matches!(config.upscaling, GameScopeUpscaling::None) {
+            *config = config.clone().with_upscaling(upscaling);
+        }
+        // Gamescope's scale is four times as fine
+        config.sharpness.get_or_insert(self.strength * 4);
+        true
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphicsManager {
     pub dxvk_dir: PathBuf,
@@ -143,11 +368,19 @@ impl GraphicsManager {
         self.dry_run = dry_run;
     }
 
+    /// Base directory shared by the CLI and launcher for downloaded layers
+    pub fn default_dir() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_default()
+            .join("ghostforge")
+            .join("graphics")
+    }
+
     pub async fn list_available_dxvk(&self) -> Result<Vec<GraphicsLayer>> {
         let mut versions = Vec::new();
 
         // Fetch DXVK releases from GitHub
-        let client = reqwest::Client::new();
+        let client = crate::download::http_client();
         let response = client
             .get("https://api.github.com/repos/doitsujin/dxvk/releases")
             .header("User-Agent", "GhostForge")
@@ -171,7 +404,7 @@ impl GraphicsManager {
                                     download_url: asset["browser_download_url"]
                                         .as_str()
                                         .map(String::from),
-                                    checksum: None,
+                                    checksum: asset["digest"].as_str().map(String::from),
                                     supported_apis: vec![
                                         "d3d9".to_string(),
                                         "d3d10core".to_string(),
@@ -193,7 +426,7 @@ impl GraphicsManager {
         let mut versions = Vec::new();
 
         // VKD3D-Proton (Valve's version)
-        let client = reqwest::Client::new();
+        let client = crate::download::http_client();
         let response = client
             .get("https://api.github.com/repos/HansKristian-Work/vkd3d-proton/releases")
             .header("User-Agent", "GhostForge")
@@ -217,7 +450,7 @@ impl GraphicsManager {
                                     download_url: asset["browser_download_url"]
                                         .as_str()
                                         .map(String::from),
-                                    checksum: None,
+                                    checksum: asset["digest"].as_str().map(String::from),
                                     supported_apis: vec!["d3d12".to_string()],
                                 });
                                 break;
@@ -289,48 +522,84 @@ impl GraphicsManager {
         Ok(installed)
     }
 
+    /// Find an installed layer by type and version, ignoring a leading "v" in the tag
+    pub fn find_installed(
+        &self,
+        layer_type: &GraphicsLayerType,
+        version: &str,
+    ) -> Result<Option<GraphicsLayer>> {
+        let wanted = version.trim_start_matches('v');
+
+        Ok(self.list_installed()?.into_iter().find(|layer| {
+            &layer.layer_type == layer_type && layer.version.trim_start_matches('v') == wanted
+        }))
+    }
+
     pub async fn install_layer(&self, layer: &GraphicsLayer) -> Result<()> {
         if layer.installed {
             return Err(anyhow::anyhow!("Layer already installed"));
         }
 
         if let Some(url) = &layer.download_url {
-            println!("📦 Downloading {}...", layer.name);
-
-            let client = reqwest::Client::new();
-            let response = client.get(url).send().await?;
-            let total_size = response.content_length().unwrap_or(0);
-
-            let pb = ProgressBar::new(total_size);
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                    .unwrap()
-                    .progress_chars("#>-"),
-            );
-
-            let tmp_file = self.cache_dir.join(format!("{}.tmp", layer.name));
-            let mut file = fs::File::create(&tmp_file)?;
-            let mut downloaded = 0u64;
-            let mut stream = response.bytes_stream();
-
-            while let Some(chunk) = stream.next().await {
-                let chunk = chunk?;
-                file.write_all(&chunk)?;
-                downloaded += chunk.len() as u64;
-                pb.set_position(downloaded);
+            out!("📦 Downloading {}...", layer.name);
+
+            let file_name = crate::download::file_name_from_url(url)
+                .ok_or_else(|| anyhow::anyhow!("Cannot tell the archive name from {}", url))?;
+            let archive = self.cache_dir.join(file_name);
+
+            let pb = crate::download::progress_bar(&layer.name);
+            crate::download::Downloader::new()
+                .download(url, &archive, &crate::download::bar_progress(&pb))
+                .await?;
+            pb.finish_with_message(format!("{} downloaded", layer.name));
+
+            match &layer.checksum {
+                Some(checksum) => {
+                    if let Err(e) = crate::utils::verify_checksum(&archive, checksum) {
+                        let _ = fs::remove_file(&archive);
+                        return Err(e);
+                    }
+                    out!("🔒 Checksum verified");
+                }
+                None => out!(
+                    "⚠️  No checksum published for {}, skipping verification",
+                    layer.name
+                ),
             }
 
-            pb.finish_with_message("Download complete");
-
             // Extract
-            println!("📂 Extracting {}...", layer.name);
-            self.extract_graphics_layer(&tmp_file, &layer.path, &layer.layer_type)?;
+            out!("📂 Extracting {}...", layer.name);
+            self.extract_graphics_layer(&archive, &layer.path, &layer.layer_type)?;
 
             // Clean up
-            fs::remove_file(&tmp_file)?;
+            fs::remove_file(&archive)?;
+
+            out!("✅ {} installed successfully", layer.name);
+        }
+
+        Ok(())
+    }
+
+    /// Delete a downloaded layer from the graphics directory
+    pub fn remove_layer(&self, layer: &GraphicsLayer) -> Result<()> {
+        if self.dry_run {
+            out!(
+                "🔄 [DRY RUN] Would remove {} from {}",
+                layer.name,
+                layer.path.display()
+            );
+            return Ok(());
+        }
+
+        if !layer.path.starts_with(&self.dxvk_dir) && !layer.path.starts_with(&self.vkd3d_dir) {
+            return Err(anyhow::anyhow!(
+                "Refusing to remove {} outside the graphics directory",
+                layer.path.display()
+            ));
+        }
 
-            println!("✅ {} installed successfully", layer.name);
+        if layer.path.exists() {
+            fs::remove_dir_all(&layer.path)?;
         }
 
         Ok(())
@@ -365,7 +634,7 @@ impl GraphicsManager {
 
     pub fn install_to_prefix(&self, layer: &GraphicsLayer, prefix_path: &Path) -> Result<()> {
         if self.dry_run {
-            println!(
+            out!(
                 "🔄 [DRY RUN] Would install {} to prefix: {}",
                 layer.name,
                 prefix_path.display()
@@ -383,7 +652,7 @@ impl GraphicsManager {
     }
 
     fn install_dxvk_to_prefix(&self, layer: &GraphicsLayer, prefix_path: &Path) -> Result<()> {
-        println!("🔧 Installing DXVK to prefix: {}", prefix_path.display());
+        out!("🔧 Installing DXVK to prefix: {}", prefix_path.display());
 
         // Copy DXVK DLLs to system32 and syswow64
         let system32_path = prefix_path.join("drive_c/windows/system32");
@@ -409,7 +678,7 @@ impl GraphicsManager {
 
             if src_path.exists() {
                 fs::copy(&src_path, &dest_path)?;
-                println!("  ✅ Installed {}", dll_name);
+                out!("  ✅ Installed {}", dll_name);
             }
         }
 
@@ -420,7 +689,7 @@ impl GraphicsManager {
     }
 
     fn install_vkd3d_to_prefix(&self, layer: &GraphicsLayer, prefix_path: &Path) -> Result<()> {
-        println!(
+        out!(
             "🔧 Installing VKD3D-Proton to prefix: {}",
             prefix_path.display()
         );
@@ -436,7 +705,7 @@ impl GraphicsManager {
 
             if src_path.exists() {
                 fs::copy(&src_path, &dest_path)?;
-                println!("  ✅ Installed {}", dll_name);
+                out!("  ✅ Installed {}", dll_name);
             }
         }
 
@@ -456,7 +725,7 @@ impl GraphicsManager {
 
         for (dll, mode) in overrides {
             if self.dry_run {
-                println!("🔄 [DRY RUN] Would set DLL override: {} = {}", dll, mode);
+                out!("🔄 [DRY RUN] Would set DLL override: {} = {}", dll, mode);
             } else {
                 Command::new("wine")
                     .env("WINEPREFIX", prefix_path)
@@ -471,7 +740,7 @@ impl GraphicsManager {
                         "/f",
                     ])
                     .output()?;
-                println!("  ✅ Set {} override to {}", dll, mode);
+                out!("  ✅ Set {} override to {}", dll, mode);
             }
         }
 
@@ -483,7 +752,7 @@ impl GraphicsManager {
 
         for (dll, mode) in overrides {
             if self.dry_run {
-                println!("🔄 [DRY RUN] Would set DLL override: {} = {}", dll, mode);
+                out!("🔄 [DRY RUN] Would set DLL override: {} = {}", dll, mode);
             } else {
                 Command::new("wine")
                     .env("WINEPREFIX", prefix_path)
@@ -498,7 +767,7 @@ impl GraphicsManager {
                         "/f",
                     ])
                     .output()?;
-                println!("  ✅ Set {} override to {}", dll, mode);
+                out!("  ✅ Set {} override to {}", dll, mode);
             }
         }
 
@@ -511,7 +780,7 @@ impl GraphicsManager {
         prefix_path: &Path,
     ) -> Result<()> {
         if self.dry_run {
-            println!(
+            out!(
                 "🔄 [DRY RUN] Would remove {:?} from prefix: {}",
                 layer_type,
                 prefix_path.display()
@@ -527,7 +796,7 @@ impl GraphicsManager {
     }
 
     fn remove_dxvk_from_prefix(&self, prefix_path: &Path) -> Result<()> {
-        println!("🗑️ Removing DXVK from prefix...");
+        out!("🗑️ Removing DXVK from prefix...");
 
         let system32_path = prefix_path.join("drive_c/windows/system32");
         let syswow64_path = prefix_path.join("drive_c/windows/syswow64");
@@ -540,7 +809,7 @@ impl GraphicsManager {
 
             if dll_64.exists() {
                 fs::remove_file(&dll_64)?;
-                println!("  🗑️ Removed {}", dll_name);
+                out!("  🗑️ Removed {}", dll_name);
             }
             if dll_32.exists() {
                 fs::remove_file(&dll_32)?;
@@ -566,7 +835,7 @@ impl GraphicsManager {
     }
 
     fn remove_vkd3d_from_prefix(&self, prefix_path: &Path) -> Result<()> {
-        println!("🗑️ Removing VKD3D-Proton from prefix...");
+        out!("🗑️ Removing VKD3D-Proton from prefix...");
 
         let system32_path = prefix_path.join("drive_c/windows/system32");
         let vkd3d_dlls = vec!["d3d12.dll", "dxcore.dll"];
@@ -575,7 +844,7 @@ impl GraphicsManager {
             let dll_path = system32_path.join(dll_name);
             if dll_path.exists() {
                 fs::remove_file(&dll_path)?;
-                println!("  🗑️ Removed {}", dll_name);
+                out!("  🗑️ Removed {}", dll_name);
             }
 
             // Reset DLL override to builtin
@@ -624,6 +893,7 @@ impl GraphicsManager {
         &self,
         game_name: &str,
         nvidia_features: &NvidiaFeatures,
+        intel_arc_features: &IntelArcFeatures,
     ) -> Vec<GraphicsLayerType> {
         let game_lower = game_name.to_lowercase();
         let mut recommendations = Vec::new();
@@ -668,6 +938,18 @@ impl GraphicsManager {
             }
         }
 
+        // Arc has no usable native Direct3D path under Wine, so every D3D
+        // game should go through DXVK, and VKD3D-Proton once Mesa allows
+        if intel_arc_features.available {
+            if intel_arc_features.supports_vkd3d()
+                && !recommendations.contains(&GraphicsLayerType::VKD3DProton)
+            {
+                recommendations.push(GraphicsLayerType::VKD3DProton);
+            }
+            recommendations.push(GraphicsLayerType::MangoHud);
+            recommendations.push(GraphicsLayerType::GameMode);
+        }
+
         recommendations
     }
 
@@ -788,79 +1070,45 @@ impl GraphicsManager {
         Ok(settings)
     }
 
-    pub fn setup_gamescope(&self, config: &GameScopeConfig) -> Result<Vec<String>> {
-        let mut args = vec!["gamescope".to_string()];
-
-        // Resolution settings
-        args.push("-w".to_string());
-        args.push(config.width.to_string());
-        args.push("-h".to_string());
-        args.push(config.height.to_string());
-
-        // Refresh rate
-        if let Some(rate) = config.refresh_rate {
-            args.push("-r".to_string());
-            args.push(rate.to_string());
-        }
-
-        // Display mode
-        if config.fullscreen {
-            args.push("-f".to_string());
-        } else if config.borderless {
-            args.push("-b".to_string());
-        }
-
-        // Adaptive sync (FreeSync/G-Sync)
-        if config.adaptive_sync {
-            args.push("--adaptive-sync".to_string());
-        }
-
-        // HDR
-        if config.hdr {
-            args.push("--hdr-enabled".to_string());
-        }
+    // Intel Arc-specific methods
+    pub fn detect_intel_arc_features(&self) -> Result<IntelArcFeatures> {
+        let mut features = IntelArcFeatures::default();
 
-        // Force grab cursor
-        if config.force_grab_cursor {
-            args.push("--force-grab-cursor".to_string());
+        let gpus = crate::utils::SystemDetector::detect_gpu()?;
+        if let Some(arc) = gpus.iter().find(|gpu| gpu.is_intel_arc()) {
+            features.available = true;
+            features.gpu_name = Some(arc.name.clone());
+            features.driver = arc.driver.clone();
+            features.mesa_version = anv_mesa_version();
         }
 
-        // Steam integration
-        if config.steam_integration {
-            args.push("--steam".to_string());
-        }
+        Ok(features)
+    }
 
-        // Upscaling settings
-        match config.upscaling {
-            GameScopeUpscaling::FSR => {
-                args.push("--fsr-upscaling".to_string());
-            }
-            GameScopeUpscaling::NIS => {
-                args.push("--nis-upscaling".to_string());
-            }
-            GameScopeUpscaling::None => {}
+    /// Launch environment for Arc. DXVK and VKD3D-Proton are forced over
+    /// WineD3D, and OpenGL games get Mesa's threaded dispatch.
+    pub fn setup_intel_arc_optimizations(
+        &self,
+        intel_arc_features: &IntelArcFeatures,
+    ) -> Vec<(String, String)> {
+        if !intel_arc_features.available {
+            return Vec::new();
         }
 
-        // Scaling filter
-        match config.scaling_filter {
-            GameScopeFilter::Nearest => {
-                args.push("--filter".to_string());
-                args.push("nearest".to_string());
-            }
-            GameScopeFilter::Linear => {
-                args.push("--filter".to_string());
-                args.push("linear".to_string());
-            }
-            GameScopeFilter::FSR => {
-                args.push("--filter".to_string());
-                args.push("fsr".to_string());
-            }
+        let mut env = vec![
+            ("PROTON_USE_WINED3D".to_string(), "0".to_string()),
+            ("mesa_glthread".to_string(), "true".to_string()),
+        ];
+        if !intel_arc_features.supports_vkd3d() {
+            // Older ANV can't run D3D12 through VKD3D-Proton, let games
+            // fall back to D3D11 instead of crashing
+            env.push(("PROTON_NO_D3D12".to_string(), "1".to_string()));
         }
+        env
+    }
 
-        // Add separator for game command
-        args.push("--".to_string());
-
-        Ok(args)
+    pub fn setup_gamescope(&self, config: &GameScopeConfig) -> Result<Vec<String>> {
+        Ok(config.args())
     }
 
     pub fn setup_mangohud_nvidia(
@@ -909,3 +1157,192 @@ impl GraphicsManager {
         vec!["gamemoderun".to_string()]
     }
 }
+
+/// Mesa version of the Intel ANV driver, e.g. `24.1.2`, from the device
+/// blocks of `vulkaninfo --summary`
+fn anv_mesa_version() -> Option<String> {
+    let output = Command::new("vulkaninfo").arg("--summary").output().ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let mut intel_device = false;
+    for line in output.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("deviceName") {
+            intel_device = name.contains("Intel");
+        } else if let Some(info) = line.strip_prefix("driverInfo") {
+            let info = info.trim_start_matches([' ', '=']);
+            if intel_device && let Some(version) = info.strip_prefix("Mesa ") {
+                return version.split_whitespace().next().map(str::to_string);
+            }
+        }
+    }
+
+    None
+}
+
+/// A DXVK or VKD3D-Proton pipeline state cache file
+#[derive(Debug, Clone)]
+pub struct StateCacheFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+fn is_state_cache(name: &str) -> bool {
+    name.ends_with(".dxvk-cache") || name.starts_with("vkd3d-proton.cache")
+}
+
+/// Where a game's state caches go when `general.central_state_cache` is on,
+/// `DXVK_STATE_CACHE_PATH` and `VKD3D_SHADER_CACHE_PATH` are pointed here
+pub fn central_state_cache_dir(cache_root: &Path, game_id: &str) -> PathBuf {
+    cache_root.join("state-cache").join(game_id)
+}
+
+/// Find a game's state caches. DXVK and VKD3D-Proton write them to the
+/// working directory, i.e. next to the executable, unless their cache path
+/// variables point elsewhere.
+pub fn find_state_caches(
+    game: &crate::game::Game,
+    cache_root: &Path,
+) -> Result<Vec<StateCacheFile>> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(parent) = game.executable.parent() {
+        dirs.push(parent.to_path_buf());
+    }
+    dirs.push(game.install_path.clone());
+    dirs.push(central_state_cache_dir(cache_root, &game.id));
+    for (key, value) in &game.environment_variables {
+        if key == "DXVK_STATE_CACHE_PATH" || key == "VKD3D_SHADER_CACHE_PATH" {
+            dirs.push(PathBuf::from(value));
+        }
+    }
+
+    let mut caches: Vec<StateCacheFile> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_state_cache(&name) || caches.iter().any(|c| c.path == path) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                caches.push(StateCacheFile {
+                    path,
+                    size: metadata.len(),
+                });
+            }
+        }
+    }
+
+    Ok(caches)
+}
+
+/// Environment for shader pre-caching. DXVK, VKD3D-Proton and the Mesa and
+/// NVIDIA drivers keep their caches in `dir`, and Steam's Fossilize layer
+/// records the game's pipelines there for `precompile_shaders` to replay.
+pub fn shader_precache_environment(dir: &Path) -> Vec<(String, String)> {
+    let dir_str = dir.to_string_lossy().to_string();
+    vec![
+        ("DXVK_STATE_CACHE".to_string(), "1".to_string()),
+        ("DXVK_STATE_CACHE_PATH".to_string(), dir_str.clone()),
+        ("VKD3D_SHADER_CACHE_PATH".to_string(), dir_str.clone()),
+        ("MESA_SHADER_CACHE_DIR".to_string(), dir_str.clone()),
+        ("__GL_SHADER_DISK_CACHE_PATH".to_string(), dir_str),
+        (
+            "__GL_SHADER_DISK_CACHE_SKIP_CLEANUP".to_string(),
+            "1".to_string(),
+        ),
+        (
+            "ENABLE_VK_LAYER_VALVE_steam_fossilize_1".to_string(),
+            "1".to_string(),
+        ),
+        (
+            "STEAM_FOSSILIZE_DUMP_PATH".to_string(),
+            fossilize_dump_dir(dir)
+                .join("steam_pipeline_cache")
+                .to_string_lossy()
+                .to_string(),
+        ),
+    ]
+}
+
+/// Directory under the shader cache `dir` that Fossilize writes its
+/// pipeline recordings to. The layer doesn't create it.
+pub fn fossilize_dump_dir(dir: &Path) -> PathBuf {
+    dir.join("fossilize")
+}
+
+/// Compile the pipelines Fossilize recorded in earlier sessions with
+/// `fossilize-replay`, so the driver cache is warm before the game starts.
+/// Returns how many recordings were replayed, none when fossilize isn't
+/// installed.
+pub async fn precompile_shaders(dir: &Path) -> Result<usize> {
+    let Ok(replay) = which::which("fossilize-replay") else {
+        return Ok(0);
+    };
+    let recordings: Vec<PathBuf> = fs::read_dir(fossilize_dump_dir(dir))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "foz"))
+                .collect()
+        })
+        .unwrap_or_default();
+    if recordings.is_empty() {
+        return Ok(0);
+    }
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    out!(
+        "🧊 Precompiling shaders from {} pipeline recordings...",
+        recordings.len()
+    );
+
+    // The driver caches the replayed pipelines in the same directories the
+    // game will read from
+    let status = tokio::process::Command::new(replay)
+        .arg("--num-threads")
+        .arg(threads.to_string())
+        .args(&recordings)
+        .envs(shader_precache_environment(dir))
+        .env_remove("ENABLE_VK_LAYER_VALVE_steam_fossilize_1")
+        .stdout(std::process::Stdio::null())
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("fossilize-replay exited with {}", status));
+    }
+
+    Ok(recordings.len())
+}
+
+/// Files and bytes in a shader cache directory, compared before and after
+/// a session to estimate what was compiled
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShaderCacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl ShaderCacheStats {
+    /// Driver and state cache entries under `dir`, not counting Fossilize's
+    /// pipeline recordings
+    pub fn collect(dir: &Path) -> Self {
+        let mut stats = Self::default();
+        for entry in walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| !e.path().starts_with(fossilize_dump_dir(dir)))
+        {
+            stats.entries += 1;
+            stats.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+        stats
+    }
+}
@@ -9,9 +9,13 @@ use poll_promise::Promise;
 #[cfg(feature = "gui")]
 // Remove unused import
 #[cfg(feature = "gui")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "gui")]
 use std::sync::Arc;
 #[cfg(feature = "gui")]
 use std::time::{Duration, Instant};
+#[cfg(feature = "gui")]
+use crate::utils::out;
 
 // Mock types when Bolt is not available
 #[cfg(all(feature = "gui", not(feature = "container-bolt")))]
@@ -20,18 +24,34 @@ mod mock_bolt {
     use serde::{Deserialize, Serialize};
     use std::sync::Arc;
 
-    #[derive(Debug, Clone)]
-    pub struct BoltGameManager;
+    pub use crate::bolt_integration::BoltSystemMetrics;
+
+    /// Container operations are unavailable, but host metrics are still
+    /// sampled by the real manager
+    pub struct BoltGameManager {
+        metrics: crate::bolt_integration::BoltGameManager,
+    }
 
     impl BoltGameManager {
         pub fn default() -> Self {
-            Self
+            Self {
+                metrics: crate::bolt_integration::BoltGameManager::default(),
+            }
         }
         pub fn get_containers(&self) -> Vec<GameContainer> {
             vec![]
         }
         pub fn get_cached_metrics(&self) -> Option<BoltSystemMetrics> {
-            None
+            self.metrics.get_cached_metrics()
+        }
+        pub fn start_metrics_sampler(&self, interval: std::time::Duration) -> anyhow::Result<()> {
+            self.metrics.start_metrics_sampler(interval)
+        }
+        pub fn is_available(&self) -> bool {
+            false
+        }
+        pub fn optimization_manager(&self) -> &crate::bolt_integration::OptimizationManager {
+            self.metrics.optimization_manager()
         }
         pub async fn launch_game(
             &self,
@@ -43,6 +63,9 @@ mod mock_bolt {
         pub async fn stop_game(&self, _id: &str) -> anyhow::Result<()> {
             Err(anyhow::anyhow!("Bolt not enabled"))
         }
+        pub async fn container_logs(&self, id: &str, tail: usize) -> anyhow::Result<String> {
+            self.metrics.container_logs(id, tail).await
+        }
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,16 +92,6 @@ mod mock_bolt {
         Creating,
         Updating,
     }
-
-    #[derive(Debug, Clone)]
-    pub struct BoltSystemMetrics {
-        pub running_containers: usize,
-        pub total_containers: usize,
-        pub cpu_usage: f64,
-        pub memory_usage: f64,
-        pub gpu_usage: f64,
-        pub network_activity: f64,
-    }
 }
 
 #[cfg(all(feature = "gui", not(feature = "container-bolt")))]
@@ -100,12 +113,30 @@ pub struct GhostForgeApp {
     protondb_client: crate::protondb::ProtonDBClient,
     show_about: bool,
     search_query: String,
+    /// Library search, kept apart from the ProtonDB search
+    library_query: String,
     protondb_games: Vec<crate::protondb::ProtonDBGame>,
     // Async state management
     loading_system_info: bool,
     loading_games: bool,
     loading_wine: bool,
+    wine_task: String,
+    // Graphics layers
+    graphics_layers: Vec<crate::graphics::GraphicsLayer>,
+    graphics_available: Vec<crate::graphics::GraphicsLayer>,
+    graphics_picker: Option<crate::graphics::GraphicsLayerType>,
+    graphics_target_game: Option<String>,
+    graphics_task: String,
     error_message: Option<String>,
+    // Async work started from UI callbacks, which don't run inside tokio
+    runtime: tokio::runtime::Handle,
+    /// Set when the GUI wasn't started from a tokio runtime and had to
+    /// start its own, which `runtime` refers to
+    _owned_runtime: Option<tokio::runtime::Runtime>,
+    /// Failures of launches and stops running in the background, shown in
+    /// the error banner
+    task_errors: std::sync::mpsc::Receiver<String>,
+    task_error_sender: std::sync::mpsc::Sender<String>,
     // Bolt integration
     bolt_manager: Arc<BoltGameManager>,
     game_containers: Vec<GameContainer>,
@@ -113,17 +144,51 @@ pub struct GhostForgeApp {
     // Async operations
     container_refresh_promise: Option<Promise<Result<Vec<GameContainer>, String>>>,
     metrics_promise: Option<Promise<Result<BoltSystemMetrics, String>>>,
+    games_promise: Option<Promise<Result<LibraryLoad, String>>>,
+    wine_promise: Option<Promise<Result<Vec<crate::wine::WineVersion>, String>>>,
+    graphics_promise: Option<Promise<Result<Vec<crate::graphics::GraphicsLayer>, String>>>,
+    protondb_promise: Option<Promise<Result<Vec<crate::protondb::ProtonDBGame>, String>>>,
+    protondb_details_promise:
+        Option<Promise<Result<crate::protondb::GameCompatibilityReport, String>>>,
+    protondb_details: Option<crate::protondb::GameCompatibilityReport>,
+    graphics_available_promise:
+        Option<Promise<Result<Vec<crate::graphics::GraphicsLayer>, String>>>,
+    cleanup_promise: Option<Promise<Result<u64, String>>>,
+    logs_promise: Option<Promise<Result<String, String>>>,
+    disk_usage_promise: Option<Promise<Vec<crate::game::GameDiskUsage>>>,
+    /// On-disk size of each library game, by id
+    disk_usage: HashMap<String, crate::game::GameDiskUsage>,
     last_refresh: Instant,
     // UI state
     selected_game: Option<String>,
+    /// Sidebar category the library is narrowed to
+    selected_filter: crate::game::GameFilter,
+    /// Ids of games launched by any forge process
+    running_games: HashSet<String>,
     view_mode: ViewMode,
     show_container_details: bool,
     container_logs: String,
+    /// Container whose logs window is open
+    logs_container: Option<String>,
+    logs_auto_scroll: bool,
+    logs_refreshed: Instant,
+    show_cleanup_confirm: bool,
+    /// Containers unused for longer than this many days are removed by cleanup
+    cleanup_days: u64,
+    cleanup_status: Option<String>,
     // Display management
     display_manager: DisplayManager,
     vrr_monitor: VrrMonitor,
     gaming_display_settings: GamingDisplaySettings,
     show_display_settings: bool,
+    display_report: Option<crate::display::DisplayChangeReport>,
+    // Persistent settings
+    config: crate::config::Config,
+    /// config.toml exists but couldn't be read. `config` holds the defaults
+    /// then, and saving them would overwrite the user's file.
+    config_load_failed: bool,
+    add_game_form: Option<AddGameForm>,
+    game_settings: Option<GameSettingsForm>,
 }
 
 #[cfg(feature = "gui")]
@@ -147,9 +212,140 @@ enum ViewMode {
     Details,
 }
 
+/// Fields of the "Add Game" dialog. Manually added games have no launcher,
+/// launching through one needs the game's ID in it, which importers record.
+#[cfg(feature = "gui")]
+#[derive(Default)]
+struct AddGameForm {
+    name: String,
+    executable: String,
+    wine_version: Option<String>,
+}
+
+/// Editable copy of a game for the per-game settings window
+#[cfg(feature = "gui")]
+struct GameSettingsForm {
+    game: crate::game::Game,
+    launch_arguments: String,
+    environment: String,
+    categories: String,
+    notes: String,
+}
+
+#[cfg(feature = "gui")]
+impl GameSettingsForm {
+    fn new(game: &crate::game::Game) -> Self {
+        Self {
+            game: game.clone(),
+            launch_arguments: crate::lutris::join_arguments(&game.launch_arguments),
+            environment: game
+                .environment_variables
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            categories: game.categories.join(", "),
+            notes: game.notes.clone().unwrap_or_default(),
+        }
+    }
+
+    /// First non-empty environment line that is not a KEY=VALUE assignment
+    fn invalid_environment_line(&self) -> Option<String> {
+        self.environment
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .find(|line| match line.split_once('=') {
+                Some((key, _)) => key.trim().is_empty(),
+                None => true,
+            })
+            .map(String::from)
+    }
+
+    fn to_game(&self) -> crate::game::Game {
+        let mut game = self.game.clone();
+        game.launch_arguments = crate::lutris::split_arguments(&self.launch_arguments);
+        game.environment_variables = self
+            .environment
+            .lines()
+            .filter_map(|line| line.trim().split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+            .collect();
+        game.categories = self
+            .categories
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(String::from)
+            .collect();
+        game.notes = Some(self.notes.trim().to_string()).filter(|n| !n.is_empty());
+        game
+    }
+}
+
+/// Graphics layer job run on a worker thread's runtime
+#[cfg(feature = "gui")]
+type BoxedLayerFuture = std::pin::Pin<
+    Box<dyn std::future::Future<Output = Result<Vec<crate::graphics::GraphicsLayer>>> + Send>,
+>;
+
+/// Sidebar categories of the library, also offered by its filter combo
+#[cfg(feature = "gui")]
+fn library_categories() -> [(&'static str, &'static str, crate::game::GameFilter); 5] {
+    use crate::game::{GameFilter, GameStatus};
+
+    let status = |status| GameFilter {
+        status: Some(status),
+        ..Default::default()
+    };
+    [
+        ("📦", "All Games", GameFilter::default()),
+        ("✅", "Installed", status(GameStatus::Installed)),
+        ("▶️", "Running", status(GameStatus::Running)),
+        (
+            "🕓",
+            "Recently Played",
+            GameFilter {
+                recently_played: true,
+                ..Default::default()
+            },
+        ),
+        (
+            "♥️",
+            "Favorites",
+            GameFilter {
+                favorites: true,
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+/// Result of loading the game library on a worker thread
+#[cfg(feature = "gui")]
+struct LibraryLoad {
+    launchers: Vec<crate::launcher::Launcher>,
+    games: Vec<crate::game::Game>,
+    warnings: Vec<String>,
+}
+
 #[cfg(feature = "gui")]
 impl Default for GhostForgeApp {
     fn default() -> Self {
+        let (runtime, owned_runtime) = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => (handle, None),
+            Err(_) => {
+                let runtime = tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(2)
+                    .enable_all()
+                    .build()
+                    .expect("Failed to start the async runtime");
+                (runtime.handle().clone(), Some(runtime))
+            }
+        };
+
+        let (task_error_sender, task_errors) = std::sync::mpsc::channel();
+
         let mut app = Self {
             current_tab: Tab::Dashboard,
             system_info: None,
@@ -159,11 +355,22 @@ impl Default for GhostForgeApp {
             protondb_client: crate::protondb::ProtonDBClient::new(),
             show_about: false,
             search_query: String::new(),
+            library_query: String::new(),
             protondb_games: Vec::new(),
             loading_system_info: false,
             loading_games: false,
             loading_wine: false,
+            wine_task: String::new(),
+            graphics_layers: Vec::new(),
+            graphics_available: Vec::new(),
+            graphics_picker: None,
+            graphics_target_game: None,
+            graphics_task: String::new(),
             error_message: None,
+            runtime,
+            _owned_runtime: owned_runtime,
+            task_errors,
+            task_error_sender,
             // Bolt integration
             bolt_manager: Arc::new(BoltGameManager::default()),
             game_containers: Vec::new(),
@@ -171,12 +378,31 @@ impl Default for GhostForgeApp {
             // Async operations
             container_refresh_promise: None,
             metrics_promise: None,
+            games_promise: None,
+            wine_promise: None,
+            graphics_promise: None,
+            graphics_available_promise: None,
+            protondb_promise: None,
+            protondb_details_promise: None,
+            protondb_details: None,
+            cleanup_promise: None,
+            logs_promise: None,
+            disk_usage_promise: None,
+            disk_usage: HashMap::new(),
             last_refresh: Instant::now(),
             // UI state
             selected_game: None,
+            selected_filter: crate::game::GameFilter::default(),
+            running_games: HashSet::new(),
             view_mode: ViewMode::Grid,
             show_container_details: false,
             container_logs: String::new(),
+            logs_container: None,
+            logs_auto_scroll: true,
+            logs_refreshed: Instant::now(),
+            show_cleanup_confirm: false,
+            cleanup_days: 30,
+            cleanup_status: None,
             // Display management
             display_manager: DisplayManager::default(),
             vrr_monitor: VrrMonitor::default(),
@@ -189,12 +415,37 @@ impl Default for GhostForgeApp {
                 fullscreen_optimizations: true,
             },
             show_display_settings: false,
+            display_report: None,
+            config: crate::config::Config::default(),
+            config_load_failed: false,
+            add_game_form: None,
+            game_settings: None,
         };
 
+        if let Err(e) = app
+            .bolt_manager
+            .start_metrics_sampler(Duration::from_secs(2))
+        {
+            app.error_message = Some(e.to_string());
+        }
+
+        // Settings first, the Wine list is read from the configured path
+        match crate::config::Config::load() {
+            Ok(config) => {
+                app.protondb_client = crate::protondb::ProtonDBClient::from_config(&config);
+                app.config = config;
+            }
+            Err(e) => {
+                app.config_load_failed = true;
+                app.error_message = Some(format!("Failed to load settings: {}", e));
+            }
+        }
+
         // Load initial data
         app.load_system_info();
         app.refresh_games();
         app.load_wine_versions();
+        app.load_graphics_layers();
 
         app
     }
@@ -205,6 +456,12 @@ impl eframe::App for GhostForgeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Apply Material Ocean Blue theme
         ctx.set_visuals(self.get_ocean_blue_theme());
+        self.poll_games_refresh(ctx);
+        self.poll_disk_usage(ctx);
+        self.poll_wine_refresh(ctx);
+        self.poll_graphics(ctx);
+        self.poll_protondb(ctx);
+        self.poll_task_errors();
         // Top menu bar with modern styling
         egui::TopBottomPanel::top("top_panel")
             .min_height(40.0)
@@ -344,23 +601,21 @@ impl eframe::App for GhostForgeApp {
                 ui.add_space(4.0);
 
                 ui.indent("categories", |ui| {
-                    let categories = [
-                        ("📦", "All Games"),
-                        ("✅", "Installed"),
-                        ("▶️", "Running"),
-                        ("🕓", "Recently Played"),
-                        ("♥️", "Favorites"),
-                    ];
-
-                    for (icon, name) in categories {
+                    for (icon, name, filter) in library_categories() {
                         ui.horizontal(|ui| {
                             ui.add_space(4.0);
-                            let _ = ui.selectable_label(
-                                false,
-                                egui::RichText::new(format!("{} {}", icon, name))
-                                    .size(12.0)
-                                    .color(egui::Color32::from_rgb(176, 190, 210)),
-                            );
+                            if ui
+                                .selectable_label(
+                                    self.selected_filter == filter,
+                                    egui::RichText::new(format!("{} {}", icon, name))
+                                        .size(12.0)
+                                        .color(egui::Color32::from_rgb(176, 190, 210)),
+                                )
+                                .clicked()
+                            {
+                                self.selected_filter = filter;
+                                self.current_tab = Tab::Games;
+                            }
                         });
                         ui.add_space(1.0);
                     }
@@ -379,7 +634,26 @@ impl eframe::App for GhostForgeApp {
                             crate::launcher::LauncherType::GOG => "🟣",
                             _ => "📦",
                         };
-                        ui.selectable_label(false, format!("{} {}", icon, launcher.name));
+                        let filter = crate::game::GameFilter {
+                            launcher: Some(
+                                launcher
+                                    .launcher_type
+                                    .config_key()
+                                    .map(str::to_string)
+                                    .unwrap_or_else(|| launcher.name.clone()),
+                            ),
+                            ..Default::default()
+                        };
+                        if ui
+                            .selectable_label(
+                                self.selected_filter == filter,
+                                format!("{} {}", icon, launcher.name),
+                            )
+                            .clicked()
+                        {
+                            self.selected_filter = filter;
+                            self.current_tab = Tab::Games;
+                        }
                     }
                     if self.launchers.is_empty() {
                         ui.small("No launchers detected");
@@ -423,6 +697,7 @@ impl eframe::App for GhostForgeApp {
         if self.last_refresh.elapsed() > Duration::from_secs(5) {
             self.refresh_containers_async(ctx);
             self.refresh_metrics_async(ctx);
+            self.refresh_running_games();
             self.last_refresh = Instant::now();
         }
 
@@ -571,142 +846,189 @@ impl GhostForgeApp {
     }
 
     fn refresh_games(&mut self) {
-        if self.loading_games {
+        if self.games_promise.is_some() {
             return;
         }
 
         self.loading_games = true;
         self.error_message = None;
 
-        // Load launchers first
+        self.games_promise = Some(Promise::spawn_thread("games_refresh", || {
+            Self::load_library().map_err(|e| format!("Failed to load game library: {}", e))
+        }));
+    }
+
+    /// Import games from detected launchers and read the library database.
+    /// Runs on a worker thread, so it drives the async importers on its own runtime.
+    fn load_library() -> Result<LibraryLoad> {
+        let config = crate::config::Config::load()?;
+        config.ensure_directories()?;
+        let game_lib = crate::game::GameLibrary::new(&config.paths.database)?;
+
         let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
         let launcher_manager = crate::launcher::LauncherManager::new(config_dir);
+        let launchers = launcher_manager.detect_launchers()?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let mut warnings = Vec::new();
+        for launcher in &launchers {
+            if let Err(e) =
+                runtime.block_on(launcher_manager.import_launcher_games(launcher, &game_lib))
+            {
+                warnings.push(format!("{}: {}", launcher.name, e));
+            }
+        }
 
-        match launcher_manager.detect_launchers() {
-            Ok(launchers) => {
-                self.launchers = launchers;
-
-                // TODO: Load games from each launcher
-                // For now, create some mock games
-                if !self.launchers.is_empty() {
-                    self.games = vec![
-                        crate::game::Game {
-                            id: "steam_730".to_string(),
-                            name: "Counter-Strike 2".to_string(),
-                            executable: std::path::PathBuf::from("csgo.exe"),
-                            install_path: std::path::PathBuf::from(
-                                "/home/user/.steam/steamapps/common/Counter-Strike Global Offensive",
-                            ),
-                            launcher: Some("Steam".to_string()),
-                            launcher_id: Some("730".to_string()),
-                            wine_version: None,
-                            wine_prefix: None,
-                            icon: None,
-                            banner: None,
-                            launch_arguments: vec![],
-                            environment_variables: vec![],
-                            pre_launch_script: None,
-                            post_launch_script: None,
-                            categories: vec!["FPS".to_string()],
-                            tags: vec!["multiplayer".to_string()],
-                            playtime_minutes: 9000, // 150 hours
-                            last_played: Some(chrono::Utc::now()),
-                            installed_date: chrono::Utc::now(),
-                            favorite: false,
-                            hidden: false,
-                            notes: None,
-                        },
-                        crate::game::Game {
-                            id: "battlenet_wow".to_string(),
-                            name: "World of Warcraft".to_string(),
-                            executable: std::path::PathBuf::from("Wow.exe"),
-                            install_path: std::path::PathBuf::from(
-                                "/home/user/Games/battlenet/drive_c/Program Files (x86)/World of Warcraft",
-                            ),
-                            launcher: Some("Battle.net".to_string()),
-                            launcher_id: Some("wow".to_string()),
-                            wine_version: Some("GE-Proton9-2".to_string()),
-                            wine_prefix: Some(std::path::PathBuf::from(
-                                "/home/user/Games/battlenet",
-                            )),
-                            icon: None,
-                            banner: None,
-                            launch_arguments: vec![],
-                            environment_variables: vec![],
-                            pre_launch_script: None,
-                            post_launch_script: None,
-                            categories: vec!["MMORPG".to_string()],
-                            tags: vec!["rpg".to_string(), "online".to_string()],
-                            playtime_minutes: 150000, // 2500 hours
-                            last_played: Some(chrono::Utc::now() - chrono::Duration::days(2)),
-                            installed_date: chrono::Utc::now() - chrono::Duration::days(365),
-                            favorite: true,
-                            hidden: false,
-                            notes: Some("Favorite MMO".to_string()),
-                        },
-                    ];
+        let games = game_lib.list_games()?;
+
+        Ok(LibraryLoad {
+            launchers,
+            games,
+            warnings,
+        })
+    }
+
+    fn poll_games_refresh(&mut self, ctx: &egui::Context) {
+        let Some(promise) = &self.games_promise else {
+            return;
+        };
+
+        match promise.ready() {
+            Some(result) => {
+                match result {
+                    Ok(load) => {
+                        self.launchers = load.launchers.clone();
+                        self.games = load.games.clone();
+                        if !load.warnings.is_empty() {
+                            self.error_message = Some(format!(
+                                "Some launchers could not be synced: {}",
+                                load.warnings.join("; ")
+                            ));
+                        }
+                    }
+                    Err(error) => {
+                        self.error_message = Some(error.clone());
+                    }
                 }
+                self.games_promise = None;
+                self.loading_games = false;
+                self.measure_disk_usage_async(ctx);
             }
-            Err(e) => {
-                self.error_message = Some(format!("Failed to detect launchers: {}", e));
+            None => ctx.request_repaint_after(Duration::from_millis(200)),
+        }
+    }
+
+    /// Measure the library's install, prefix and shader cache sizes on a
+    /// worker thread, `du` over large installs takes a while
+    fn measure_disk_usage_async(&mut self, ctx: &egui::Context) {
+        if self.disk_usage_promise.is_some() {
+            return;
+        }
+
+        let games = self.games.clone();
+        let cache_root = self.config.paths.cache.clone();
+        let wine = self.config.wine.clone();
+        let ctx = ctx.clone();
+        self.disk_usage_promise = Some(Promise::spawn_thread("disk_usage", move || {
+            let usage = crate::game::measure_disk_usage(&games, &cache_root, &wine);
+            ctx.request_repaint();
+            usage
+        }));
+    }
+
+    fn poll_disk_usage(&mut self, ctx: &egui::Context) {
+        let Some(promise) = &self.disk_usage_promise else {
+            return;
+        };
+
+        match promise.ready() {
+            Some(usage) => {
+                self.disk_usage = usage
+                    .iter()
+                    .map(|u| (u.game_id.clone(), u.clone()))
+                    .collect();
+                self.disk_usage_promise = None;
             }
+            None => ctx.request_repaint_after(Duration::from_millis(500)),
         }
+    }
 
-        self.loading_games = false;
+    fn wine_manager(&self) -> crate::wine::WineManager {
+        crate::wine::WineManager::from_config(&self.config)
     }
 
     fn load_wine_versions(&mut self) {
-        if self.loading_wine {
+        if self.wine_promise.is_some() {
             return;
         }
 
         self.loading_wine = true;
+        self.wine_task = "Loading Wine versions...".to_string();
+
+        let manager = self.wine_manager();
+        self.wine_promise = Some(Promise::spawn_thread("wine_refresh", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            runtime
+                .block_on(manager.list_installed())
+                .map_err(|e| format!("Failed to list Wine versions: {}", e))
+        }));
+    }
 
-        let wine_dir = dirs::data_dir()
-            .unwrap_or_default()
-            .join("ghostforge")
-            .join("wine-versions");
-        let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
-        let manager = crate::wine::WineManager::new(wine_dir, config_dir);
-
-        // let _runtime = Arc::clone(&self.runtime);
-        let _manager = manager;
-
-        // This should be done with proper async state management in a real app
-        // For now, we'll create mock data
-
-        // For now, create mock data
-        self.wine_versions = vec![
-            crate::wine::WineVersion {
-                name: "System Wine".to_string(),
-                version: "9.0".to_string(),
-                path: std::path::PathBuf::from("/usr/bin/wine"),
-                wine_type: crate::wine::WineType::Wine,
-                arch: vec!["win64".to_string()],
-                installed: true,
-                system: true,
-                download_url: None,
-                checksum: None,
-            },
-            crate::wine::WineVersion {
-                name: "Proton GE 9-2".to_string(),
-                version: "9.2".to_string(),
-                path: std::path::PathBuf::from(
-                    "/home/user/.local/share/ghostforge/wine-versions/GE-Proton9-2",
-                ),
-                wine_type: crate::wine::WineType::ProtonGE,
-                arch: vec!["win64".to_string()],
-                installed: true,
-                system: false,
-                download_url: Some(
-                    "https://github.com/GloriousEggroll/proton-ge-custom/releases/".to_string(),
-                ),
-                checksum: None,
-            },
-        ];
+    /// Download and install the newest release of a Wine flavour, then reload the list
+    fn install_latest_wine(&mut self, wine_type: crate::wine::WineType, label: &str) {
+        if self.wine_promise.is_some() {
+            return;
+        }
+
+        self.loading_wine = true;
+        self.wine_task = format!("Installing {}...", label);
+        let label = label.to_string();
+        let manager = self.wine_manager();
+
+        self.wine_promise = Some(Promise::spawn_thread("wine_install", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+
+            runtime
+                .block_on(async {
+                    let latest = manager
+                        .latest_available(wine_type)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("No {} release available", label))?;
+                    if !latest.path.exists() {
+                        manager.install_wine_version(&latest).await?;
+                    }
+                    manager.list_installed().await
+                })
+                .map_err(|e| format!("Failed to install {}: {}", label, e))
+        }));
+    }
 
-        self.loading_wine = false;
+    fn poll_wine_refresh(&mut self, ctx: &egui::Context) {
+        let Some(promise) = &self.wine_promise else {
+            return;
+        };
+
+        match promise.ready() {
+            Some(result) => {
+                match result {
+                    Ok(versions) => self.wine_versions = versions.clone(),
+                    Err(error) => self.error_message = Some(error.clone()),
+                }
+                self.wine_promise = None;
+                self.loading_wine = false;
+            }
+            None => ctx.request_repaint_after(Duration::from_millis(200)),
+        }
     }
+
     fn show_dashboard(&mut self, ui: &mut egui::Ui) {
         ui.heading("📊 Dashboard");
         ui.separator();
@@ -879,22 +1201,10 @@ impl GhostForgeApp {
                                         |ui| {
                                             ui.small("Running");
                                             if ui.small_button("⏹️").clicked() {
-                                                let bolt_manager: Arc<BoltGameManager> =
-                                                    Arc::clone(&self.bolt_manager);
-                                                let container_id = container.id.clone();
-                                                let ctx = ui.ctx().clone();
-
-                                                tokio::spawn(async move {
-                                                    if let Err(e) =
-                                                        bolt_manager.stop_game(&container_id).await
-                                                    {
-                                                        eprintln!(
-                                                            "Failed to stop container: {}",
-                                                            e
-                                                        );
-                                                    }
-                                                    ctx.request_repaint();
-                                                });
+                                                self.spawn_stop_container(
+                                                    container.id.clone(),
+                                                    ui.ctx(),
+                                                );
                                             }
                                         },
                                     );
@@ -941,6 +1251,36 @@ impl GhostForgeApp {
         });
     }
 
+    /// Library games passing the sidebar filter and search. Games count as
+    /// running when launched by forge or when their container is running.
+    fn filtered_games(&self) -> Vec<crate::game::Game> {
+        let mut running = self.running_games.clone();
+        running.extend(
+            self.game_containers
+                .iter()
+                .filter(|c| matches!(c.status, ContainerStatus::Running))
+                .map(|c| c.game_id.clone()),
+        );
+
+        let mut games: Vec<_> = self
+            .games
+            .iter()
+            .filter(|game| self.selected_filter.matches(game, &running))
+            .filter(|game| game.matches_query(&self.library_query))
+            .cloned()
+            .collect();
+        if self.selected_filter.recently_played {
+            games.sort_by_key(|g| std::cmp::Reverse(g.last_played));
+        }
+        games
+    }
+
+    fn refresh_running_games(&mut self) {
+        self.running_games = crate::game_launcher::RunningGameRegistry::load()
+            .map(|registry| registry.games.into_keys().collect())
+            .unwrap_or_default();
+    }
+
     fn show_games(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.heading("🎯 Game Library");
@@ -965,7 +1305,12 @@ impl GhostForgeApp {
                 }
                 ui.separator();
                 if ui.button("➕ Add Game").clicked() {
-                    // TODO: Open add game dialog
+                    self.add_game_form = Some(AddGameForm::default());
+                }
+                if !self.disk_usage.is_empty() {
+                    let total: u64 = self.disk_usage.values().map(|u| u.total()).sum();
+                    ui.label(format!("💾 {}", crate::utils::format_size(total)))
+                        .on_hover_text("Disk space used by the library");
                 }
                 if ui.button("🔄 Refresh").clicked() {
                     self.refresh_games();
@@ -979,25 +1324,32 @@ impl GhostForgeApp {
         // Search and filter bar
         ui.horizontal(|ui| {
             ui.label("Search:");
-            ui.text_edit_singleline(&mut self.search_query);
+            ui.add(egui::TextEdit::singleline(&mut self.library_query).hint_text("Name or tag"));
 
             ui.separator();
 
             // Filter options
             ui.label("Filter:");
+            let categories = library_categories();
+            let selected_text = categories
+                .iter()
+                .find(|(_, _, filter)| *filter == self.selected_filter)
+                .map(|(_, name, _)| name.to_string())
+                .or_else(|| self.selected_filter.launcher.clone())
+                .unwrap_or_else(|| "Custom".to_string());
             egui::ComboBox::from_id_salt("game_filter")
-                .selected_text("All Games")
+                .selected_text(selected_text)
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut "all", "all", "All Games");
-                    ui.selectable_value(&mut "installed", "installed", "Installed Only");
-                    ui.selectable_value(&mut "running", "running", "Currently Running");
-                    ui.selectable_value(&mut "favorites", "favorites", "Favorites");
+                    for (_, name, filter) in categories {
+                        ui.selectable_value(&mut self.selected_filter, filter, name);
+                    }
                 });
         });
 
         ui.separator();
 
         // Game grid/list (inspired by Lutris GameStore)
+        let games = self.filtered_games();
         egui::ScrollArea::vertical().show(ui, |ui| {
             if self.loading_games {
                 ui.centered_and_justified(|ui| {
@@ -1014,6 +1366,10 @@ impl GhostForgeApp {
                         }
                     });
                 });
+            } else if games.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.label("No games match the search and filter");
+                });
             } else {
                 match self.view_mode {
                     ViewMode::Grid => {
@@ -1023,8 +1379,6 @@ impl GhostForgeApp {
                         let cards_per_row =
                             ((available_width / (card_width + 10.0)).floor() as usize).max(1);
 
-                        // Clone games to avoid borrowing issues
-                        let games = self.games.clone();
                         for games_chunk in games.chunks(cards_per_row) {
                             ui.horizontal(|ui| {
                                 for game in games_chunk {
@@ -1035,7 +1389,6 @@ impl GhostForgeApp {
                     }
                     ViewMode::List => {
                         // List view like Lutris list mode
-                        let games = self.games.clone();
                         for game in &games {
                             self.show_game_list_item(ui, game);
                         }
@@ -1047,6 +1400,337 @@ impl GhostForgeApp {
                 }
             }
         });
+
+        self.show_add_game_dialog(ui.ctx());
+        self.show_game_settings(ui.ctx());
+    }
+
+    fn show_game_settings(&mut self, ctx: &egui::Context) {
+        let Some(form) = &mut self.game_settings else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save = false;
+        let mut favorite_toggled = false;
+        egui::Window::new(format!("⚙ {}", form.game.name))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::Grid::new("game_settings_form")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Wine version:");
+                        egui::ComboBox::from_id_salt("game_settings_wine")
+                            .selected_text(form.game.wine_version.as_deref().unwrap_or("Default"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut form.game.wine_version, None, "Default");
+                                for version in &self.wine_versions {
+                                    ui.selectable_value(
+                                        &mut form.game.wine_version,
+                                        Some(version.name.clone()),
+                                        &version.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Vulkan device:")
+                            .on_hover_text("For systems where the game picks the wrong GPU");
+                        egui::ComboBox::from_id_salt("game_settings_vulkan_device")
+                            .selected_text(form.game.vulkan_device.as_deref().unwrap_or("Default"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut form.game.vulkan_device, None, "Default");
+                                let devices = self
+                                    .system_info
+                                    .iter()
+                                    .flat_map(|info| &info.vulkan.devices);
+                                for device in devices {
+                                    ui.selectable_value(
+                                        &mut form.game.vulkan_device,
+                                        Some(device.clone()),
+                                        device,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("FPS limit:")
+                            .on_hover_text("Capped with MangoHud or libstrangle");
+                        ui.horizontal(|ui| {
+                            let mut capped = form.game.fps_limit.is_some();
+                            if ui.checkbox(&mut capped, "").changed() {
+                                form.game.fps_limit = capped.then_some(60);
+                            }
+                            if let Some(limit) = &mut form.game.fps_limit {
+                                ui.add(
+                                    egui::DragValue::new(limit)
+                                        .range(crate::game::FPS_LIMITS)
+                                        .suffix(" FPS"),
+                                );
+                            }
+                            if let Some(display) = self.display_manager.primary_display() {
+                                let cap =
+                                    crate::vrr_monitor::vrr_fps_cap(display.current_refresh_rate);
+                                if ui
+                                    .button("Match VRR")
+                                    .on_hover_text(format!(
+                                        "Cap at {} FPS, just under the {} Hz refresh rate",
+                                        cap, display.current_refresh_rate
+                                    ))
+                                    .clicked()
+                                {
+                                    form.game.fps_limit = Some(cap);
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Launch arguments:");
+                        ui.text_edit_singleline(&mut form.launch_arguments);
+                        ui.end_row();
+
+                        ui.label("Environment:")
+                            .on_hover_text("One KEY=VALUE assignment per line");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut form.environment)
+                                .desired_rows(4)
+                                .hint_text("DXVK_HUD=fps"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Categories:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut form.categories)
+                                .hint_text("Comma separated"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Notes:");
+                        ui.text_edit_multiline(&mut form.notes);
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    favorite_toggled = ui
+                        .checkbox(&mut form.game.favorite, "♥️ Favorite")
+                        .changed();
+                    ui.checkbox(&mut form.game.hidden, "Hide from library");
+                });
+
+                let invalid_env = form.invalid_environment_line();
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(invalid_env.is_none(), egui::Button::new("💾 Save"))
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if let Some(line) = invalid_env {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 193, 7),
+                            format!("Invalid environment line: {}", line),
+                        );
+                    }
+                });
+            });
+
+        // The heart indicator follows the checkbox without waiting for Save
+        if favorite_toggled {
+            let (id, favorite) = (form.game.id.clone(), form.game.favorite);
+            let result =
+                crate::game::GameLibrary::new(&self.config.paths.database).and_then(|game_lib| {
+                    match game_lib.get_game(&id)? {
+                        Some(mut stored) => {
+                            stored.favorite = favorite;
+                            game_lib.update_game(&stored)
+                        }
+                        None => Ok(()),
+                    }
+                });
+            match result {
+                Ok(()) => {
+                    if let Some(game) = self.games.iter_mut().find(|g| g.id == id) {
+                        game.favorite = favorite;
+                    }
+                }
+                Err(e) => self.error_message = Some(format!("Failed to update favorite: {}", e)),
+            }
+        }
+
+        if save {
+            let game = form.to_game();
+            let result = crate::game::GameLibrary::new(&self.config.paths.database)
+                .and_then(|game_lib| game_lib.update_game(&game));
+            match result {
+                Ok(()) => {
+                    if game.hidden {
+                        self.games.retain(|g| g.id != game.id);
+                    } else if let Some(existing) = self.games.iter_mut().find(|g| g.id == game.id) {
+                        *existing = game;
+                    }
+                    open = false;
+                }
+                Err(e) => self.error_message = Some(format!("Failed to save game settings: {}", e)),
+            }
+        }
+
+        if !open {
+            self.game_settings = None;
+        }
+    }
+
+    fn show_add_game_dialog(&mut self, ctx: &egui::Context) {
+        let Some(form) = &mut self.add_game_form else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save = false;
+        egui::Window::new("➕ Add Game")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("add_game_form")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut form.name);
+                        ui.end_row();
+
+                        ui.label("Executable:");
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut form.executable);
+                            if ui.button("📂 Browse").clicked()
+                                && let Some(path) = rfd::FileDialog::new()
+                                    .add_filter("Executables", &["exe", "sh", "x86_64", "AppImage"])
+                                    .add_filter("All files", &["*"])
+                                    .pick_file()
+                            {
+                                if form.name.trim().is_empty()
+                                    && let Some(stem) = path.file_stem()
+                                {
+                                    form.name = stem.to_string_lossy().to_string();
+                                }
+                                form.executable = path.display().to_string();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Wine version:");
+                        egui::ComboBox::from_id_salt("add_game_wine")
+                            .selected_text(form.wine_version.as_deref().unwrap_or("Default"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut form.wine_version, None, "Default");
+                                for version in &self.wine_versions {
+                                    ui.selectable_value(
+                                        &mut form.wine_version,
+                                        Some(version.name.clone()),
+                                        &version.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                let executable = std::path::Path::new(form.executable.trim());
+                let problem = if form.name.trim().is_empty() {
+                    Some("Enter a name for the game")
+                } else if form.executable.trim().is_empty() {
+                    Some("Choose the game's executable")
+                } else if !executable.is_file() {
+                    Some("Executable does not exist")
+                } else {
+                    None
+                };
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(problem.is_none(), egui::Button::new("💾 Save"))
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if let Some(problem) = problem {
+                        ui.colored_label(egui::Color32::from_rgb(255, 193, 7), problem);
+                    }
+                });
+            });
+
+        if save {
+            match self.save_new_game() {
+                Ok(()) => {
+                    open = false;
+                    self.refresh_games();
+                }
+                Err(e) => self.error_message = Some(format!("Failed to add game: {}", e)),
+            }
+        }
+
+        if !open {
+            self.add_game_form = None;
+        }
+    }
+
+    fn save_new_game(&self) -> Result<()> {
+        let Some(form) = &self.add_game_form else {
+            return Ok(());
+        };
+
+        let executable = std::path::PathBuf::from(form.executable.trim());
+        let install_path = executable
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_default();
+
+        let mut game = crate::game::Game {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: form.name.trim().to_string(),
+            executable,
+            install_path,
+            launcher: None,
+            launcher_id: None,
+            wine_version: form.wine_version.clone(),
+            wine_prefix: None,
+            icon: None,
+            banner: None,
+            launch_arguments: vec![],
+            environment_variables: vec![],
+            pre_launch_script: None,
+            post_launch_script: None,
+            categories: vec![],
+            tags: vec![],
+            playtime_minutes: 0,
+            last_played: None,
+            installed_date: chrono::Utc::now(),
+            favorite: false,
+            hidden: false,
+            notes: None,
+            dxvk_version: None,
+            vkd3d_version: None,
+            mangohud: None,
+            gpu_index: None,
+            vulkan_device: None,
+            upscaling: None,
+            profile: None,
+            fps_limit: None,
+            gamemode: None,
+        };
+        if game.uses_wine_prefix() {
+            game.wine_prefix = Some(game.default_prefix(&self.config.wine));
+        }
+
+        let game_lib = crate::game::GameLibrary::new(&self.config.paths.database)?;
+        game_lib.add_game(&game)
     }
 
     fn show_wine(&mut self, ui: &mut egui::Ui) {
@@ -1078,7 +1762,7 @@ impl GhostForgeApp {
             if self.loading_wine {
                 ui.horizontal(|ui| {
                     ui.spinner();
-                    ui.label("Loading Wine versions...");
+                    ui.label(&self.wine_task);
                 });
             } else if self.wine_versions.is_empty() {
                 ui.centered_and_justified(|ui| {
@@ -1086,7 +1770,7 @@ impl GhostForgeApp {
                         ui.label("🍷 No Wine versions found");
                         ui.label("Install Wine or Proton to get started.");
                         if ui.button("Install GE-Proton").clicked() {
-                            // TODO: Install GE-Proton
+                            self.install_latest_wine(crate::wine::WineType::ProtonGE, "GE-Proton");
                         }
                     });
                 });
@@ -1157,73 +1841,362 @@ impl GhostForgeApp {
         // Quick install section
         ui.heading("Quick Install");
         ui.horizontal(|ui| {
-            if ui.button("🔧 Install Latest GE-Proton").clicked() {
-                // TODO: Install latest GE-Proton
+            let busy = self.loading_wine;
+            if ui
+                .add_enabled(!busy, egui::Button::new("🔧 Install Latest GE-Proton"))
+                .clicked()
+            {
+                self.install_latest_wine(crate::wine::WineType::ProtonGE, "GE-Proton");
             }
-            if ui.button("🍷 Install Wine Staging").clicked() {
-                // TODO: Install Wine Staging
+            if ui
+                .add_enabled(!busy, egui::Button::new("🍷 Install Wine Staging"))
+                .clicked()
+            {
+                self.install_latest_wine(crate::wine::WineType::WineStaging, "Wine Staging");
             }
-            if ui.button("🐍 Install Lutris Wine").clicked() {
-                // TODO: Install Lutris Wine
+            if ui
+                .add_enabled(!busy, egui::Button::new("🐍 Install Lutris Wine"))
+                .clicked()
+            {
+                self.install_latest_wine(crate::wine::WineType::Lutris, "Lutris Wine");
             }
         });
     }
 
-    fn show_graphics(&mut self, ui: &mut egui::Ui) {
-        ui.heading("🖥️ Graphics Management");
-        ui.separator();
+    fn graphics_manager() -> Result<crate::graphics::GraphicsManager> {
+        let mut manager =
+            crate::graphics::GraphicsManager::new(crate::graphics::GraphicsManager::default_dir())?;
+        manager.set_dry_run(false);
+        Ok(manager)
+    }
 
-        ui.horizontal(|ui| {
-            if ui.button("📥 Install DXVK").clicked() {
-                // TODO: Install DXVK
-            }
-            if ui.button("📥 Install VKD3D").clicked() {
-                // TODO: Install VKD3D
-            }
-            if ui.button("🔄 Update All").clicked() {
-                // TODO: Update all graphics layers
-            }
+    /// Run a graphics operation on a worker thread; it yields the refreshed installed list
+    fn spawn_graphics_task<F>(&mut self, task: String, job: F)
+    where
+        F: FnOnce(crate::graphics::GraphicsManager) -> BoxedLayerFuture + Send + 'static,
+    {
+        if self.graphics_promise.is_some() {
+            return;
+        }
+
+        self.graphics_task = task;
+        self.graphics_promise = Some(Promise::spawn_thread("graphics_task", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            let manager = Self::graphics_manager().map_err(|e| e.to_string())?;
+            runtime.block_on(job(manager)).map_err(|e| e.to_string())
+        }));
+    }
+
+    fn load_graphics_layers(&mut self) {
+        self.spawn_graphics_task("Loading graphics layers...".to_string(), |manager| {
+            Box::pin(async move { manager.list_installed() })
         });
+    }
 
-        ui.separator();
+    fn open_graphics_picker(&mut self, layer_type: crate::graphics::GraphicsLayerType) {
+        if self.graphics_available_promise.is_some() {
+            return;
+        }
 
-        ui.label("Installed Graphics Layers:");
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    ui.label("DXVK 2.4");
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("🗑 Remove").clicked() {
-                            // TODO: Remove DXVK
+        self.graphics_picker = Some(layer_type.clone());
+        self.graphics_available.clear();
+        self.graphics_available_promise =
+            Some(Promise::spawn_thread("graphics_available", move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                let manager = Self::graphics_manager().map_err(|e| e.to_string())?;
+                runtime
+                    .block_on(async {
+                        match layer_type {
+                            crate::graphics::GraphicsLayerType::DXVK => {
+                                manager.list_available_dxvk().await
+                            }
+                            _ => manager.list_available_vkd3d().await,
                         }
-                        ui.label("✅ Active");
-                    });
-                });
-            });
+                    })
+                    .map_err(|e| format!("Failed to fetch releases: {}", e))
+            }));
+    }
 
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    ui.label("VKD3D 2.13");
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("🗑 Remove").clicked() {
-                            // TODO: Remove VKD3D
+    fn poll_graphics(&mut self, ctx: &egui::Context) {
+        if let Some(promise) = &self.graphics_promise {
+            match promise.ready() {
+                Some(result) => {
+                    match result {
+                        Ok(layers) => self.graphics_layers = layers.clone(),
+                        Err(error) => self.error_message = Some(error.clone()),
+                    }
+                    self.graphics_promise = None;
+                }
+                None => ctx.request_repaint_after(Duration::from_millis(200)),
+            }
+        }
+
+        if let Some(promise) = &self.graphics_available_promise {
+            match promise.ready() {
+                Some(result) => {
+                    match result {
+                        Ok(layers) => self.graphics_available = layers.clone(),
+                        Err(error) => {
+                            self.error_message = Some(error.clone());
+                            self.graphics_picker = None;
                         }
-                        ui.label("⚪ Available");
-                    });
+                    }
+                    self.graphics_available_promise = None;
+                }
+                None => ctx.request_repaint_after(Duration::from_millis(200)),
+            }
+        }
+    }
+
+    fn show_graphics(&mut self, ui: &mut egui::Ui) {
+        use crate::graphics::GraphicsLayerType;
+
+        ui.heading("🖥️ Graphics Management");
+        ui.separator();
+
+        let busy = self.graphics_promise.is_some();
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!busy, egui::Button::new("📥 Install DXVK"))
+                .clicked()
+            {
+                self.open_graphics_picker(GraphicsLayerType::DXVK);
+            }
+            if ui
+                .add_enabled(!busy, egui::Button::new("📥 Install VKD3D"))
+                .clicked()
+            {
+                self.open_graphics_picker(GraphicsLayerType::VKD3DProton);
+            }
+            if ui
+                .add_enabled(!busy, egui::Button::new("🔄 Update All"))
+                .clicked()
+            {
+                self.spawn_graphics_task(
+                    "Installing latest DXVK and VKD3D-Proton...".to_string(),
+                    |manager| {
+                        Box::pin(async move {
+                            let mut latest = Vec::new();
+                            latest.extend(manager.list_available_dxvk().await?.into_iter().next());
+                            latest.extend(manager.list_available_vkd3d().await?.into_iter().next());
+                            for layer in latest.iter().filter(|l| !l.path.exists()) {
+                                manager.install_layer(layer).await?;
+                            }
+                            manager.list_installed()
+                        })
+                    },
+                );
+            }
+        });
+
+        ui.separator();
+
+        // Target game for per-prefix apply
+        ui.horizontal(|ui| {
+            ui.label("Apply to game:");
+            let selected_name = self
+                .graphics_target_game
+                .as_ref()
+                .and_then(|id| self.games.iter().find(|g| &g.id == id))
+                .map(|g| g.name.clone())
+                .unwrap_or_else(|| "Select a game".to_string());
+            egui::ComboBox::from_id_salt("graphics_target_game")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for game in &self.games {
+                        ui.selectable_value(
+                            &mut self.graphics_target_game,
+                            Some(game.id.clone()),
+                            &game.name,
+                        );
+                    }
                 });
+        });
+
+        ui.separator();
+
+        if busy {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(&self.graphics_task);
             });
+        }
+
+        let target = self
+            .graphics_target_game
+            .as_ref()
+            .and_then(|id| self.games.iter().find(|g| &g.id == id))
+            .cloned();
+
+        ui.label("Installed Graphics Layers:");
+        let mut action: Option<(crate::graphics::GraphicsLayer, bool)> = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if self.graphics_layers.is_empty() && !busy {
+                ui.label("No graphics layers installed yet.");
+            }
+
+            for layer in &self.graphics_layers {
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(&layer.name);
+                        ui.small(layer.supported_apis.join(", "));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .add_enabled(!busy, egui::Button::new("🗑 Remove"))
+                                .clicked()
+                            {
+                                action = Some((layer.clone(), false));
+                            }
+                            if ui
+                                .add_enabled(
+                                    !busy && target.is_some(),
+                                    egui::Button::new("🔧 Apply"),
+                                )
+                                .on_hover_text("Install into the selected game's prefix")
+                                .clicked()
+                            {
+                                action = Some((layer.clone(), true));
+                            }
+                        });
+                    });
+                });
+            }
         });
+
+        match action {
+            Some((layer, true)) => {
+                if let Some(game) = target {
+                    let prefix = game.prefix_path(&self.config.wine);
+                    self.spawn_graphics_task(
+                        format!("Applying {} to {}...", layer.name, game.name),
+                        move |manager| {
+                            Box::pin(async move {
+                                crate::prefix::PrefixSnapshot::create(
+                                    &prefix,
+                                    &format!("graphics: {}", layer.name),
+                                )?;
+                                manager.install_to_prefix(&layer, &prefix)?;
+                                manager.list_installed()
+                            })
+                        },
+                    );
+                }
+            }
+            Some((layer, false)) => {
+                self.spawn_graphics_task(format!("Removing {}...", layer.name), move |manager| {
+                    Box::pin(async move {
+                        manager.remove_layer(&layer)?;
+                        manager.list_installed()
+                    })
+                });
+            }
+            None => {}
+        }
+
+        self.show_graphics_picker(ui.ctx());
+    }
+
+    fn show_graphics_picker(&mut self, ctx: &egui::Context) {
+        let Some(layer_type) = self.graphics_picker.clone() else {
+            return;
+        };
+
+        let title = match layer_type {
+            crate::graphics::GraphicsLayerType::DXVK => "Install DXVK",
+            _ => "Install VKD3D-Proton",
+        };
+
+        let mut open = true;
+        let mut install = None;
+        egui::Window::new(title)
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if self.graphics_available_promise.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Fetching releases...");
+                    });
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for layer in &self.graphics_available {
+                            ui.horizontal(|ui| {
+                                ui.label(&layer.name);
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if layer.path.exists() {
+                                            ui.colored_label(egui::Color32::GREEN, "Installed");
+                                        } else if ui.button("📥 Install").clicked() {
+                                            install = Some(layer.clone());
+                                        }
+                                    },
+                                );
+                            });
+                        }
+                    });
+            });
+
+        if let Some(layer) = install {
+            self.spawn_graphics_task(format!("Installing {}...", layer.name), move |manager| {
+                Box::pin(async move {
+                    manager.install_layer(&layer).await?;
+                    manager.list_installed()
+                })
+            });
+            open = false;
+        }
+
+        if !open {
+            self.graphics_picker = None;
+        }
     }
 
     fn show_settings(&mut self, ui: &mut egui::Ui) {
         ui.heading("⚙️ Settings");
         ui.separator();
 
+        if self.config_load_failed {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!(
+                    "⚠️ {} couldn't be loaded. Fix or remove it and restart GhostForge to change settings.",
+                    crate::config::Config::config_path().display()
+                ),
+            );
+            ui.separator();
+            ui.disable();
+        }
+
+        let mut changed = false;
+        let general = &mut self.config.general;
+
         ui.group(|ui| {
             ui.label("General Settings");
-            ui.checkbox(&mut true, "Start with system");
-            ui.checkbox(&mut false, "Minimize to system tray");
-            ui.checkbox(&mut true, "Check for updates automatically");
+            changed |= ui
+                .checkbox(&mut general.enable_gamemode, "Enable GameMode")
+                .changed();
+            changed |= ui
+                .checkbox(&mut general.enable_mangohud, "Enable MangoHud overlay")
+                .changed();
+            changed |= ui
+                .checkbox(&mut general.enable_esync, "Enable esync")
+                .changed();
+            changed |= ui
+                .checkbox(&mut general.enable_fsync, "Enable fsync")
+                .changed();
         });
 
         ui.separator();
@@ -1233,10 +2206,24 @@ impl GhostForgeApp {
             ui.horizontal(|ui| {
                 ui.label("Default Wine Version:");
                 egui::ComboBox::from_id_salt("wine_version")
-                    .selected_text("System Wine")
+                    .selected_text(general.default_wine_version.clone())
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut "system", "system", "System Wine");
-                        ui.selectable_value(&mut "proton", "proton", "Proton GE 9-2");
+                        changed |= ui
+                            .selectable_value(
+                                &mut general.default_wine_version,
+                                "system".to_string(),
+                                "System Wine",
+                            )
+                            .changed();
+                        for version in self.wine_versions.iter().filter(|v| !v.system) {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut general.default_wine_version,
+                                    version.name.clone(),
+                                    &version.name,
+                                )
+                                .changed();
+                        }
                     });
             });
         });
@@ -1245,10 +2232,26 @@ impl GhostForgeApp {
 
         ui.group(|ui| {
             ui.label("Graphics Settings");
-            ui.checkbox(&mut true, "Enable DXVK by default");
-            ui.checkbox(&mut false, "Enable VKD3D by default");
-            ui.checkbox(&mut true, "Use GPU acceleration");
+            changed |= ui
+                .checkbox(&mut general.enable_dxvk, "Enable DXVK by default")
+                .changed();
+            changed |= ui
+                .checkbox(&mut general.enable_vkd3d, "Enable VKD3D by default")
+                .changed();
+            changed |= ui
+                .checkbox(
+                    &mut self.config.gpu.nvidia_prime_render_offload,
+                    "Use discrete GPU (PRIME render offload)",
+                )
+                .changed();
         });
+
+        if changed
+            && !self.config_load_failed
+            && let Err(e) = self.config.save()
+        {
+            self.error_message = Some(format!("Failed to save settings: {}", e));
+        }
     }
 
     fn show_protondb(&mut self, ui: &mut egui::Ui) {
@@ -1258,18 +2261,34 @@ impl GhostForgeApp {
         ui.horizontal(|ui| {
             ui.label("Search games:");
             ui.text_edit_singleline(&mut self.search_query);
-            if ui.button("🔍 Search").clicked() {
-                // TODO: Implement async ProtonDB search
+            let busy = self.protondb_promise.is_some();
+            if ui
+                .add_enabled(
+                    !busy && !self.search_query.trim().is_empty(),
+                    egui::Button::new("🔍 Search"),
+                )
+                .clicked()
+            {
+                self.search_protondb();
             }
-            if ui.button("🔥 Trending").clicked() {
-                // TODO: Load trending games
+            if ui
+                .add_enabled(!busy, egui::Button::new("🔥 Trending"))
+                .clicked()
+            {
+                self.load_trending_protondb();
             }
         });
 
         ui.separator();
 
+        let mut details_for = None;
         egui::ScrollArea::vertical().show(ui, |ui| {
-            if self.protondb_games.is_empty() {
+            if self.protondb_promise.is_some() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Querying ProtonDB...");
+                });
+            } else if self.protondb_games.is_empty() {
                 ui.centered_and_justified(|ui| {
                     ui.label("Search for games to see ProtonDB compatibility data");
                 });
@@ -1312,7 +2331,7 @@ impl GhostForgeApp {
                                     ui.colored_label(tier_color, tier_text);
 
                                     if ui.button("📋 Details").clicked() {
-                                        // TODO: Show game details
+                                        details_for = Some((game.appid, game.name.clone()));
                                     }
                                 },
                             );
@@ -1321,6 +2340,194 @@ impl GhostForgeApp {
                 }
             }
         });
+
+        if let Some((appid, name)) = details_for {
+            self.load_protondb_details(appid, name);
+        }
+
+        self.show_protondb_details(ui.ctx());
+    }
+
+    fn spawn_protondb_query<F, Fut>(&mut self, query: F)
+    where
+        F: FnOnce(crate::protondb::ProtonDBClient) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<Vec<crate::protondb::ProtonDBGame>>>,
+    {
+        if self.protondb_promise.is_some() {
+            return;
+        }
+
+        let client = self.protondb_client.clone();
+        self.protondb_promise = Some(Promise::spawn_thread("protondb_query", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            runtime
+                .block_on(query(client))
+                .map_err(|e| format!("ProtonDB request failed: {}", e))
+        }));
+    }
+
+    fn search_protondb(&mut self) {
+        let query = self.search_query.trim().to_string();
+
+        // Steam games already in the library have known app IDs
+        let known: Vec<(u32, String)> = self
+            .games
+            .iter()
+            .filter(|g| g.launcher.as_deref() == Some("Steam"))
+            .filter(|g| g.name.to_lowercase().contains(&query.to_lowercase()))
+            .filter_map(|g| Some((g.launcher_id.as_ref()?.parse().ok()?, g.name.clone())))
+            .collect();
+
+        self.spawn_protondb_query(move |client| async move {
+            let mut apps = known;
+            if let Some((appid, name)) = client.find_steam_app(&query).await?
+                && !apps.iter().any(|(id, _)| *id == appid)
+            {
+                apps.push((appid, name));
+            }
+
+            let mut games = Vec::new();
+            for (appid, name) in apps {
+                let summary = client.get_game_summary(appid).await?;
+                games.push(crate::protondb::ProtonDBGame {
+                    appid,
+                    name,
+                    confidence: summary
+                        .as_ref()
+                        .map(|s| s.confidence.clone())
+                        .unwrap_or_else(|| "pending".to_string()),
+                    score: summary.as_ref().map_or(0.0, |s| s.score),
+                    tier: summary
+                        .as_ref()
+                        .map_or(crate::protondb::ProtonDBTier::Pending, |s| s.tier.clone()),
+                    total_reports: summary.as_ref().map_or(0, |s| s.total),
+                    recent_reports: 0,
+                    trending: None,
+                    best_reported_tier: None,
+                });
+            }
+
+            Ok(games)
+        });
+    }
+
+    fn load_trending_protondb(&mut self) {
+        self.spawn_protondb_query(
+            |client| async move { client.get_trending_games(Some(25)).await },
+        );
+    }
+
+    fn load_protondb_details(&mut self, appid: u32, name: String) {
+        if self.protondb_details_promise.is_some() {
+            return;
+        }
+
+        self.protondb_details = None;
+        let client = self.protondb_client.clone();
+        self.protondb_details_promise =
+            Some(Promise::spawn_thread("protondb_details", move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                runtime
+                    .block_on(client.generate_compatibility_report(appid, &name))
+                    .map_err(|e| format!("Failed to load ProtonDB report for {}: {}", name, e))
+            }));
+    }
+
+    fn poll_protondb(&mut self, ctx: &egui::Context) {
+        if let Some(promise) = &self.protondb_promise {
+            match promise.ready() {
+                Some(result) => {
+                    match result {
+                        Ok(games) => self.protondb_games = games.clone(),
+                        Err(error) => self.error_message = Some(error.clone()),
+                    }
+                    self.protondb_promise = None;
+                }
+                None => ctx.request_repaint_after(Duration::from_millis(200)),
+            }
+        }
+
+        if let Some(promise) = &self.protondb_details_promise {
+            match promise.ready() {
+                Some(result) => {
+                    match result {
+                        Ok(report) => self.protondb_details = Some(report.clone()),
+                        Err(error) => self.error_message = Some(error.clone()),
+                    }
+                    self.protondb_details_promise = None;
+                }
+                None => ctx.request_repaint_after(Duration::from_millis(200)),
+            }
+        }
+    }
+
+    fn show_protondb_details(&mut self, ctx: &egui::Context) {
+        if self.protondb_details_promise.is_some() {
+            egui::Window::new("ProtonDB Report")
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Loading compatibility report...");
+                    });
+                });
+            return;
+        }
+
+        let Some(report) = &self.protondb_details else {
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(format!("ProtonDB: {}", report.game_name))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(format!("App ID: {}", report.appid));
+                ui.horizontal(|ui| {
+                    ui.strong(&report.tier_display);
+                    ui.label(&report.tier_description);
+                });
+                if report.protondb_available {
+                    ui.label(format!(
+                        "Confidence: {} ({} reports, score {:.2})",
+                        report.confidence, report.total_reports, report.score
+                    ));
+                }
+                ui.label(format!("Recommended: {}", report.recommended_proton));
+                ui.label(format!("Setup: {}", report.get_setup_complexity()));
+                ui.label(format!(
+                    "Likelihood of success: {:.0}%",
+                    report.get_likelihood_of_success() * 100.0
+                ));
+                if let Some(anti_cheat) = &report.anti_cheat {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "Anti-cheat: {} ({:?})",
+                            anti_cheat.names(),
+                            anti_cheat.status
+                        ));
+                        ui.hyperlink_to("AreWeAntiCheatYet", &anti_cheat.url);
+                    });
+                }
+
+                ui.separator();
+                ui.label("Tips:");
+                for tip in &report.compatibility_tips {
+                    ui.label(format!("• {}", tip));
+                }
+            });
+
+        if !open {
+            self.protondb_details = None;
+        }
     }
 
     // Async container management methods
@@ -1344,6 +2551,185 @@ impl GhostForgeApp {
             }));
     }
 
+    /// Remove containers that haven't been used within `cleanup_days`,
+    /// together with their images and data
+    fn cleanup_containers_async(&mut self, ctx: &egui::Context) {
+        if self.cleanup_promise.is_some() {
+            return;
+        }
+
+        let days = self.cleanup_days;
+        let ctx = ctx.clone();
+        self.cleanup_status = None;
+        self.cleanup_promise = Some(Promise::spawn_thread("container_cleanup", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+
+            let result = runtime
+                .block_on(async {
+                    let mut manager = crate::container::ContainerManager::new(config_dir)?;
+                    manager.load_containers()?;
+                    manager.cleanup_unused_containers(days).await
+                })
+                .map_err(|e| format!("Container cleanup failed: {}", e));
+            ctx.request_repaint();
+            result
+        }));
+    }
+
+    fn poll_cleanup(&mut self, ctx: &egui::Context) {
+        let Some(promise) = &self.cleanup_promise else {
+            return;
+        };
+
+        match promise.ready() {
+            Some(result) => {
+                match result {
+                    Ok(0) => self.cleanup_status = Some("✅ No unused containers to remove".into()),
+                    Ok(count) => {
+                        self.cleanup_status =
+                            Some(format!("✅ Removed {} unused container(s)", count))
+                    }
+                    Err(error) => self.error_message = Some(error.clone()),
+                }
+                self.cleanup_promise = None;
+                self.refresh_containers_async(ctx);
+            }
+            None => ctx.request_repaint_after(Duration::from_millis(200)),
+        }
+    }
+
+    fn show_cleanup_dialog(&mut self, ctx: &egui::Context) {
+        if !self.show_cleanup_confirm {
+            return;
+        }
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("🗑️ Cleanup Containers")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Remove containers unused for more than");
+                    ui.add(egui::DragValue::new(&mut self.cleanup_days).range(1..=365));
+                    ui.label("days");
+                });
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 193, 7),
+                    "⚠️ This deletes the container images and data. Game installs are not affected.",
+                );
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("🗑️ Remove").clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed {
+            self.cleanup_containers_async(ctx);
+        }
+        if confirmed || cancelled {
+            self.show_cleanup_confirm = false;
+        }
+    }
+
+    /// Fetch the recent output of the container whose logs window is open
+    fn fetch_container_logs(&mut self, ctx: &egui::Context) {
+        let Some(container_id) = self.logs_container.clone() else {
+            return;
+        };
+        if self.logs_promise.is_some() {
+            return;
+        }
+
+        let bolt_manager: Arc<BoltGameManager> = Arc::clone(&self.bolt_manager);
+        let ctx = ctx.clone();
+        self.logs_refreshed = Instant::now();
+        self.logs_promise = Some(Promise::spawn_thread("container_logs", move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            let result = runtime
+                .block_on(bolt_manager.container_logs(&container_id, 500))
+                .map_err(|e| e.to_string());
+            ctx.request_repaint();
+            result
+        }));
+    }
+
+    fn show_logs_window(&mut self, ctx: &egui::Context) {
+        let Some(container_id) = self.logs_container.clone() else {
+            return;
+        };
+
+        if let Some(promise) = &self.logs_promise
+            && let Some(result) = promise.ready()
+        {
+            self.container_logs = match result {
+                Ok(logs) => logs.clone(),
+                Err(error) => format!("❌ {}", error),
+            };
+            self.logs_promise = None;
+        }
+
+        // Keep following the output while the window is open
+        if self.logs_refreshed.elapsed() > Duration::from_secs(2) {
+            self.fetch_container_logs(ctx);
+        }
+        ctx.request_repaint_after(Duration::from_secs(2));
+
+        let name = self
+            .game_containers
+            .iter()
+            .find(|c| c.id == container_id)
+            .map_or(container_id.clone(), |c| c.name.clone());
+
+        let mut open = true;
+        egui::Window::new(format!("📋 Logs: {}", name))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([700.0, 400.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.logs_auto_scroll, "Auto-scroll");
+                    if ui.button("📋 Copy").clicked() {
+                        ui.ctx().copy_text(self.container_logs.clone());
+                    }
+                    if self.logs_promise.is_some() {
+                        ui.spinner();
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::both()
+                    .stick_to_bottom(self.logs_auto_scroll)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        if self.container_logs.is_empty() {
+                            ui.weak("No output yet");
+                        } else {
+                            ui.monospace(&self.container_logs);
+                        }
+                    });
+            });
+
+        if !open {
+            self.logs_container = None;
+            self.container_logs.clear();
+        }
+    }
+
     fn refresh_metrics_async(&mut self, ctx: &egui::Context) {
         if self.metrics_promise.is_some() {
             return; // Already refreshing
@@ -1422,7 +2808,7 @@ impl GhostForgeApp {
                     }
 
                     if ui.small_button("⚙").on_hover_text("Settings").clicked() {
-                        // TODO: Open game settings
+                        self.game_settings = Some(GameSettingsForm::new(game));
                     }
 
                     if ui.small_button("ℹ️").on_hover_text("Info").clicked() {
@@ -1475,7 +2861,24 @@ impl GhostForgeApp {
                     }
 
                     if ui.small_button("⚙").on_hover_text("Settings").clicked() {
-                        // TODO: Open game settings
+                        self.game_settings = Some(GameSettingsForm::new(game));
+                    }
+
+                    // Disk usage column
+                    let size = crate::utils::format_size;
+                    match self.disk_usage.get(&game.id) {
+                        Some(usage) => {
+                            ui.add_sized([80.0, 20.0], egui::Label::new(size(usage.total())))
+                                .on_hover_text(format!(
+                                    "Install: {}\nPrefix: {}\nShader cache: {}",
+                                    size(usage.install),
+                                    size(usage.prefix),
+                                    size(usage.shader_cache)
+                                ));
+                        }
+                        None => {
+                            ui.add_sized([80.0, 20.0], egui::Label::new("—"));
+                        }
                     }
                 });
             });
@@ -1488,25 +2891,33 @@ impl GhostForgeApp {
             .game_containers
             .iter()
             .any(|c| c.game_id == game.id && matches!(c.status, ContainerStatus::Running));
+        let running_locally = crate::game_launcher::RunningGameRegistry::load()
+            .is_ok_and(|registry| registry.games.contains_key(&game.id));
 
         if is_running {
             self.stop_game_container(&game.id, ctx);
+        } else if running_locally {
+            self.stop_local_game(&game.id, ctx);
+        } else if !self.bolt_manager.is_available() {
+            self.launch_game_locally(game, ctx);
         } else if game.install_path.exists() {
             // Launch game using Bolt
             let bolt_manager: Arc<BoltGameManager> = Arc::clone(&self.bolt_manager);
+            let errors = self.task_error_sender.clone();
             let game_clone = game.clone();
             let ctx = ctx.clone();
 
-            tokio::spawn(async move {
+            self.runtime.spawn(async move {
                 match bolt_manager.launch_game(&game_clone.id, &game_clone).await {
                     Ok(container_id) => {
-                        println!(
+                        out!(
                             "🎮 Launched {} in container: {}",
-                            game_clone.name, container_id
+                            game_clone.name,
+                            container_id
                         );
                     }
                     Err(e) => {
-                        eprintln!("❌ Failed to launch {}: {}", game_clone.name, e);
+                        let _ = errors.send(format!("Failed to launch {}: {}", game_clone.name, e));
                     }
                 }
                 ctx.request_repaint();
@@ -1514,22 +2925,85 @@ impl GhostForgeApp {
         }
     }
 
+    /// Launch without Bolt, running Wine/Proton directly like 'forge launch'
+    fn launch_game_locally(&mut self, game: &crate::game::Game, ctx: &egui::Context) {
+        use crate::game_launcher::{GameLauncher, LaunchOptions};
+
+        let mut options = LaunchOptions::default();
+        if let Err(e) = options.apply_mangohud(game, self.config.general.enable_mangohud) {
+            self.error_message = Some(format!("Failed to set up MangoHud: {}", e));
+            return;
+        }
+        if let Some(profile) = self
+            .bolt_manager
+            .optimization_manager()
+            .profile_for_game(game)
+        {
+            options.apply_profile(profile, game);
+        }
+
+        let launcher = GameLauncher::new(self.config.clone());
+        let errors = self.task_error_sender.clone();
+        let game_clone = game.clone();
+        let ctx = ctx.clone();
+
+        self.runtime.spawn(async move {
+            match launcher.launch_game(&game_clone, options).await {
+                Ok(pid) => {
+                    out!("🎮 Launched {} with PID {}", game_clone.name, pid);
+                    ctx.request_repaint();
+                    // Playtime and restoring tuned settings happen on exit
+                    launcher.wait_for_exit(&game_clone.id).await;
+                }
+                Err(e) => {
+                    let _ = errors.send(format!("Failed to launch {}: {}", game_clone.name, e));
+                }
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn stop_local_game(&mut self, game_id: &str, ctx: &egui::Context) {
+        let launcher = crate::game_launcher::GameLauncher::new(self.config.clone());
+        let errors = self.task_error_sender.clone();
+        let game_id = game_id.to_string();
+        let ctx = ctx.clone();
+
+        self.runtime.spawn(async move {
+            if let Err(e) = launcher.stop_game(&game_id).await {
+                let _ = errors.send(format!("Failed to stop game: {}", e));
+            }
+            ctx.request_repaint();
+        });
+    }
+
     fn stop_game_container(&mut self, game_id: &str, ctx: &egui::Context) {
         if let Some(container) = self
             .game_containers
             .iter()
             .find(|c| c.game_id == game_id && matches!(c.status, ContainerStatus::Running))
         {
-            let bolt_manager: Arc<BoltGameManager> = Arc::clone(&self.bolt_manager);
-            let container_id = container.id.clone();
-            let ctx = ctx.clone();
+            self.spawn_stop_container(container.id.clone(), ctx);
+        }
+    }
 
-            tokio::spawn(async move {
-                if let Err(e) = bolt_manager.stop_game(&container_id).await {
-                    eprintln!("Failed to stop container: {}", e);
-                }
-                ctx.request_repaint();
-            });
+    fn spawn_stop_container(&self, container_id: String, ctx: &egui::Context) {
+        let bolt_manager: Arc<BoltGameManager> = Arc::clone(&self.bolt_manager);
+        let errors = self.task_error_sender.clone();
+        let ctx = ctx.clone();
+
+        self.runtime.spawn(async move {
+            if let Err(e) = bolt_manager.stop_game(&container_id).await {
+                let _ = errors.send(format!("Failed to stop container: {}", e));
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_task_errors(&mut self) {
+        // Only the latest failure fits in the banner
+        while let Ok(error) = self.task_errors.try_recv() {
+            self.error_message = Some(error);
         }
     }
 
@@ -1543,14 +3017,38 @@ impl GhostForgeApp {
                 if ui.button("📊 Metrics").clicked() {
                     self.refresh_metrics_async(ui.ctx());
                 }
-                if ui.button("🗑️ Cleanup").clicked() {
-                    // TODO: Cleanup stopped containers
+                let cleaning = self.cleanup_promise.is_some();
+                if ui
+                    .add_enabled(!cleaning, egui::Button::new("🗑️ Cleanup"))
+                    .clicked()
+                {
+                    self.show_cleanup_confirm = true;
                 }
             });
         });
 
         ui.separator();
 
+        self.poll_cleanup(ui.ctx());
+        if self.cleanup_promise.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Removing unused containers...");
+            });
+        } else if let Some(status) = &self.cleanup_status {
+            let mut dismiss = false;
+            ui.horizontal(|ui| {
+                ui.label(status);
+                if ui.small_button("❌").clicked() {
+                    dismiss = true;
+                }
+            });
+            if dismiss {
+                self.cleanup_status = None;
+            }
+        }
+        self.show_cleanup_dialog(ui.ctx());
+
         // Check for completed async operations
         if let Some(promise) = &self.container_refresh_promise {
             if let Some(result) = promise.ready() {
@@ -1617,6 +3115,13 @@ impl GhostForgeApp {
                         ui.label("GPU");
                     });
                 });
+
+                ui.group(|ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.strong(format!("{:.1} MB/s", metrics.network_activity));
+                        ui.label("Network");
+                    });
+                });
             });
 
             ui.separator();
@@ -1636,6 +3141,8 @@ impl GhostForgeApp {
                 });
             });
         } else {
+            let mut open_logs = None;
+            let mut stop_container = None;
             egui::ScrollArea::vertical().show(ui, |ui| {
                 for container in &self.game_containers {
                     ui.group(|ui| {
@@ -1676,22 +3183,7 @@ impl GhostForgeApp {
                                         ContainerStatus::Running => {
                                             if ui.small_button("⏹️").on_hover_text("Stop").clicked()
                                             {
-                                                let bolt_manager: Arc<BoltGameManager> =
-                                                    Arc::clone(&self.bolt_manager);
-                                                let container_id = container.id.clone();
-                                                let ctx = ui.ctx().clone();
-
-                                                tokio::spawn(async move {
-                                                    if let Err(e) =
-                                                        bolt_manager.stop_game(&container_id).await
-                                                    {
-                                                        eprintln!(
-                                                            "Failed to stop container: {}",
-                                                            e
-                                                        );
-                                                    }
-                                                    ctx.request_repaint();
-                                                });
+                                                stop_container = Some(container.id.clone());
                                             }
                                         }
                                         ContainerStatus::Stopped => {
@@ -1712,7 +3204,7 @@ impl GhostForgeApp {
                                     }
 
                                     if ui.small_button("📋").on_hover_text("Logs").clicked() {
-                                        // TODO: Show container logs
+                                        open_logs = Some(container.id.clone());
                                     }
 
                                     ui.colored_label(
@@ -1725,8 +3217,19 @@ impl GhostForgeApp {
                     });
                 }
             });
+
+            if let Some(container_id) = stop_container {
+                self.spawn_stop_container(container_id, ui.ctx());
+            }
+            if let Some(container_id) = open_logs {
+                self.container_logs.clear();
+                self.logs_container = Some(container_id);
+                self.fetch_container_logs(ui.ctx());
+            }
         }
 
+        self.show_logs_window(ui.ctx());
+
         // Container details popup
         if self.show_container_details {
             if let Some(selected_id) = &self.selected_game {
@@ -1779,12 +3282,23 @@ impl GhostForgeApp {
                         self.error_message = Some(format!("Failed to detect displays: {}", e));
                     }
                 }
+                if ui.button("↩️ Restore").clicked() {
+                    match self.display_manager.restore_display_settings() {
+                        Ok(report) => self.display_report = Some(report),
+                        Err(e) => {
+                            self.error_message = Some(format!("Failed to restore displays: {}", e))
+                        }
+                    }
+                }
                 if ui.button("⚙️ Gaming Optimize").clicked() {
-                    if let Err(e) = self
+                    match self
                         .display_manager
-                        .optimize_for_gaming(self.gaming_display_settings.target_fps)
+                        .optimize_for_gaming(&self.gaming_display_settings)
                     {
-                        self.error_message = Some(format!("Failed to optimize displays: {}", e));
+                        Ok(report) => self.display_report = Some(report),
+                        Err(e) => {
+                            self.error_message = Some(format!("Failed to optimize displays: {}", e))
+                        }
                     }
                 }
                 if ui.button("📊 Start VRR Monitor").clicked() {
@@ -1797,6 +3311,25 @@ impl GhostForgeApp {
 
         ui.separator();
 
+        let mut dismiss_report = false;
+        if let Some(report) = &self.display_report {
+            ui.group(|ui| {
+                for change in &report.changed {
+                    ui.colored_label(egui::Color32::GREEN, format!("✅ {}", change));
+                }
+                for refusal in &report.refused {
+                    ui.colored_label(egui::Color32::YELLOW, format!("⚠️ {}", refusal));
+                }
+                if report.changed.is_empty() && report.refused.is_empty() {
+                    ui.label("Displays are already optimized");
+                }
+                dismiss_report = ui.small_button("Dismiss").clicked();
+            });
+        }
+        if dismiss_report {
+            self.display_report = None;
+        }
+
         // Session type indicator
         ui.horizontal(|ui| {
             let session_type = if self.display_manager.is_wayland_session() {
@@ -1814,6 +3347,7 @@ impl GhostForgeApp {
         ui.separator();
 
         // Display configuration
+        let mut hdr_toggle = None;
         egui::ScrollArea::vertical().show(ui, |ui| {
             for display in self.display_manager.get_displays() {
                 ui.group(|ui| {
@@ -1860,7 +3394,13 @@ impl GhostForgeApp {
                                 } else {
                                     "🌙 HDR OFF"
                                 };
-                                ui.colored_label(hdr_color, hdr_text);
+                                if ui
+                                    .button(egui::RichText::new(hdr_text).color(hdr_color))
+                                    .on_hover_text("Toggle HDR")
+                                    .clicked()
+                                {
+                                    hdr_toggle = Some((display.id.clone(), !display.hdr_enabled));
+                                }
                             }
 
                             // Primary indicator
@@ -1892,9 +3432,19 @@ impl GhostForgeApp {
 
             // VRR Performance Metrics
             if self.vrr_monitor.is_monitoring_active() {
+                if let Err(e) = self.vrr_monitor.update_metrics() {
+                    self.error_message = Some(format!("Failed to read frame timing: {}", e));
+                }
+                ui.ctx().request_repaint_after(Duration::from_secs(1));
+
                 ui.group(|ui| {
                     ui.heading("📊 Real-time Performance");
 
+                    if !self.vrr_monitor.is_presenting() {
+                        ui.label("💤 Idle - no game is presenting");
+                        ui.small("Frame timing is read from MangoHud, enable it for the game to see live stats");
+                    }
+
                     if let Some(metrics) = self.vrr_monitor.get_real_time_stats() {
                         ui.horizontal(|ui| {
                             ui.group(|ui| {
@@ -2034,6 +3584,12 @@ impl GhostForgeApp {
                 );
             });
         });
+
+        if let Some((display_id, enabled)) = hdr_toggle
+            && let Err(e) = self.display_manager.set_hdr(&display_id, enabled)
+        {
+            self.error_message = Some(format!("Failed to switch HDR: {}", e));
+        }
     }
 }
 
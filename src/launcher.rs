@@ -1,3 +1,4 @@
+use crate::utils::out;
 use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -31,6 +32,90 @@ pub enum LauncherType {
     Custom,
 }
 
+impl LauncherType {
+    /// Parse a launcher name as used on the command line and in the config
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "steam" => Some(Self::Steam),
+            "battlenet" | "battle.net" => Some(Self::BattleNet),
+            "epic" => Some(Self::Epic),
+            "gog" => Some(Self::GOG),
+            "ubisoft" | "uplay" => Some(Self::Ubisoft),
+            "ea" | "origin" => Some(Self::EA),
+            _ => None,
+        }
+    }
+
+    /// Key of this launcher in the `[launchers]` config section
+    pub fn config_key(&self) -> Option<&'static str> {
+        match self {
+            Self::Steam => Some("steam"),
+            Self::BattleNet => Some("battlenet"),
+            Self::Epic => Some("epic"),
+            Self::GOG => Some("gog"),
+            Self::Ubisoft => Some("ubisoft"),
+            Self::EA => Some("ea"),
+            _ => None,
+        }
+    }
+}
+
+/// A GOG install found by Galaxy, Heroic or Minigalaxy
+struct GogInstall {
+    product_id: Option<String>,
+    title: Option<String>,
+    path: PathBuf,
+}
+
+/// Installer for a Windows launcher that runs in its own Wine prefix
+struct WineLauncherInstaller {
+    /// Prefix directory under ~/Games, matching the paths detection looks at
+    prefix_dir: &'static str,
+    url: &'static str,
+    file_name: &'static str,
+    args: &'static [&'static str],
+}
+
+fn wine_launcher_installer(launcher_type: &LauncherType) -> Option<WineLauncherInstaller> {
+    match launcher_type {
+        LauncherType::Epic => Some(WineLauncherInstaller {
+            prefix_dir: "epic-games-store",
+            url: "https://launcher-public-service-prod06.ol.epicgames.com/launcher/api/installer/download/EpicGamesLauncherInstaller.msi",
+            file_name: "EpicGamesLauncherInstaller.msi",
+            args: &["/qn"],
+        }),
+        LauncherType::GOG => Some(WineLauncherInstaller {
+            prefix_dir: "gog-galaxy",
+            url: "https://webinstallers.gog-statics.com/download/GOG_Galaxy_2.0.exe",
+            file_name: "GOG_Galaxy_2.0.exe",
+            args: &[],
+        }),
+        LauncherType::Ubisoft => Some(WineLauncherInstaller {
+            prefix_dir: "ubisoft-connect",
+            url: "https://static3.cdn.ubi.com/orbit/launcher_installer/UbisoftConnectInstaller.exe",
+            file_name: "UbisoftConnectInstaller.exe",
+            args: &["/S"],
+        }),
+        LauncherType::EA => Some(WineLauncherInstaller {
+            prefix_dir: "ea-app",
+            url: "https://origin-a.akamaihd.net/EA-Desktop-Client-Download/installer-releases/EAappInstaller.exe",
+            file_name: "EAappInstaller.exe",
+            args: &[],
+        }),
+        _ => None,
+    }
+}
+
+/// Steam app ID of a Battle.net game that is also sold on Steam, so its
+/// ProtonDB reports can be looked up
+pub fn battlenet_steam_appid(code: &str) -> Option<u32> {
+    match code {
+        "pro" => Some(2357570), // Overwatch 2
+        "d4" => Some(2344520),  // Diablo IV
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherGame {
     pub id: String,
@@ -89,6 +174,34 @@ impl LauncherManager {
         Ok(launchers)
     }
 
+    /// Detect a single launcher by type
+    pub fn detect_launcher(&self, launcher_type: &LauncherType) -> Result<Option<Launcher>> {
+        match launcher_type {
+            LauncherType::Steam => self.detect_steam(),
+            LauncherType::BattleNet => self.detect_battlenet(),
+            LauncherType::Epic => self.detect_epic(),
+            LauncherType::GOG => self.detect_gog(),
+            LauncherType::Ubisoft => self.detect_ubisoft(),
+            LauncherType::EA => self.detect_ea(),
+            _ => Ok(None),
+        }
+    }
+
+    /// Location registered with `forge launcher setup`, checked before the defaults.
+    /// This is the Wine prefix for Windows launchers and the install path for Steam.
+    fn configured_location(&self, launcher_type: &LauncherType) -> Option<PathBuf> {
+        let config = crate::config::Config::load().ok()?;
+        let launcher = config.get_launcher(launcher_type.config_key()?)?;
+        if !launcher.enabled {
+            return None;
+        }
+
+        match launcher_type {
+            LauncherType::Steam => Some(launcher.path.clone()),
+            _ => launcher.prefix_path.clone(),
+        }
+    }
+
     fn detect_steam(&self) -> Result<Option<Launcher>> {
         let possible_paths = vec![
             dirs::home_dir().unwrap().join(".local/share/Steam"),
@@ -97,7 +210,11 @@ impl LauncherManager {
             PathBuf::from("/opt/steam"),
         ];
 
-        for path in possible_paths {
+        for path in self
+            .configured_location(&LauncherType::Steam)
+            .into_iter()
+            .chain(possible_paths)
+        {
             if path.exists() {
                 let executable = path
                     .join("steam.sh")
@@ -131,9 +248,14 @@ impl LauncherManager {
             dirs::home_dir()
                 .unwrap()
                 .join(".local/share/lutris/runners/wine/battlenet"),
+            dirs::home_dir().unwrap().join(".wine-battlenet"),
         ];
 
-        for prefix in possible_prefixes {
+        for prefix in self
+            .configured_location(&LauncherType::BattleNet)
+            .into_iter()
+            .chain(possible_prefixes)
+        {
             let battle_net_path = prefix.join("drive_c/Program Files (x86)/Battle.net");
             if battle_net_path.exists() {
                 let executable = battle_net_path.join("Battle.net.exe");
@@ -168,7 +290,11 @@ impl LauncherManager {
                 .join(".local/share/lutris/runners/wine/epic"),
         ];
 
-        for prefix in possible_prefixes {
+        for prefix in self
+            .configured_location(&LauncherType::Epic)
+            .into_iter()
+            .chain(possible_prefixes)
+        {
             let epic_path = prefix.join("drive_c/Program Files (x86)/Epic Games/Launcher");
             if epic_path.exists() {
                 let executable = epic_path.join("Portal/Binaries/Win32/EpicGamesLauncher.exe");
@@ -206,6 +332,24 @@ impl LauncherManager {
             }
         }
 
+        // Legendary CLI (the Epic backend Heroic is built on)
+        if let Ok(legendary) = which::which("legendary") {
+            let config_path = legendary_config_dir();
+            if config_path.exists() {
+                return Ok(Some(Launcher {
+                    name: "Legendary (Epic)".to_string(),
+                    launcher_type: LauncherType::Epic,
+                    path: config_path.clone(),
+                    executable: legendary,
+                    config_path,
+                    games_path: vec![dirs::home_dir().unwrap().join("Games")],
+                    installed: true,
+                    wine_prefix: None,
+                    wine_version: None,
+                }));
+            }
+        }
+
         Ok(None)
     }
 
@@ -216,7 +360,11 @@ impl LauncherManager {
             dirs::home_dir().unwrap().join("Games/gog-galaxy"),
         ];
 
-        for prefix in possible_prefixes {
+        for prefix in self
+            .configured_location(&LauncherType::GOG)
+            .into_iter()
+            .chain(possible_prefixes)
+        {
             let gog_path = prefix.join("drive_c/Program Files (x86)/GOG Galaxy");
             if gog_path.exists() {
                 let executable = gog_path.join("GalaxyClient.exe");
@@ -254,6 +402,24 @@ impl LauncherManager {
             }
         }
 
+        // Heroic keeps its own GOG install list
+        if let Ok(heroic) = which::which("heroic") {
+            let config_path = dirs::config_dir().unwrap().join("heroic/gog_store");
+            if config_path.exists() {
+                return Ok(Some(Launcher {
+                    name: "Heroic (GOG)".to_string(),
+                    launcher_type: LauncherType::GOG,
+                    path: config_path.clone(),
+                    executable: heroic,
+                    config_path,
+                    games_path: vec![dirs::home_dir().unwrap().join("Games/Heroic")],
+                    installed: true,
+                    wine_prefix: None,
+                    wine_version: None,
+                }));
+            }
+        }
+
         Ok(None)
     }
 
@@ -263,7 +429,11 @@ impl LauncherManager {
             dirs::home_dir().unwrap().join("Games/ubisoft-connect"),
         ];
 
-        for prefix in possible_prefixes {
+        for prefix in self
+            .configured_location(&LauncherType::Ubisoft)
+            .into_iter()
+            .chain(possible_prefixes)
+        {
             let ubisoft_path =
                 prefix.join("drive_c/Program Files (x86)/Ubisoft/Ubisoft Game Launcher");
             if ubisoft_path.exists() {
@@ -293,7 +463,11 @@ impl LauncherManager {
             dirs::home_dir().unwrap().join("Games/ea-app"),
         ];
 
-        for prefix in possible_prefixes {
+        for prefix in self
+            .configured_location(&LauncherType::EA)
+            .into_iter()
+            .chain(possible_prefixes)
+        {
             let ea_path = prefix.join("drive_c/Program Files/Electronic Arts/EA Desktop");
             if ea_path.exists() {
                 let executable = ea_path.join("EA Desktop.exe");
@@ -317,35 +491,58 @@ impl LauncherManager {
     }
 
     pub fn sync_steam_games(&self, steam_launcher: &Launcher) -> Result<Vec<LauncherGame>> {
-        let mut games = Vec::new();
-        let libraryfolders_vdf = steam_launcher
+        let steam_root = steam_launcher
             .config_path
             .parent()
-            .unwrap()
-            .join("steamapps/libraryfolders.vdf");
-
-        if !libraryfolders_vdf.exists() {
-            return Ok(games);
-        }
+            .unwrap_or(&steam_launcher.path);
+        self.scan_steam_libraries(steam_root)
+    }
 
-        // Parse Steam library folders
-        let content = fs::read_to_string(&libraryfolders_vdf)?;
-        let library_paths = self.parse_steam_libraries(&content)?;
+    /// Installed games in every Steam library folder listed by the Steam
+    /// installation at `steam_root`, including ones on other drives
+    fn scan_steam_libraries(&self, steam_root: &Path) -> Result<Vec<LauncherGame>> {
+        let mut games: Vec<LauncherGame> = Vec::new();
+
+        // Newer Steam keeps the authoritative copy in config/
+        let libraryfolders_vdf = ["config/libraryfolders.vdf", "steamapps/libraryfolders.vdf"]
+            .iter()
+            .map(|file| steam_root.join(file))
+            .find(|path| path.exists());
+        let content = match libraryfolders_vdf {
+            Some(path) => fs::read_to_string(path)?,
+            None => String::new(),
+        };
 
-        for library_path in library_paths {
+        for library_path in self.parse_steam_libraries(steam_root, &content)? {
             let steamapps_dir = library_path.join("steamapps");
-            if !steamapps_dir.exists() {
+            let Ok(entries) = fs::read_dir(&steamapps_dir) else {
+                // Unmounted drives stay in the list until removed in Steam
                 continue;
-            }
+            };
 
-            // Parse .acf files for game information
-            for entry in fs::read_dir(&steamapps_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("acf") {
-                    if let Ok(game) = self.parse_steam_acf(&path) {
-                        games.push(game);
-                    }
+            // Parse the appmanifest files for game information
+            let mut manifests: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension().and_then(|s| s.to_str()) == Some("acf")
+                        && path
+                            .file_name()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|name| name.starts_with("appmanifest_"))
+                })
+                .collect();
+            manifests.sort();
+
+            for path in manifests {
+                let Ok(game) = self.parse_steam_acf(&path) else {
+                    continue;
+                };
+                // A game moved between drives can leave a manifest behind,
+                // keep the copy that is actually installed
+                match games.iter_mut().find(|g| g.launcher_id == game.launcher_id) {
+                    Some(existing) if !existing.installed && game.installed => *existing = game,
+                    Some(_) => {}
+                    None => games.push(game),
                 }
             }
         }
@@ -353,14 +550,35 @@ impl LauncherManager {
         Ok(games)
     }
 
-    fn parse_steam_libraries(&self, content: &str) -> Result<Vec<PathBuf>> {
-        let mut paths = vec![dirs::home_dir().unwrap().join(".local/share/Steam")];
-
-        // Simple regex to find path entries in libraryfolders.vdf
-        let re = Regex::new(r#""path"\s+"([^"]+)""#)?;
-        for cap in re.captures_iter(content) {
-            if let Some(path_str) = cap.get(1) {
-                paths.push(PathBuf::from(path_str.as_str()));
+    /// Library roots from libraryfolders.vdf, starting with the Steam
+    /// installation itself, each listed once however it is spelled
+    fn parse_steam_libraries(&self, steam_root: &Path, content: &str) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![steam_root.to_path_buf()];
+        let mut seen =
+            vec![fs::canonicalize(steam_root).unwrap_or_else(|_| steam_root.to_path_buf())];
+
+        // Libraries are sections with a "path", files from before 2021 list
+        // them as numbered values instead
+        let vdf = Vdf::parse(content)?;
+        let folders = vdf.get("libraryfolders").unwrap_or(&vdf);
+        let found = folders
+            .entries()
+            .iter()
+            .filter_map(|(key, value)| match value {
+                Vdf::Section(_) => value.get("path").and_then(Vdf::as_str),
+                Vdf::Value(path)
+                    if key.eq_ignore_ascii_case("path") || key.parse::<u32>().is_ok() =>
+                {
+                    Some(path.as_str())
+                }
+                Vdf::Value(_) => None,
+            });
+
+        for path in found.into_iter().map(PathBuf::from) {
+            let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !seen.contains(&canonical) {
+                seen.push(canonical);
+                paths.push(path);
             }
         }
 
@@ -496,63 +714,587 @@ impl LauncherManager {
         Ok(games)
     }
 
-    pub fn launch_game(&self, game: &LauncherGame, wine_cmd: Option<String>) -> Result<()> {
-        match game.launcher {
-            LauncherType::Steam => {
-                // Use Steam URL protocol
-                Command::new("xdg-open").arg(&game.launch_command).spawn()?;
-            }
-            LauncherType::BattleNet
-            | LauncherType::Epic
-            | LauncherType::GOG
-            | LauncherType::Ubisoft
-            | LauncherType::EA => {
-                // Launch through Wine
-                if let Some(wine) = wine_cmd {
-                    if let Some(exe) = &game.executable {
-                        Command::new(wine)
-                            .arg(exe)
-                            .current_dir(&game.install_path)
-                            .spawn()?;
+    pub fn sync_epic_games(&self, epic_launcher: &Launcher) -> Result<Vec<LauncherGame>> {
+        let mut games: Vec<LauncherGame> = Vec::new();
+
+        // The Epic Games Launcher keeps one .item manifest per installed game
+        if let Some(prefix) = &epic_launcher.wine_prefix {
+            let manifests_dir =
+                prefix.join("drive_c/ProgramData/Epic/EpicGamesLauncher/Data/Manifests");
+            if let Ok(entries) = fs::read_dir(&manifests_dir) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|s| s.to_str()) == Some("item")
+                        && let Ok(game) = self.parse_epic_item(prefix, &path)
+                    {
+                        games.push(game);
                     }
-                } else {
-                    return Err(anyhow::anyhow!(
-                        "Wine command not specified for Windows game"
-                    ));
                 }
             }
-            _ => {
-                return Err(anyhow::anyhow!("Launcher type not supported"));
+        }
+
+        // Legendary, standalone or bundled with Heroic, also knows owned titles
+        let config_dir = dirs::config_dir().unwrap_or_default();
+        let legendary_dirs = [
+            legendary_config_dir(),
+            config_dir.join("heroic/legendaryConfig/legendary"),
+        ];
+
+        let mut legendary_games = Vec::new();
+        for legendary_dir in legendary_dirs {
+            match self.parse_legendary_library(&legendary_dir) {
+                Ok(library) => legendary_games.extend(library),
+                Err(e) => out!(
+                    "⚠️ Could not read the legendary library in {}: {}",
+                    legendary_dir.display(),
+                    e
+                ),
+            }
+        }
+        if which::which("legendary").is_ok() {
+            match self.list_legendary_games() {
+                Ok(installed) => legendary_games.extend(installed),
+                Err(e) => out!("⚠️ Could not list games with legendary: {}", e),
             }
         }
 
-        Ok(())
+        for game in legendary_games {
+            match games.iter_mut().find(|g| g.launcher_id == game.launcher_id) {
+                Some(existing) if !existing.installed && game.installed => *existing = game,
+                Some(_) => {}
+                None => games.push(game),
+            }
+        }
+
+        Ok(games)
+    }
+
+    /// Installed Epic games as reported by `legendary list-installed`
+    pub fn list_legendary_games(&self) -> Result<Vec<LauncherGame>> {
+        let output = Command::new("legendary")
+            .args(["list-installed", "--json"])
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "legendary list-installed failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let installed: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+        Ok(installed
+            .iter()
+            .filter(|entry| !entry["is_dlc"].as_bool().unwrap_or(false))
+            .filter_map(|entry| {
+                let app_name = entry["app_name"].as_str()?;
+                let install_path = PathBuf::from(entry["install_path"].as_str()?);
+                let executable = entry["executable"]
+                    .as_str()
+                    .filter(|exe| !exe.is_empty())
+                    .map(|exe| install_path.join(exe.replace('\\', "/")));
+
+                Some(LauncherGame {
+                    id: format!("epic_{}", app_name),
+                    name: entry["title"].as_str().unwrap_or(app_name).to_string(),
+                    launcher: LauncherType::Epic,
+                    launcher_id: app_name.to_string(),
+                    installed: install_path.exists(),
+                    install_path,
+                    executable,
+                    launch_command: format!("legendary launch {}", app_name),
+                    icon: None,
+                })
+            })
+            .collect())
+    }
+
+    fn parse_epic_item(&self, prefix: &Path, item_path: &Path) -> Result<LauncherGame> {
+        let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(item_path)?)?;
+
+        let app_name = manifest["AppName"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing AppName in {}", item_path.display()))?;
+        let name = manifest["DisplayName"].as_str().unwrap_or(app_name);
+        let install_location = manifest["InstallLocation"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing InstallLocation in {}", item_path.display()))?;
+
+        let install_path = windows_path_in_prefix(prefix, install_location);
+        let executable = manifest["LaunchExecutable"]
+            .as_str()
+            .filter(|exe| !exe.is_empty())
+            .map(|exe| install_path.join(exe.replace('\\', "/")));
+        let incomplete = manifest["bIsIncompleteInstall"].as_bool().unwrap_or(false);
+
+        Ok(LauncherGame {
+            id: format!("epic_{}", app_name),
+            name: name.to_string(),
+            launcher: LauncherType::Epic,
+            launcher_id: app_name.to_string(),
+            install_path: install_path.clone(),
+            executable,
+            launch_command: format!(
+                "com.epicgames.launcher://apps/{}?action=launch&silent=true",
+                app_name
+            ),
+            icon: None,
+            installed: !incomplete && install_path.exists(),
+        })
+    }
+
+    /// Read Legendary's installed.json and metadata cache
+    fn parse_legendary_library(&self, legendary_dir: &Path) -> Result<Vec<LauncherGame>> {
+        let mut games = Vec::new();
+
+        let installed_json = legendary_dir.join("installed.json");
+        if installed_json.exists() {
+            let installed: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&installed_json)?)?;
+
+            if let Some(entries) = installed.as_object() {
+                for (app_name, entry) in entries {
+                    if entry["is_dlc"].as_bool().unwrap_or(false) {
+                        continue;
+                    }
+
+                    let install_path = PathBuf::from(entry["install_path"].as_str().unwrap_or(""));
+                    let executable = entry["executable"]
+                        .as_str()
+                        .filter(|exe| !exe.is_empty())
+                        .map(|exe| install_path.join(exe.replace('\\', "/")));
+
+                    games.push(LauncherGame {
+                        id: format!("epic_{}", app_name),
+                        name: entry["title"].as_str().unwrap_or(app_name).to_string(),
+                        launcher: LauncherType::Epic,
+                        launcher_id: app_name.clone(),
+                        install_path: install_path.clone(),
+                        executable,
+                        launch_command: format!("legendary launch {}", app_name),
+                        icon: None,
+                        installed: install_path.exists(),
+                    });
+                }
+            }
+        }
+
+        // Every owned title has a metadata file, installed or not
+        if let Ok(entries) = fs::read_dir(legendary_dir.join("metadata")) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                    continue;
+                }
+
+                let Ok(content) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let Ok(metadata) = serde_json::from_str::<serde_json::Value>(&content) else {
+                    continue;
+                };
+
+                // DLC point back at their base game
+                if !metadata["metadata"]["mainGameItem"].is_null() {
+                    continue;
+                }
+
+                let Some(app_name) = metadata["app_name"].as_str() else {
+                    continue;
+                };
+                if games.iter().any(|g| g.launcher_id == app_name) {
+                    continue;
+                }
+
+                games.push(LauncherGame {
+                    id: format!("epic_{}", app_name),
+                    name: metadata["app_title"]
+                        .as_str()
+                        .unwrap_or(app_name)
+                        .to_string(),
+                    launcher: LauncherType::Epic,
+                    launcher_id: app_name.to_string(),
+                    install_path: PathBuf::new(),
+                    executable: None,
+                    launch_command: format!("legendary launch {}", app_name),
+                    icon: None,
+                    installed: false,
+                });
+            }
+        }
+
+        Ok(games)
+    }
+
+    pub fn sync_gog_games(&self, gog_launcher: &Launcher) -> Result<Vec<LauncherGame>> {
+        // Installs from whichever client is present
+        let mut installs: Vec<GogInstall> = Vec::new();
+
+        if let Some(prefix) = &gog_launcher.wine_prefix {
+            let database = prefix.join("drive_c/ProgramData/GOG.com/Galaxy/storage/galaxy-2.0.db");
+            if database.exists() {
+                match self.read_galaxy_installs(prefix, &database) {
+                    Ok(found) => installs.extend(found),
+                    Err(e) => out!("⚠️ Could not read GOG Galaxy database: {}", e),
+                }
+            }
+        }
+
+        // Without Galaxy, fall back to Heroic's install list and Minigalaxy's library folder
+        if installs.is_empty() {
+            let config_dir = dirs::config_dir().unwrap_or_default();
+
+            let heroic_installed = config_dir.join("heroic/gog_store/installed.json");
+            if let Ok(content) = fs::read_to_string(&heroic_installed)
+                && let Ok(installed) = serde_json::from_str::<serde_json::Value>(&content)
+            {
+                for entry in installed["installed"].as_array().into_iter().flatten() {
+                    if let Some(path) = entry["install_path"].as_str() {
+                        installs.push(GogInstall {
+                            product_id: entry["appName"].as_str().map(String::from),
+                            title: None,
+                            path: PathBuf::from(path),
+                        });
+                    }
+                }
+            }
+
+            let mut library_dirs = gog_launcher.games_path.clone();
+            if let Ok(content) = fs::read_to_string(config_dir.join("minigalaxy/config.json"))
+                && let Ok(config) = serde_json::from_str::<serde_json::Value>(&content)
+                && let Some(dir) = config["install_dir"].as_str()
+            {
+                library_dirs.push(PathBuf::from(dir));
+            }
+
+            for library_dir in library_dirs {
+                if let Ok(entries) = fs::read_dir(&library_dir) {
+                    for entry in entries.flatten() {
+                        if entry.path().is_dir() {
+                            installs.push(GogInstall {
+                                product_id: None,
+                                title: None,
+                                path: entry.path(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut games: Vec<LauncherGame> = Vec::new();
+        for install in installs {
+            if let Some(game) =
+                self.parse_gog_install(&install.path, install.product_id, install.title)
+                && !games.iter().any(|g| g.launcher_id == game.launcher_id)
+            {
+                games.push(game);
+            }
+        }
+
+        Ok(games)
+    }
+
+    fn read_galaxy_installs(&self, prefix: &Path, database: &Path) -> Result<Vec<GogInstall>> {
+        let connection = rusqlite::Connection::open_with_flags(
+            database,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+
+        let mut statement = connection.prepare(
+            "SELECT ibp.productId, ibp.installationPath, ld.title
+             FROM InstalledBaseProducts ibp
+             LEFT JOIN LimitedDetails ld ON ld.productId = ibp.productId",
+        )?;
+
+        let installs = statement
+            .query_map([], |row| {
+                let product_id: i64 = row.get(0)?;
+                let install_path: String = row.get(1)?;
+                let title: Option<String> = row.get(2)?;
+                Ok(GogInstall {
+                    product_id: Some(product_id.to_string()),
+                    title,
+                    path: windows_path_in_prefix(prefix, &install_path),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(installs)
     }
 
+    /// Build a game from an install directory using its `goggame-*.info` file
+    fn parse_gog_install(
+        &self,
+        install_path: &Path,
+        product_id: Option<String>,
+        title: Option<String>,
+    ) -> Option<LauncherGame> {
+        if !install_path.exists() {
+            return None;
+        }
+
+        // Native Linux builds keep the Windows-style metadata under game/
+        let info = [install_path.to_path_buf(), install_path.join("game")]
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("goggame-") && n.ends_with(".info"))
+            })
+            .filter_map(|path| {
+                let info: serde_json::Value =
+                    serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+                Some((path.parent()?.to_path_buf(), info))
+            })
+            // DLC ship their own info files, the base game's id matches rootGameId
+            .max_by_key(|(_, info)| {
+                let game_id = info["gameId"].as_str();
+                (game_id.is_some() && game_id == info["rootGameId"].as_str()) as u8
+                    + (game_id.is_some() && game_id == product_id.as_deref()) as u8
+            });
+
+        let (game_id, name, executable) = match &info {
+            Some((info_dir, info)) => {
+                let tasks = info["playTasks"].as_array().cloned().unwrap_or_default();
+                let is_game_file = |task: &&serde_json::Value| {
+                    task["type"].as_str() == Some("FileTask")
+                        && task["category"].as_str().unwrap_or("game") == "game"
+                };
+                let primary = tasks
+                    .iter()
+                    .filter(is_game_file)
+                    .find(|task| task["isPrimary"].as_bool().unwrap_or(false))
+                    .or_else(|| tasks.iter().find(is_game_file));
+
+                (
+                    info["gameId"].as_str().map(String::from).or(product_id),
+                    info["name"].as_str().map(String::from).or(title),
+                    primary
+                        .and_then(|task| task["path"].as_str())
+                        .map(|path| info_dir.join(path.replace('\\', "/"))),
+                )
+            }
+            None => {
+                // Without GOG metadata only an installer's start.sh or a
+                // launcher's record tells a game apart from any other folder
+                let start_script = install_path.join("start.sh");
+                let start_script = start_script.exists().then_some(start_script);
+                if start_script.is_none() && product_id.is_none() {
+                    return None;
+                }
+                (product_id, title, start_script)
+            }
+        };
+
+        let name = name.or_else(|| {
+            install_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+        })?;
+        let launcher_id = game_id.unwrap_or_else(|| name.to_lowercase().replace(' ', "_"));
+
+        Some(LauncherGame {
+            id: format!("gog_{}", launcher_id),
+            name,
+            launcher: LauncherType::GOG,
+            launcher_id: launcher_id.clone(),
+            install_path: install_path.to_path_buf(),
+            launch_command: executable
+                .as_ref()
+                .map(|exe| exe.display().to_string())
+                .unwrap_or_default(),
+            executable,
+            icon: None,
+            installed: true,
+        })
+    }
+
+    /// Set up a launcher and register it in the config so syncing can find it.
+    ///
+    /// With `path`, an existing installation is registered instead of installing fresh.
     pub async fn setup_launcher(
         &self,
         launcher_type: LauncherType,
-        _path: Option<PathBuf>,
+        path: Option<PathBuf>,
     ) -> Result<Launcher> {
-        // Implementation for manually setting up a launcher
-        match launcher_type {
-            LauncherType::Steam => {
-                if let Some(steam) = self.detect_steam()? {
-                    return Ok(steam);
+        if let Some(path) = path {
+            return self.register_existing_launcher(&launcher_type, &path);
+        }
+
+        let launcher = match launcher_type {
+            LauncherType::Steam => self
+                .detect_steam()?
+                .ok_or_else(|| anyhow::anyhow!("Steam not found. Please install Steam first."))?,
+            LauncherType::BattleNet => match self.detect_battlenet()? {
+                Some(existing) => existing,
+                None => self.setup_battlenet_launcher().await?,
+            },
+            LauncherType::Epic | LauncherType::GOG | LauncherType::Ubisoft | LauncherType::EA => {
+                match self.detect_launcher(&launcher_type)? {
+                    Some(existing) => existing,
+                    None => self.setup_wine_launcher(&launcher_type).await?,
                 }
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Launcher setup not yet implemented for {:?}",
+                    launcher_type
+                ));
+            }
+        };
+
+        self.register_launcher(&launcher)?;
+        Ok(launcher)
+    }
+
+    /// Save a launcher's location in the config
+    fn register_launcher(&self, launcher: &Launcher) -> Result<()> {
+        let Some(key) = launcher.launcher_type.config_key() else {
+            return Ok(());
+        };
+
+        let mut config = crate::config::Config::load()?;
+        config.set_launcher(
+            key,
+            Some(crate::config::LauncherConfig {
+                enabled: true,
+                path: launcher.path.clone(),
+                wine_version: launcher.wine_version.clone(),
+                prefix_path: launcher.wine_prefix.clone(),
+                auto_sync: true,
+            }),
+        );
+        config.save()
+    }
+
+    /// Register a launcher the user installed themselves
+    fn register_existing_launcher(
+        &self,
+        launcher_type: &LauncherType,
+        path: &Path,
+    ) -> Result<Launcher> {
+        let key = launcher_type
+            .config_key()
+            .ok_or_else(|| anyhow::anyhow!("Unsupported launcher: {:?}", launcher_type))?;
+        let path = fs::canonicalize(path)
+            .map_err(|_| anyhow::anyhow!("Path does not exist: {}", path.display()))?;
+
+        // Windows launchers are found through their Wine prefix
+        let location = if *launcher_type == LauncherType::Steam {
+            path.clone()
+        } else {
+            path.ancestors()
+                .find(|p| p.join("drive_c").is_dir())
+                .map(Path::to_path_buf)
+                .ok_or_else(|| anyhow::anyhow!("{} is not inside a Wine prefix", path.display()))?
+        };
+
+        let mut config = crate::config::Config::load()?;
+        let previous = config.get_launcher(key).cloned();
+        config.set_launcher(
+            key,
+            Some(crate::config::LauncherConfig {
+                enabled: true,
+                path: location.clone(),
+                wine_version: None,
+                prefix_path: (*launcher_type != LauncherType::Steam).then(|| location.clone()),
+                auto_sync: true,
+            }),
+        );
+        config.save()?;
+
+        match self.detect_launcher(launcher_type)? {
+            Some(launcher)
+                if launcher.wine_prefix.as_ref().unwrap_or(&launcher.path) == &location =>
+            {
+                self.register_launcher(&launcher)?;
+                Ok(launcher)
+            }
+            _ => {
+                config.set_launcher(key, previous);
+                config.save()?;
                 Err(anyhow::anyhow!(
-                    "Steam not found. Please install Steam first."
+                    "No {:?} installation found at {}",
+                    launcher_type,
+                    location.display()
                 ))
             }
-            LauncherType::BattleNet => {
-                // Download and install Battle.net installer
-                self.setup_battlenet_launcher().await
-            }
-            _ => Err(anyhow::anyhow!(
-                "Launcher setup not yet implemented for {:?}",
+        }
+    }
+
+    /// Create a dedicated Wine prefix and run the launcher's installer in it
+    async fn setup_wine_launcher(&self, launcher_type: &LauncherType) -> Result<Launcher> {
+        let installer = wine_launcher_installer(launcher_type)
+            .ok_or_else(|| anyhow::anyhow!("No installer known for {:?}", launcher_type))?;
+
+        out!("🎮 Setting up {:?} launcher...", launcher_type);
+
+        if matches!(launcher_type, LauncherType::Epic | LauncherType::GOG)
+            && which::which("heroic").is_ok()
+        {
+            out!("💡 Heroic Games Launcher is installed and can manage Epic/GOG games natively");
+        }
+
+        if which::which("wine").is_err() {
+            return Err(anyhow::anyhow!(
+                "Wine is required for {:?}. Please install Wine first.",
                 launcher_type
-            )),
+            ));
         }
+
+        let prefix = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+            .join("Games")
+            .join(installer.prefix_dir);
+        Self::init_wine_prefix(&prefix)?;
+
+        let installer_path = self
+            .download_installer(installer.url, installer.file_name)
+            .await?;
+
+        out!("🔧 Running {}...", installer.file_name);
+        let mut cmd = std::process::Command::new("wine");
+        cmd.env("WINEPREFIX", &prefix);
+        if installer.file_name.ends_with(".msi") {
+            cmd.args(["msiexec", "/i"]);
+        }
+        let status = cmd.arg(&installer_path).args(installer.args).status()?;
+
+        if !status.success() {
+            return Err(anyhow::anyhow!(
+                "{} exited with {}",
+                installer.file_name,
+                status
+            ));
+        }
+
+        let launcher = self.detect_launcher(launcher_type)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "{:?} installation finished but the launcher was not found in {}",
+                launcher_type,
+                prefix.display()
+            )
+        })?;
+
+        out!("✅ {} setup completed successfully!", launcher.name);
+        Ok(launcher)
+    }
+
+    /// Create and initialize a Wine prefix if it doesn't exist yet
+    fn init_wine_prefix(prefix: &Path) -> Result<()> {
+        if prefix.join("drive_c").exists() {
+            return Ok(());
+        }
+
+        crate::prefix::PrefixManager::create(
+            prefix,
+            crate::prefix::DEFAULT_ARCH,
+            crate::prefix::DEFAULT_WINDOWS_VERSION,
+            None,
+        )
     }
 
     /// Import games from all detected launchers into the database
@@ -561,10 +1303,10 @@ impl LauncherManager {
         let mut total_imported = 0;
 
         for launcher in launchers {
-            println!("🔄 Syncing games from {}...", launcher.name);
+            out!("🔄 Syncing games from {}...", launcher.name);
             let imported = self.import_launcher_games(&launcher, game_lib).await?;
             total_imported += imported;
-            println!("✅ Imported {} games from {}", imported, launcher.name);
+            out!("✅ Imported {} games from {}", imported, launcher.name);
         }
 
         Ok(total_imported)
@@ -581,7 +1323,13 @@ impl LauncherManager {
             LauncherType::BattleNet => self.import_battlenet_games(launcher, game_lib).await?,
             LauncherType::Epic => self.import_epic_games(launcher, game_lib).await?,
             LauncherType::GOG => self.import_gog_games(launcher, game_lib).await?,
-            _ => 0,
+            _ => {
+                out!(
+                    "⚠️ Importing games from {} is not supported yet",
+                    launcher.name
+                );
+                0
+            }
         };
 
         Ok(games)
@@ -593,9 +1341,26 @@ impl LauncherManager {
         game_lib: &crate::game::GameLibrary,
     ) -> Result<u32> {
         let steam_games = self.sync_steam_games(launcher)?;
+        let steam_root = launcher.config_path.parent().unwrap_or(&launcher.path);
+        let user_config = SteamUserConfig::load(steam_root);
         let mut imported_count = 0;
 
         for steam_game in steam_games {
+            // Keep the launch options and Proton version picked in Steam
+            let launch_options = user_config
+                .launch_options
+                .get(&steam_game.launcher_id)
+                .map(|options| SteamLaunchOptions::parse(options))
+                .unwrap_or_default();
+            let mangohud = launch_options
+                .wrappers
+                .iter()
+                .any(|wrapper| wrapper == "mangohud")
+                .then(|| crate::mangohud::MangoHudSettings {
+                    enabled: true,
+                    ..Default::default()
+                });
+
             // Convert LauncherGame to Game
             let game = crate::game::Game {
                 id: steam_game.id.clone(),
@@ -608,12 +1373,16 @@ impl LauncherManager {
                 install_path: steam_game.install_path.clone(),
                 launcher: Some("Steam".to_string()),
                 launcher_id: Some(steam_game.launcher_id.clone()),
-                wine_version: None, // Steam games typically don't need Wine
+                // Forced compatibility tool, Steam decides otherwise
+                wine_version: user_config
+                    .compat_tools
+                    .get(&steam_game.launcher_id)
+                    .cloned(),
                 wine_prefix: None,
                 icon: steam_game.icon.clone(),
                 banner: None,
-                launch_arguments: vec![], // Steam handles launch arguments
-                environment_variables: vec![],
+                launch_arguments: launch_options.arguments,
+                environment_variables: launch_options.environment,
                 pre_launch_script: None,
                 post_launch_script: None,
                 categories: vec!["Steam".to_string()],
@@ -624,16 +1393,53 @@ impl LauncherManager {
                 favorite: false,
                 hidden: false,
                 notes: None,
+                dxvk_version: None,
+                vkd3d_version: None,
+                mangohud,
+                gpu_index: None,
+                vulkan_device: None,
+                upscaling: None,
+                profile: None,
+                fps_limit: None,
+                gamemode: None,
             };
 
             // Check if game already exists
-            if game_lib.get_game(&game.id)?.is_none() {
-                game_lib.add_game(&game)?;
-                imported_count += 1;
-
-                // Try to get ProtonDB data for the game
-                if let Ok(appid) = steam_game.launcher_id.parse::<u32>() {
-                    let _ = self.fetch_and_cache_protondb_data(appid, &game.name).await;
+            match game_lib.get_game(&game.id)? {
+                None => {
+                    game_lib.add_game(&game)?;
+                    imported_count += 1;
+
+                    // Try to get ProtonDB data for the game
+                    if let Ok(appid) = steam_game.launcher_id.parse::<u32>() {
+                        let _ = self.fetch_and_cache_protondb_data(appid, &game.name).await;
+                    }
+                }
+                // Earlier imports dropped Steam's settings, fill in what the
+                // record doesn't have without replacing anything set since
+                Some(mut existing) => {
+                    let mut changed = false;
+                    if existing.launch_arguments.is_empty() && !game.launch_arguments.is_empty() {
+                        existing.launch_arguments = game.launch_arguments;
+                        changed = true;
+                    }
+                    if existing.environment_variables.is_empty()
+                        && !game.environment_variables.is_empty()
+                    {
+                        existing.environment_variables = game.environment_variables;
+                        changed = true;
+                    }
+                    if existing.wine_version.is_none() && game.wine_version.is_some() {
+                        existing.wine_version = game.wine_version;
+                        changed = true;
+                    }
+                    if existing.mangohud.is_none() && game.mangohud.is_some() {
+                        existing.mangohud = game.mangohud;
+                        changed = true;
+                    }
+                    if changed {
+                        game_lib.update_game(&existing)?;
+                    }
                 }
             }
         }
@@ -689,6 +1495,15 @@ impl LauncherManager {
                         .unwrap_or(&std::path::PathBuf::from("unknown"))
                         .display()
                 )),
+                dxvk_version: None,
+                vkd3d_version: None,
+                mangohud: None,
+                gpu_index: None,
+                vulkan_device: None,
+                upscaling: None,
+                profile: None,
+                fps_limit: None,
+                gamemode: None,
             };
 
             if game_lib.get_game(&game.id)?.is_none() {
@@ -702,22 +1517,130 @@ impl LauncherManager {
 
     async fn import_epic_games(
         &self,
-        _launcher: &Launcher,
-        _game_lib: &crate::game::GameLibrary,
+        launcher: &Launcher,
+        game_lib: &crate::game::GameLibrary,
     ) -> Result<u32> {
-        // TODO: Implement Epic Games import
-        println!("Epic Games import not yet implemented");
-        Ok(0)
+        let epic_games = self.sync_epic_games(launcher)?;
+        let mut imported_count = 0;
+        let mut not_installed = 0;
+
+        for epic_game in epic_games {
+            if !epic_game.installed {
+                not_installed += 1;
+                continue;
+            }
+
+            let game = crate::game::Game {
+                id: epic_game.id.clone(),
+                name: epic_game.name.clone(),
+                executable: epic_game.executable.unwrap_or_else(|| {
+                    self.find_game_executable(&epic_game.install_path)
+                        .unwrap_or_else(|| epic_game.install_path.join("game.exe"))
+                }),
+                install_path: epic_game.install_path.clone(),
+                launcher: Some("Epic".to_string()),
+                launcher_id: Some(epic_game.launcher_id.clone()),
+                wine_version: None,
+                // Legendary keeps its own per-game prefixes
+                wine_prefix: if epic_game.launch_command.starts_with("legendary ") {
+                    None
+                } else {
+                    launcher.wine_prefix.clone()
+                },
+                icon: epic_game.icon.clone(),
+                banner: None,
+                launch_arguments: vec![],
+                environment_variables: vec![],
+                pre_launch_script: None,
+                post_launch_script: None,
+                categories: vec!["Epic".to_string()],
+                tags: vec![],
+                playtime_minutes: 0,
+                last_played: None,
+                installed_date: chrono::Utc::now(),
+                favorite: false,
+                hidden: false,
+                notes: None,
+                dxvk_version: None,
+                vkd3d_version: None,
+                mangohud: None,
+                gpu_index: None,
+                vulkan_device: None,
+                upscaling: None,
+                profile: None,
+                fps_limit: None,
+                gamemode: None,
+            };
+
+            if game_lib.get_game(&game.id)?.is_none() {
+                game_lib.add_game(&game)?;
+                imported_count += 1;
+            }
+        }
+
+        if not_installed > 0 {
+            out!(
+                "  ℹ️ {} owned Epic games are not installed and were skipped",
+                not_installed
+            );
+        }
+
+        Ok(imported_count)
     }
 
     async fn import_gog_games(
         &self,
-        _launcher: &Launcher,
-        _game_lib: &crate::game::GameLibrary,
+        launcher: &Launcher,
+        game_lib: &crate::game::GameLibrary,
     ) -> Result<u32> {
-        // TODO: Implement GOG Galaxy import
-        println!("GOG Galaxy import not yet implemented");
-        Ok(0)
+        let gog_games = self.sync_gog_games(launcher)?;
+        let mut imported_count = 0;
+
+        for gog_game in gog_games {
+            let game = crate::game::Game {
+                id: gog_game.id.clone(),
+                name: gog_game.name.clone(),
+                executable: gog_game.executable.unwrap_or_else(|| {
+                    self.find_game_executable(&gog_game.install_path)
+                        .unwrap_or_else(|| gog_game.install_path.join("game.exe"))
+                }),
+                install_path: gog_game.install_path.clone(),
+                launcher: Some("GOG".to_string()),
+                launcher_id: Some(gog_game.launcher_id.clone()),
+                wine_version: None,
+                wine_prefix: launcher.wine_prefix.clone(),
+                icon: gog_game.icon.clone(),
+                banner: None,
+                launch_arguments: vec![],
+                environment_variables: vec![],
+                pre_launch_script: None,
+                post_launch_script: None,
+                categories: vec!["GOG".to_string()],
+                tags: vec![],
+                playtime_minutes: 0,
+                last_played: None,
+                installed_date: chrono::Utc::now(),
+                favorite: false,
+                hidden: false,
+                notes: None,
+                dxvk_version: None,
+                vkd3d_version: None,
+                mangohud: None,
+                gpu_index: None,
+                vulkan_device: None,
+                upscaling: None,
+                profile: None,
+                fps_limit: None,
+                gamemode: None,
+            };
+
+            if game_lib.get_game(&game.id)?.is_none() {
+                game_lib.add_game(&game)?;
+                imported_count += 1;
+            }
+        }
+
+        Ok(imported_count)
     }
 
     /// Find the main executable for a game
@@ -768,24 +1691,23 @@ impl LauncherManager {
     }
 
     async fn fetch_and_cache_protondb_data(&self, steam_appid: u32, game_name: &str) -> Result<()> {
-        let protondb = crate::protondb::ProtonDBClient::new();
-        let cache_dir = dirs::cache_dir()
-            .unwrap_or_default()
-            .join("ghostforge")
-            .join("protondb");
+        let config = crate::config::Config::load()?;
+        let protondb = crate::protondb::ProtonDBClient::from_config(&config);
+        let cache_dir = config.paths.cache.join("protondb");
 
         match protondb.get_game_summary(steam_appid).await {
             Ok(Some(_summary)) => {
                 protondb.cache_game_data(steam_appid, &cache_dir).await?;
-                println!("  📊 Cached ProtonDB data for {}", game_name);
+                out!("  📊 Cached ProtonDB data for {}", game_name);
             }
             Ok(None) => {
-                println!("  ⚠️ No ProtonDB data found for {}", game_name);
+                out!("  ⚠️ No ProtonDB data found for {}", game_name);
             }
             Err(e) => {
-                println!(
+                out!(
                     "  ⚠️ Failed to fetch ProtonDB data for {}: {}",
-                    game_name, e
+                    game_name,
+                    e
                 );
             }
         }
@@ -795,7 +1717,7 @@ impl LauncherManager {
 
     /// Setup Battle.net launcher with Wine integration for WoW, Diablo 4, etc.
     async fn setup_battlenet_launcher(&self) -> Result<Launcher> {
-        println!("🎮 Setting up Battle.net launcher for WoW/Diablo 4...");
+        out!("🎮 Setting up Battle.net launcher for WoW/Diablo 4...");
 
         // Check if Wine is installed
         if which::which("wine").is_err() {
@@ -809,29 +1731,18 @@ impl LauncherManager {
             .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
             .join(".wine-battlenet");
 
-        if !battlenet_prefix.exists() {
-            println!("📁 Creating Battle.net Wine prefix...");
-            std::fs::create_dir_all(&battlenet_prefix)?;
-
-            // Initialize Wine prefix
-            let status = std::process::Command::new("env")
-                .env("WINEPREFIX", &battlenet_prefix)
-                .arg("wineboot")
-                .arg("--init")
-                .status()?;
-
-            if !status.success() {
-                return Err(anyhow::anyhow!(
-                    "Failed to initialize Wine prefix for Battle.net"
-                ));
-            }
-        }
+        Self::init_wine_prefix(&battlenet_prefix)?;
 
         // Download Battle.net installer
-        let installer_path = self.download_battlenet_installer().await?;
+        let installer_path = self
+            .download_installer(
+                "https://www.battle.net/download/getInstallerForGame?os=win&locale=enUS&version=LIVE&gameProgram=BATTLENET_APP",
+                "Battle.net-Setup.exe",
+            )
+            .await?;
 
         // Install Battle.net
-        println!("🔧 Installing Battle.net...");
+        out!("🔧 Installing Battle.net...");
         let install_status = std::process::Command::new("env")
             .env("WINEPREFIX", &battlenet_prefix)
             .arg("wine")
@@ -856,7 +1767,7 @@ impl LauncherManager {
             ));
         }
 
-        println!("✅ Battle.net setup completed successfully!");
+        out!("✅ Battle.net setup completed successfully!");
 
         Ok(Launcher {
             name: "Battle.net".to_string(),
@@ -874,13 +1785,16 @@ impl LauncherManager {
         })
     }
 
-    /// Download the latest Battle.net installer
-    async fn download_battlenet_installer(&self) -> Result<std::path::PathBuf> {
-        let installer_url = "https://www.battle.net/download/getInstallerForGame?os=win&locale=enUS&version=LIVE&gameProgram=BATTLENET_APP";
+    /// Download a launcher installer into the cache, reusing a cached copy
+    async fn download_installer(
+        &self,
+        installer_url: &str,
+        file_name: &str,
+    ) -> Result<std::path::PathBuf> {
         let installer_path = dirs::cache_dir()
             .unwrap_or_default()
             .join("ghostforge")
-            .join("Battle.net-Setup.exe");
+            .join(file_name);
 
         // Create cache directory
         if let Some(parent) = installer_path.parent() {
@@ -889,28 +1803,19 @@ impl LauncherManager {
 
         // Download installer if not already cached
         if !installer_path.exists() {
-            println!("⬇️ Downloading Battle.net installer...");
-
-            let client = reqwest::Client::new();
-            let response = client
-                .get(installer_url)
-                .header(
-                    "User-Agent",
-                    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+            out!("⬇️ Downloading {}...", file_name);
+
+            let pb = crate::download::progress_bar(file_name);
+            crate::download::Downloader::new()
+                .with_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+                .download(
+                    installer_url,
+                    &installer_path,
+                    &crate::download::bar_progress(&pb),
                 )
-                .send()
                 .await?;
-
-            if !response.status().is_success() {
-                return Err(anyhow::anyhow!(
-                    "Failed to download Battle.net installer: {}",
-                    response.status()
-                ));
-            }
-
-            let bytes = response.bytes().await?;
-            std::fs::write(&installer_path, bytes)?;
-            println!("✅ Battle.net installer downloaded");
+            pb.finish_and_clear();
+            out!("✅ {} downloaded", file_name);
         }
 
         Ok(installer_path)
@@ -918,7 +1823,7 @@ impl LauncherManager {
 
     /// Apply Wine optimizations specifically for Battle.net and Blizzard games
     fn apply_battlenet_wine_optimizations(&self, prefix_path: &std::path::Path) -> Result<()> {
-        println!("⚡ Applying Battle.net Wine optimizations...");
+        out!("⚡ Applying Battle.net Wine optimizations...");
 
         let env_cmd = |args: &[&str]| -> Result<()> {
             let status = std::process::Command::new("env")
@@ -926,7 +1831,7 @@ impl LauncherManager {
                 .args(args)
                 .status()?;
             if !status.success() {
-                println!("⚠️ Wine optimization command failed: {:?}", args);
+                out!("⚠️ Wine optimization command failed: {:?}", args);
             }
             Ok(())
         };
@@ -996,18 +1901,551 @@ impl LauncherManager {
             match winetricks_cmd {
                 Ok(status) => {
                     if status.success() {
-                        println!("✅ Winetricks optimizations applied");
+                        out!("✅ Winetricks optimizations applied");
                     } else {
-                        println!("⚠️ Some winetricks optimizations failed (this is often fine)");
+                        out!("⚠️ Some winetricks optimizations failed (this is often fine)");
                     }
                 }
                 Err(e) => {
-                    println!("⚠️ Could not run winetricks: {} (this is often fine)", e);
+                    out!("⚠️ Could not run winetricks: {} (this is often fine)", e);
                 }
             }
         }
 
-        println!("✅ Battle.net Wine optimizations completed");
+        out!("✅ Battle.net Wine optimizations completed");
         Ok(())
     }
 }
+
+/// A node of Valve's KeyValues (VDF) format
+#[derive(Debug, Clone, PartialEq)]
+pub enum Vdf {
+    Value(String),
+    Section(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    /// Parse VDF text into its top-level section
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut tokens = vdf_tokens(content).into_iter();
+        let root = Self::parse_section(&mut tokens)?;
+        Ok(root)
+    }
+
+    fn parse_section(tokens: &mut std::vec::IntoIter<VdfToken>) -> Result<Self> {
+        let mut entries = Vec::new();
+        while let Some(token) = tokens.next() {
+            let key = match token {
+                VdfToken::Close => return Ok(Self::Section(entries)),
+                VdfToken::Open => return Err(anyhow::anyhow!("Unexpected '{{' in VDF")),
+                VdfToken::Text(key) => key,
+            };
+            let value = match tokens.next() {
+                Some(VdfToken::Open) => Self::parse_section(tokens)?,
+                Some(VdfToken::Text(value)) => Self::Value(value),
+                _ => return Err(anyhow::anyhow!("Missing value for '{}' in VDF", key)),
+            };
+            entries.push((key, value));
+        }
+        Ok(Self::Section(entries))
+    }
+
+    /// Child with `key`, compared case-insensitively like Steam does
+    pub fn get(&self, key: &str) -> Option<&Vdf> {
+        match self {
+            Self::Section(entries) => entries
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+            Self::Value(_) => None,
+        }
+    }
+
+    /// Descendant at `path`
+    pub fn path(&self, path: &[&str]) -> Option<&Vdf> {
+        path.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Section(_) => None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, Vdf)] {
+        match self {
+            Self::Section(entries) => entries,
+            Self::Value(_) => &[],
+        }
+    }
+}
+
+enum VdfToken {
+    Open,
+    Close,
+    Text(String),
+}
+
+fn vdf_tokens(content: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().take_while(|&c| c != '\n').for_each(drop);
+            }
+            // Platform conditionals like [$WIN32] don't apply on Linux
+            '[' => {
+                chars.by_ref().take_while(|&c| c != ']').for_each(drop);
+            }
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(c) => text.push(c),
+                            None => {}
+                        },
+                        c => text.push(c),
+                    }
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut text = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Per-game settings the user made in Steam: launch options from the most
+/// recently used account's localconfig.vdf and forced compatibility tools
+/// from config.vdf, both by app ID
+#[derive(Debug, Clone, Default)]
+pub struct SteamUserConfig {
+    pub launch_options: std::collections::HashMap<String, String>,
+    pub compat_tools: std::collections::HashMap<String, String>,
+}
+
+impl SteamUserConfig {
+    pub fn load(steam_root: &Path) -> Self {
+        let mut config = Self::default();
+
+        let localconfig = fs::read_dir(steam_root.join("userdata"))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join("config/localconfig.vdf"))
+            .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+            .max()
+            .and_then(|(_, path)| fs::read_to_string(path).ok())
+            .and_then(|content| Vdf::parse(&content).ok());
+        let apps = localconfig.as_ref().and_then(|vdf| {
+            vdf.path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"])
+        });
+        for (appid, app) in apps.map(Vdf::entries).unwrap_or_default() {
+            let options = app.get("LaunchOptions").and_then(Vdf::as_str);
+            if let Some(options) = options.filter(|o| !o.trim().is_empty()) {
+                config
+                    .launch_options
+                    .insert(appid.clone(), options.trim().to_string());
+            }
+        }
+
+        let install_config = fs::read_to_string(steam_root.join("config/config.vdf"))
+            .ok()
+            .and_then(|content| Vdf::parse(&content).ok());
+        let mappings = install_config.as_ref().and_then(|vdf| {
+            vdf.path(&[
+                "InstallConfigStore",
+                "Software",
+                "Valve",
+                "Steam",
+                "CompatToolMapping",
+            ])
+        });
+        // App 0 is the default for every game, not a choice for this one
+        for (appid, mapping) in mappings.map(Vdf::entries).unwrap_or_default() {
+            let tool = mapping.get("name").and_then(Vdf::as_str);
+            if let Some(tool) = tool.filter(|t| !t.is_empty() && appid != "0") {
+                config.compat_tools.insert(appid.clone(), tool.to_string());
+            }
+        }
+
+        config
+    }
+}
+
+/// What Steam launch options like `PROTON_LOG=1 mangohud %command% -novid`
+/// amount to: environment variables and wrappers before `%command%`, game
+/// arguments after it or when there is no `%command%`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SteamLaunchOptions {
+    pub environment: Vec<(String, String)>,
+    pub wrappers: Vec<String>,
+    pub arguments: Vec<String>,
+}
+
+impl SteamLaunchOptions {
+    pub fn parse(options: &str) -> Self {
+        let words = crate::lutris::split_arguments(options);
+        let Some(command) = words.iter().position(|word| word == "%command%") else {
+            return Self {
+                arguments: words,
+                ..Default::default()
+            };
+        };
+
+        let mut parsed = Self {
+            arguments: words[command + 1..].to_vec(),
+            ..Default::default()
+        };
+        for word in &words[..command] {
+            let assignment = word.split_once('=').filter(|(key, _)| {
+                !key.is_empty()
+                    && !key.starts_with(|c: char| c.is_ascii_digit())
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+            match assignment {
+                Some((key, value)) if parsed.wrappers.is_empty() => parsed
+                    .environment
+                    .push((key.to_string(), value.to_string())),
+                _ => parsed.wrappers.push(word.clone()),
+            }
+        }
+        parsed
+    }
+}
+
+/// Legendary's config directory, moved with `LEGENDARY_CONFIG_PATH`
+pub fn legendary_config_dir() -> PathBuf {
+    std::env::var_os("LEGENDARY_CONFIG_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dirs::config_dir().unwrap_or_default().join("legendary"))
+}
+
+/// Whether the `legendary` CLI is installed and has installed `app_name`,
+/// so it can launch the game
+pub fn legendary_installed(app_name: &str) -> bool {
+    which::which("legendary").is_ok()
+        && fs::read_to_string(legendary_config_dir().join("installed.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|installed| installed.get(app_name).is_some())
+}
+
+/// Wine build `legendary launch` runs a game with
+#[derive(Debug, Clone)]
+pub enum LegendaryRunner {
+    /// Whatever is set in legendary's own config
+    Default,
+    /// Path to a wine binary
+    Wine(PathBuf),
+    /// Path to a Proton `proton` script, which needs `STEAM_COMPAT_DATA_PATH`
+    Proton(PathBuf),
+}
+
+/// Arguments for `legendary` to launch `app_name` with `runner`, in `prefix`
+/// when given
+pub fn legendary_launch_args(
+    app_name: &str,
+    runner: &LegendaryRunner,
+    prefix: Option<&Path>,
+) -> Vec<String> {
+    let mut args = vec!["launch".to_string(), app_name.to_string()];
+    match runner {
+        LegendaryRunner::Default => {}
+        LegendaryRunner::Wine(wine) => {
+            args.push("--wine".to_string());
+            args.push(wine.to_string_lossy().to_string());
+        }
+        LegendaryRunner::Proton(proton) => {
+            args.push("--no-wine".to_string());
+            args.push("--wrapper".to_string());
+            args.push(format!("\"{}\" run", proton.display()));
+        }
+    }
+    // Proton takes its prefix from STEAM_COMPAT_DATA_PATH instead
+    if let Some(prefix) = prefix.filter(|_| !matches!(runner, LegendaryRunner::Proton(_))) {
+        args.push("--wine-prefix".to_string());
+        args.push(prefix.to_string_lossy().to_string());
+    }
+    args
+}
+
+/// Map a Windows path like `C:\Games\Foo` to its location inside a Wine prefix
+fn windows_path_in_prefix(prefix: &Path, windows_path: &str) -> PathBuf {
+    let normalized = windows_path.replace('\\', "/");
+    match normalized.split_once(':') {
+        Some((drive, rest)) if drive.len() == 1 => {
+            let drive = drive.to_lowercase();
+            let rest = rest.trim_start_matches('/');
+            if drive == "c" {
+                prefix.join("drive_c").join(rest)
+            } else {
+                prefix
+                    .join("dosdevices")
+                    .join(format!("{}:", drive))
+                    .join(rest)
+            }
+        }
+        _ => PathBuf::from(normalized),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// libraryfolders.vdf listing the Steam install, an SSD and an HDD
+    const LIBRARY_FOLDERS: &str = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"{root}"
+		"label"		""
+		"contentid"		"1234567890"
+		"totalsize"		"0"
+		"apps"
+		{
+			"228980"		"123456"
+		}
+	}
+	"1"
+	{
+		"path"		"{ssd}"
+		"label"		"SSD"
+		"apps"
+		{
+			"1091500"		"70000000000"
+		}
+	}
+	"2"
+	{
+		"path"		"{hdd}"
+		"label"		"HDD"
+		"apps"
+		{
+			"292030"		"50000000000"
+		}
+	}
+	"3"
+	{
+		"path"		"{root}/"
+	}
+}
+"#;
+
+    fn write_manifest(library: &Path, appid: &str, name: &str, installdir: &str, installed: bool) {
+        let steamapps = library.join("steamapps");
+        fs::create_dir_all(&steamapps).unwrap();
+        fs::write(
+            steamapps.join(format!("appmanifest_{}.acf", appid)),
+            format!(
+                "\"AppState\"\n{{\n\t\"appid\"\t\t\"{}\"\n\t\"name\"\t\t\"{}\"\n\t\"installdir\"\t\t\"{}\"\n}}\n",
+                appid, name, installdir
+            ),
+        )
+        .unwrap();
+        if installed {
+            fs::create_dir_all(steamapps.join("common").join(installdir)).unwrap();
+        }
+    }
+
+    #[test]
+    fn scans_every_library_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Steam");
+        let ssd = dir.path().join("ssd/SteamLibrary");
+        let hdd = dir.path().join("hdd/SteamLibrary");
+
+        write_manifest(
+            &root,
+            "228980",
+            "Steamworks Common Redistributables",
+            "Steamworks Shared",
+            true,
+        );
+        write_manifest(&ssd, "1091500", "Cyberpunk 2077", "Cyberpunk 2077", true);
+        write_manifest(
+            &hdd,
+            "292030",
+            "The Witcher 3: Wild Hunt",
+            "The Witcher 3",
+            true,
+        );
+        // Left behind when Cyberpunk moved from the HDD to the SSD
+        write_manifest(&hdd, "1091500", "Cyberpunk 2077", "Cyberpunk 2077", false);
+
+        let vdf = LIBRARY_FOLDERS
+            .replace("{root}", &root.to_string_lossy())
+            .replace("{ssd}", &ssd.to_string_lossy())
+            .replace("{hdd}", &hdd.to_string_lossy());
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(root.join("config/libraryfolders.vdf"), vdf).unwrap();
+
+        let manager = LauncherManager::new(dir.path().join("config"));
+        let mut games = manager.scan_steam_libraries(&root).unwrap();
+        games.sort_by(|a, b| a.launcher_id.cmp(&b.launcher_id));
+
+        let found: Vec<(&str, &Path, bool)> = games
+            .iter()
+            .map(|g| {
+                (
+                    g.launcher_id.as_str(),
+                    g.install_path.as_path(),
+                    g.installed,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "1091500",
+                    ssd.join("steamapps/common/Cyberpunk 2077").as_path(),
+                    true
+                ),
+                (
+                    "228980",
+                    root.join("steamapps/common/Steamworks Shared").as_path(),
+                    true
+                ),
+                (
+                    "292030",
+                    hdd.join("steamapps/common/The Witcher 3").as_path(),
+                    true
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unescapes_library_paths() {
+        let manager = LauncherManager::new(PathBuf::from("/nonexistent"));
+        let vdf = r#""path"		"/mnt/games\\Steam Library""#;
+        let paths = manager
+            .parse_steam_libraries(Path::new("/home/user/.local/share/Steam"), vdf)
+            .unwrap();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/games\\Steam Library"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_steam_launch_options_and_compat_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("userdata/12345678/config")).unwrap();
+        fs::write(
+            root.join("userdata/12345678/config/localconfig.vdf"),
+            r#"
+"UserLocalConfigStore"
+{
+	"Software"
+	{
+		"Valve"
+		{
+			"Steam"
+			{
+				"Apps"
+				{
+					"1091500"
+					{
+						"LaunchOptions"		"PROTON_ENABLE_NVAPI=1 mangohud %command% --launcher-skip \"-name\" \"V\""
+						"LastPlayed"		"1700000000"
+					}
+					"292030"
+					{
+						"LaunchOptions"		""
+					}
+				}
+			}
+		}
+	}
+}
+"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(
+            root.join("config/config.vdf"),
+            r#"
+"InstallConfigStore"
+{
+	"Software"
+	{
+		"Valve"
+		{
+			"Steam"
+			{
+				"CompatToolMapping"
+				{
+					"0"
+					{
+						"name"		"proton_experimental"
+						"config"		""
+						"priority"		"75"
+					}
+					"1091500"
+					{
+						"name"		"GE-Proton9-20"
+						"config"		""
+						"priority"		"250"
+					}
+				}
+			}
+		}
+	}
+}
+"#,
+        )
+        .unwrap();
+
+        let config = SteamUserConfig::load(root);
+        assert_eq!(config.compat_tools.len(), 1);
+        assert_eq!(config.compat_tools["1091500"], "GE-Proton9-20");
+        assert_eq!(config.launch_options.len(), 1);
+
+        let options = SteamLaunchOptions::parse(&config.launch_options["1091500"]);
+        assert_eq!(
+            options,
+            SteamLaunchOptions {
+                environment: vec![("PROTON_ENABLE_NVAPI".to_string(), "1".to_string())],
+                wrappers: vec!["mangohud".to_string()],
+                arguments: vec![
+                    "--launcher-skip".to_string(),
+                    "-name".to_string(),
+                    "V".to_string()
+                ],
+            }
+        );
+        assert_eq!(
+            SteamLaunchOptions::parse("-novid -high").arguments,
+            ["-novid", "-high"]
+        );
+    }
+}
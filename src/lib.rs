@@ -1,16 +1,23 @@
+pub mod anticheat;
 pub mod bolt_integration;
 pub mod cli;
 pub mod config;
 pub mod container;
 pub mod display;
+pub mod doctor;
+pub mod download;
 pub mod error;
 pub mod game;
+pub mod game_hints;
 pub mod game_launcher;
 pub mod graphics;
 pub mod launcher;
+pub mod lutris;
+pub mod mangohud;
 pub mod performance;
 pub mod prefix;
 pub mod protondb;
+pub mod tuning;
 pub mod utils;
 pub mod vrr_monitor;
 pub mod wine;
@@ -18,3 +25,6 @@ pub mod winetricks;
 
 #[cfg(feature = "gui")]
 pub mod gui;
+
+#[cfg(feature = "tui")]
+pub mod tui;
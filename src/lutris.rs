@@ -0,0 +1,326 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags, Row};
+use serde_yaml::Value;
+use std::path::{Path, PathBuf};
+
+/// Where a Lutris installation keeps its game database and per-game configs
+#[derive(Debug, Clone)]
+pub struct LutrisPaths {
+    pub database: PathBuf,
+    pub config_dirs: Vec<PathBuf>,
+    /// Wine builds managed by Lutris, one directory per version
+    pub wine_runners_dir: PathBuf,
+}
+
+impl LutrisPaths {
+    /// Locate a native or Flatpak Lutris installation
+    pub fn detect() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        let data_dir = dirs::data_dir().unwrap_or_else(|| home.join(".local/share"));
+        let config_dir = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+        let flatpak = home.join(".var/app/net.lutris.Lutris");
+
+        let roots = [
+            (data_dir.join("lutris"), config_dir.join("lutris")),
+            (flatpak.join("data/lutris"), flatpak.join("config/lutris")),
+        ];
+
+        roots.into_iter().find_map(|(data, config)| {
+            let database = data.join("pga.db");
+            database.exists().then(|| Self {
+                database,
+                // Lutris 0.5.13 moved game configs from the config to the data dir
+                config_dirs: vec![data.join("games"), config.join("games")],
+                wine_runners_dir: data.join("runners/wine"),
+            })
+        })
+    }
+}
+
+/// A game entry from Lutris's `pga.db`, together with its YAML config
+#[derive(Debug, Clone)]
+pub struct LutrisGame {
+    pub slug: String,
+    pub name: String,
+    pub runner: Option<String>,
+    pub directory: Option<PathBuf>,
+    pub installed: bool,
+    pub installed_at: Option<DateTime<Utc>>,
+    pub last_played: Option<DateTime<Utc>>,
+    pub playtime_hours: f64,
+    pub hidden: bool,
+    /// Name of the YAML config under Lutris's `games` directory
+    pub configpath: Option<String>,
+    pub config: Option<Value>,
+}
+
+/// Read every game from the Lutris database, attaching its YAML config when
+/// one can be found
+pub fn read_games(paths: &LutrisPaths) -> Result<Vec<LutrisGame>> {
+    let conn = Connection::open_with_flags(&paths.database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", paths.database.display(), e))?;
+
+    // Column sets vary between Lutris releases, so read by name and treat
+    // missing columns as empty
+    let mut stmt = conn.prepare("SELECT * FROM games")?;
+    let games = stmt
+        .query_map([], |row| Ok(game_from_row(row)))?
+        .filter_map(|game| game.ok().flatten())
+        .map(|mut game| {
+            game.config = find_config(paths, game.configpath.as_deref())
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .and_then(|content| serde_yaml::from_str(&content).ok());
+            game
+        })
+        .collect();
+
+    Ok(games)
+}
+
+fn game_from_row(row: &Row) -> Option<LutrisGame> {
+    let text = |column: &str| {
+        row.get::<_, Option<String>>(column)
+            .ok()
+            .flatten()
+            .filter(|s| !s.is_empty())
+    };
+    let int = |column: &str| row.get::<_, Option<i64>>(column).ok().flatten();
+    let timestamp = |column: &str| int(column).and_then(|ts| DateTime::from_timestamp(ts, 0));
+
+    let slug = text("slug")?;
+    Some(LutrisGame {
+        name: text("name").unwrap_or_else(|| slug.clone()),
+        runner: text("runner"),
+        directory: text("directory").map(PathBuf::from),
+        installed: int("installed").unwrap_or(0) != 0,
+        installed_at: timestamp("installed_at"),
+        last_played: timestamp("lastplayed"),
+        playtime_hours: row
+            .get::<_, Option<f64>>("playtime")
+            .ok()
+            .flatten()
+            .unwrap_or(0.0),
+        hidden: int("hidden").unwrap_or(0) != 0,
+        configpath: text("configpath"),
+        config: None,
+        slug,
+    })
+}
+
+fn find_config(paths: &LutrisPaths, configpath: Option<&str>) -> Option<PathBuf> {
+    let configpath = configpath?;
+    paths
+        .config_dirs
+        .iter()
+        .map(|dir| dir.join(format!("{}.yml", configpath)))
+        .find(|path| path.exists())
+}
+
+impl LutrisGame {
+    fn setting(&self, section: &str, key: &str) -> Option<&Value> {
+        self.config
+            .as_ref()
+            .map(|config| &config[section][key])
+            .filter(|value| !value.is_null())
+    }
+
+    fn text_setting(&self, section: &str, key: &str) -> Option<String> {
+        match self.setting(section, key)? {
+            Value::String(s) if !s.is_empty() => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    fn flag_setting(&self, section: &str, key: &str) -> Option<bool> {
+        match self.setting(section, key)? {
+            Value::Bool(b) => Some(*b),
+            Value::String(s) => Some(s == "true"),
+            _ => None,
+        }
+    }
+
+    /// Resolve a path from the config, relative paths being relative to the
+    /// game directory
+    fn path_setting(&self, section: &str, key: &str) -> Option<PathBuf> {
+        let path = PathBuf::from(expand_home(&self.text_setting(section, key)?));
+        if path.is_relative()
+            && let Some(directory) = &self.directory
+        {
+            return Some(directory.join(path));
+        }
+        Some(path)
+    }
+
+    fn is_wine(&self) -> bool {
+        matches!(self.runner.as_deref(), Some("wine") | Some("proton"))
+    }
+
+    pub fn wine_prefix(&self) -> Option<PathBuf> {
+        if self.is_wine() {
+            self.path_setting("game", "prefix")
+        } else {
+            None
+        }
+    }
+
+    /// Convert to a GhostForge library entry, mapping the runner's Wine
+    /// version, prefix, arguments and environment. Lutris's own Wine builds
+    /// are referenced by path since GhostForge doesn't manage them.
+    pub fn to_game(&self, paths: &LutrisPaths) -> Option<crate::game::Game> {
+        let executable = self.path_setting("game", "exe")?;
+        let wine_prefix = self.wine_prefix();
+        let install_path = self
+            .directory
+            .clone()
+            .or_else(|| executable.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+
+        let mut environment_variables: Vec<(String, String)> = Vec::new();
+        if let Some(Value::Mapping(env)) = self.setting("system", "env") {
+            for (key, value) in env {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+                    _ => continue,
+                };
+                if let Some(key) = key.as_str() {
+                    environment_variables.push((key.to_string(), value));
+                }
+            }
+        }
+
+        let mut wine_version = None;
+        if self.is_wine() {
+            wine_version = self
+                .text_setting("wine", "version")
+                .map(|version| paths.wine_runners_dir.join(version))
+                .filter(|dir| dir.join("bin/wine").exists())
+                .map(|dir| dir.to_string_lossy().to_string());
+            for (key, env) in [("esync", "WINEESYNC"), ("fsync", "WINEFSYNC")] {
+                if self.flag_setting("wine", key) == Some(true) {
+                    environment_variables.push((env.to_string(), "1".to_string()));
+                }
+            }
+        }
+
+        let launch_arguments = self
+            .text_setting("game", "args")
+            .map(|args| split_arguments(&args))
+            .unwrap_or_default();
+
+        Some(crate::game::Game {
+            id: format!("lutris_{}", self.slug),
+            name: self.name.clone(),
+            executable,
+            install_path,
+            launcher: Some("Lutris".to_string()),
+            launcher_id: Some(self.slug.clone()),
+            wine_version,
+            wine_prefix,
+            icon: None,
+            banner: None,
+            launch_arguments,
+            environment_variables,
+            pre_launch_script: None,
+            post_launch_script: None,
+            categories: vec!["Lutris".to_string()],
+            tags: self.runner.iter().cloned().collect(),
+            playtime_minutes: (self.playtime_hours * 60.0).round() as u64,
+            last_played: self.last_played,
+            installed_date: self.installed_at.unwrap_or_else(Utc::now),
+            favorite: false,
+            hidden: self.hidden,
+            notes: Some(format!("Imported from Lutris ({})", self.slug)),
+            // DXVK/VKD3D were already installed into the prefix by Lutris
+            dxvk_version: None,
+            vkd3d_version: None,
+            mangohud: self.flag_setting("system", "mangohud").map(|enabled| {
+                crate::mangohud::MangoHudSettings {
+                    enabled,
+                    ..Default::default()
+                }
+            }),
+            gpu_index: None,
+            vulkan_device: None,
+            upscaling: None,
+            profile: None,
+            fps_limit: None,
+            gamemode: None,
+        })
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Split an argument string into words like a shell would, honoring single
+/// and double quotes and backslash escapes. Used for Lutris, Steam and other
+/// imported launch arguments.
+pub(crate) fn split_arguments(args: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    let mut chars = args.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(c) = chars.next() {
+                    current.get_or_insert_default().push(c);
+                }
+            }
+            (Some(_), c) => current.get_or_insert_default().push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_default();
+            }
+            (None, c) if c.is_whitespace() => parts.extend(current.take()),
+            (None, c) => current.get_or_insert_default().push(c),
+        }
+    }
+    parts.extend(current);
+
+    parts
+}
+
+/// Join arguments back into a string `split_arguments` reads the same way,
+/// quoting the ones with spaces or quotes
+pub(crate) fn join_arguments(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && !arg
+                    .chars()
+                    .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\'));
+            if plain {
+                arg.clone()
+            } else {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_round_trip() {
+        let args = split_arguments(r#"-fullscreen "C:\\My Games" 'it''s' "" -x"#);
+        assert_eq!(args, ["-fullscreen", r"C:\My Games", "its", "", "-x"]);
+        assert_eq!(split_arguments(&join_arguments(&args)), args);
+
+        let args = vec![r#"say "hi""#.to_string(), "don't".to_string()];
+        assert_eq!(split_arguments(&join_arguments(&args)), args);
+    }
+}
@@ -1,18 +1,27 @@
+mod anticheat;
 mod bolt_integration;
 mod cli;
 mod config;
 mod container;
 mod display;
+mod doctor;
+mod download;
 mod error;
 mod game;
+mod game_hints;
 mod game_launcher;
 mod graphics;
 #[cfg(feature = "gui")]
 mod gui;
 mod launcher;
+mod lutris;
+mod mangohud;
 mod performance;
 mod prefix;
 mod protondb;
+#[cfg(feature = "tui")]
+mod tui;
+mod tuning;
 mod utils;
 mod vrr_monitor;
 mod wine;
@@ -24,15 +33,13 @@ use tracing_subscriber;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
     // Check if we should launch GUI mode
     let args: Vec<String> = std::env::args().collect();
 
     // Launch GUI if no arguments or explicit --gui flag
     if args.len() == 1 || args.contains(&"--gui".to_string()) || args.contains(&"gui".to_string()) {
+        init_tracing(0, utils::use_color(false));
+
         #[cfg(feature = "gui")]
         {
             return gui::run_gui();
@@ -46,5 +53,24 @@ async fn main() -> Result<()> {
 
     // Otherwise run CLI
     let cli = cli::Cli::parse();
+    let color = utils::use_color(cli.no_color);
+    colored::control::set_override(color);
+    utils::set_plain_output(!color);
+    init_tracing(cli.verbose, color);
+
     cli.execute().await
 }
+
+/// `-v` logs at debug and `-vv` at trace level, otherwise `RUST_LOG` decides
+fn init_tracing(verbose: u8, color: bool) {
+    let filter = match verbose {
+        0 => tracing_subscriber::EnvFilter::from_default_env(),
+        1 => tracing_subscriber::EnvFilter::new("debug"),
+        _ => tracing_subscriber::EnvFilter::new("trace"),
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_ansi(color)
+        .init();
+}
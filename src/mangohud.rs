@@ -0,0 +1,129 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Corners and edges MangoHud can place its overlay at
+pub const POSITIONS: &[&str] = &[
+    "top-left",
+    "top-center",
+    "top-right",
+    "middle-left",
+    "middle-right",
+    "bottom-left",
+    "bottom-center",
+    "bottom-right",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MangoHudPreset {
+    /// Frame rate only
+    #[default]
+    Minimal,
+    /// Frame rate and timing, CPU/GPU load, temperatures, clocks and memory
+    Full,
+}
+
+impl MangoHudPreset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "minimal" => Some(Self::Minimal),
+            "full" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Full => "full",
+        }
+    }
+
+    fn options(&self) -> &'static [&'static str] {
+        match self {
+            Self::Minimal => &[
+                "fps",
+                "frametime=0",
+                "frame_timing=0",
+                "cpu_stats=0",
+                "gpu_stats=0",
+                "hud_compact",
+            ],
+            Self::Full => &[
+                "fps",
+                "frametime",
+                "frame_timing",
+                "cpu_stats",
+                "cpu_temp",
+                "cpu_mhz",
+                "cpu_power",
+                "gpu_stats",
+                "gpu_temp",
+                "gpu_core_clock",
+                "gpu_mem_clock",
+                "gpu_power",
+                "ram",
+                "vram",
+                "wine",
+                "gamemode",
+            ],
+        }
+    }
+}
+
+/// Per-game MangoHud overlay settings, replacing the global
+/// `general.enable_mangohud` toggle for that game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MangoHudSettings {
+    pub enabled: bool,
+    #[serde(default)]
+    pub preset: MangoHudPreset,
+    /// One of `POSITIONS`, MangoHud's default (top-left) when unset
+    #[serde(default)]
+    pub position: Option<String>,
+}
+
+impl Default for MangoHudSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            preset: MangoHudPreset::default(),
+            position: None,
+        }
+    }
+}
+
+impl MangoHudSettings {
+    pub fn config_dir() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot find config directory"))?
+            .join("ghostforge")
+            .join("mangohud"))
+    }
+
+    /// Contents of the `MangoHud.conf` for these settings
+    pub fn render(&self) -> String {
+        let mut config =
+            String::from("# Generated by GhostForge, use 'forge game mangohud' to change\n");
+        for option in self.preset.options() {
+            config.push_str(option);
+            config.push('\n');
+        }
+        if let Some(position) = &self.position {
+            config.push_str(&format!("position={}\n", position));
+        }
+        config
+    }
+
+    /// Write the game's config file, which launches point
+    /// `MANGOHUD_CONFIGFILE` at
+    pub fn write_config(&self, game_id: &str) -> Result<PathBuf> {
+        let dir = Self::config_dir()?;
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.conf", game_id));
+        std::fs::write(&path, self.render())?;
+        Ok(path)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::out;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -255,7 +256,7 @@ impl PerformanceMonitor {
     }
 
     pub async fn start_monitoring(&mut self) -> Result<()> {
-        println!(
+        out!(
             "🔍 Starting performance monitoring for game: {}",
             self.game_id
         );
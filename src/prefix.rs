@@ -1,12 +1,24 @@
+use crate::utils::out;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use uuid::Uuid;
 
+/// Prefix architectures `WINEARCH` accepts
+pub const ARCHES: &[&str] = &["win32", "win64"];
+
+/// Windows versions Wine can report to applications
+pub const WINDOWS_VERSIONS: &[&str] = &[
+    "win11", "win10", "win81", "win8", "win7", "vista", "winxp64", "winxp",
+];
+
+pub const DEFAULT_ARCH: &str = "win64";
+pub const DEFAULT_WINDOWS_VERSION: &str = "win10";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrefixManager {
     pub prefixes_dir: PathBuf,
@@ -79,6 +91,83 @@ impl PrefixManager {
         Ok(manager)
     }
 
+    /// Create a Wine prefix at `path` with `wineboot`, then set the Windows
+    /// version it reports. `wine_version` is resolved like a game's Wine
+    /// version, with None meaning the system Wine.
+    pub fn create(
+        path: &Path,
+        arch: &str,
+        windows_version: &str,
+        wine_version: Option<&str>,
+    ) -> Result<()> {
+        if !ARCHES.contains(&arch) {
+            return Err(anyhow::anyhow!(
+                "Unknown architecture: {}. Use 'win32' or 'win64'",
+                arch
+            ));
+        }
+        if !WINDOWS_VERSIONS.contains(&windows_version) {
+            return Err(anyhow::anyhow!(
+                "Unknown Windows version: {}. Use one of: {}",
+                windows_version,
+                WINDOWS_VERSIONS.join(", ")
+            ));
+        }
+        if path.join("system.reg").exists() {
+            return Err(anyhow::anyhow!(
+                "A Wine prefix already exists at {}",
+                path.display()
+            ));
+        }
+
+        let wine = wine_binary(wine_version)?;
+        fs::create_dir_all(path)?;
+
+        out!("🍷 Creating {} Wine prefix at {}...", arch, path.display());
+        let output = Command::new(&wine)
+            .env("WINEPREFIX", path)
+            .env("WINEARCH", arch)
+            .env("WINEDLLOVERRIDES", "winemenubuilder.exe=d")
+            .args(["wineboot", "--init"])
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to initialize prefix: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let output = Command::new(&wine)
+            .env("WINEPREFIX", path)
+            .args([
+                "reg",
+                "add",
+                "HKEY_CURRENT_USER\\Software\\Wine",
+                "/v",
+                "Version",
+                "/d",
+                windows_version,
+                "/f",
+            ])
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to set Windows version: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        // The registry is only written to disk once wineserver exits
+        Command::new(wine.with_file_name("wineserver"))
+            .env("WINEPREFIX", path)
+            .arg("-w")
+            .status()?;
+        fs::write(path.join(CREATED_MARKER), "")?;
+
+        out!("✅ Prefix created ({}, {})", arch, windows_version);
+        Ok(())
+    }
+
     pub fn set_dry_run(&mut self, dry_run: bool) {
         self.dry_run = dry_run;
     }
@@ -249,7 +338,7 @@ impl PrefixManager {
         };
 
         if self.dry_run {
-            println!(
+            out!(
                 "🔄 [DRY RUN] Would create prefix from template '{}':",
                 template_name
             );
@@ -263,7 +352,7 @@ impl PrefixManager {
             return Ok(prefix);
         }
 
-        println!(
+        out!(
             "🍷 Creating Wine prefix from '{}' template...",
             template_name
         );
@@ -283,35 +372,18 @@ impl PrefixManager {
         // Check initial health
         prefix.health_status = self.check_prefix_health(&prefix)?;
 
-        println!("✅ Prefix '{}' created successfully", prefix_name);
+        out!("✅ Prefix '{}' created successfully", prefix_name);
 
         Ok(prefix)
     }
 
     fn initialize_prefix(&self, prefix: &WinePrefix) -> Result<()> {
-        println!("⏳ Initializing Wine prefix...");
-
-        let wine_cmd = if prefix.wine_version == "system" {
-            "wine".to_string()
-        } else {
-            // In a real implementation, you'd locate the specific Wine version
-            "wine".to_string()
-        };
-
-        let mut cmd = Command::new(&wine_cmd);
-        cmd.env("WINEPREFIX", &prefix.path);
-        cmd.env("WINEARCH", &prefix.arch);
-        cmd.args(&["wineboot", "--init"]);
-
-        let output = cmd.output()?;
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to initialize prefix: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
-
-        Ok(())
+        Self::create(
+            &prefix.path,
+            &prefix.arch,
+            &prefix.windows_version,
+            Some(&prefix.wine_version),
+        )
     }
 
     fn apply_template_configuration(
@@ -319,10 +391,7 @@ impl PrefixManager {
         prefix: &WinePrefix,
         template: &PrefixTemplate,
     ) -> Result<()> {
-        println!("⚙️ Applying template configuration...");
-
-        // Set Windows version
-        self.set_windows_version(prefix, &template.windows_version)?;
+        out!("⚙️ Applying template configuration...");
 
         // Apply DLL overrides
         for (dll, mode) in &template.dll_overrides {
@@ -336,38 +405,19 @@ impl PrefixManager {
 
         // Install winetricks packages (would integrate with our winetricks module)
         for package in &template.winetricks_packages {
-            println!("📦 Would install winetricks package: {}", package);
+            out!("📦 Would install winetricks package: {}", package);
             // self.install_winetricks_package(prefix, package)?;
         }
 
         // Apply graphics layers (would integrate with our graphics module)
         for layer in &template.graphics_layers {
-            println!("🎨 Would apply graphics layer: {}", layer);
+            out!("🎨 Would apply graphics layer: {}", layer);
             // self.apply_graphics_layer(prefix, layer)?;
         }
 
         Ok(())
     }
 
-    fn set_windows_version(&self, prefix: &WinePrefix, version: &str) -> Result<()> {
-        Command::new("wine")
-            .env("WINEPREFIX", &prefix.path)
-            .args(&[
-                "reg",
-                "add",
-                "HKEY_CURRENT_USER\\Software\\Wine",
-                "/v",
-                "Version",
-                "/d",
-                version,
-                "/f",
-            ])
-            .output()?;
-
-        println!("  ✅ Set Windows version to {}", version);
-        Ok(())
-    }
-
     fn set_dll_override(&self, prefix: &WinePrefix, dll: &str, mode: &str) -> Result<()> {
         Command::new("wine")
             .env("WINEPREFIX", &prefix.path)
@@ -383,7 +433,7 @@ impl PrefixManager {
             ])
             .output()?;
 
-        println!("  ✅ Set {} override to {}", dll, mode);
+        out!("  ✅ Set {} override to {}", dll, mode);
         Ok(())
     }
 
@@ -398,7 +448,7 @@ impl PrefixManager {
                 .args(&["reg", "add", reg_path, "/v", value_name, "/d", value, "/f"])
                 .output()?;
 
-            println!("  ✅ Set registry {}/{} = {}", reg_path, value_name, value);
+            out!("  ✅ Set registry {}/{} = {}", reg_path, value_name, value);
         }
 
         Ok(())
@@ -410,7 +460,7 @@ impl PrefixManager {
         let new_path = self.prefixes_dir.join(&new_id);
 
         if self.dry_run {
-            println!("🔄 [DRY RUN] Would clone prefix:");
+            out!("🔄 [DRY RUN] Would clone prefix:");
             println!(
                 "  From: {} ({})",
                 source_prefix.name,
@@ -420,9 +470,10 @@ impl PrefixManager {
             return Ok(source_prefix); // Return source for demo
         }
 
-        println!(
+        out!(
             "📋 Cloning prefix '{}' to '{}'...",
-            source_prefix.name, new_name
+            source_prefix.name,
+            new_name
         );
 
         // Copy the entire prefix directory
@@ -439,7 +490,7 @@ impl PrefixManager {
         // Save metadata for the new prefix
         self.save_prefix_metadata(&new_prefix)?;
 
-        println!("✅ Prefix cloned successfully");
+        out!("✅ Prefix cloned successfully");
 
         Ok(new_prefix)
     }
@@ -531,14 +582,14 @@ impl PrefixManager {
 
     pub fn repair_prefix(&self, prefix: &WinePrefix) -> Result<()> {
         if self.dry_run {
-            println!("🔄 [DRY RUN] Would repair prefix: {}", prefix.name);
+            out!("🔄 [DRY RUN] Would repair prefix: {}", prefix.name);
             println!("  • Run wineboot --init");
             println!("  • Reinstall essential packages");
             println!("  • Reapply DLL overrides");
             return Ok(());
         }
 
-        println!("🔧 Repairing prefix '{}'...", prefix.name);
+        out!("🔧 Repairing prefix '{}'...", prefix.name);
 
         // Reinitialize the prefix
         Command::new("wine")
@@ -556,7 +607,7 @@ impl PrefixManager {
             self.set_registry_value(prefix, key, value)?;
         }
 
-        println!("✅ Prefix repair completed");
+        out!("✅ Prefix repair completed");
 
         Ok(())
     }
@@ -565,37 +616,661 @@ impl PrefixManager {
         let prefix = self.get_prefix(id)?;
 
         if self.dry_run {
-            println!("🔄 [DRY RUN] Would delete prefix: {}", prefix.name);
+            out!("🔄 [DRY RUN] Would delete prefix: {}", prefix.name);
             println!("  Path: {}", prefix.path.display());
             return Ok(());
         }
 
-        println!("🗑️ Deleting prefix '{}'...", prefix.name);
+        out!("🗑️ Deleting prefix '{}'...", prefix.name);
 
         if prefix.path.exists() {
             fs::remove_dir_all(&prefix.path)?;
-            println!("✅ Prefix deleted successfully");
+            out!("✅ Prefix deleted successfully");
         }
 
         Ok(())
     }
 
     pub fn get_prefix_size(&self, prefix: &WinePrefix) -> Result<u64> {
-        if !prefix.path.exists() {
-            return Ok(0);
+        crate::utils::disk_usage(&prefix.path)
+    }
+}
+
+/// Replace Windows paths (`Z:\\home\\...`) under `old` with `new` in the
+/// prefix's registry hives, returning how many references were changed
+pub fn rewrite_registry_paths(prefix: &Path, old: &Path, new: &Path) -> Result<usize> {
+    // .reg files escape backslashes, so each separator is doubled
+    let reg_path = |path: &Path| format!("Z:{}", path.to_string_lossy().replace('/', "\\\\"));
+    let (old, new) = (reg_path(old), reg_path(new));
+
+    let mut changed = 0;
+    for hive in ["system.reg", "user.reg", "userdef.reg"] {
+        let path = prefix.join(hive);
+        let Ok(mut content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        // Whole components only, so `Games\\Foo` leaves `Games\\FooBar` alone
+        let mut count = 0;
+        for end in ["\\\\", "\""] {
+            let (from, to) = (format!("{}{}", old, end), format!("{}{}", new, end));
+            count += content.matches(&from).count();
+            content = content.replace(&from, &to);
+        }
+        if count > 0 {
+            fs::write(&path, content)?;
+            changed += count;
         }
+    }
+    Ok(changed)
+}
 
-        let output = Command::new("du")
-            .args(&["-sb", prefix.path.to_str().unwrap()])
-            .output()?;
+/// Directories other than `~/Games/*` where GhostForge and the launchers it
+/// sets up keep prefixes
+fn extra_prefix_locations() -> Vec<PathBuf> {
+    let mut locations = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        locations.push(home.join(".wine"));
+        locations.push(home.join(".wine-battlenet"));
+    }
+    if let Some(data_dir) = dirs::data_dir()
+        && let Ok(entries) = fs::read_dir(data_dir.join("ghostforge").join("prefixes"))
+    {
+        locations.extend(entries.filter_map(|e| e.ok()).map(|e| e.path()));
+    }
+    locations
+}
+
+/// Find initialized prefixes in the known locations: every directory under
+/// `~/Games` (game and launcher prefixes), the Battle.net prefix, the default
+/// `~/.wine`, GhostForge-managed prefixes, plus `extra` such as the prefixes
+/// games in the library are configured with
+pub fn discover_prefixes(extra: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = extra.into_iter().collect();
+    if let Some(games_dir) = dirs::home_dir().map(|home| home.join("Games"))
+        && let Ok(entries) = fs::read_dir(games_dir)
+    {
+        candidates.extend(entries.filter_map(|e| e.ok()).map(|e| e.path()));
+    }
+    candidates.extend(extra_prefix_locations());
+
+    let mut prefixes: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if candidate.join("system.reg").exists() && !prefixes.contains(&candidate) {
+            prefixes.push(candidate);
+        }
+    }
+    prefixes.sort();
+    prefixes
+}
+
+/// File `PrefixManager::create` leaves in the prefixes it makes
+const CREATED_MARKER: &str = ".ghostforge-prefix";
+
+/// Whether GhostForge made the prefix: with `forge prefix create`, or as a
+/// game's default prefix under `wine.default_prefix_path`
+fn created_by_ghostforge(prefix: &Path, wine: &crate::config::WineConfig) -> bool {
+    prefix.join(CREATED_MARKER).exists() || prefix.parent() == Some(&wine.default_prefix_path)
+}
+
+/// Prefixes GhostForge made that nothing uses any more: no game in the
+/// library has it as its prefix or is installed inside it, no launcher runs
+/// from it and no Lutris game is configured with it. Prefixes made by hand
+/// or by other tools are never included, nor are the default `~/.wine` and
+/// the shared Battle.net prefix.
+pub fn find_orphaned_prefixes(
+    games: &[crate::game::Game],
+    launchers: &[crate::launcher::Launcher],
+    wine: &crate::config::WineConfig,
+) -> Vec<PathBuf> {
+    let mut in_use: Vec<PathBuf> = games.iter().map(|game| game.prefix_path(wine)).collect();
+    for launcher in launchers {
+        in_use.extend(launcher.wine_prefix.clone());
+    }
+    if let Some(paths) = crate::lutris::LutrisPaths::detect()
+        && let Ok(lutris_games) = crate::lutris::read_games(&paths)
+    {
+        in_use.extend(lutris_games.iter().filter_map(|game| game.wine_prefix()));
+    }
+    if let Some(home) = dirs::home_dir() {
+        in_use.push(home.join(".wine"));
+        in_use.push(home.join(".wine-battlenet"));
+    }
+
+    let default_prefixes: Vec<PathBuf> = fs::read_dir(&wine.default_prefix_path)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    discover_prefixes(default_prefixes)
+        .into_iter()
+        .filter(|prefix| created_by_ghostforge(prefix, wine))
+        .filter(|prefix| !in_use.contains(prefix))
+        .filter(|prefix| {
+            !games
+                .iter()
+                .any(|game| game.install_path.starts_with(prefix))
+                && !launchers
+                    .iter()
+                    .any(|launcher| launcher.path.starts_with(prefix))
+        })
+        .collect()
+}
+
+/// Prefix state read from its registry hives and files
+#[derive(Debug, Clone)]
+pub struct PrefixInfo {
+    pub arch: Option<String>,
+    /// None when Wine's default is in use
+    pub windows_version: Option<String>,
+    /// Steam records the Proton version next to its prefixes
+    pub proton_version: Option<String>,
+    pub dll_overrides: Vec<(String, String)>,
+    /// Display names from the Windows uninstall registry
+    pub installed_programs: Vec<String>,
+    /// Verbs winetricks logged as installed
+    pub winetricks_verbs: Vec<String>,
+    /// Graphics layers enabled through DLL overrides
+    pub graphics_layers: Vec<String>,
+    pub size_bytes: u64,
+}
+
+impl PrefixInfo {
+    pub fn read(path: &Path) -> Result<Self> {
+        let system_reg = path.join("system.reg");
+        if !system_reg.exists() {
+            return Err(anyhow::anyhow!("No Wine prefix at {}", path.display()));
+        }
+
+        let system = String::from_utf8_lossy(&fs::read(&system_reg)?).to_string();
+        let user = fs::read(path.join("user.reg"))
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+            .unwrap_or_default();
+
+        let arch = system
+            .lines()
+            .find_map(|line| line.strip_prefix("#arch="))
+            .map(str::to_string);
+
+        let windows_version = registry_values(&user, "Software\\Wine")
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Version"))
+            .map(|(_, value)| value);
+
+        let dll_overrides = registry_values(&user, "Software\\Wine\\DllOverrides");
+
+        let mut installed_programs: Vec<String> = registry_sections(&system)
+            .into_iter()
+            .filter(|(key, _)| {
+                let key = key.to_lowercase();
+                key.starts_with("software\\microsoft\\windows\\currentversion\\uninstall\\")
+                    || key.starts_with(
+                        "software\\wow6432node\\microsoft\\windows\\currentversion\\uninstall\\",
+                    )
+            })
+            .filter_map(|(_, values)| {
+                values
+                    .into_iter()
+                    .find(|(name, _)| name == "DisplayName")
+                    .map(|(_, value)| value)
+            })
+            .collect();
+        installed_programs.sort();
+        installed_programs.dedup();
+
+        let winetricks_verbs = fs::read_to_string(path.join("winetricks.log"))
+            .map(|log| {
+                let mut verbs: Vec<String> = Vec::new();
+                for verb in log.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    if !verbs.iter().any(|v| v == verb) {
+                        verbs.push(verb.to_string());
+                    }
+                }
+                verbs
+            })
+            .unwrap_or_default();
+
+        let is_native = |dll: &str| {
+            dll_overrides
+                .iter()
+                .any(|(name, mode)| name.eq_ignore_ascii_case(dll) && mode.starts_with('n'))
+        };
+        let mut graphics_layers = Vec::new();
+        if is_native("dxgi") || is_native("d3d11") {
+            graphics_layers.push("DXVK".to_string());
+        }
+        if is_native("d3d12") {
+            graphics_layers.push("VKD3D-Proton".to_string());
+        }
+        if is_native("nvapi64") || is_native("nvapi") {
+            graphics_layers.push("DXVK-NVAPI".to_string());
+        }
+
+        // Steam's compatdata/<appid>/pfx has the Proton version in compatdata/<appid>/version
+        let proton_version = path
+            .parent()
+            .filter(|_| path.file_name().is_some_and(|name| name == "pfx"))
+            .and_then(|parent| fs::read_to_string(parent.join("version")).ok())
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty());
+
+        Ok(Self {
+            arch,
+            windows_version,
+            proton_version,
+            dll_overrides,
+            installed_programs,
+            winetricks_verbs,
+            graphics_layers,
+            size_bytes: crate::utils::disk_usage(path)?,
+        })
+    }
+}
+
+/// Split a Wine `.reg` file into its keys and their string values
+fn registry_sections(content: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix('[') {
+            if let Some(end) = rest.find(']') {
+                sections.push((unescape(&rest[..end]), Vec::new()));
+            }
+            continue;
+        }
+
+        let Some((_, values)) = sections.last_mut() else {
+            continue;
+        };
+        let Some((name, value)) = line
+            .strip_prefix('"')
+            .and_then(|rest| rest.split_once("\"=\""))
+        else {
+            continue;
+        };
+        if let Some(value) = value.strip_suffix('"') {
+            values.push((unescape(name), unescape(value)));
+        }
+    }
+
+    sections
+}
+
+fn registry_values(content: &str, key: &str) -> Vec<(String, String)> {
+    registry_sections(content)
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, values)| values)
+        .unwrap_or_default()
+}
+
+fn unescape(value: &str) -> String {
+    value.replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+/// Locate the `wine` binary for a Wine version as games store it: system
+/// Wine, the path of a Wine build or binary, or the name of a build in the
+/// Wine versions directory. Proton builds keep Wine under `files` or `dist`.
+pub fn wine_binary(wine_version: Option<&str>) -> Result<PathBuf> {
+    let version = match wine_version {
+        None | Some("wine") | Some("system") => return Ok(PathBuf::from("wine")),
+        Some(version) => version,
+    };
+
+    let path = Path::new(version);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let dir = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        crate::config::Config::load()?
+            .wine
+            .wine_versions_path
+            .join(version)
+    };
+    ["bin/wine", "files/bin/wine", "dist/bin/wine"]
+        .iter()
+        .map(|bin| dir.join(bin))
+        .find(|bin| bin.exists())
+        .ok_or_else(|| anyhow::anyhow!("Wine version {} not found in {}", version, dir.display()))
+}
+
+/// Snapshots kept per prefix, the oldest are deleted beyond this
+pub const MAX_SNAPSHOTS: usize = 5;
+
+/// Registry hives saved by a snapshot
+const SNAPSHOT_HIVES: &[&str] = &["system.reg", "user.reg", "userdef.reg"];
+
+/// DLL directories covered by a snapshot, which is where winetricks and
+/// graphics layers put their DLLs
+const SNAPSHOT_DLL_DIRS: &[&str] = &["drive_c/windows/system32", "drive_c/windows/syswow64"];
+
+/// A copy of a prefix's registry and a manifest of its system DLLs, taken
+/// before winetricks or graphics layers change it. DLL contents are kept
+/// once per prefix by hash, so a snapshot only copies the DLLs that changed
+/// since the last one. Files those tools put elsewhere, e.g. under Program
+/// Files, are not covered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefixSnapshot {
+    pub id: String,
+    pub prefix: PathBuf,
+    pub reason: String,
+    pub created: DateTime<Utc>,
+    /// DLLs by their path in the prefix, None for snapshots that copied
+    /// the DLL directories whole
+    #[serde(default)]
+    dlls: Option<HashMap<String, SnapshotDll>>,
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// A DLL as a snapshot recorded it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SnapshotDll {
+    /// Proton prefixes link their DLLs into the Proton installation
+    Link { target: PathBuf },
+    /// Contents are in the prefix's DLL store under their SHA-256
+    File {
+        sha256: String,
+        size: u64,
+        modified: DateTime<Utc>,
+    },
+}
+
+impl SnapshotDll {
+    fn same_contents(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Link { target }, Self::Link { target: other }) => target == other,
+            (Self::File { sha256, .. }, Self::File { sha256: other, .. }) => sha256 == other,
+            _ => false,
+        }
+    }
+}
+
+impl PrefixSnapshot {
+    /// Directory holding the snapshots of a prefix
+    pub fn snapshots_dir(prefix: &Path) -> Result<PathBuf> {
+        let key = prefix.to_string_lossy().trim_matches('/').replace('/', "_");
+        Ok(dirs::data_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot find data directory"))?
+            .join("ghostforge")
+            .join("snapshots")
+            .join(key))
+    }
+
+    /// DLL contents shared by the snapshots of a prefix, named by hash
+    fn dll_store(prefix: &Path) -> Result<PathBuf> {
+        Ok(Self::snapshots_dir(prefix)?.join("dlls"))
+    }
+
+    /// Snapshot a prefix, pruning the oldest beyond `MAX_SNAPSHOTS`. Returns
+    /// None when the prefix hasn't been initialized yet, as there is nothing
+    /// to roll back to.
+    pub fn create(prefix: &Path, reason: &str) -> Result<Option<Self>> {
+        if !prefix.join("system.reg").exists() {
+            return Ok(None);
+        }
+
+        let previous = Self::list(prefix)?
+            .into_iter()
+            .find_map(|snapshot| snapshot.dlls)
+            .unwrap_or_default();
+
+        let created = Utc::now();
+        let id = created.format("%Y%m%d-%H%M%S-%3f").to_string();
+        let path = Self::snapshots_dir(prefix)?.join(&id);
+        fs::create_dir_all(&path)?;
+
+        let mut snapshot = Self {
+            id,
+            prefix: prefix.to_path_buf(),
+            reason: reason.to_string(),
+            created,
+            dlls: None,
+            path,
+        };
+
+        let result = snapshot.copy_files(&previous).and_then(|dlls| {
+            snapshot.dlls = Some(dlls);
+            fs::write(
+                snapshot.path.join("snapshot.json"),
+                serde_json::to_string_pretty(&snapshot)?,
+            )?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            let _ = fs::remove_dir_all(&snapshot.path);
+            return Err(anyhow::anyhow!("Failed to snapshot prefix: {}", e));
+        }
+
+        let mut kept = Self::list(prefix)?;
+        for old in kept.split_off(MAX_SNAPSHOTS.min(kept.len())) {
+            fs::remove_dir_all(&old.path)?;
+        }
+        Self::prune_dll_store(prefix, &kept)?;
 
-        if output.status.success() {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            if let Some(size_str) = output_str.split_whitespace().next() {
-                return Ok(size_str.parse().unwrap_or(0));
+        Ok(Some(snapshot))
+    }
+
+    /// Snapshots of a prefix, newest first
+    pub fn list(prefix: &Path) -> Result<Vec<Self>> {
+        let dir = Self::snapshots_dir(prefix)?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let Ok(content) = fs::read_to_string(path.join("snapshot.json")) else {
+                continue;
+            };
+            if let Ok(mut snapshot) = serde_json::from_str::<Self>(&content) {
+                snapshot.path = path;
+                snapshots.push(snapshot);
+            }
+        }
+
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.created));
+        Ok(snapshots)
+    }
+
+    /// Put the snapshot's registry and DLLs back, deleting DLLs that were
+    /// added since it was taken
+    pub fn restore(&self) -> Result<()> {
+        for hive in SNAPSHOT_HIVES {
+            let saved = self.path.join(hive);
+            if saved.exists() {
+                copy_entry(&saved, &self.prefix.join(hive))?;
             }
         }
 
-        Ok(0)
+        let Some(dlls) = &self.dlls else {
+            return self.restore_copied_dlls();
+        };
+
+        for dir in SNAPSHOT_DLL_DIRS {
+            let prefix_dir = self.prefix.join(dir);
+            if !prefix_dir.exists() {
+                continue;
+            }
+            for dll in dll_names(&prefix_dir)? {
+                if !dlls.contains_key(&format!("{}/{}", dir, dll)) {
+                    fs::remove_file(prefix_dir.join(&dll))?;
+                }
+            }
+        }
+
+        let store = Self::dll_store(&self.prefix)?;
+        for (name, saved) in dlls {
+            let dest = self.prefix.join(name);
+            // DLLs that didn't change are left alone
+            let current = snapshot_dll(&dest, Some(saved)).ok();
+            if current.is_some_and(|current| current.same_contents(saved)) {
+                continue;
+            }
+            if dest.symlink_metadata().is_ok() {
+                fs::remove_file(&dest)?;
+            }
+            match saved {
+                SnapshotDll::Link { target } => std::os::unix::fs::symlink(target, &dest)?,
+                SnapshotDll::File { sha256, .. } => {
+                    fs::copy(store.join(sha256), &dest)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restore a snapshot that holds whole copies of the DLL directories
+    fn restore_copied_dlls(&self) -> Result<()> {
+        for dir in SNAPSHOT_DLL_DIRS {
+            let saved_dir = self.path.join(dir);
+            let prefix_dir = self.prefix.join(dir);
+            if !saved_dir.exists() || !prefix_dir.exists() {
+                continue;
+            }
+
+            for dll in dll_names(&prefix_dir)? {
+                if saved_dir.join(&dll).symlink_metadata().is_err() {
+                    fs::remove_file(prefix_dir.join(&dll))?;
+                }
+            }
+            for dll in dll_names(&saved_dir)? {
+                copy_entry(&saved_dir.join(&dll), &prefix_dir.join(&dll))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy the registry and record the DLLs, adding the contents of DLLs
+    /// that aren't in the store yet. DLLs unchanged since `previous` aren't
+    /// read again.
+    fn copy_files(
+        &self,
+        previous: &HashMap<String, SnapshotDll>,
+    ) -> Result<HashMap<String, SnapshotDll>> {
+        for hive in SNAPSHOT_HIVES {
+            let source = self.prefix.join(hive);
+            if source.exists() {
+                copy_entry(&source, &self.path.join(hive))?;
+            }
+        }
+
+        let store = Self::dll_store(&self.prefix)?;
+        fs::create_dir_all(&store)?;
+        let mut dlls = HashMap::new();
+        for dir in SNAPSHOT_DLL_DIRS {
+            let source_dir = self.prefix.join(dir);
+            if !source_dir.exists() {
+                continue;
+            }
+
+            for dll in dll_names(&source_dir)? {
+                let name = format!("{}/{}", dir, dll);
+                let source = source_dir.join(&dll);
+                let recorded = snapshot_dll(&source, previous.get(&name))?;
+                if let SnapshotDll::File { sha256, .. } = &recorded {
+                    let stored = store.join(sha256);
+                    if !stored.exists() {
+                        fs::copy(&source, &stored)?;
+                    }
+                }
+                dlls.insert(name, recorded);
+            }
+        }
+
+        Ok(dlls)
+    }
+
+    /// Delete stored DLL contents no remaining snapshot refers to
+    fn prune_dll_store(prefix: &Path, snapshots: &[Self]) -> Result<()> {
+        let store = Self::dll_store(prefix)?;
+        if !store.exists() {
+            return Ok(());
+        }
+
+        let referenced: HashSet<&str> = snapshots
+            .iter()
+            .flat_map(|snapshot| snapshot.dlls.iter().flat_map(|dlls| dlls.values()))
+            .filter_map(|dll| match dll {
+                SnapshotDll::File { sha256, .. } => Some(sha256.as_str()),
+                SnapshotDll::Link { .. } => None,
+            })
+            .collect();
+        for entry in fs::read_dir(&store)? {
+            let entry = entry?;
+            if !referenced.contains(entry.file_name().to_string_lossy().as_ref()) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Record a DLL, reusing `previous`'s hash when its size and modification
+/// time haven't changed
+fn snapshot_dll(path: &Path, previous: Option<&SnapshotDll>) -> Result<SnapshotDll> {
+    use sha2::{Digest, Sha256};
+
+    let metadata = path.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        return Ok(SnapshotDll::Link {
+            target: fs::read_link(path)?,
+        });
+    }
+
+    let size = metadata.len();
+    let modified = DateTime::<Utc>::from(metadata.modified()?);
+    if let Some(SnapshotDll::File {
+        sha256,
+        size: previous_size,
+        modified: previous_modified,
+    }) = previous
+        && *previous_size == size
+        && *previous_modified == modified
+    {
+        return Ok(SnapshotDll::File {
+            sha256: sha256.clone(),
+            size,
+            modified,
+        });
+    }
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(SnapshotDll::File {
+        sha256: format!("{:x}", hasher.finalize()),
+        size,
+        modified,
+    })
+}
+
+fn dll_names(dir: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if name.to_lowercase().ends_with(".dll") {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// Copy a file, or recreate it if it's a symlink, as Proton prefixes link
+/// their DLLs into the Proton installation. The destination is removed
+/// first so a link there isn't written through.
+fn copy_entry(source: &Path, dest: &Path) -> Result<()> {
+    if dest.symlink_metadata().is_ok() {
+        fs::remove_file(dest)?;
+    }
+
+    if source.symlink_metadata()?.file_type().is_symlink() {
+        std::os::unix::fs::symlink(fs::read_link(source)?, dest)?;
+    } else {
+        fs::copy(source, dest)?;
     }
+    Ok(())
 }
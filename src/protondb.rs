@@ -1,7 +1,16 @@
+use crate::download::send_with_retry;
 use anyhow::Result;
 use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long cached compatibility reports are trusted by default
+pub const DEFAULT_CACHE_TTL_HOURS: u64 = 24;
+
+/// The longest a launch waits on ProtonDB before going ahead without it
+pub const LAUNCH_LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
 
 // ProtonDB API Response Structures
 #[derive(Debug, Deserialize)]
@@ -65,6 +74,16 @@ pub struct ProtonDBClient {
     pub base_url: String,
     #[allow(dead_code)]
     pub client: reqwest::Client,
+    pub cache_dir: Option<PathBuf>,
+    pub cache_ttl: chrono::Duration,
+    pub refresh: bool,
+}
+
+/// Compatibility report stored on disk with the time it was fetched
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedReport {
+    cached_at: chrono::DateTime<chrono::Utc>,
+    report: GameCompatibilityReport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,20 +144,131 @@ impl ProtonDBClient {
     pub fn new() -> Self {
         Self {
             base_url: "https://www.protondb.com/api/v1".to_string(),
-            client: reqwest::Client::new(),
+            client: crate::download::http_client(),
+            cache_dir: Some(Self::default_cache_dir()),
+            cache_ttl: chrono::Duration::hours(DEFAULT_CACHE_TTL_HOURS as i64),
+            refresh: false,
+        }
+    }
+
+    /// Client using the configured cache directory and cache lifetime
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            cache_dir: Some(config.paths.cache.join("protondb")),
+            ..Self::new()
+        }
+        .with_cache_ttl(config.general.protondb_cache_ttl_hours)
+    }
+
+    pub fn default_cache_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_default()
+            .join("ghostforge")
+            .join("protondb")
+    }
+
+    /// Trust cached reports for `hours` before asking ProtonDB again
+    pub fn with_cache_ttl(mut self, hours: u64) -> Self {
+        self.cache_ttl = chrono::Duration::hours(hours as i64);
+        self
+    }
+
+    /// Ignore fresh cache entries and always fetch, still updating the cache
+    pub fn set_refresh(&mut self, refresh: bool) {
+        self.refresh = refresh;
+    }
+
+    fn report_cache_file(&self, steam_appid: u32, kind: &str) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}-{}.json", steam_appid, kind)))
+    }
+
+    /// Read a cached report; stale entries are only returned when `allow_stale` is set
+    fn read_cached_report(
+        &self,
+        steam_appid: u32,
+        kind: &str,
+        allow_stale: bool,
+    ) -> Option<GameCompatibilityReport> {
+        let path = self.report_cache_file(steam_appid, kind)?;
+        let contents = std::fs::read_to_string(path).ok()?;
+        let cached: CachedReport = serde_json::from_str(&contents).ok()?;
+
+        let fresh = chrono::Utc::now() - cached.cached_at < self.cache_ttl;
+        (allow_stale || (fresh && !self.refresh)).then_some(cached.report)
+    }
+
+    /// Any cached report for the app, however old, without asking ProtonDB
+    pub fn cached_report(&self, steam_appid: u32) -> Option<GameCompatibilityReport> {
+        self.read_cached_report(steam_appid, "detailed", true)
+            .or_else(|| self.read_cached_report(steam_appid, "summary", true))
+    }
+
+    fn write_cached_report(&self, steam_appid: u32, kind: &str, report: &GameCompatibilityReport) {
+        let Some(path) = self.report_cache_file(steam_appid, kind) else {
+            return;
+        };
+
+        let cached = CachedReport {
+            cached_at: chrono::Utc::now(),
+            report: report.clone(),
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(serde_json::to_string_pretty(&cached)?))
+            .and_then(|json| Ok(std::fs::write(&path, json)?));
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache ProtonDB report for {}: {}", steam_appid, e);
         }
     }
 
+    /// Delete cached data for one app, or for every app when `steam_appid` is None.
+    /// Returns the number of files removed.
+    pub fn invalidate_cache(&self, steam_appid: Option<u32>) -> Result<usize> {
+        let Some(dir) = &self.cache_dir else {
+            return Ok(0);
+        };
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !name.ends_with(".json") {
+                continue;
+            }
+
+            let matches = match steam_appid {
+                Some(appid) => {
+                    let appid = appid.to_string();
+                    name.strip_prefix(&appid)
+                        .is_some_and(|rest| rest == ".json" || rest.starts_with('-'))
+                }
+                None => true,
+            };
+            if matches {
+                std::fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
     /// Get game compatibility rating and reports
     pub async fn get_game_summary(&self, steam_appid: u32) -> Result<Option<ProtonDBSummary>> {
         let url = format!("{}/reports/summaries/{}.json", self.base_url, steam_appid);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "GhostForge/1.0")
-            .send()
-            .await?;
+        let request = self.client.get(&url).header("User-Agent", "GhostForge/1.0");
+        let response = send_with_retry(request).await?;
 
         if response.status() == 404 {
             return Ok(None);
@@ -160,12 +290,8 @@ impl ProtonDBClient {
             self.base_url, steam_appid, limit
         );
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "GhostForge/1.0")
-            .send()
-            .await?;
+        let request = self.client.get(&url).header("User-Agent", "GhostForge/1.0");
+        let response = send_with_retry(request).await?;
 
         if response.status() == 404 {
             return Ok(Vec::new());
@@ -183,12 +309,8 @@ impl ProtonDBClient {
         // For now, this is a placeholder implementation
         let url = format!("{}/aggregate/summaries.json", self.base_url);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "GhostForge/1.0")
-            .send()
-            .await?;
+        let request = self.client.get(&url).header("User-Agent", "GhostForge/1.0");
+        let response = send_with_retry(request).await?;
 
         if response.status() != 200 {
             return Ok(Vec::new());
@@ -209,12 +331,8 @@ impl ProtonDBClient {
         let limit = limit.unwrap_or(50);
         let url = format!("{}/aggregate/summaries.json", self.base_url);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "GhostForge/1.0")
-            .send()
-            .await?;
+        let request = self.client.get(&url).header("User-Agent", "GhostForge/1.0");
+        let response = send_with_retry(request).await?;
 
         let mut games: Vec<ProtonDBGame> = response.json().await?;
 
@@ -335,11 +453,41 @@ impl ProtonDBClient {
         Ok(None)
     }
 
-    /// Generate a compatibility report for GhostForge UI
+    /// Generate a compatibility report for GhostForge UI, served from the cache while fresh
     pub async fn generate_compatibility_report(
         &self,
         steam_appid: u32,
         game_name: &str,
+    ) -> Result<GameCompatibilityReport> {
+        if let Some(mut report) = self.read_cached_report(steam_appid, "summary", false) {
+            report.game_name = game_name.to_string();
+            return Ok(report);
+        }
+
+        match self
+            .fetch_compatibility_report(steam_appid, game_name)
+            .await
+        {
+            Ok(report) => {
+                self.write_cached_report(steam_appid, "summary", &report);
+                Ok(report)
+            }
+            // Offline: a stale report beats no report
+            Err(e) => match self.read_cached_report(steam_appid, "summary", true) {
+                Some(mut report) => {
+                    tracing::warn!("ProtonDB unreachable, using cached report: {}", e);
+                    report.game_name = game_name.to_string();
+                    Ok(report)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn fetch_compatibility_report(
+        &self,
+        steam_appid: u32,
+        game_name: &str,
     ) -> Result<GameCompatibilityReport> {
         let summary = self.get_game_summary(steam_appid).await?;
 
@@ -364,6 +512,7 @@ impl ProtonDBClient {
                 recommended_proton,
                 compatibility_tips: tips,
                 last_updated: chrono::Utc::now(),
+                anti_cheat: crate::anticheat::known_title(game_name, Some(steam_appid)),
             }
         } else {
             // No ProtonDB data available
@@ -384,17 +533,41 @@ impl ProtonDBClient {
                     "Submit a report to help the community!".to_string(),
                 ],
                 last_updated: chrono::Utc::now(),
+                anti_cheat: crate::anticheat::known_title(game_name, Some(steam_appid)),
             }
         };
 
         Ok(report)
     }
 
-    /// Get Steam app ID from game name
-    pub async fn get_steam_appid(&self, game_name: &str) -> Result<Option<u32>> {
+    /// Find the best matching Steam app for a (possibly partial) game name.
+    ///
+    /// An exact match in the installed Steam library wins; otherwise the Steam
+    /// store is asked, falling back to a fuzzy local match when offline. Only
+    /// installed apps are known offline, as they are read from their
+    /// appmanifest files and not from Steam's appinfo.vdf.
+    pub async fn find_steam_app(&self, game_name: &str) -> Result<Option<(u32, String)>> {
+        let local_apps = local_steam_apps();
+
+        if let Some(app) = best_local_steam_app(game_name, &local_apps, false) {
+            return Ok(Some(app));
+        }
+
+        match self.search_steam_store(game_name).await {
+            Ok(Some(app)) => Ok(Some(app)),
+            Ok(None) => Ok(best_local_steam_app(game_name, &local_apps, true)),
+            Err(e) => match best_local_steam_app(game_name, &local_apps, true) {
+                Some(app) => Ok(Some(app)),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Search the Steam store's app list for a game name
+    async fn search_steam_store(&self, game_name: &str) -> Result<Option<(u32, String)>> {
         let steam_url = "https://api.steampowered.com/ISteamApps/GetAppList/v2/";
 
-        let response = self.client.get(steam_url).send().await?;
+        let response = send_with_retry(self.client.get(steam_url)).await?;
 
         if response.status() != 200 {
             return Ok(None);
@@ -402,28 +575,45 @@ impl ProtonDBClient {
 
         let steam_response: SteamAppListResponse = response.json().await?;
 
-        // Find exact match first, then partial match
-        for app in &steam_response.applist.apps {
-            if app.name.to_lowercase() == game_name.to_lowercase() {
-                return Ok(Some(app.appid));
-            }
-        }
-
-        // Try partial match
-        for app in &steam_response.applist.apps {
-            if app.name.to_lowercase().contains(&game_name.to_lowercase()) {
-                return Ok(Some(app.appid));
-            }
-        }
+        // Exact matches score highest, then substring, then fuzzy matches
+        let best = steam_response
+            .applist
+            .apps
+            .into_iter()
+            .filter_map(|app| {
+                crate::utils::fuzzy_match_score(game_name, &app.name).map(|score| (score, app))
+            })
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, app)| (app.appid, app.name));
 
-        Ok(None)
+        Ok(best)
     }
 
-    /// Get comprehensive compatibility report for a game
+    /// Get comprehensive compatibility report for a game, served from the cache while fresh
     pub async fn get_compatibility_info(
         &self,
         steam_appid: u32,
     ) -> Result<GameCompatibilityReport> {
+        if let Some(report) = self.read_cached_report(steam_appid, "detailed", false) {
+            return Ok(report);
+        }
+
+        match self.fetch_compatibility_info(steam_appid).await {
+            Ok(report) => {
+                self.write_cached_report(steam_appid, "detailed", &report);
+                Ok(report)
+            }
+            Err(e) => match self.read_cached_report(steam_appid, "detailed", true) {
+                Some(report) => {
+                    tracing::warn!("ProtonDB unreachable, using cached report: {}", e);
+                    Ok(report)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn fetch_compatibility_info(&self, steam_appid: u32) -> Result<GameCompatibilityReport> {
         let summary = self
             .get_game_summary(steam_appid)
             .await?
@@ -472,6 +662,7 @@ impl ProtonDBClient {
             recommended_proton,
             compatibility_tips: self.suggest_winetricks(&summary.tier),
             last_updated: chrono::Utc::now(),
+            anti_cheat: crate::anticheat::known_title("", Some(steam_appid)),
         })
     }
 
@@ -558,6 +749,71 @@ impl ProtonDBClient {
     }
 }
 
+/// Installed Steam apps from libraryfolders.vdf and the appmanifest files
+fn local_steam_apps() -> Vec<crate::launcher::LauncherGame> {
+    let config_dir = dirs::config_dir().unwrap_or_default().join("ghostforge");
+    let manager = crate::launcher::LauncherManager::new(config_dir);
+
+    match manager.detect_launcher(&crate::launcher::LauncherType::Steam) {
+        Ok(Some(steam)) => manager.sync_steam_games(&steam).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Lowercase and drop trademark symbols and punctuation so
+/// "DOOM®: Eternal" and "doom eternal" compare equal
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, '™' | '®' | '©'))
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Pick the installed app matching `game_name` by store name or install folder.
+///
+/// Several editions can share a name (a remaster next to the original, a
+/// demo next to the full game); installed copies win, then the lowest app ID.
+fn best_local_steam_app(
+    game_name: &str,
+    apps: &[crate::launcher::LauncherGame],
+    allow_fuzzy: bool,
+) -> Option<(u32, String)> {
+    let wanted = normalize_title(game_name);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    let folder_name = |app: &crate::launcher::LauncherGame| {
+        app.install_path
+            .file_name()
+            .map(|n| normalize_title(&n.to_string_lossy()))
+            .unwrap_or_default()
+    };
+
+    let candidates = apps.iter().filter_map(|app| {
+        let appid = app.launcher_id.parse::<u32>().ok()?;
+        let score = if normalize_title(&app.name) == wanted {
+            2000
+        } else if folder_name(app) == wanted {
+            1500
+        } else if allow_fuzzy {
+            crate::utils::fuzzy_match_score(game_name, &app.name)?
+        } else {
+            return None;
+        };
+        Some((score, app.installed, appid, app))
+    });
+
+    candidates
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(b.2.cmp(&a.2)))
+        .map(|(_, _, appid, app)| (appid, app.name.clone()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameCompatibilityReport {
     pub appid: u32,
@@ -572,6 +828,9 @@ pub struct GameCompatibilityReport {
     pub recommended_proton: String,
     pub compatibility_tips: Vec<String>,
     pub last_updated: chrono::DateTime<chrono::Utc>,
+    /// Known anti-cheat and its Linux status
+    #[serde(default)]
+    pub anti_cheat: Option<crate::anticheat::AntiCheatInfo>,
 }
 
 // Helper functions for integration with GhostForge
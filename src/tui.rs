@@ -0,0 +1,598 @@
+use crate::game::{Game, GameLibrary};
+use crate::game_launcher::{GameLauncher, LaunchOptions};
+use crate::protondb::{ProtonDBClient, ProtonDBSummary};
+use crate::utils::{SystemDetector, SystemInfo, out};
+use crate::wine::WineVersion;
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::Frame;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use std::fs::File;
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+type TuiTerminal = Terminal<CrosstermBackend<File>>;
+
+/// Points stdout and stderr at a log file so the launcher's progress lines
+/// and running games can't write over the interface, restored on drop
+struct OutputCapture {
+    stdout: RawFd,
+    stderr: RawFd,
+}
+
+impl OutputCapture {
+    /// Returns the capture and a handle to the real terminal for drawing
+    fn start(log_path: &Path) -> Result<(Self, File)> {
+        use nix::unistd::{dup, dup2};
+
+        let log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)?;
+        let stdout = dup(std::io::stdout().as_raw_fd())?;
+        let stderr = dup(std::io::stderr().as_raw_fd())?;
+        let terminal = dup(stdout)?;
+        dup2(log.as_raw_fd(), std::io::stdout().as_raw_fd())?;
+        dup2(log.as_raw_fd(), std::io::stderr().as_raw_fd())?;
+
+        // SAFETY: `terminal` is a fresh descriptor owned by nothing else
+        let terminal = unsafe { File::from_raw_fd(terminal) };
+        Ok((Self { stdout, stderr }, terminal))
+    }
+}
+
+impl Drop for OutputCapture {
+    fn drop(&mut self) {
+        use nix::unistd::{close, dup2};
+
+        let _ = dup2(self.stdout, std::io::stdout().as_raw_fd());
+        let _ = dup2(self.stderr, std::io::stderr().as_raw_fd());
+        let _ = close(self.stdout);
+        let _ = close(self.stderr);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tab {
+    Games,
+    Wine,
+    ProtonDB,
+    Containers,
+    System,
+}
+
+impl Tab {
+    const ALL: [Tab; 5] = [
+        Tab::Games,
+        Tab::Wine,
+        Tab::ProtonDB,
+        Tab::Containers,
+        Tab::System,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Tab::Games => "🎯 Games",
+            Tab::Wine => "🍷 Wine/Proton",
+            Tab::ProtonDB => "🌐 ProtonDB",
+            Tab::Containers => "📦 Containers",
+            Tab::System => "🖥️ System",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Tab::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+}
+
+struct TuiApp {
+    tab: Tab,
+    launcher: GameLauncher,
+    games: Vec<Game>,
+    games_state: ListState,
+    wine_versions: Vec<WineVersion>,
+    wine_state: ListState,
+    containers: Vec<crate::container::GameContainer>,
+    containers_state: ListState,
+    protondb_query: String,
+    protondb_editing: bool,
+    protondb_results: Vec<(u32, String, Option<ProtonDBSummary>)>,
+    protondb_state: ListState,
+    system_info: Option<SystemInfo>,
+    status: String,
+    should_quit: bool,
+    protondb: ProtonDBClient,
+    /// Where launcher output goes while the interface is up
+    log_path: PathBuf,
+}
+
+impl TuiApp {
+    async fn new() -> Result<Self> {
+        let config = crate::config::Config::load()?;
+        config.ensure_directories()?;
+        let games = GameLibrary::new(&config.paths.database)?.list_games()?;
+
+        let config_dir = dirs::config_dir().unwrap().join("ghostforge");
+        let wine_versions = crate::wine::WineManager::from_config(&config)
+            .list_installed()
+            .await
+            .unwrap_or_default();
+
+        let containers = crate::container::ContainerManager::new(config_dir)
+            .and_then(|mut manager| {
+                manager.load_containers()?;
+                Ok(manager.containers.into_values().collect())
+            })
+            .unwrap_or_default();
+
+        let log_path = config.paths.logs.join("tui.log");
+        let protondb = ProtonDBClient::from_config(&config);
+        let mut app = Self {
+            tab: Tab::Games,
+            launcher: GameLauncher::new(config),
+            games,
+            games_state: ListState::default(),
+            wine_versions,
+            wine_state: ListState::default(),
+            containers,
+            containers_state: ListState::default(),
+            protondb_query: String::new(),
+            protondb_editing: false,
+            protondb_results: Vec::new(),
+            protondb_state: ListState::default(),
+            system_info: SystemDetector::get_system_info().ok(),
+            status: "←/→ switch tabs · ↑/↓ select · Enter launch · s stop · q quit".to_string(),
+            should_quit: false,
+            protondb,
+            log_path,
+        };
+
+        if !app.games.is_empty() {
+            app.games_state.select(Some(0));
+        }
+        if !app.wine_versions.is_empty() {
+            app.wine_state.select(Some(0));
+        }
+        if !app.containers.is_empty() {
+            app.containers_state.select(Some(0));
+        }
+
+        Ok(app)
+    }
+
+    fn current_list(&mut self) -> Option<(&mut ListState, usize)> {
+        match self.tab {
+            Tab::Games => Some((&mut self.games_state, self.games.len())),
+            Tab::Wine => Some((&mut self.wine_state, self.wine_versions.len())),
+            Tab::ProtonDB => Some((&mut self.protondb_state, self.protondb_results.len())),
+            Tab::Containers => Some((&mut self.containers_state, self.containers.len())),
+            Tab::System => None,
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if let Some((state, len)) = self.current_list() {
+            if len == 0 {
+                return;
+            }
+            let current = state.selected().unwrap_or(0) as isize;
+            let next = (current + delta).rem_euclid(len as isize) as usize;
+            state.select(Some(next));
+        }
+    }
+
+    fn switch_tab(&mut self, delta: isize) {
+        let count = Tab::ALL.len() as isize;
+        let next = (self.tab.index() as isize + delta).rem_euclid(count) as usize;
+        self.tab = Tab::ALL[next];
+    }
+
+    fn selected_game(&self) -> Option<&Game> {
+        self.games_state.selected().and_then(|i| self.games.get(i))
+    }
+
+    async fn handle_key(&mut self, terminal: &mut TuiTerminal, key: KeyCode) -> Result<()> {
+        // Typing a ProtonDB query captures every key until Enter or Esc
+        if self.protondb_editing {
+            match key {
+                KeyCode::Enter => {
+                    self.protondb_editing = false;
+                    self.search_protondb(terminal).await?;
+                }
+                KeyCode::Esc => self.protondb_editing = false,
+                KeyCode::Backspace => {
+                    self.protondb_query.pop();
+                }
+                KeyCode::Char(c) => self.protondb_query.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Left | KeyCode::BackTab => self.switch_tab(-1),
+            KeyCode::Right | KeyCode::Tab => self.switch_tab(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Enter if self.tab == Tab::Games => self.launch_selected().await,
+            KeyCode::Char('s') if self.tab == Tab::Games => self.stop_selected().await,
+            KeyCode::Char('/') if self.tab == Tab::ProtonDB => {
+                self.protondb_editing = true;
+                self.status = "Type a game name, Enter to search, Esc to cancel".to_string();
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    async fn launch_selected(&mut self) {
+        let Some(game) = self.selected_game().cloned() else {
+            return;
+        };
+
+        if self.launcher.get_running_games().contains_key(&game.id) {
+            self.status = format!("{} is already running", game.name);
+            return;
+        }
+
+        let options = LaunchOptions {
+            wine_version: game.wine_version.clone(),
+            ..LaunchOptions::default()
+        };
+
+        self.status = match self.launcher.launch_game(&game, options).await {
+            Ok(pid) => format!("🚀 {} launched (PID {})", game.name, pid),
+            Err(e) => format!("❌ Failed to launch {}: {}", game.name, e),
+        };
+    }
+
+    async fn stop_selected(&mut self) {
+        let Some(game) = self.selected_game().cloned() else {
+            return;
+        };
+
+        if !self.launcher.get_running_games().contains_key(&game.id) {
+            self.status = format!("{} is not running", game.name);
+            return;
+        }
+
+        self.status = match self.launcher.stop_game(&game.id).await {
+            Ok(()) => format!("🛑 {} stopped", game.name),
+            Err(e) => format!("❌ Failed to stop {}: {}", game.name, e),
+        };
+    }
+
+    async fn search_protondb(&mut self, terminal: &mut TuiTerminal) -> Result<()> {
+        if self.protondb_query.trim().is_empty() {
+            return Ok(());
+        }
+
+        self.status = format!("🔍 Searching ProtonDB for {}...", self.protondb_query);
+        terminal.draw(|frame| self.draw(frame))?;
+
+        let client = self.protondb.clone();
+        self.protondb_results.clear();
+        self.protondb_state.select(None);
+
+        match client.find_steam_app(&self.protondb_query).await {
+            Ok(Some((appid, name))) => {
+                let summary = client.get_game_summary(appid).await.ok().flatten();
+                self.protondb_results.push((appid, name, summary));
+                self.protondb_state.select(Some(0));
+                self.status = "Search complete".to_string();
+            }
+            Ok(None) => self.status = format!("No Steam app matches {}", self.protondb_query),
+            Err(e) => self.status = format!("❌ ProtonDB search failed: {}", e),
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs_area, body_area, status_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let tabs = Tabs::new(Tab::ALL.iter().map(|t| t.title()))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" 👻 GhostForge "),
+            )
+            .select(self.tab.index())
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_widget(tabs, tabs_area);
+
+        match self.tab {
+            Tab::Games => self.draw_games(frame, body_area),
+            Tab::Wine => self.draw_wine(frame, body_area),
+            Tab::ProtonDB => self.draw_protondb(frame, body_area),
+            Tab::Containers => self.draw_containers(frame, body_area),
+            Tab::System => self.draw_system(frame, body_area),
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.status.as_str()).style(Style::default().fg(Color::DarkGray)),
+            status_area,
+        );
+    }
+
+    fn draw_games(&mut self, frame: &mut Frame, area: Rect) {
+        let [list_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+
+        let running = self.launcher.get_running_games();
+        let items: Vec<ListItem> = if self.games.is_empty() {
+            vec![ListItem::new(
+                "No games yet. Use 'forge game add' or 'forge launcher sync'",
+            )]
+        } else {
+            self.games
+                .iter()
+                .map(|game| {
+                    let marker = if running.contains_key(&game.id) {
+                        "▶ "
+                    } else {
+                        "  "
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(marker, Style::default().fg(Color::Green)),
+                        Span::raw(game.name.clone()),
+                    ]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Library "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, list_area, &mut self.games_state);
+
+        let details = match self.selected_game() {
+            Some(game) => {
+                let state = match running.get(&game.id) {
+                    Some(run) => format!(
+                        "Running (PID {})",
+                        run.pid.map(|p| p.to_string()).unwrap_or_default()
+                    ),
+                    None => "Stopped".to_string(),
+                };
+                vec![
+                    Line::from(Span::styled(
+                        game.name.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(format!("ID: {}", game.id)),
+                    Line::from(format!(
+                        "Launcher: {}",
+                        game.launcher.as_deref().unwrap_or("Manual")
+                    )),
+                    Line::from(format!(
+                        "Wine/Proton: {}",
+                        game.wine_version.as_deref().unwrap_or("default")
+                    )),
+                    Line::from(format!("Path: {}", game.install_path.display())),
+                    Line::from(format!("Playtime: {} min", game.playtime_minutes)),
+                    Line::from(format!("Status: {}", state)),
+                ]
+            }
+            None => vec![Line::from("Select a game")],
+        };
+
+        frame.render_widget(
+            Paragraph::new(details)
+                .block(Block::default().borders(Borders::ALL).title(" Details "))
+                .wrap(Wrap { trim: true }),
+            details_area,
+        );
+    }
+
+    fn draw_wine(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = if self.wine_versions.is_empty() {
+            vec![ListItem::new(
+                "No Wine/Proton versions found. Use 'forge wine install'",
+            )]
+        } else {
+            self.wine_versions
+                .iter()
+                .map(|v| {
+                    let system = if v.system { " (system)" } else { "" };
+                    ListItem::new(format!(
+                        "{} v{} [{:?}]{}",
+                        v.name, v.version, v.wine_type, system
+                    ))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Installed Wine/Proton "),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.wine_state);
+    }
+
+    fn draw_protondb(&mut self, frame: &mut Frame, area: Rect) {
+        let [input_area, results_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        let input_style = if self.protondb_editing {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        frame.render_widget(
+            Paragraph::new(self.protondb_query.as_str())
+                .style(input_style)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Search (press / to type) "),
+                ),
+            input_area,
+        );
+
+        let items: Vec<ListItem> = self
+            .protondb_results
+            .iter()
+            .map(|(appid, name, summary)| {
+                let rating = match summary {
+                    Some(summary) => {
+                        let (label, _) = ProtonDBClient::format_tier(&summary.tier);
+                        format!("{} ({} reports)", label, summary.total)
+                    }
+                    None => "no reports".to_string(),
+                };
+                ListItem::new(format!("{} [{}] - {}", name, appid, rating))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Results "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, results_area, &mut self.protondb_state);
+    }
+
+    fn draw_containers(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = if self.containers.is_empty() {
+            vec![ListItem::new("No game containers")]
+        } else {
+            self.containers
+                .iter()
+                .map(|c| {
+                    let last_used = c
+                        .last_used
+                        .map(|t| t.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|| "never".to_string());
+                    ListItem::new(format!(
+                        "{} ({}) - {} - last used {}",
+                        c.name, c.game_id, c.wine_version, last_used
+                    ))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Containers "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.containers_state);
+    }
+
+    fn draw_system(&mut self, frame: &mut Frame, area: Rect) {
+        let lines = match &self.system_info {
+            Some(info) => {
+                let mut lines = vec![
+                    Line::from(format!("OS: {}", info.os)),
+                    Line::from(format!("Kernel: {}", info.kernel)),
+                    Line::from(format!(
+                        "Desktop: {}",
+                        info.desktop.as_deref().unwrap_or("unknown")
+                    )),
+                    Line::from(format!(
+                        "CPU: {} ({} cores / {} threads)",
+                        info.cpu.brand, info.cpu.cores, info.cpu.threads
+                    )),
+                    Line::from(format!(
+                        "Memory: {} total, {} available",
+                        crate::utils::format_size(info.memory.total),
+                        crate::utils::format_size(info.memory.available)
+                    )),
+                ];
+                for gpu in &info.gpu {
+                    lines.push(Line::from(format!(
+                        "GPU: {} ({:?}) driver {}",
+                        gpu.name,
+                        gpu.vendor,
+                        gpu.driver.as_deref().unwrap_or("unknown")
+                    )));
+                }
+                lines.push(Line::from(format!(
+                    "Vulkan: {}",
+                    if info.vulkan.available {
+                        "available"
+                    } else {
+                        "missing"
+                    }
+                )));
+                lines
+            }
+            None => vec![Line::from("System information unavailable")],
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(" System "))
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+}
+
+/// Run the terminal interface until the user quits
+pub async fn run_tui() -> Result<()> {
+    let mut app = TuiApp::new().await?;
+    let (capture, mut output) = OutputCapture::start(&app.log_path)?;
+
+    enable_raw_mode()?;
+    execute!(output, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(output))?;
+
+    let result = run_event_loop(&mut terminal, &mut app).await;
+
+    // Always give the terminal back, even if the loop failed
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    drop(capture);
+
+    if app.log_path.metadata().is_ok_and(|m| m.len() > 0) {
+        out!(
+            "📜 Launcher output was written to {}",
+            app.log_path.display()
+        );
+    }
+
+    result
+}
+
+async fn run_event_loop(terminal: &mut TuiTerminal, app: &mut TuiApp) -> Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        // Poll with a timeout so running-game markers refresh on their own
+        if event::poll(Duration::from_millis(250))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(terminal, key.code).await?;
+        }
+    }
+
+    Ok(())
+}
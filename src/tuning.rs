@@ -0,0 +1,768 @@
+use crate::utils::out;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use which::which;
+
+use crate::bolt_integration::{AmdConfig, FanCurvePoint, NvidiaConfig};
+
+const CPU_GOVERNOR_PATH: &str = "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor";
+const NVIDIA_CORE_OFFSET_ATTR: &str = "GPUGraphicsClockOffsetAllPerformanceLevels";
+const NVIDIA_MEMORY_OFFSET_ATTR: &str = "GPUMemoryTransferRateOffsetAllPerformanceLevels";
+
+/// Settings that were in effect before tuning a game session, kept so they
+/// can be put back once the game exits. Only values that were actually
+/// changed are recorded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TuningSnapshot {
+    pub cpu_governor: Option<String>,
+    pub nvidia_persistence_mode: Option<bool>,
+    pub nvidia_power_limit: Option<f64>,
+    pub nvidia_core_clock_offset: Option<i32>,
+    pub nvidia_memory_clock_offset: Option<i32>,
+    /// sysfs device directory of the AMD GPU the settings below belong to
+    pub amd_device: Option<PathBuf>,
+    pub amd_performance_level: Option<String>,
+    pub amd_power_profile: Option<u32>,
+    #[serde(default)]
+    pub amd_fan_curve_customized: bool,
+}
+
+impl TuningSnapshot {
+    pub fn is_empty(&self) -> bool {
+        self.cpu_governor.is_none()
+            && self.nvidia_persistence_mode.is_none()
+            && self.nvidia_power_limit.is_none()
+            && self.nvidia_core_clock_offset.is_none()
+            && self.nvidia_memory_clock_offset.is_none()
+            && self.amd_performance_level.is_none()
+            && self.amd_power_profile.is_none()
+            && !self.amd_fan_curve_customized
+    }
+
+    /// Take values from a later snapshot only where this one has none, so the
+    /// original settings survive overlapping sessions
+    fn fill_from(&mut self, later: &TuningSnapshot) {
+        if self.cpu_governor.is_none() {
+            self.cpu_governor = later.cpu_governor.clone();
+        }
+        self.nvidia_persistence_mode = self
+            .nvidia_persistence_mode
+            .or(later.nvidia_persistence_mode);
+        self.nvidia_power_limit = self.nvidia_power_limit.or(later.nvidia_power_limit);
+        self.nvidia_core_clock_offset = self
+            .nvidia_core_clock_offset
+            .or(later.nvidia_core_clock_offset);
+        self.nvidia_memory_clock_offset = self
+            .nvidia_memory_clock_offset
+            .or(later.nvidia_memory_clock_offset);
+        if self.amd_device.is_none() {
+            self.amd_device = later.amd_device.clone();
+        }
+        if self.amd_performance_level.is_none() {
+            self.amd_performance_level = later.amd_performance_level.clone();
+        }
+        self.amd_power_profile = self.amd_power_profile.or(later.amd_power_profile);
+        self.amd_fan_curve_customized |= later.amd_fan_curve_customized;
+    }
+}
+
+/// What a tuning step changed and what it could not, since each setting is
+/// applied independently and may fail on its own.
+#[derive(Debug, Clone, Default)]
+pub struct TuningReport {
+    pub applied: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+impl TuningReport {
+    pub fn print(&self) {
+        for setting in &self.applied {
+            out!("  ✅ {}", setting);
+        }
+        for failure in &self.failed {
+            out!("  ⚠️  {}", failure);
+        }
+    }
+}
+
+/// Tuning that is currently in effect, saved to disk so the original
+/// settings can still be restored after a crash (`forge optimize --reset`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TuningState {
+    /// Sessions (game IDs or container names) that rely on the tuning
+    pub sessions: Vec<String>,
+    /// Settings from before the first of those sessions started
+    pub previous: TuningSnapshot,
+}
+
+impl TuningState {
+    pub fn path() -> Result<PathBuf> {
+        Ok(dirs::data_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot find data directory"))?
+            .join("ghostforge")
+            .join("tuning-state.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid tuning state {}: {}", path.display(), e))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if self.sessions.is_empty() && self.previous.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record that `session` is running with tuning applied, `snapshot` holding
+    /// whatever it changed itself
+    pub fn begin_session(session: &str, snapshot: &TuningSnapshot) -> Result<()> {
+        let mut state = Self::load()?;
+        if !state.sessions.iter().any(|s| s == session) {
+            state.sessions.push(session.to_string());
+        }
+        state.previous.fill_from(snapshot);
+        state.save()
+    }
+
+    /// Mark `session` as finished, restoring the original settings once no
+    /// other tuned session is left
+    pub fn end_session(session: &str) -> Result<TuningReport> {
+        let mut state = Self::load()?;
+        state.sessions.retain(|s| s != session);
+        if !state.sessions.is_empty() {
+            state.save()?;
+            return Ok(TuningReport::default());
+        }
+
+        Self::restore_previous(state)
+    }
+
+    /// Restore the original settings regardless of running sessions
+    pub fn reset() -> Result<TuningReport> {
+        let mut state = Self::load()?;
+        state.sessions.clear();
+        Self::restore_previous(state)
+    }
+
+    fn restore_previous(mut state: TuningState) -> Result<TuningReport> {
+        let report = restore(&state.previous);
+
+        // Keep the state around when something could not be restored so a
+        // later reset can retry
+        if report.failed.is_empty() {
+            state.previous = TuningSnapshot::default();
+        }
+        state.save()?;
+        Ok(report)
+    }
+}
+
+/// Switch the CPU governor and apply the NVIDIA or AMD GPU tuning, returning
+/// the previous values of everything that changed
+pub fn apply(
+    cpu_governor: Option<&str>,
+    nvidia: Option<&NvidiaConfig>,
+    amd: Option<&AmdConfig>,
+) -> (TuningSnapshot, TuningReport) {
+    let mut snapshot = TuningSnapshot::default();
+    let mut report = TuningReport::default();
+
+    if let Some(governor) = cpu_governor {
+        match apply_cpu_governor(governor, &mut snapshot) {
+            Ok(true) => report.applied.push(format!("CPU governor: {}", governor)),
+            Ok(false) => {}
+            Err(e) => report.failed.push(format!("CPU governor: {}", e)),
+        }
+    }
+
+    if let Some(config) = nvidia {
+        apply_nvidia(config, &mut snapshot, &mut report);
+    }
+
+    if let Some(config) = amd {
+        apply_amd(config, &mut snapshot, &mut report);
+    }
+
+    (snapshot, report)
+}
+
+/// Describe what `apply` would change, reading the current settings but
+/// writing nothing
+pub fn plan(
+    cpu_governor: Option<&str>,
+    nvidia: Option<&NvidiaConfig>,
+    amd: Option<&AmdConfig>,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if let Some(governor) = cpu_governor {
+        changes.push(match std::fs::read_to_string(CPU_GOVERNOR_PATH) {
+            Ok(current) if current.trim() == governor => {
+                format!("CPU governor: already {}", governor)
+            }
+            Ok(current) => format!("CPU governor: {} → {}", current.trim(), governor),
+            Err(_) => format!(
+                "CPU governor: {} (frequency scaling not available)",
+                governor
+            ),
+        });
+    }
+
+    if let Some(config) = nvidia {
+        if let Some(watts) = config.power_limit {
+            changes.push(match query_nvidia_power_state() {
+                Ok(state) => {
+                    let target = (watts as f64).clamp(state.min_limit, state.max_limit);
+                    format!("GPU power limit: {:.0} W → {:.0} W", state.limit, target)
+                }
+                Err(e) => format!("GPU power limit: {} W ({})", watts, e),
+            });
+        }
+        for (label, attribute, offset) in [
+            (
+                "GPU core clock offset",
+                NVIDIA_CORE_OFFSET_ATTR,
+                config.core_clock_offset,
+            ),
+            (
+                "GPU memory clock offset",
+                NVIDIA_MEMORY_OFFSET_ATTR,
+                config.memory_clock_offset,
+            ),
+        ] {
+            let Some(offset) = offset else { continue };
+            changes.push(match query_nvidia_attribute(attribute) {
+                Ok(current) => format!("{}: {:+} MHz → {:+} MHz", label, current, offset),
+                Err(e) => format!("{}: {:+} MHz ({})", label, offset, e),
+            });
+        }
+    }
+
+    if let Some(config) = amd {
+        if let Some(profile) = &config.power_profile {
+            changes.push(format!("AMD power profile: {}", profile));
+        }
+        if !config.fan_curve.is_empty() {
+            let points: Vec<String> = config
+                .fan_curve
+                .iter()
+                .map(|point| format!("{}°C→{}%", point.temperature, point.speed))
+                .collect();
+            changes.push(format!("AMD fan curve: {}", points.join(", ")));
+        }
+    }
+
+    changes
+}
+
+/// Whether the proprietary NVIDIA driver is loaded
+pub fn has_nvidia_gpu() -> bool {
+    Path::new("/proc/driver/nvidia/version").exists()
+}
+
+/// sysfs device directory of the first AMD GPU, e.g. `/sys/class/drm/card0/device`
+pub fn amd_gpu_device() -> Option<PathBuf> {
+    let mut cards: Vec<PathBuf> = std::fs::read_dir("/sys/class/drm")
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("card") && !name.contains('-')
+        })
+        .map(|entry| entry.path().join("device"))
+        .collect();
+    cards.sort();
+
+    cards.into_iter().find(|device| {
+        std::fs::read_to_string(device.join("vendor")).is_ok_and(|vendor| vendor.trim() == "0x1002")
+    })
+}
+
+/// Put back every setting recorded in `snapshot`
+pub fn restore(snapshot: &TuningSnapshot) -> TuningReport {
+    let mut report = TuningReport::default();
+
+    if let Some(governor) = &snapshot.cpu_governor {
+        match crate::utils::set_cpu_governor(governor) {
+            Ok(()) => report
+                .applied
+                .push(format!("CPU governor restored to {}", governor)),
+            Err(e) => report.failed.push(format!("CPU governor: {}", e)),
+        }
+    }
+
+    if let Some(watts) = snapshot.nvidia_power_limit {
+        match set_nvidia_power_limit(watts) {
+            Ok(()) => report
+                .applied
+                .push(format!("GPU power limit restored to {:.0} W", watts)),
+            Err(e) => report.failed.push(format!("GPU power limit: {}", e)),
+        }
+    }
+
+    if snapshot.nvidia_persistence_mode == Some(false) {
+        match set_nvidia_persistence_mode(false) {
+            Ok(()) => report
+                .applied
+                .push("GPU persistence mode disabled again".to_string()),
+            Err(e) => report.failed.push(format!("GPU persistence mode: {}", e)),
+        }
+    }
+
+    if let Some(device) = &snapshot.amd_device {
+        restore_amd(device, snapshot, &mut report);
+    }
+
+    for (label, attribute, previous) in [
+        (
+            "GPU core clock offset",
+            NVIDIA_CORE_OFFSET_ATTR,
+            snapshot.nvidia_core_clock_offset,
+        ),
+        (
+            "GPU memory clock offset",
+            NVIDIA_MEMORY_OFFSET_ATTR,
+            snapshot.nvidia_memory_clock_offset,
+        ),
+    ] {
+        let Some(offset) = previous else { continue };
+        match set_nvidia_attribute(attribute, offset) {
+            Ok(()) => report
+                .applied
+                .push(format!("{} restored to {:+} MHz", label, offset)),
+            Err(e) => report.failed.push(format!("{}: {}", label, e)),
+        }
+    }
+
+    report
+}
+
+/// Returns whether the governor had to be changed
+fn apply_cpu_governor(governor: &str, snapshot: &mut TuningSnapshot) -> Result<bool> {
+    let current = std::fs::read_to_string(CPU_GOVERNOR_PATH)
+        .map_err(|_| anyhow::anyhow!("CPU frequency scaling is not available"))?;
+    let current = current.trim();
+    if current == governor {
+        return Ok(false);
+    }
+
+    crate::utils::set_cpu_governor(governor)?;
+    snapshot
+        .cpu_governor
+        .get_or_insert_with(|| current.to_string());
+    Ok(true)
+}
+
+#[derive(Debug)]
+struct NvidiaPowerState {
+    limit: f64,
+    min_limit: f64,
+    max_limit: f64,
+    persistence_mode: bool,
+}
+
+/// Apply the power limit and clock offsets from `config` to the first NVIDIA GPU
+fn apply_nvidia(config: &NvidiaConfig, snapshot: &mut TuningSnapshot, report: &mut TuningReport) {
+    if let Some(watts) = config.power_limit {
+        match apply_nvidia_power_limit(watts, snapshot) {
+            Ok(message) => report.applied.push(message),
+            Err(e) => report.failed.push(format!("GPU power limit: {}", e)),
+        }
+    }
+
+    for (label, attribute, offset, previous) in [
+        (
+            "GPU core clock offset",
+            NVIDIA_CORE_OFFSET_ATTR,
+            config.core_clock_offset,
+            &mut snapshot.nvidia_core_clock_offset,
+        ),
+        (
+            "GPU memory clock offset",
+            NVIDIA_MEMORY_OFFSET_ATTR,
+            config.memory_clock_offset,
+            &mut snapshot.nvidia_memory_clock_offset,
+        ),
+    ] {
+        let Some(offset) = offset else { continue };
+        match apply_nvidia_offset(attribute, offset, previous) {
+            Ok(true) => report.applied.push(format!("{}: {:+} MHz", label, offset)),
+            Ok(false) => {}
+            Err(e) => report.failed.push(format!("{}: {}", label, e)),
+        }
+    }
+}
+
+fn apply_nvidia_power_limit(watts: u32, snapshot: &mut TuningSnapshot) -> Result<String> {
+    let state = query_nvidia_power_state()?;
+
+    // nvidia-smi rejects limits outside the board's range, so clamp first
+    let target = (watts as f64).clamp(state.min_limit, state.max_limit);
+    if (target - state.limit).abs() < 0.5 {
+        return Ok(format!("GPU power limit already {:.0} W", target));
+    }
+
+    // Without persistence mode the driver can unload between clients and
+    // drop the limit, so keep it on for the session
+    if !state.persistence_mode {
+        set_nvidia_persistence_mode(true)?;
+        snapshot.nvidia_persistence_mode.get_or_insert(false);
+    }
+
+    set_nvidia_power_limit(target)?;
+    snapshot.nvidia_power_limit.get_or_insert(state.limit);
+
+    if target != watts as f64 {
+        Ok(format!(
+            "GPU power limit: {:.0} W (requested {} W, board allows {:.0}-{:.0} W)",
+            target, watts, state.min_limit, state.max_limit
+        ))
+    } else {
+        Ok(format!("GPU power limit: {:.0} W", target))
+    }
+}
+
+fn query_nvidia_power_state() -> Result<NvidiaPowerState> {
+    if which("nvidia-smi").is_err() {
+        return Err(anyhow::anyhow!("nvidia-smi is not installed"));
+    }
+
+    let output = Command::new("nvidia-smi")
+        .args([
+            "-i",
+            "0",
+            "--query-gpu=power.limit,power.min_limit,power.max_limit,persistence_mode",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "nvidia-smi failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<&str> = stdout
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .collect();
+    if fields.len() < 4 {
+        return Err(anyhow::anyhow!(
+            "unexpected nvidia-smi output: {}",
+            stdout.trim()
+        ));
+    }
+
+    // Boards without power management or persistence report "[N/A]" or
+    // "[Not Supported]" here
+    let parse_watts = |value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| anyhow::anyhow!("power limits are not supported by this GPU/driver"))
+    };
+    let persistence_mode = match fields[3] {
+        "Enabled" => true,
+        "Disabled" => false,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "persistence mode is not supported by this GPU/driver"
+            ));
+        }
+    };
+
+    Ok(NvidiaPowerState {
+        limit: parse_watts(fields[0])?,
+        min_limit: parse_watts(fields[1])?,
+        max_limit: parse_watts(fields[2])?,
+        persistence_mode,
+    })
+}
+
+fn set_nvidia_power_limit(watts: f64) -> Result<()> {
+    let status = crate::utils::privileged_command("nvidia-smi")?
+        .args(["-i", "0", "-pl", &format!("{:.0}", watts)])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "nvidia-smi exited with {} (insufficient permissions?)",
+            status
+        ));
+    }
+    Ok(())
+}
+
+fn set_nvidia_persistence_mode(enabled: bool) -> Result<()> {
+    let status = crate::utils::privileged_command("nvidia-smi")?
+        .args(["-i", "0", "-pm", if enabled { "1" } else { "0" }])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "nvidia-smi exited with {} (insufficient permissions?)",
+            status
+        ));
+    }
+    Ok(())
+}
+
+/// Returns whether the offset had to be changed
+fn apply_nvidia_offset(attribute: &str, offset: i32, previous: &mut Option<i32>) -> Result<bool> {
+    let current = query_nvidia_attribute(attribute)?;
+    if current == offset {
+        return Ok(false);
+    }
+
+    set_nvidia_attribute(attribute, offset)?;
+    previous.get_or_insert(current);
+    Ok(true)
+}
+
+fn query_nvidia_attribute(attribute: &str) -> Result<i32> {
+    if which("nvidia-settings").is_err() {
+        return Err(anyhow::anyhow!("nvidia-settings is not installed"));
+    }
+
+    let output = Command::new("nvidia-settings")
+        .args(["-t", "-q", &format!("[gpu:0]/{}", attribute)])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Clock offsets are only exposed with Coolbits enabled in the X config
+    match stdout.trim().parse::<i32>() {
+        Ok(value) if output.status.success() => Ok(value),
+        _ => Err(anyhow::anyhow!(
+            "not available (needs an X session and Coolbits enabled)"
+        )),
+    }
+}
+
+fn set_nvidia_attribute(attribute: &str, value: i32) -> Result<()> {
+    let output = Command::new("nvidia-settings")
+        .args(["-a", &format!("[gpu:0]/{}={}", attribute, value)])
+        .output()?;
+
+    // nvidia-settings exits 0 even when an assignment is rejected
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || stderr.contains("ERROR") {
+        return Err(anyhow::anyhow!(
+            "nvidia-settings rejected the change: {}",
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Apply the power profile and fan curve from `config` to the first AMD GPU.
+/// The Vulkan driver selection is part of the launch environment instead.
+fn apply_amd(config: &AmdConfig, snapshot: &mut TuningSnapshot, report: &mut TuningReport) {
+    if config.power_profile.is_none() && config.fan_curve.is_empty() {
+        return;
+    }
+
+    let Some(device) = amd_gpu_device() else {
+        report
+            .failed
+            .push("AMD GPU tuning: no AMD GPU found".to_string());
+        return;
+    };
+
+    if let Some(profile) = &config.power_profile {
+        match apply_amd_power_profile(&device, profile, snapshot) {
+            Ok(true) => report
+                .applied
+                .push(format!("GPU power profile: {}", profile)),
+            Ok(false) => {}
+            Err(e) => report.failed.push(format!("GPU power profile: {}", e)),
+        }
+    }
+
+    if !config.fan_curve.is_empty() {
+        match apply_amd_fan_curve(&device, &config.fan_curve, snapshot) {
+            Ok(()) => report.applied.push(format!(
+                "GPU fan curve: {}",
+                config
+                    .fan_curve
+                    .iter()
+                    .map(|p| format!("{}°C→{}%", p.temperature, p.speed))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Err(e) => report.failed.push(format!("GPU fan curve: {}", e)),
+        }
+    }
+}
+
+fn restore_amd(device: &Path, snapshot: &TuningSnapshot, report: &mut TuningReport) {
+    if snapshot.amd_fan_curve_customized {
+        let fan_curve = device.join("gpu_od/fan_ctrl/fan_curve");
+        match write_sysfs(&fan_curve, "r").and_then(|_| write_sysfs(&fan_curve, "c")) {
+            Ok(()) => report
+                .applied
+                .push("GPU fan curve reset to default".to_string()),
+            Err(e) => report.failed.push(format!("GPU fan curve: {}", e)),
+        }
+    }
+
+    if let Some(index) = snapshot.amd_power_profile {
+        match write_sysfs(&device.join("pp_power_profile_mode"), &index.to_string()) {
+            Ok(()) => report
+                .applied
+                .push("GPU power profile restored".to_string()),
+            Err(e) => report.failed.push(format!("GPU power profile: {}", e)),
+        }
+    }
+
+    if let Some(level) = &snapshot.amd_performance_level {
+        match write_sysfs(&device.join("power_dpm_force_performance_level"), level) {
+            Ok(()) => report
+                .applied
+                .push(format!("GPU performance level restored to {}", level)),
+            Err(e) => report.failed.push(format!("GPU performance level: {}", e)),
+        }
+    }
+}
+
+/// Returns whether the power profile had to be changed
+fn apply_amd_power_profile(
+    device: &Path,
+    profile: &str,
+    snapshot: &mut TuningSnapshot,
+) -> Result<bool> {
+    let modes = std::fs::read_to_string(device.join("pp_power_profile_mode"))
+        .map_err(|_| anyhow::anyhow!("power profiles are not supported by this GPU/driver"))?;
+    let profiles = parse_power_profiles(&modes);
+
+    let target = profiles
+        .iter()
+        .find(|(_, name, _)| name.eq_ignore_ascii_case(profile))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "unknown profile '{}' (available: {})",
+                profile,
+                profiles
+                    .iter()
+                    .map(|(_, name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+    let active = profiles.iter().find(|(_, _, active)| *active);
+
+    // Power profiles only take effect with the performance level on manual
+    let level_path = device.join("power_dpm_force_performance_level");
+    let level = std::fs::read_to_string(&level_path)?.trim().to_string();
+    if level == "manual" && active.is_some_and(|(index, _, _)| *index == target.0) {
+        return Ok(false);
+    }
+
+    snapshot
+        .amd_device
+        .get_or_insert_with(|| device.to_path_buf());
+    if level != "manual" {
+        write_sysfs(&level_path, "manual")?;
+        snapshot.amd_performance_level.get_or_insert(level);
+    }
+
+    write_sysfs(&device.join("pp_power_profile_mode"), &target.0.to_string())?;
+    if let Some((index, _, _)) = active {
+        snapshot.amd_power_profile.get_or_insert(*index);
+    }
+    Ok(true)
+}
+
+/// Parse `pp_power_profile_mode` into (index, name, active) entries. The
+/// layout differs between GPU generations, but every profile line starts
+/// with its index and name and the active one is marked with `*`.
+fn parse_power_profiles(modes: &str) -> Vec<(u32, String, bool)> {
+    modes
+        .lines()
+        .filter_map(|line| {
+            let header = line.split(':').next()?;
+            let mut tokens = header.split_whitespace();
+            let index = tokens.next()?.parse::<u32>().ok()?;
+            let name = tokens.next()?.trim_end_matches('*').to_string();
+            Some((index, name, header.contains('*')))
+        })
+        .collect()
+}
+
+fn apply_amd_fan_curve(
+    device: &Path,
+    curve: &[FanCurvePoint],
+    snapshot: &mut TuningSnapshot,
+) -> Result<()> {
+    let fan_curve = device.join("gpu_od/fan_ctrl/fan_curve");
+    if !fan_curve.exists() {
+        return Err(anyhow::anyhow!(
+            "needs an RDNA3 or newer GPU with overdrive enabled (amdgpu.ppfeaturemask)"
+        ));
+    }
+
+    snapshot
+        .amd_device
+        .get_or_insert_with(|| device.to_path_buf());
+    snapshot.amd_fan_curve_customized = true;
+
+    // Each point is written on its own, then committed
+    for (index, point) in curve.iter().enumerate() {
+        write_sysfs(
+            &fan_curve,
+            &format!("{} {} {}", index, point.temperature, point.speed),
+        )?;
+    }
+    write_sysfs(&fan_curve, "c")
+}
+
+/// Write a sysfs attribute, going through a privileged `tee` when the file
+/// is only writable by root
+fn write_sysfs(path: &Path, value: &str) -> Result<()> {
+    match std::fs::write(path, value) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() != std::io::ErrorKind::PermissionDenied => {
+            return Err(anyhow::anyhow!("writing {}: {}", path.display(), e));
+        }
+        Err(_) => {}
+    }
+
+    let mut child = crate::utils::privileged_command("tee")?
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(value.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "writing {} exited with {} (insufficient permissions?)",
+            path.display(),
+            status
+        ));
+    }
+    Ok(())
+}
@@ -1,10 +1,27 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use sysinfo::System;
 use which::which;
 
+/// `println!` for messages with emoji, which are dropped when colors are
+/// off (see `set_plain_output`)
+macro_rules! out {
+    ($($arg:tt)*) => {
+        ::std::println!("{}", $crate::utils::plain_text(&::std::format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` for messages with emoji, see `out!`
+macro_rules! eout {
+    ($($arg:tt)*) => {
+        ::std::eprintln!("{}", $crate::utils::plain_text(&::std::format!($($arg)*)))
+    };
+}
+
+pub(crate) use {eout, out};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub os: String,
@@ -26,6 +43,115 @@ pub struct GpuInfo {
     pub vram: Option<u64>,
     pub vulkan_support: bool,
     pub dxvk_support: bool,
+    /// PCI address, e.g. `0000:03:00.0`
+    #[serde(default)]
+    pub pci_address: Option<String>,
+    /// Whether the firmware set this GPU up as the primary display device,
+    /// usually the integrated one on hybrid laptops
+    #[serde(default)]
+    pub boot_gpu: bool,
+}
+
+impl GpuInfo {
+    /// Intel's discrete Arc cards (Alchemist/DG2 and Battlemage)
+    pub fn is_intel_arc(&self) -> bool {
+        matches!(self.vendor, GpuVendor::Intel)
+            && ["Arc", "DG2", "Battlemage"]
+                .iter()
+                .any(|marker| self.name.contains(marker))
+    }
+
+    /// Environment for PRIME render offload onto this GPU
+    pub fn offload_environment(&self) -> Vec<(String, String)> {
+        let nvidia_driver =
+            matches!(self.vendor, GpuVendor::Nvidia) && self.driver.as_deref() != Some("nouveau");
+        if nvidia_driver {
+            return [
+                ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+                ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+                ("__VK_LAYER_NV_optimus", "NVIDIA_only"),
+            ]
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        }
+
+        // Mesa takes the PCI address as pci-0000_03_00_0
+        let dri_prime = self
+            .pci_address
+            .as_ref()
+            .map(|address| format!("pci-{}", address.replace([':', '.'], "_")))
+            .unwrap_or_else(|| "1".to_string());
+        vec![("DRI_PRIME".to_string(), dri_prime)]
+    }
+}
+
+/// Environment that makes a game render on GPU `index` of
+/// `SystemDetector::detect_gpu`, or on the discrete GPU when `None` and
+/// `gpu.nvidia_prime_render_offload` is on. Empty on single-GPU systems and
+/// when the GPU already drives the display.
+pub fn prime_environment(
+    index: Option<usize>,
+    offload_by_default: bool,
+) -> Result<Vec<(String, String)>> {
+    if index.is_none() && !offload_by_default {
+        return Ok(Vec::new());
+    }
+
+    let gpus = SystemDetector::detect_gpu()?;
+    if gpus.len() < 2 {
+        return Ok(Vec::new());
+    }
+
+    let gpu = match index {
+        Some(index) => Some(gpus.get(index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "GPU {} not found, 'forge game gpu <game>' lists the GPUs",
+                index
+            )
+        })?),
+        None => default_render_gpu(&gpus),
+    };
+
+    Ok(gpu
+        .filter(|gpu| !gpu.boot_gpu)
+        .map(GpuInfo::offload_environment)
+        .unwrap_or_default())
+}
+
+/// Whether the kernel provides ntsync for Wine, through the `/dev/ntsync`
+/// device of Linux 6.14+
+pub fn ntsync_available() -> bool {
+    Path::new("/dev/ntsync").exists()
+}
+
+/// Environment that points Mesa's device selection, DXVK and VKD3D-Proton at
+/// the Vulkan device called `name`. DXVK and VKD3D-Proton match on part of
+/// the name, Mesa needs the IDs of a detected device.
+pub fn vulkan_device_environment(name: &str) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("DXVK_FILTER_DEVICE_NAME".to_string(), name.to_string()),
+        ("VKD3D_FILTER_DEVICE_NAME".to_string(), name.to_string()),
+    ];
+    if let Some(device) = SystemDetector::vulkan_devices()
+        .into_iter()
+        .find(|device| device.name.contains(name))
+    {
+        env.push((
+            "MESA_VK_DEVICE_SELECT".to_string(),
+            format!("{:04x}:{:04x}", device.vendor_id, device.device_id),
+        ));
+    }
+    env
+}
+
+/// The GPU games run on by default, the first one not driving the display,
+/// preferring NVIDIA
+pub fn default_render_gpu(gpus: &[GpuInfo]) -> Option<&GpuInfo> {
+    let offload = || gpus.iter().filter(|gpu| !gpu.boot_gpu);
+    offload()
+        .find(|gpu| matches!(gpu.vendor, GpuVendor::Nvidia))
+        .or_else(|| offload().next())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +185,14 @@ pub struct VulkanInfo {
     pub devices: Vec<String>,
 }
 
+/// A physical device from `vulkaninfo --summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulkanDevice {
+    pub name: String,
+    pub vendor_id: u32,
+    pub device_id: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WineSupport {
     pub installed: bool,
@@ -143,7 +277,7 @@ impl SystemDetector {
         None
     }
 
-    fn detect_gpu() -> Result<Vec<GpuInfo>> {
+    pub fn detect_gpu() -> Result<Vec<GpuInfo>> {
         let mut gpus = Vec::new();
 
         // Use lspci to detect GPUs
@@ -175,6 +309,8 @@ impl SystemDetector {
                         vram: None,
                         vulkan_support: false,
                         dxvk_support: false,
+                        pci_address: None,
+                        boot_gpu: true,
                     });
                 }
             }
@@ -191,26 +327,48 @@ impl SystemDetector {
     }
 
     fn parse_gpu_line(line: &str) -> Result<GpuInfo> {
-        let vendor = if line.to_lowercase().contains("nvidia") {
-            GpuVendor::Nvidia
-        } else if line.to_lowercase().contains("amd") || line.to_lowercase().contains("ati") {
-            GpuVendor::AMD
-        } else if line.to_lowercase().contains("intel") {
-            GpuVendor::Intel
+        // `lspci -nn` lines look like
+        // `03:00.0 VGA compatible controller [0300]: Intel Corporation DG2 [Arc A770] [8086:56a0] (rev 08)`,
+        // the name is the description after the class without the IDs
+        let description = match line.split_once("]: ") {
+            Some((_, description)) => description,
+            None => line.split_once(": ").map(|(_, d)| d).unwrap_or(""),
+        };
+        let description = description.split(" (rev ").next().unwrap_or_default();
+        let name = match description.rsplit_once(" [") {
+            Some((name, ids)) if ids.len() == 10 && ids.as_bytes()[4] == b':' => name,
+            _ => description,
+        }
+        .trim();
+        let name = if name.is_empty() {
+            "Unknown GPU".to_string()
         } else {
-            GpuVendor::Unknown
+            name.to_string()
         };
 
-        // Extract GPU name (everything after the colon)
-        let name = if let Some(colon_pos) = line.find(':') {
-            let after_colon = &line[colon_pos + 1..];
-            if let Some(bracket_pos) = after_colon.find('[') {
-                after_colon[..bracket_pos].trim().to_string()
+        // lspci leaves out the PCI domain when there is only one
+        let pci_address = line.split_whitespace().next().map(|slot| {
+            if slot.matches(':').count() == 1 {
+                format!("0000:{}", slot)
             } else {
-                after_colon.trim().to_string()
+                slot.to_string()
             }
+        });
+        let boot_gpu = pci_address.as_ref().is_some_and(|address| {
+            std::fs::read_to_string(format!("/sys/bus/pci/devices/{}/boot_vga", address))
+                .is_ok_and(|value| value.trim() == "1")
+        });
+
+        // Match on the name only, "ati" is also part of "compatible"
+        let lower = name.to_lowercase();
+        let vendor = if lower.contains("nvidia") {
+            GpuVendor::Nvidia
+        } else if lower.contains("advanced micro devices") || lower.contains("amd/ati") {
+            GpuVendor::AMD
+        } else if lower.contains("intel") {
+            GpuVendor::Intel
         } else {
-            "Unknown GPU".to_string()
+            GpuVendor::Unknown
         };
 
         Ok(GpuInfo {
@@ -220,6 +378,8 @@ impl SystemDetector {
             vram: None,
             vulkan_support: false,
             dxvk_support: false,
+            pci_address,
+            boot_gpu,
         })
     }
 
@@ -252,7 +412,14 @@ impl SystemDetector {
                     Some("radeon".to_string())
                 }
             }
-            GpuVendor::Intel => Some("i915".to_string()),
+            GpuVendor::Intel => {
+                // Battlemage and newer are driven by xe instead of i915
+                if Path::new("/sys/module/xe").exists() {
+                    Some("xe".to_string())
+                } else {
+                    Some("i915".to_string())
+                }
+            }
             _ => None,
         }
     }
@@ -322,6 +489,36 @@ impl SystemDetector {
         Ok(vulkan_info)
     }
 
+    pub fn vulkan_devices() -> Vec<VulkanDevice> {
+        let Ok(output) = Command::new("vulkaninfo").arg("--summary").output() else {
+            return Vec::new();
+        };
+        let output = String::from_utf8_lossy(&output.stdout);
+
+        // IDs come before the name in each device block
+        let mut devices = Vec::new();
+        let (mut vendor_id, mut device_id) = (0, 0);
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            let hex = || u32::from_str_radix(value.trim_start_matches("0x"), 16).unwrap_or(0);
+            match key.trim() {
+                "vendorID" => vendor_id = hex(),
+                "deviceID" => device_id = hex(),
+                "deviceName" => devices.push(VulkanDevice {
+                    name: value.to_string(),
+                    vendor_id,
+                    device_id,
+                }),
+                _ => {}
+            }
+        }
+
+        devices
+    }
+
     fn get_wine_support() -> Result<WineSupport> {
         let wine_installed = which("wine").is_ok();
 
@@ -392,7 +589,7 @@ impl SystemDetector {
     }
 
     pub fn optimize_system_for_gaming() -> Result<()> {
-        println!("🎮 Applying gaming optimizations...");
+        out!("🎮 Applying gaming optimizations...");
 
         // Set CPU governor to performance
         if Self::check_command_exists("cpupower") {
@@ -424,7 +621,7 @@ impl SystemDetector {
             }
         }
 
-        println!("✅ System optimization suggestions applied");
+        out!("✅ System optimization suggestions applied");
         Ok(())
     }
 
@@ -491,3 +688,298 @@ impl SystemDetector {
         }
     }
 }
+
+/// Score how well `query` matches `candidate`, or `None` if it doesn't match at all.
+///
+/// Higher is better: exact matches beat substrings, which beat all-words matches,
+/// which beat in-order character matches ("cp2077" → "Cyberpunk 2077").
+pub fn fuzzy_match_score(query: &str, candidate: &str) -> Option<i64> {
+    let query = query.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    if query.is_empty() {
+        return None;
+    }
+
+    // Shorter candidates rank higher so "Portal" beats "Portal 2 Soundtrack"
+    let length_penalty = candidate.len() as i64 - query.len() as i64;
+
+    if candidate == query {
+        return Some(1000);
+    }
+
+    if let Some(pos) = candidate.find(&query) {
+        return Some(800 - pos as i64 - length_penalty);
+    }
+
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.len() > 1 && words.iter().all(|word| candidate.contains(word)) {
+        return Some(500 - length_penalty);
+    }
+
+    // Every query character must appear in order; penalize the gaps between them.
+    // Very short queries would match nearly everything this way.
+    if query.chars().filter(|c| !c.is_whitespace()).count() < 3 {
+        return None;
+    }
+
+    let mut gaps = 0i64;
+    let mut chars = candidate.chars();
+    for qc in query.chars().filter(|c| !c.is_whitespace()) {
+        let mut skipped = 0i64;
+        loop {
+            match chars.next() {
+                Some(cc) if cc == qc => break,
+                Some(_) => skipped += 1,
+                None => return None,
+            }
+        }
+        gaps += skipped;
+    }
+
+    Some(200 - gaps - length_penalty)
+}
+
+/// Set the CPU frequency governor on all cores via `cpupower`.
+///
+/// Runs through `pkexec` when not already root.
+pub fn set_cpu_governor(governor: &str) -> Result<()> {
+    if which("cpupower").is_err() {
+        return Err(anyhow::anyhow!("cpupower is not installed"));
+    }
+
+    let status = privileged_command("cpupower")?
+        .args(["frequency-set", "-g", governor])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "cpupower exited with {} (insufficient permissions?)",
+            status
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build a command for `program` that runs as root, through `pkexec` when
+/// not already root.
+pub fn privileged_command(program: &str) -> Result<Command> {
+    if nix::unistd::geteuid().is_root() {
+        Ok(Command::new(program))
+    } else if which("pkexec").is_ok() {
+        let mut cmd = Command::new("pkexec");
+        cmd.arg(program);
+        Ok(cmd)
+    } else {
+        Err(anyhow::anyhow!(
+            "{} requires root and pkexec is not available",
+            program
+        ))
+    }
+}
+
+/// Size of everything under `path` in bytes, 0 when it doesn't exist
+pub fn disk_usage(path: &Path) -> Result<u64> {
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let output = Command::new("du").arg("-sb").arg(path).output()?;
+
+    if output.status.success() {
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        if let Some(size_str) = output_str.split_whitespace().next() {
+            return Ok(size_str.parse().unwrap_or(0));
+        }
+    }
+
+    Ok(0)
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `path`
+pub fn free_space(path: &Path) -> Result<u64> {
+    let stat = nix::sys::statvfs::statvfs(path)?;
+    Ok(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+/// Whether two existing paths are on the same filesystem, so a rename
+/// between them doesn't copy any data
+pub fn same_filesystem(a: &Path, b: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(std::fs::metadata(a)?.dev() == std::fs::metadata(b)?.dev())
+}
+
+/// Move a directory to `dest`, which must not exist. Within a filesystem
+/// this is a rename; across filesystems the tree is copied, checked against
+/// the original and only then is the original deleted. Free space is not
+/// checked here.
+pub fn move_directory(src: &Path, dest: &Path) -> Result<()> {
+    let dest_parent = dest
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid destination {}", dest.display()))?;
+    std::fs::create_dir_all(dest_parent)?;
+
+    if same_filesystem(src, dest_parent)? {
+        std::fs::rename(src, dest)?;
+        return Ok(());
+    }
+
+    let copied = copy_tree(src, dest).and_then(|copied| {
+        let found = tree_totals(dest)?;
+        if found != copied {
+            return Err(anyhow::anyhow!(
+                "copy of {} is incomplete ({} of {} files)",
+                src.display(),
+                found.0,
+                copied.0
+            ));
+        }
+        Ok(())
+    });
+    if let Err(e) = copied {
+        let _ = std::fs::remove_dir_all(dest);
+        return Err(e);
+    }
+
+    std::fs::remove_dir_all(src)?;
+    Ok(())
+}
+
+/// Copy a directory tree keeping symlinks, such as a prefix's `dosdevices`,
+/// as links. Returns the number of files and their total size.
+fn copy_tree(src: &Path, dest: &Path) -> Result<(u64, u64)> {
+    let mut totals = (0, 0);
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let target = dest.join(entry.path().strip_prefix(src)?);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if file_type.is_symlink() {
+            std::os::unix::fs::symlink(std::fs::read_link(entry.path())?, &target)?;
+        } else {
+            totals.0 += 1;
+            totals.1 += std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(totals)
+}
+
+/// Number of regular files under `path` and their total size
+fn tree_totals(path: &Path) -> Result<(u64, u64)> {
+    let mut totals = (0, 0);
+    for entry in walkdir::WalkDir::new(path) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            totals.0 += 1;
+            totals.1 += entry.metadata()?.len();
+        }
+    }
+    Ok(totals)
+}
+
+/// Format a byte count for display, e.g. `1.5 GiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Whether terminal output should be colored: not when `--no-color` was
+/// given, `NO_COLOR` is set or stdout isn't a terminal
+pub fn use_color(no_color_flag: bool) -> bool {
+    use std::io::IsTerminal;
+
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    !no_color_flag && !no_color_env && std::io::stdout().is_terminal()
+}
+
+static PLAIN_OUTPUT: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Strip emoji from everything printed from now on, set along with `use_color`
+pub fn set_plain_output(plain: bool) {
+    PLAIN_OUTPUT.store(plain, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// `text` as it should be printed: unchanged normally, without emoji and the
+/// spaces after them when output is plain, so "✅ Done" becomes "Done"
+pub fn plain_text(text: &str) -> std::borrow::Cow<'_, str> {
+    let is_emoji = |c: char| {
+        matches!(c,
+            '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2300}'..='\u{23FF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{FE0F}'
+            | '\u{200D}'
+            | '\u{20E3}')
+    };
+    if !PLAIN_OUTPUT.load(std::sync::atomic::Ordering::Relaxed) || !text.chars().any(is_emoji) {
+        return std::borrow::Cow::Borrowed(text);
+    }
+
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_emoji(c) {
+            while chars.next_if(|&c| is_emoji(c) || c == ' ').is_some() {}
+        } else {
+            plain.push(c);
+        }
+    }
+    std::borrow::Cow::Owned(plain)
+}
+
+/// Verify a downloaded file against a checksum written as `sha256:<hex>` or
+/// `sha512:<hex>`. Bare hex digests are told apart by their length.
+pub fn verify_checksum(path: &Path, checksum: &str) -> Result<()> {
+    use sha2::{Digest, Sha256, Sha512};
+
+    let (algorithm, expected) = match checksum.split_once(':') {
+        Some((algorithm, digest)) => (algorithm.to_lowercase(), digest.trim()),
+        None if checksum.trim().len() == 128 => ("sha512".to_string(), checksum.trim()),
+        None => ("sha256".to_string(), checksum.trim()),
+    };
+
+    let mut file = std::fs::File::open(path)?;
+    let actual = match algorithm.as_str() {
+        "sha256" => {
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            format!("{:x}", hasher.finalize())
+        }
+        "sha512" => {
+            let mut hasher = Sha512::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            format!("{:x}", hasher.finalize())
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unsupported checksum algorithm: {}",
+                algorithm
+            ));
+        }
+    };
+
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(anyhow::anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        ));
+    }
+
+    Ok(())
+}
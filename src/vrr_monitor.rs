@@ -4,16 +4,29 @@
 //! and gaming-specific display metrics.
 
 use crate::display::{Display, DisplayManager};
+use crate::utils::out;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+/// How often `update_metrics` re-reads frame timing
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+/// A frame log that hasn't been written for this long means nothing is presenting
+const IDLE_TIMEOUT: Duration = Duration::from_secs(2);
+/// MangoHud writes one averaged sample per interval
+const LOG_INTERVAL_MS: u32 = 100;
+/// Enough of the log tail for ~10 seconds of samples
+const LOG_TAIL_BYTES: u64 = 16 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct VrrMonitor {
     display_metrics: Vec<DisplayMetrics>,
     frame_history: VecDeque<FrameData>,
     monitoring_active: bool,
-    #[allow(dead_code)]
+    /// Whether a game was presenting at the last update
+    presenting: bool,
     last_update: Instant,
 }
 
@@ -84,6 +97,7 @@ impl VrrMonitor {
             display_metrics: Vec::new(),
             frame_history: VecDeque::with_capacity(1000), // Keep last 1000 frames
             monitoring_active: false,
+            presenting: false,
             last_update: Instant::now(),
         }
     }
@@ -116,9 +130,12 @@ impl VrrMonitor {
         }
 
         self.monitoring_active = true;
-        self.last_update = Instant::now();
+        self.presenting = false;
+        self.frame_history.clear();
+        // Make the first update_metrics call sample immediately
+        self.last_update = Instant::now() - UPDATE_INTERVAL;
 
-        println!(
+        out!(
             "🎮 Started VRR monitoring for {} displays",
             self.display_metrics.len()
         );
@@ -127,21 +144,57 @@ impl VrrMonitor {
 
     pub fn stop_monitoring(&mut self) {
         self.monitoring_active = false;
-        println!("⏹️ Stopped VRR monitoring");
+        out!("⏹️ Stopped VRR monitoring");
     }
 
+    /// Refresh frame timing from the newest MangoHud frame log and the
+    /// connectors' active refresh rates. Calls within a second of the last
+    /// update are ignored, so this can be called every UI frame.
     pub fn update_metrics(&mut self) -> anyhow::Result<()> {
-        if !self.monitoring_active {
+        if !self.monitoring_active || self.last_update.elapsed() < UPDATE_INTERVAL {
             return Ok(());
         }
 
-        let now = Instant::now();
-        let delta = now.duration_since(self.last_update);
+        let frames = active_frame_log()
+            .map(|log| read_frame_log(&log))
+            .transpose()?
+            .unwrap_or_default();
+        self.presenting = !frames.is_empty();
 
-        // Update each display's metrics - simplified for now
         for metrics in &mut self.display_metrics {
-            // Skip metrics update that causes borrowing issues
-            // TODO: Implement proper metrics update without borrowing conflicts
+            if let Some(rate) = Self::get_current_refresh_rate(&metrics.display_id) {
+                metrics.target_fps = rate as f64;
+                metrics.vrr_range.max_hz = rate;
+            }
+            metrics.vrr_range.current_hz = metrics.target_fps as u32;
+            metrics.current_fps = 0.0;
+            metrics.frame_time_ms = 0.0;
+            metrics.frame_time_variance = 0.0;
+        }
+
+        // The log doesn't say which output the game is on, so attribute it
+        // to the primary display
+        self.frame_history.clear();
+        if let Some(display_id) = self.display_metrics.first().map(|m| m.display_id.clone()) {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+            let count = frames.len() as u64;
+            for (i, frame_time) in frames.into_iter().enumerate() {
+                self.frame_history.push_back(FrameData {
+                    timestamp: now - (count - i as u64) * LOG_INTERVAL_MS as u64,
+                    display_id: display_id.clone(),
+                    frame_time,
+                    presentation_time: Duration::ZERO,
+                    vrr_hz: 0,
+                    tearing_detected: false,
+                    sync_event: false,
+                });
+            }
+        }
+
+        if let Some(metrics) = self.display_metrics.first_mut()
+            && let Some(latest) = self.frame_history.back()
+        {
+            Self::calculate_display_metrics(metrics, latest);
         }
 
         // Analyze frame history for patterns
@@ -150,106 +203,46 @@ impl VrrMonitor {
         // Clean old frame data
         self.cleanup_old_frames();
 
-        self.last_update = now;
+        self.last_update = Instant::now();
         Ok(())
     }
 
-    // TODO: Reimplement this method to avoid borrowing conflicts
-    // fn update_display_metrics(&mut self, metrics: &mut DisplayMetrics, _delta: Duration) -> anyhow::Result<()> {
-    //     // Implementation temporarily removed to fix compilation
-    //     Ok(())
-    // }
-
-    fn get_current_refresh_rate(&self, display_id: &str) -> anyhow::Result<u32> {
-        // Try to get current refresh rate from various sources
-
-        // Method 1: Read from DRM VRR property
-        if let Ok(content) =
-            std::fs::read_to_string(&format!("/sys/class/drm/{}/vrr_enabled", display_id))
-        {
-            if content.trim() == "1" {
-                // VRR is active, try to get current rate
-                if let Ok(rate_content) = std::fs::read_to_string(&format!(
-                    "/sys/class/drm/{}/current_refresh_rate",
-                    display_id
-                )) {
-                    if let Ok(rate) = rate_content.trim().parse::<u32>() {
-                        return Ok(rate);
-                    }
-                }
-            }
-        }
-
-        // Method 2: Use xrandr for X11
-        if let Ok(output) = std::process::Command::new("xrandr")
-            .args(&["--query"])
+    /// Refresh rate of the connector's active mode, as reported by xrandr
+    fn get_current_refresh_rate(display_id: &str) -> Option<u32> {
+        let output = std::process::Command::new("xrandr")
+            .args(["--query"])
             .output()
-        {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if line.contains(display_id) && line.contains('*') {
-                    // Parse current refresh rate from line like "1920x1080     60.00*+  59.93"
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    for part in parts {
-                        if part.contains('*') {
-                            if let Ok(rate) = part.trim_end_matches('*').parse::<f32>() {
-                                return Ok(rate.round() as u32);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Fallback: return 60Hz
-        Ok(60)
-    }
-
-    fn sample_frame_data(&self, display_id: &str) -> anyhow::Result<FrameData> {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
-
-        // This is a simplified implementation
-        // Real implementation would use:
-        // - DRM page flip events
-        // - Presentation timing APIs
-        // - GPU performance counters
-        // - Wayland presentation feedback
-
-        let frame_time = Duration::from_micros(16667); // ~60fps baseline
-        let presentation_time = Duration::from_micros(100); // Typical presentation latency
-
-        Ok(FrameData {
-            timestamp: now,
-            display_id: display_id.to_string(),
-            frame_time,
-            presentation_time,
-            vrr_hz: self.get_current_refresh_rate(display_id).unwrap_or(60),
-            tearing_detected: false,
-            sync_event: true,
-        })
+            .ok()?;
+        let output_str = String::from_utf8_lossy(&output.stdout);
+
+        // Mode lines follow their output's header line, e.g.
+        // "   1920x1080     60.00*+  59.93"
+        output_str
+            .lines()
+            .skip_while(|line| !line.starts_with(&format!("{} ", display_id)))
+            .skip(1)
+            .take_while(|line| line.starts_with(' '))
+            .flat_map(|line| line.split_whitespace().skip(1))
+            .find(|rate| rate.contains('*'))
+            .and_then(|rate| rate.trim_end_matches(['*', '+']).parse::<f32>().ok())
+            .map(|rate| rate.round() as u32)
     }
 
-    fn calculate_display_metrics(&self, metrics: &mut DisplayMetrics, frame_data: &FrameData) {
+    fn calculate_display_metrics(metrics: &mut DisplayMetrics, frame_data: &FrameData) {
         // Calculate FPS from frame time
         let frame_time_ms = frame_data.frame_time.as_secs_f64() * 1000.0;
+        if frame_time_ms <= 0.0 {
+            return;
+        }
         metrics.frame_time_ms = frame_time_ms;
         metrics.current_fps = 1000.0 / frame_time_ms;
 
-        // Update VRR metrics
-        metrics.vrr_range.current_hz = frame_data.vrr_hz;
-
-        // Track sync events
-        if frame_data.sync_event {
-            metrics.adaptive_sync_events += 1;
-        }
-
-        // Track tearings
-        if frame_data.tearing_detected {
-            metrics.tearings += 1;
+        // Inside the VRR window the panel refreshes in step with the game
+        if metrics.vrr_active {
+            metrics.vrr_range.current_hz = (metrics.current_fps.round() as u32)
+                .min(metrics.vrr_range.max_hz)
+                .max(metrics.vrr_range.min_hz);
         }
-
-        // Calculate presentation latency
-        metrics.presentation_latency = frame_data.presentation_time.as_secs_f64() * 1000.0;
     }
 
     fn analyze_frame_patterns(&mut self) {
@@ -457,11 +450,19 @@ impl VrrMonitor {
         }
     }
 
+    /// Stats for the primary display, or `None` while no game is presenting
     pub fn get_real_time_stats(&self) -> Option<&DisplayMetrics> {
-        // Return stats for the primary display
+        if !self.presenting {
+            return None;
+        }
         self.display_metrics.first()
     }
 
+    /// Whether the last update found a game presenting frames
+    pub fn is_presenting(&self) -> bool {
+        self.presenting
+    }
+
     pub fn is_monitoring_active(&self) -> bool {
         self.monitoring_active
     }
@@ -472,3 +473,94 @@ impl Default for VrrMonitor {
         Self::new()
     }
 }
+
+/// How far below the refresh rate a VRR frame cap sits. Frames arriving
+/// faster than the display refreshes fall back to V-Sync or tearing.
+pub const VRR_CAP_MARGIN: u32 = 3;
+
+/// Frame rate cap that keeps a game inside the VRR range of a display
+/// refreshing at `refresh_hz`
+pub fn vrr_fps_cap(refresh_hz: u32) -> u32 {
+    refresh_hz.saturating_sub(VRR_CAP_MARGIN).max(1)
+}
+
+/// The primary display's refresh rate and whether VRR is on
+pub fn primary_display_refresh() -> Option<(u32, bool)> {
+    let manager = DisplayManager::new().ok()?;
+    let display = manager.primary_display()?;
+    Some((display.current_refresh_rate, display.vrr_enabled))
+}
+
+/// Where games launched with MangoHud write their frame time logs
+pub fn frame_log_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("ghostforge")
+        .join("frametimes")
+}
+
+/// `MANGOHUD_CONFIG` options that make MangoHud log frame times where
+/// `VrrMonitor` reads them. Logs from earlier days are pruned.
+pub fn mangohud_log_config() -> Option<String> {
+    let dir = frame_log_dir();
+    std::fs::create_dir_all(&dir).ok()?;
+
+    let day = Duration::from_secs(24 * 60 * 60);
+    for entry in std::fs::read_dir(&dir).ok()?.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified.elapsed().unwrap_or_default() > day);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+
+    Some(format!(
+        "output_folder={},autostart_log=1,log_interval={}",
+        dir.display(),
+        LOG_INTERVAL_MS
+    ))
+}
+
+/// The newest frame log, if it is still being written
+fn active_frame_log() -> Option<PathBuf> {
+    std::fs::read_dir(frame_log_dir())
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "csv"))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .filter(|(modified, _)| modified.elapsed().unwrap_or_default() < IDLE_TIMEOUT)
+        .map(|(_, path)| path)
+}
+
+/// Frame times from the tail of a MangoHud CSV log. The log starts with a
+/// system info block, followed by a header naming the sample columns.
+fn read_frame_log(path: &Path) -> anyhow::Result<Vec<Duration>> {
+    let mut file = std::fs::File::open(path)?;
+
+    let mut head = vec![0; 4096];
+    let read = file.read(&mut head)?;
+    let head = String::from_utf8_lossy(&head[..read]);
+    let column = head
+        .lines()
+        .find(|line| line.starts_with("fps,"))
+        .and_then(|header| header.split(',').position(|name| name == "frametime"))
+        .unwrap_or(1);
+
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut tail = String::new();
+    file.read_to_string(&mut tail)?;
+
+    Ok(tail
+        .lines()
+        // The first line of the tail is usually cut off
+        .skip(if start > 0 { 1 } else { 0 })
+        .filter_map(|line| line.split(',').nth(column)?.parse::<f64>().ok())
+        .filter(|ms| *ms > 0.0)
+        .map(|ms| Duration::from_secs_f64(ms / 1000.0))
+        .collect())
+}
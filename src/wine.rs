@@ -1,15 +1,13 @@
+use crate::utils::out;
 use anyhow::Result;
 use flate2::read::GzDecoder;
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
 use regex::Regex;
-use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
 use tar::Archive;
 use which::which;
 
@@ -55,6 +53,25 @@ pub struct WineManager {
     config_dir: PathBuf,
 }
 
+/// Result of comparing installed GE-Proton builds against upstream releases
+#[derive(Debug, Clone)]
+pub struct WineUpdateCheck {
+    /// Newest upstream release, if it isn't installed yet
+    pub latest: Option<WineVersion>,
+    /// Installed builds older than the newest upstream release
+    pub outdated: Vec<WineVersion>,
+    /// False when no GE-Proton build is installed, nothing is up for updating
+    pub installed: bool,
+}
+
+/// Parse a GE-Proton release name like "GE-Proton9-20" into a sortable key
+pub fn ge_proton_release(name: &str) -> Option<(u32, u32)> {
+    static RELEASE: OnceLock<Regex> = OnceLock::new();
+    let re = RELEASE.get_or_init(|| Regex::new(r"GE-Proton(\d+)-(\d+)").unwrap());
+    let captures = re.captures(name)?;
+    Some((captures[1].parse().ok()?, captures[2].parse().ok()?))
+}
+
 impl WineManager {
     pub fn new(wine_dir: PathBuf, config_dir: PathBuf) -> Self {
         Self {
@@ -63,6 +80,13 @@ impl WineManager {
         }
     }
 
+    /// Manager for `wine.wine_versions_path`, shared by the CLI, GUI and TUI
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let config_path = crate::config::Config::config_path();
+        let config_dir = config_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Self::new(config.wine.wine_versions_path.clone(), config_dir)
+    }
+
     pub async fn list_installed(&self) -> Result<Vec<WineVersion>> {
         let mut versions = Vec::new();
 
@@ -91,6 +115,12 @@ impl WineManager {
             dirs::home_dir()
                 .unwrap()
                 .join(".steam/steam/steamapps/common"),
+            dirs::home_dir()
+                .unwrap()
+                .join(".local/share/Steam/compatibilitytools.d"),
+            dirs::home_dir()
+                .unwrap()
+                .join(".steam/root/compatibilitytools.d"),
             PathBuf::from("/usr/share/steam/compatibilitytools.d"),
         ];
 
@@ -101,10 +131,10 @@ impl WineManager {
                     let path = entry.path();
                     if path.is_dir() {
                         let name = path.file_name().unwrap().to_str().unwrap();
-                        if name.starts_with("Proton") || name.contains("proton") {
-                            if let Ok(version) = self.detect_proton_version(&path) {
-                                versions.push(version);
-                            }
+                        if name.to_lowercase().contains("proton")
+                            && let Ok(version) = self.detect_proton_version(&path)
+                        {
+                            versions.push(version);
                         }
                     }
                 }
@@ -119,6 +149,11 @@ impl WineManager {
                 if path.is_dir() {
                     if let Ok(version) = self.detect_wine_version(&path) {
                         versions.push(version);
+                    } else if let Some(root) = Self::find_proton_root(&path) {
+                        // Proton archives unpack into a versioned subdirectory
+                        if let Ok(version) = self.detect_proton_version(&root) {
+                            versions.push(version);
+                        }
                     }
                 }
             }
@@ -145,8 +180,72 @@ impl WineManager {
         Ok(versions)
     }
 
+    /// Check whether a newer GE-Proton release than the installed builds exists.
+    ///
+    /// When `only` is set, just the installed build with that name is considered.
+    pub async fn check_ge_proton_updates(&self, only: Option<&str>) -> Result<WineUpdateCheck> {
+        let installed: Vec<WineVersion> = self
+            .list_installed()
+            .await?
+            .into_iter()
+            .filter(|v| ge_proton_release(&v.name).is_some())
+            .filter(|v| only.is_none_or(|name| v.name == name || v.version == name))
+            .collect();
+
+        if let Some(name) = only
+            && installed.is_empty()
+        {
+            return Err(anyhow::anyhow!(
+                "No installed GE-Proton build named '{}'",
+                name
+            ));
+        }
+
+        let newest = self
+            .fetch_ge_proton_releases()
+            .await?
+            .into_iter()
+            .filter_map(|v| ge_proton_release(&v.version).map(|key| (key, v)))
+            .max_by_key(|(key, _)| *key);
+
+        let Some((newest_key, newest)) = newest else {
+            return Ok(WineUpdateCheck {
+                latest: None,
+                outdated: Vec::new(),
+                installed: !installed.is_empty(),
+            });
+        };
+
+        let any_installed = !installed.is_empty();
+        let already_installed = installed
+            .iter()
+            .any(|v| ge_proton_release(&v.name) == Some(newest_key));
+        let outdated = installed
+            .into_iter()
+            .filter(|v| ge_proton_release(&v.name).is_some_and(|key| key < newest_key))
+            .collect();
+
+        Ok(WineUpdateCheck {
+            latest: (!already_installed).then_some(newest),
+            outdated,
+            installed: any_installed,
+        })
+    }
+
+    /// Newest downloadable release of the given Wine flavour, if any
+    pub async fn latest_available(&self, wine_type: WineType) -> Result<Option<WineVersion>> {
+        let versions = match wine_type {
+            WineType::ProtonGE => self.fetch_ge_proton_releases().await?,
+            WineType::WineStaging => self.fetch_wine_builds().await?,
+            WineType::Lutris => self.fetch_lutris_wine().await?,
+            _ => Vec::new(),
+        };
+
+        Ok(versions.into_iter().find(|v| v.wine_type == wine_type))
+    }
+
     async fn fetch_ge_proton_releases(&self) -> Result<Vec<WineVersion>> {
-        let client = reqwest::Client::new();
+        let client = crate::download::http_client();
         let response = client
             .get("https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases")
             .header("User-Agent", "GhostForge")
@@ -173,7 +272,7 @@ impl WineManager {
                                     download_url: asset["browser_download_url"]
                                         .as_str()
                                         .map(String::from),
-                                    checksum: None,
+                                    checksum: asset["digest"].as_str().map(String::from),
                                 });
                                 break;
                             }
@@ -202,7 +301,7 @@ impl WineManager {
     }
 
     async fn fetch_lutris_wine(&self) -> Result<Vec<WineVersion>> {
-        let client = reqwest::Client::new();
+        let client = crate::download::http_client();
         let response = client
             .get("https://api.github.com/repos/lutris/wine/releases")
             .header("User-Agent", "GhostForge")
@@ -244,42 +343,43 @@ impl WineManager {
 
             fs::create_dir_all(&self.wine_dir)?;
 
-            let client = reqwest::Client::new();
-            let response = client.get(url).send().await?;
-            let total_size = response
-                .content_length()
-                .ok_or_else(|| anyhow::anyhow!("Failed to get content length"))?;
-
-            let pb = ProgressBar::new(total_size);
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                    .unwrap()
-                    .progress_chars("#>-"),
-            );
-
-            let tmp_file = self.wine_dir.join(format!("{}.tmp", version.name));
-            let mut file = fs::File::create(&tmp_file)?;
-            let mut downloaded = 0u64;
-            let mut stream = response.bytes_stream();
-
-            while let Some(chunk) = stream.next().await {
-                let chunk = chunk?;
-                file.write_all(&chunk)?;
-                downloaded += chunk.len() as u64;
-                pb.set_position(downloaded);
-            }
+            let file_name = crate::download::file_name_from_url(url)
+                .ok_or_else(|| anyhow::anyhow!("Cannot tell the archive name from {}", url))?;
+            let archive = self.wine_dir.join(file_name);
 
-            pb.finish_with_message("Download complete");
+            let pb = crate::download::progress_bar(&version.name);
+            crate::download::Downloader::new()
+                .download(url, &archive, &crate::download::bar_progress(&pb))
+                .await?;
+            pb.finish_with_message(format!("{} downloaded", version.name));
+
+            let checksum = match &version.checksum {
+                Some(checksum) => Some(checksum.clone()),
+                None if version.wine_type == WineType::ProtonGE => fetch_sha512sum(url).await,
+                None => None,
+            };
+            match checksum {
+                Some(checksum) => {
+                    if let Err(e) = crate::utils::verify_checksum(&archive, &checksum) {
+                        let _ = fs::remove_file(&archive);
+                        return Err(e);
+                    }
+                    out!("🔒 Checksum verified");
+                }
+                None => out!(
+                    "⚠️  No checksum published for {}, skipping verification",
+                    version.name
+                ),
+            }
 
             // Extract the archive
             println!("Extracting {}...", version.name);
-            self.extract_archive(&tmp_file, &version.path)?;
+            self.extract_archive(&archive, &version.path)?;
 
             // Clean up
-            fs::remove_file(&tmp_file)?;
+            fs::remove_file(&archive)?;
 
-            println!("✅ {} installed successfully", version.name);
+            out!("✅ {} installed successfully", version.name);
         } else {
             return Err(anyhow::anyhow!("No download URL available"));
         }
@@ -311,26 +411,12 @@ impl WineManager {
     }
 
     pub fn create_prefix(&self, prefix: &WinePrefix, wine_version: &WineVersion) -> Result<()> {
-        fs::create_dir_all(&prefix.path)?;
-
-        let wine_bin = self.get_wine_binary(&wine_version)?;
-
-        // Set environment variables
-        let mut cmd = Command::new(&wine_bin);
-        cmd.env("WINEPREFIX", &prefix.path);
-        cmd.env("WINEARCH", &prefix.arch);
-
-        // Initialize the prefix
-        cmd.arg("wineboot");
-        cmd.arg("--init");
-
-        let status = cmd.status()?;
-        if !status.success() {
-            return Err(anyhow::anyhow!("Failed to create Wine prefix"));
-        }
-
-        // Set Windows version
-        self.set_windows_version(prefix, &prefix.windows_version)?;
+        crate::prefix::PrefixManager::create(
+            &prefix.path,
+            &prefix.arch,
+            &prefix.windows_version,
+            Some(&wine_version.path.to_string_lossy()),
+        )?;
 
         // Apply DLL overrides
         for (dll, mode) in &prefix.dll_overrides {
@@ -451,6 +537,18 @@ impl WineManager {
         })
     }
 
+    fn find_proton_root(path: &Path) -> Option<PathBuf> {
+        if path.join("proton").exists() {
+            return Some(path.to_path_buf());
+        }
+
+        fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|p| p.is_dir() && p.join("proton").exists())
+    }
+
     fn detect_wine_version(&self, path: &Path) -> Result<WineVersion> {
         let name = path.file_name().unwrap().to_str().unwrap();
         let wine_bin = path.join("bin/wine");
@@ -481,21 +579,6 @@ impl WineManager {
         }
     }
 
-    fn get_wine_binary(&self, version: &WineVersion) -> Result<PathBuf> {
-        match version.wine_type {
-            WineType::Proton | WineType::ProtonGE | WineType::ProtonTKG => {
-                Ok(version.path.join("proton"))
-            }
-            _ => {
-                if version.system {
-                    Ok(version.path.clone())
-                } else {
-                    Ok(version.path.join("bin/wine"))
-                }
-            }
-        }
-    }
-
     pub fn remove_wine_version(&self, version: &WineVersion) -> Result<()> {
         if version.system {
             return Err(anyhow::anyhow!("Cannot remove system Wine"));
@@ -509,3 +592,19 @@ impl WineManager {
         Ok(())
     }
 }
+
+/// GE-Proton publishes a `.sha512sum` file next to each tarball
+async fn fetch_sha512sum(url: &str) -> Option<String> {
+    let sum_url = format!("{}.sha512sum", url.strip_suffix(".tar.gz")?);
+    let request = crate::download::http_client()
+        .get(sum_url)
+        .header("User-Agent", "GhostForge");
+    let response = crate::download::send_with_retry(request)
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
+    let body = response.text().await.ok()?;
+    let digest = body.split_whitespace().next()?;
+    Some(format!("sha512:{}", digest))
+}
@@ -1,3 +1,4 @@
+use crate::utils::out;
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,21 @@ pub struct WinetrickVerb {
     pub wine_versions: Vec<String>, // Compatible Wine versions
 }
 
+impl WinetrickVerb {
+    /// A winetricks verb GhostForge has no metadata for
+    pub fn custom(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: name.to_string(),
+            category: WinetrickCategory::Custom,
+            size_mb: None,
+            required_for: vec![],
+            conflicts_with: vec![],
+            wine_versions: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WinetrickCategory {
     Font,
@@ -160,7 +176,7 @@ impl WinetricksManager {
     }
 
     pub async fn install_verb(&self, prefix_path: &Path, verb: &WinetrickVerb) -> Result<()> {
-        println!("📦 Installing {}: {}", verb.name, verb.description);
+        out!("📦 Installing {}: {}", verb.name, verb.description);
 
         if let Some(size) = verb.size_mb {
             println!("   Download size: ~{} MB", size);
@@ -177,7 +193,7 @@ impl WinetricksManager {
         pb.set_message(format!("Installing {}", verb.name));
 
         if self.dry_run {
-            println!(
+            out!(
                 "🔄 [DRY RUN] Would run: WINEPREFIX={} {} --unattended --force {}",
                 prefix_path.display(),
                 self.winetricks_path.display(),
@@ -198,9 +214,14 @@ impl WinetricksManager {
             pb.finish_with_message(format!("✅ {} [SIMULATED]", verb.name));
         } else {
             // Run the actual winetricks command
+            // winetricks keeps downloads in W_CACHE/<verb> and runs them from
+            // there, so every prefix shares one copy of each installer
             let mut cmd = Command::new(&self.winetricks_path);
             cmd.env("WINEPREFIX", prefix_path);
-            cmd.env("WINETRICKS_CACHE", &self.cache_dir);
+            cmd.env("W_CACHE", &self.cache_dir);
+            if self.cache_dir.join(&verb.name).is_dir() {
+                println!("   Using cached download");
+            }
 
             // Silent installation flags
             cmd.args(&["--unattended", "--force"]);
@@ -246,12 +267,12 @@ impl WinetricksManager {
             }
         }
 
-        println!("✅ Successfully installed {}", verb.name);
+        out!("✅ Successfully installed {}", verb.name);
         Ok(())
     }
 
     pub async fn install_battlenet_essentials(&self, prefix_path: &Path) -> Result<()> {
-        println!("🎮 Setting up Battle.net gaming environment...");
+        out!("🎮 Setting up Battle.net gaming environment...");
         println!("This will install essential components for Battle.net games.\n");
 
         let essentials = Self::get_battlenet_essentials();
@@ -262,43 +283,43 @@ impl WinetricksManager {
 
             match self.install_verb(prefix_path, verb).await {
                 Ok(_) => {
-                    println!("   ✅ {} completed successfully", verb.name);
+                    out!("   ✅ {} completed successfully", verb.name);
                 }
                 Err(e) => {
-                    println!("   ❌ {} failed: {}", verb.name, e);
-                    println!("   ⚠️  You may need to install this manually later");
+                    out!("   ❌ {} failed: {}", verb.name, e);
+                    out!("   ⚠️  You may need to install this manually later");
                 }
             }
         }
 
-        println!("\n🎉 Battle.net setup completed!");
+        out!("\n🎉 Battle.net setup completed!");
         println!("Your prefix should now be ready for Battle.net games.");
 
         Ok(())
     }
 
     pub async fn optimize_for_wow(&self, prefix_path: &Path) -> Result<()> {
-        println!("🐉 Optimizing prefix for World of Warcraft...");
+        out!("🐉 Optimizing prefix for World of Warcraft...");
 
         let wow_verbs = Self::get_wow_specific();
 
         for verb in wow_verbs {
             println!("Applying: {}", verb.description);
             match self.install_verb(prefix_path, &verb).await {
-                Ok(_) => println!("   ✅ Applied: {}", verb.name),
-                Err(e) => println!("   ⚠️  Failed to apply {}: {}", verb.name, e),
+                Ok(_) => out!("   ✅ Applied: {}", verb.name),
+                Err(e) => out!("   ⚠️  Failed to apply {}: {}", verb.name, e),
             }
         }
 
         // Set specific Wine registry keys for WoW
         self.set_wow_registry_tweaks(prefix_path)?;
 
-        println!("✅ World of Warcraft optimizations complete!");
+        out!("✅ World of Warcraft optimizations complete!");
         Ok(())
     }
 
     fn set_wow_registry_tweaks(&self, prefix_path: &Path) -> Result<()> {
-        println!("🔧 Applying World of Warcraft registry tweaks...");
+        out!("🔧 Applying World of Warcraft registry tweaks...");
 
         let tweaks = vec![
             // Enable Wine staging fsync for better performance
@@ -336,14 +357,14 @@ impl WinetricksManager {
 
         for (key, value, data) in tweaks {
             // SAFETY: Only simulate registry changes for demo
-            println!(
+            out!(
                 "🔄 [DEMO MODE] Would run: WINEPREFIX={} wine reg add '{}' /v '{}' /d '{}' /f",
                 prefix_path.display(),
                 key,
                 value,
                 data
             );
-            println!("   ✅ [SIMULATED] Set {}/{} = {}", key, value, data);
+            out!("   ✅ [SIMULATED] Set {}/{} = {}", key, value, data);
         }
 
         Ok(())
@@ -382,31 +403,32 @@ impl WinetricksManager {
         prefix_path: &Path,
         wine_version: Option<&str>,
     ) -> Result<()> {
-        println!(
+        out!(
             "🍷 Creating new Battle.net Wine prefix at: {}",
             prefix_path.display()
         );
 
-        // Create the prefix directory
-        std::fs::create_dir_all(prefix_path)?;
-
-        // SAFETY: Only simulate Wine prefix creation for demo
-        let wine_cmd = wine_version.unwrap_or("wine");
-        println!(
-            "🔄 [DEMO MODE] Would run: WINEPREFIX={} WINEARCH=win64 {} wineboot --init",
-            prefix_path.display(),
-            wine_cmd
-        );
-
-        println!("⏳ [SIMULATING] Initializing Wine prefix...");
-        thread::sleep(Duration::from_millis(1500)); // Simulate work
-
-        println!("✅ Wine prefix initialized successfully!");
+        if prefix_path.join("system.reg").exists() {
+            out!("📁 Using the existing prefix");
+        } else if self.dry_run {
+            out!(
+                "🔄 [DRY RUN] Would run: WINEPREFIX={} WINEARCH=win64 {} wineboot --init",
+                prefix_path.display(),
+                wine_version.unwrap_or("wine")
+            );
+        } else {
+            crate::prefix::PrefixManager::create(
+                prefix_path,
+                "win64",
+                crate::prefix::DEFAULT_WINDOWS_VERSION,
+                wine_version,
+            )?;
+        }
 
         // Install Battle.net essentials
         self.install_battlenet_essentials(prefix_path).await?;
 
-        println!("\n🎉 Battle.net prefix setup complete!");
+        out!("\n🎉 Battle.net prefix setup complete!");
         println!("You can now install Battle.net in this prefix:");
         println!(
             "  WINEPREFIX={} wine /path/to/Battle.net-Setup.exe",
@@ -417,9 +439,13 @@ impl WinetricksManager {
     }
 
     pub fn list_installed_verbs(&self, prefix_path: &Path) -> Result<Vec<String>> {
-        // This is a simplified version - in practice, you'd need to check
-        // the Wine prefix's uninstall registry for installed components
-        let mut installed = Vec::new();
+        // winetricks logs every verb it installs, components installed by
+        // other means are recognized by their files
+        let mut installed: Vec<String> =
+            std::fs::read_to_string(prefix_path.join("winetricks.log"))
+                .map(|log| log.lines().map(|l| l.trim().to_string()).collect())
+                .unwrap_or_default();
+        installed.retain(|verb| !verb.is_empty());
 
         // Check for common files/registry entries that indicate installation
         let checks = vec![
@@ -433,7 +459,7 @@ impl WinetricksManager {
 
         for (verb, check_path) in checks {
             let full_path = prefix_path.join(check_path);
-            if full_path.exists() {
+            if full_path.exists() && !installed.iter().any(|v| v == verb) {
                 installed.push(verb.to_string());
             }
         }
@@ -442,13 +468,42 @@ impl WinetricksManager {
     }
 }
 
+/// Where downloaded verb installers are kept, shared by every prefix
+pub fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("ghostforge")
+        .join("winetricks")
+}
+
+/// Cached downloads per verb with their size in bytes, largest first
+pub fn cache_entries(cache_dir: &Path) -> Result<Vec<(String, u64)>> {
+    let mut entries = Vec::new();
+    if !cache_dir.is_dir() {
+        return Ok(entries);
+    }
+    for entry in std::fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        let size = crate::utils::disk_usage(&entry.path())?;
+        entries.push((entry.file_name().to_string_lossy().to_string(), size));
+    }
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(entries)
+}
+
+/// Delete every cached download, returning the bytes freed
+pub fn clear_cache(cache_dir: &Path) -> Result<u64> {
+    let freed = cache_entries(cache_dir)?.iter().map(|(_, size)| size).sum();
+    if cache_dir.is_dir() {
+        std::fs::remove_dir_all(cache_dir)?;
+        std::fs::create_dir_all(cache_dir)?;
+    }
+    Ok(freed)
+}
+
 // Utility functions for specific game setups
 pub async fn setup_wow_prefix(prefix_path: &Path, wine_version: Option<&str>) -> Result<()> {
-    let cache_dir = dirs::cache_dir()
-        .unwrap()
-        .join("ghostforge")
-        .join("winetricks");
-    let manager = WinetricksManager::new(cache_dir)?;
+    let manager = WinetricksManager::new(default_cache_dir())?;
 
     // Create prefix and install essentials
     manager
@@ -458,7 +513,7 @@ pub async fn setup_wow_prefix(prefix_path: &Path, wine_version: Option<&str>) ->
     // Apply WoW-specific optimizations
     manager.optimize_for_wow(prefix_path).await?;
 
-    println!("\n🐲 World of Warcraft prefix is ready!");
+    out!("\n🐲 World of Warcraft prefix is ready!");
     println!("Install Battle.net, then download World of Warcraft.");
     println!("For best performance, enable DXVK and use Wine-staging or GE-Proton.");
 
@@ -466,11 +521,7 @@ pub async fn setup_wow_prefix(prefix_path: &Path, wine_version: Option<&str>) ->
 }
 
 pub async fn setup_diablo_prefix(prefix_path: &Path, wine_version: Option<&str>) -> Result<()> {
-    let cache_dir = dirs::cache_dir()
-        .unwrap()
-        .join("ghostforge")
-        .join("winetricks");
-    let manager = WinetricksManager::new(cache_dir)?;
+    let manager = WinetricksManager::new(default_cache_dir())?;
 
     // Create prefix with essentials
     manager
@@ -488,10 +539,10 @@ pub async fn setup_diablo_prefix(prefix_path: &Path, wine_version: Option<&str>)
         wine_versions: vec!["all".to_string()],
     };
 
-    println!("\n⚔️  Installing Diablo-specific components...");
+    out!("\n⚔️  Installing Diablo-specific components...");
     manager.install_verb(prefix_path, &vcrun2022).await?;
 
-    println!("\n⚔️  Diablo prefix is ready!");
+    out!("\n⚔️  Diablo prefix is ready!");
     println!("This prefix is optimized for Diablo III and Diablo IV.");
 
     Ok(())